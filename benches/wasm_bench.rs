@@ -0,0 +1,67 @@
+#[macro_use]
+extern crate criterion;
+extern crate blockstack_lib;
+
+use blockstack_lib::{
+    chainstate::stacks::StacksBlockId,
+    chainstate::stacks::index::storage::TrieFileStorage,
+    vm::ast,
+    vm::ClarityVersion,
+    vm::contexts::OwnedEnvironment,
+    vm::database::{MarfedKV, NULL_HEADER_DB},
+    vm::SymbolicExpression,
+    vm::types::{QualifiedContractIdentifier, Value},
+    vm::wasm::WasmContract,
+};
+
+use criterion::Criterion;
+
+// a contract whose cost is dominated by evaluating function bodies, rather than by storage.
+const CONTRACT: &str = "
+(define-constant seeds (list 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20
+                             21 22 23 24 25 26 27 28 29 30 31 32 33 34 35 36 37 38 39 40))
+(define-private (step (x int) (acc int))
+  (let ((next (+ (* acc 31) x)))
+    (if (and (> next 0) (is-eq (mod next 2) 0))
+        (mod next 1000003)
+        (mod (+ next 7) 1000003))))
+(define-private (round (x int) (acc int))
+  (fold step seeds (+ acc x)))
+(define-public (run (seed int))
+  (ok (fold round seeds seed)))";
+
+fn run_contract(c: &mut Criterion, name: &str, compiled: bool) {
+    let contract_identifier = QualifiedContractIdentifier::local("bench").unwrap();
+
+    let mut marf_kv = MarfedKV::temporary();
+    marf_kv.begin(&TrieFileStorage::block_sentinel(),
+                  &StacksBlockId([1 as u8; 32]));
+    marf_kv.as_clarity_db(&NULL_HEADER_DB).initialize();
+
+    let mut owned_env = OwnedEnvironment::new(marf_kv.as_clarity_db(&NULL_HEADER_DB));
+    owned_env.initialize_contract(contract_identifier.clone(), CONTRACT).unwrap();
+
+    if compiled {
+        let contract_ast = ast::build_ast(&contract_identifier, CONTRACT, &mut (), ClarityVersion::Clarity1).unwrap();
+        let contract = WasmContract::compile_expressions(&contract_identifier, &contract_ast.expressions).unwrap();
+        owned_env.register_wasm_contract(contract).unwrap();
+    }
+
+    let sender = Value::from(contract_identifier.issuer.clone());
+    let args = [SymbolicExpression::atom_value(Value::Int(17))];
+
+    c.bench_function(name, |b| b.iter(|| {
+        owned_env.execute_transaction(sender.clone(), contract_identifier.clone(), "run", &args).unwrap()
+    }));
+}
+
+pub fn interpreted_benchmark(c: &mut Criterion) {
+    run_contract(c, "interpreted", false);
+}
+
+pub fn compiled_benchmark(c: &mut Criterion) {
+    run_contract(c, "compiled", true);
+}
+
+criterion_group!(benches, interpreted_benchmark, compiled_benchmark);
+criterion_main!(benches);
@@ -5,6 +5,8 @@
 #![allow(non_upper_case_globals)]
 
 extern crate blockstack_lib;
+#[macro_use] extern crate serde_derive;
+#[macro_use] extern crate serde_json;
 
 use std::{io, fs, env};
 use std::io::prelude::*;
@@ -13,14 +15,16 @@ use std::io::Read;
 use blockstack_lib::util::{log, strings::StacksString, hash::hex_bytes, hash::to_hex};
 use blockstack_lib::vm;
 use blockstack_lib::vm::{
-    Value, ClarityName, ContractName, types::PrincipalData,
+    Value, ClarityName, ContractName, ClarityVersion, types::PrincipalData,
     errors::{RuntimeErrorType, Error as ClarityError }
 };
 use blockstack_lib::chainstate::stacks::{
     C32_ADDRESS_VERSION_MAINNET_SINGLESIG, C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
-    StacksPrivateKey, TransactionSpendingCondition, TransactionAuth, TransactionVersion,
+    StacksPrivateKey, TransactionSpendingCondition, TransactionAuth, TransactionVersion, TransactionAuthField,
     StacksPublicKey, TransactionPayload, StacksTransactionSigner,
-    StacksTransaction, TransactionSmartContract, TransactionContractCall, StacksAddress, TokenTransferMemo };
+    StacksTransaction, TransactionSmartContract, TransactionContractCall, TransactionContractInit, StacksAddress, TokenTransferMemo,
+    TransactionPostCondition, TransactionPostConditionMode, PostConditionPrincipal, AssetInfo,
+    FungibleConditionCode, NonfungibleConditionCode };
 use blockstack_lib::burnchains::Address;
 use blockstack_lib::address::AddressHashMode;
 use blockstack_lib::net::{Error as NetError, StacksMessageCodec};
@@ -39,20 +43,68 @@ This CLI has these methods:
   contract-call    used to generate and sign a contract-call transaction
   generate-sk      used to generate a secret key for transaction signing
   token-transfer   used to generate and sign a transfer transaction
+  sponsor          used to add a sponsor's signature to an origin-signed sponsored transaction
+
+  multisig-create      used to generate an unsigned multisig transaction
+  multisig-sign        used to add a signature to a partially-signed multisig transaction
+  multisig-append-key  used to add a non-signer's public key to a partially-signed multisig transaction
+  multisig-inspect     used to show the signing progress of a partially-signed multisig transaction
+  multisig-finalize    used to turn a fully-signed multisig transaction into a broadcastable one
 
 For usage information on those methods, call `blockstack-cli [method] -h`
 
+publish, contract-call, token-transfer and multisig-create accept post-conditions, which
+restrict the assets the transaction may transfer, as method arguments:
+
+   --pc [post-condition]   attaches a post-condition of the form
+                           `principal::condition-code::asset::amount-or-value`, where
+                             principal is `origin`, a standard address, or a contract identifier,
+                             condition-code is one of sent-eq, sent-gt, sent-ge, sent-lt or sent-le
+                               for STX and fungible tokens, or sent or not-sent for non-fungible tokens,
+                             asset is `stx` or `contract-identifier::asset-name`, and
+                             amount-or-value is an amount of the asset, or the _evaluated_ Clarity
+                               value of a non-fungible token
+   --pc-mode [allow|deny]  whether the transaction may transfer assets that no post-condition
+                           covers (default: deny)
+
+e.g.,
+
+   blockstack-cli token-transfer $secret_key 10 0 SPJT598WY1RJN792HRKRHRQYFB7RJ5ZCG6J6GEZ4 1000 \
+      --pc origin::sent-le::stx::1000
+   blockstack-cli contract-call $secret_key 10 0 SPJT598WY1RJN792HRKRHRQYFB7RJ5ZCG6J6GEZ4 names transfer -e u1 \
+      --pc origin::sent::SPJT598WY1RJN792HRKRHRQYFB7RJ5ZCG6J6GEZ4.names::names::u1
+
 `blockstack-cli` accepts flag options as well:
 
    --testnet       instruct the transaction generator to use a testnet version byte instead of MAINNET (default)
+   --sponsored     instruct publish, contract-call and token-transfer to generate a sponsored transaction,
+                   signed only by its origin.  The [fee-rate] is ignored, since the sponsor pays the fee.
+                   Pass the transaction to a sponsor, who adds their signature with the sponsor method.
 
 ";
 
-const PUBLISH_USAGE: &str = "blockstack-cli (options) publish [publisher-secret-key-hex] [fee-rate] [nonce] [contract-name] [file-name.clar]
+const PUBLISH_USAGE: &str = "blockstack-cli (options) publish [publisher-secret-key-hex] [fee-rate] [nonce] [contract-name] [file-name.clar] [init-args...]
 
 The publish command generates and signs a contract publish transaction. If successful,
 this command outputs the hex string encoding of the transaction to stdout, and exits with
-code 0";
+code 0
+
+The contract may optionally be initialized with arguments, which are bound as constants
+before the contract's own definitions, and funded with microSTX from the publisher. Each
+init argument is named, and supplied the same way as contract-call arguments:
+
+  -e [name] [expr]  binds `name` to the value of an _evaluated_ expression
+  -x [name] [hex]   binds `name` to a hex-serialized Clarity value
+  --amount [ustx]   transfers `ustx` microSTX to the contract when it is published. Unless
+                    another STX post-condition is given for the origin, this adds the
+                    post-condition that the origin sends exactly `ustx` microSTX.
+
+e.g.,
+
+   blockstack-cli publish $secret_key 10 0 foo-contract ./foo-contract.clar \\
+      -e owner \\'SPJT598WY1RJN792HRKRHRQYFB7RJ5ZCG6J6GEZ4 \\
+      -x max-supply 0100000000000000000000000000000064 \\
+      --amount 1000";
 
 const CALL_USAGE: &str = "blockstack-cli (options) contract-call [origin-secret-key-hex] [fee-rate] [nonce] [contract-publisher-address] [contract-name] [function-name] [args...]
 
@@ -82,6 +134,58 @@ The transfer command generates and signs a STX transfer transaction. If successf
 this command outputs the hex string encoding of the transaction to stdout, and exits with
 code 0";
 
+const SPONSOR_USAGE: &str = "blockstack-cli (options) sponsor [origin-signed-tx-hex] [sponsor-secret-key-hex] [fee-rate] [nonce]
+
+The sponsor command adds a sponsor's spending condition, with the given [fee-rate] and
+[nonce], to a sponsored transaction that its origin has already signed (i.e. one generated
+with the --sponsored flag), and signs it with the sponsor's key. The sponsor pays the
+transaction fee. If successful, this command outputs the hex string encoding of the
+transaction to stdout, and exits with code 0";
+
+const MULTISIG_CREATE_USAGE: &str = "blockstack-cli (options) multisig-create [num-signatures] [public-keys] [fee-rate] [nonce] [method] [method-args...]
+
+The multisig-create command generates an unsigned transaction from a multisig account,
+which requires [num-signatures] signatures from the comma-separated, hex-encoded list
+of [public-keys]. The order of the public keys determines the account's address, and is
+the order in which the signers must sign.
+
+[method] is one of publish, contract-call or token-transfer, and [method-args...] are the
+arguments of that method which follow its nonce.
+
+If successful, this command outputs a partially-signed transaction to stdout as JSON.
+Pass it to multisig-sign and multisig-append-key until it has all of its signatures,
+and then to multisig-finalize.
+
+e.g.,
+
+   blockstack-cli multisig-create 2 $pubkey_1,$pubkey_2,$pubkey_3 10 0 \\
+      token-transfer SPJT598WY1RJN792HRKRHRQYFB7RJ5ZCG6J6GEZ4 1000 > partial.json";
+
+const MULTISIG_SIGN_USAGE: &str = "blockstack-cli (options) multisig-sign [partial-tx-file] [signer-secret-key-hex]
+
+The multisig-sign command adds a signature to a partially-signed multisig transaction,
+read from [partial-tx-file] (or stdin, if it is `-`). Signers must sign in the order of the
+account's public keys, so the public keys of any signers skipped over are added as
+non-signers. If successful, this command outputs the updated partially-signed transaction.";
+
+const MULTISIG_APPEND_KEY_USAGE: &str = "blockstack-cli (options) multisig-append-key [partial-tx-file] [public-key-hex]
+
+The multisig-append-key command adds the public key of a signer who will not sign to a
+partially-signed multisig transaction, read from [partial-tx-file] (or stdin, if it is `-`).
+If successful, this command outputs the updated partially-signed transaction.";
+
+const MULTISIG_INSPECT_USAGE: &str = "blockstack-cli (options) multisig-inspect [partial-tx-file]
+
+The multisig-inspect command shows which of a partially-signed multisig transaction's
+signers have signed, read from [partial-tx-file] (or stdin, if it is `-`).";
+
+const MULTISIG_FINALIZE_USAGE: &str = "blockstack-cli (options) multisig-finalize [partial-tx-file]
+
+The multisig-finalize command adds the public keys of any remaining non-signers to a
+partially-signed multisig transaction with all of its signatures, read from [partial-tx-file]
+(or stdin, if it is `-`), and checks the signatures. If successful, this command outputs
+the hex string encoding of the transaction to stdout, and exits with code 0";
+
 const GENERATE_USAGE: &str = "blockstack-cli (options) generate-sk
 
 This method generates a secret key, outputting the hex encoding of the
@@ -159,6 +263,12 @@ impl From<blockstack_lib::util::HexError> for CliError {
     }
 }
 
+impl From<serde_json::Error> for CliError {
+    fn from(value: serde_json::Error) -> Self {
+        CliError::Message(format!("Failed to parse partially-signed transaction: {}", value))
+    }
+}
+
 impl From<blockstack_lib::vm::types::serialization::SerializationError> for CliError {
     fn from(value: blockstack_lib::vm::types::serialization::SerializationError) -> Self {
         CliError::Message(format!("Failed to deserialize: {}", value))
@@ -172,6 +282,58 @@ fn make_contract_publish(contract_name: String, contract_content: String) -> Res
     Ok(TransactionSmartContract { name, code_body })
 }
 
+fn eval_value_arg(eval_method: &str, input: &str) -> Result<Option<Value>, CliError> {
+    let value = match eval_method {
+        "-x" => {
+            Value::try_deserialize_hex_untyped(input)?
+        },
+        "-e" => {
+            vm::execute(input)?
+                .ok_or("Supplied argument did not evaluate to a Value")?
+        },
+        _ => {
+            return Ok(None)
+        }
+    };
+    Ok(Some(value))
+}
+
+fn parse_contract_init(init_args: &[String]) -> Result<Option<TransactionContractInit>, CliError> {
+    if init_args.len() == 0 {
+        return Ok(None)
+    }
+
+    let usage_err = "publish init arguments must be supplied as a list of `-e [name] ...`, `-x [name] 0000...` or `--amount [ustx]` arguments";
+    let mut arguments = Vec::new();
+    let mut amount = 0;
+    let mut arg_iterator = 0;
+    while arg_iterator < init_args.len() {
+        let method = &init_args[arg_iterator];
+        if method == "--amount" {
+            let input = init_args.get(arg_iterator+1).ok_or(usage_err)?;
+            amount = input.parse()?;
+            arg_iterator += 2;
+            continue;
+        }
+
+        let (name, input) = match (init_args.get(arg_iterator+1), init_args.get(arg_iterator+2)) {
+            (Some(name), Some(input)) => (name, input),
+            _ => return Err(usage_err.into())
+        };
+        let name = ClarityName::try_from(name.clone())?;
+        if arguments.iter().any(|(existing, _)| *existing == name) {
+            return Err(CliError::Message(format!("Init argument '{}' supplied more than once", name.as_str())))
+        }
+        let value = eval_value_arg(method, input)?
+            .ok_or(usage_err)?;
+
+        arguments.push((name, value));
+        arg_iterator += 3;
+    }
+
+    Ok(Some(TransactionContractInit { arguments, amount }))
+}
+
 fn make_contract_call(contract_address: String, contract_name: String, function_name: String, function_args: Vec<Value>) -> Result<TransactionContractCall, CliError> {
     let address = StacksAddress::from_string(&contract_address)
         .ok_or("Failed to parse contract address")?;
@@ -184,6 +346,123 @@ fn make_contract_call(contract_address: String, contract_name: String, function_
 }
 
 
+/// Post-conditions and the post-condition mode to set on a transaction, from `--pc` and `--pc-mode` arguments
+#[derive(Debug, Clone, PartialEq, Default)]
+struct PostConditionArgs {
+    post_conditions: Vec<TransactionPostCondition>,
+    mode: Option<TransactionPostConditionMode>
+}
+
+impl PostConditionArgs {
+    fn apply(&self, tx: &mut StacksTransaction) {
+        tx.post_conditions = self.post_conditions.clone();
+        if let Some(mode) = self.mode {
+            tx.post_condition_mode = mode;
+        }
+    }
+}
+
+fn parse_post_condition_principal(input: &str) -> Result<PostConditionPrincipal, CliError> {
+    if input == "origin" {
+        return Ok(PostConditionPrincipal::Origin)
+    }
+    let mut parts = input.splitn(2, '.');
+    let address = StacksAddress::from_string(parts.next().unwrap_or(""))
+        .ok_or_else(|| CliError::Message(format!("Failed to parse post-condition principal '{}'", input)))?;
+    match parts.next() {
+        Some(contract_name) => Ok(PostConditionPrincipal::Contract(address, ContractName::try_from(contract_name.to_string())?)),
+        None => Ok(PostConditionPrincipal::Standard(address))
+    }
+}
+
+fn parse_fungible_condition_code(input: &str) -> Option<FungibleConditionCode> {
+    match input {
+        "sent-eq" => Some(FungibleConditionCode::SentEq),
+        "sent-gt" => Some(FungibleConditionCode::SentGt),
+        "sent-ge" => Some(FungibleConditionCode::SentGe),
+        "sent-lt" => Some(FungibleConditionCode::SentLt),
+        "sent-le" => Some(FungibleConditionCode::SentLe),
+        _ => None
+    }
+}
+
+fn parse_nonfungible_condition_code(input: &str) -> Option<NonfungibleConditionCode> {
+    match input {
+        "sent" => Some(NonfungibleConditionCode::Sent),
+        "not-sent" => Some(NonfungibleConditionCode::NotSent),
+        _ => None
+    }
+}
+
+/// Parse a post-condition of the form `principal::condition-code::asset::amount-or-value`
+fn parse_post_condition(input: &str) -> Result<TransactionPostCondition, CliError> {
+    let parts : Vec<&str> = input.splitn(5, "::").collect();
+    if parts.len() < 4 {
+        return Err(CliError::Message(format!("Failed to parse post-condition '{}': expected principal::condition-code::asset::amount-or-value", input)))
+    }
+    let principal = parse_post_condition_principal(parts[0])?;
+    let bad_condition_code = || CliError::Message(format!("Failed to parse post-condition '{}': unknown condition code '{}'", input, parts[1]));
+
+    if parts.len() == 4 {
+        if parts[2] != "stx" {
+            return Err(CliError::Message(format!("Failed to parse post-condition '{}': expected an asset of `stx` or `contract-identifier::asset-name`", input)))
+        }
+        let code = parse_fungible_condition_code(parts[1]).ok_or_else(bad_condition_code)?;
+        return Ok(TransactionPostCondition::STX(principal, code, parts[3].parse()?))
+    }
+
+    let mut contract_parts = parts[2].splitn(2, '.');
+    let contract_address = StacksAddress::from_string(contract_parts.next().unwrap_or(""))
+        .ok_or_else(|| CliError::Message(format!("Failed to parse post-condition asset contract '{}'", parts[2])))?;
+    let contract_name = ContractName::try_from(contract_parts.next()
+                                               .ok_or_else(|| CliError::Message(format!("Failed to parse post-condition asset contract '{}'", parts[2])))?
+                                               .to_string())?;
+    let asset_info = AssetInfo {
+        contract_address,
+        contract_name,
+        asset_name: ClarityName::try_from(parts[3].to_string())?
+    };
+
+    if let Some(code) = parse_nonfungible_condition_code(parts[1]) {
+        // asset values are displayed as Clarity2 literals, e.g. "..." is a string-ascii.
+        let asset_value = vm::execute_with_version(parts[4], ClarityVersion::latest())?
+            .ok_or("Supplied argument did not evaluate to a Value")?;
+        Ok(TransactionPostCondition::Nonfungible(principal, asset_info, asset_value, code))
+    } else {
+        let code = parse_fungible_condition_code(parts[1]).ok_or_else(bad_condition_code)?;
+        Ok(TransactionPostCondition::Fungible(principal, asset_info, code, parts[4].parse()?))
+    }
+}
+
+/// Remove any `--pc [post-condition]` and `--pc-mode [allow|deny]` arguments from a method's
+/// arguments, returning the remaining arguments and the parsed post-condition arguments.
+fn parse_post_condition_args(args: &[String]) -> Result<(Vec<String>, PostConditionArgs), CliError> {
+    let mut remaining = vec![];
+    let mut pc_args = PostConditionArgs::default();
+    let mut arg_iterator = 0;
+    while arg_iterator < args.len() {
+        let flag = args[arg_iterator].as_str();
+        if flag != "--pc" && flag != "--pc-mode" {
+            remaining.push(args[arg_iterator].clone());
+            arg_iterator += 1;
+            continue;
+        }
+        let input = args.get(arg_iterator + 1)
+            .ok_or_else(|| CliError::Message(format!("{} requires an argument", flag)))?;
+        if flag == "--pc" {
+            pc_args.post_conditions.push(parse_post_condition(input)?);
+        } else {
+            pc_args.mode = match input.as_str() {
+                "allow" => Some(TransactionPostConditionMode::Allow),
+                "deny" => Some(TransactionPostConditionMode::Deny),
+                _ => return Err(CliError::Message(format!("Unknown post-condition mode '{}': expected allow or deny", input)))
+            };
+        }
+        arg_iterator += 2;
+    }
+    Ok((remaining, pc_args))
+}
+
 fn make_standard_single_sig_tx(version: TransactionVersion, chain_id: u32, payload: TransactionPayload,
                                publicKey: &StacksPublicKey, nonce: u64, fee_rate: u64) -> StacksTransaction {
     let mut spending_condition = TransactionSpendingCondition::new_singlesig_p2pkh(publicKey.clone())
@@ -196,6 +475,48 @@ fn make_standard_single_sig_tx(version: TransactionVersion, chain_id: u32, paylo
     tx
 }
 
+fn make_sponsored_single_sig_tx(version: TransactionVersion, chain_id: u32, payload: TransactionPayload,
+                                publicKey: &StacksPublicKey, nonce: u64) -> StacksTransaction {
+    let mut spending_condition = TransactionSpendingCondition::new_singlesig_p2pkh(publicKey.clone())
+        .expect("Failed to create p2pkh spending condition from public key.");
+    spending_condition.set_nonce(nonce);
+    // the sponsor sets the fee rate when they sign
+    let auth = TransactionAuth::Sponsored(spending_condition, TransactionSpendingCondition::new_initial_sighash());
+    let mut tx = StacksTransaction::new(version, auth, payload);
+    tx.chain_id = chain_id;
+    tx
+}
+
+/// Sign a sponsored transaction's origin.  The transaction is incomplete until its sponsor signs.
+fn sign_transaction_single_sig_sponsored(transaction: &str, secret_key: &StacksPrivateKey) -> Result<StacksTransaction, CliError> {
+    let transaction = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(transaction)?))?;
+
+    let mut tx_signer = StacksTransactionSigner::new(&transaction);
+    tx_signer.sign_origin(secret_key)?;
+
+    Ok(tx_signer.get_tx_incomplete())
+}
+
+/// Make and sign a single-sig transaction, which is only signed by its origin if it is sponsored.
+fn make_signed_single_sig_tx(version: TransactionVersion, chain_id: u32, payload: TransactionPayload, pc_args: &PostConditionArgs,
+                             secret_key: &StacksPrivateKey, nonce: u64, fee_rate: u64, sponsored: bool) -> Result<StacksTransaction, CliError> {
+    let public_key = StacksPublicKey::from_private(secret_key);
+    let mut unsigned_tx = if sponsored {
+        make_sponsored_single_sig_tx(version, chain_id, payload, &public_key, nonce)
+    } else {
+        make_standard_single_sig_tx(version, chain_id, payload, &public_key, nonce, fee_rate)
+    };
+    pc_args.apply(&mut unsigned_tx);
+
+    let mut unsigned_tx_bytes = vec![];
+    unsigned_tx.consensus_serialize(&mut unsigned_tx_bytes).expect("FATAL: invalid transaction");
+    if sponsored {
+        sign_transaction_single_sig_sponsored(&to_hex(&unsigned_tx_bytes), secret_key)
+    } else {
+        sign_transaction_single_sig_standard(&to_hex(&unsigned_tx_bytes), secret_key)
+    }
+}
+
 fn sign_transaction_single_sig_standard(transaction: &str, secret_key: &StacksPrivateKey) -> Result<StacksTransaction, CliError> {
     let transaction = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(transaction)?))?;
 
@@ -206,18 +527,11 @@ fn sign_transaction_single_sig_standard(transaction: &str, secret_key: &StacksPr
        .ok_or("TX did not finish signing -- was this a standard single signature transaction?")?)
 }
 
-fn handle_contract_publish(args: &[String], version: TransactionVersion, chain_id: u32) -> Result<String, CliError> {
-    if args.len() >= 1 && args[0] == "-h" {
-        return Err(CliError::Message(format!("USAGE:\n {}", PUBLISH_USAGE)))
-    }
-    if args.len() != 5 {
-        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", PUBLISH_USAGE)))
-    }
-    let sk_publisher = &args[0];
-    let fee_rate = args[1].parse()?;
-    let nonce = args[2].parse()?;
-    let contract_name = &args[3];
-    let contract_file = &args[4];
+/// Make a contract publish payload from `[contract-name] [file-name.clar] [init-args...]`
+fn parse_contract_publish_payload(args: &[String]) -> Result<TransactionPayload, CliError> {
+    let contract_name = &args[0];
+    let contract_file = &args[1];
+    let init = parse_contract_init(&args[2..])?;
 
     let contract_contents = if contract_file == "-" {
         let mut buffer = String::new();
@@ -227,36 +541,17 @@ fn handle_contract_publish(args: &[String], version: TransactionVersion, chain_i
         fs::read_to_string(contract_file)?
     };
 
-    let sk_publisher = StacksPrivateKey::from_hex(sk_publisher)?;
-
     let payload = make_contract_publish(contract_name.clone(), contract_contents)?;
-    let unsigned_tx = make_standard_single_sig_tx(version, chain_id, payload.into(), &StacksPublicKey::from_private(&sk_publisher),
-                                                  nonce, fee_rate);
-    let mut unsigned_tx_bytes = vec![];
-    unsigned_tx.consensus_serialize(&mut unsigned_tx_bytes).expect("FATAL: invalid transaction");
-    let signed_tx = sign_transaction_single_sig_standard(
-        &to_hex(&unsigned_tx_bytes), &sk_publisher)?;
-
-    let mut signed_tx_bytes = vec![];
-    signed_tx.consensus_serialize(&mut signed_tx_bytes).expect("FATAL: invalid signed transaction");
-    Ok(to_hex(&signed_tx_bytes))
+    Ok(TransactionPayload::SmartContract(payload, init))
 }
 
-fn handle_contract_call(args: &[String], version: TransactionVersion, chain_id: u32) -> Result<String, CliError> {
-    if args.len() >= 1 && args[0] == "-h" {
-        return Err(CliError::Message(format!("USAGE:\n {}", CALL_USAGE)))
-    }
-    if args.len() < 6 {
-        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", CALL_USAGE)))
-    }
-    let sk_origin = &args[0];
-    let fee_rate = args[1].parse()?;
-    let nonce = args[2].parse()?;
-    let contract_address = &args[3];
-    let contract_name = &args[4];
-    let function_name = &args[5];
+/// Make a contract-call payload from `[contract-publisher-address] [contract-name] [function-name] [args...]`
+fn parse_contract_call_payload(args: &[String]) -> Result<TransactionPayload, CliError> {
+    let contract_address = &args[0];
+    let contract_name = &args[1];
+    let function_name = &args[2];
 
-    let val_args = &args[6..];
+    let val_args = &args[3..];
 
     if val_args.len() % 2 != 0 {
         return Err("contract-call arguments must be supplied as a list of `-e ...` or `-x 0000...` pairs".into())
@@ -267,73 +562,396 @@ fn handle_contract_call(args: &[String], version: TransactionVersion, chain_id:
     while arg_iterator < val_args.len() {
         let eval_method = &val_args[arg_iterator];
         let input = &val_args[arg_iterator+1];
-        let value = match eval_method.as_str() {
-            "-x" => {
-                Value::try_deserialize_hex_untyped(input)?
-            },
-            "-e" => {
-                vm::execute(input)?
-                    .ok_or("Supplied argument did not evaluate to a Value")?
-            },
-            _ => {
-                return Err("contract-call arguments must be supplied as a list of `-e ...` or `-x 0000...` pairs".into())
-            }
-        };
+        let value = eval_value_arg(eval_method, input)?
+            .ok_or("contract-call arguments must be supplied as a list of `-e ...` or `-x 0000...` pairs")?;
 
         values.push(value);
         arg_iterator += 2;
     }
 
+    let payload = make_contract_call(contract_address.clone(), contract_name.clone(), function_name.clone(), values)?;
+    Ok(payload.into())
+}
+
+/// Make a token transfer payload from `[recipient-address] [amount] [memo]`
+fn parse_token_transfer_payload(args: &[String]) -> Result<TransactionPayload, CliError> {
+    let recipient_address = PrincipalData::parse(&args[0])
+        .map_err(|_e| "Failed to parse recipient")?;
+    let amount = args[1].parse()?;
+    let memo = {
+        let mut memo = [0; 34];
+        let mut bytes = if args.len() == 3 { args[2].as_bytes().to_vec() } else { vec![] };
+        bytes.resize(34, 0);
+        memo.copy_from_slice(&bytes);
+        TokenTransferMemo(memo)
+    };
+
+    Ok(TransactionPayload::TokenTransfer(recipient_address, amount, memo))
+}
+
+fn handle_contract_publish(args: &[String], version: TransactionVersion, chain_id: u32, sponsored: bool) -> Result<String, CliError> {
+    if args.len() >= 1 && args[0] == "-h" {
+        return Err(CliError::Message(format!("USAGE:\n {}", PUBLISH_USAGE)))
+    }
+    let (args, mut pc_args) = parse_post_condition_args(args)?;
+    if args.len() < 5 {
+        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", PUBLISH_USAGE)))
+    }
+    let sk_publisher = &args[0];
+    let fee_rate = args[1].parse()?;
+    let nonce = args[2].parse()?;
+    let payload = parse_contract_publish_payload(&args[3..])?;
+
+    // funding the contract transfers STX from the origin, which a deny-mode transaction only
+    //   permits with a post-condition for it.
+    if let TransactionPayload::SmartContract(_, Some(ref init)) = payload {
+        let has_origin_stx_condition = pc_args.post_conditions.iter().any(|pc| match pc {
+            TransactionPostCondition::STX(PostConditionPrincipal::Origin, ..) => true,
+            _ => false
+        });
+        if init.amount > 0 && !has_origin_stx_condition {
+            pc_args.post_conditions.push(
+                TransactionPostCondition::STX(PostConditionPrincipal::Origin, FungibleConditionCode::SentEq, init.amount));
+        }
+    }
+
+    let sk_publisher = StacksPrivateKey::from_hex(sk_publisher)?;
+
+    let signed_tx = make_signed_single_sig_tx(version, chain_id, payload, &pc_args, &sk_publisher, nonce, fee_rate, sponsored)?;
+
+    let mut signed_tx_bytes = vec![];
+    signed_tx.consensus_serialize(&mut signed_tx_bytes).expect("FATAL: invalid signed transaction");
+    Ok(to_hex(&signed_tx_bytes))
+}
+
+fn handle_contract_call(args: &[String], version: TransactionVersion, chain_id: u32, sponsored: bool) -> Result<String, CliError> {
+    if args.len() >= 1 && args[0] == "-h" {
+        return Err(CliError::Message(format!("USAGE:\n {}", CALL_USAGE)))
+    }
+    let (args, pc_args) = parse_post_condition_args(args)?;
+    if args.len() < 6 {
+        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", CALL_USAGE)))
+    }
+    let sk_origin = &args[0];
+    let fee_rate = args[1].parse()?;
+    let nonce = args[2].parse()?;
+    let payload = parse_contract_call_payload(&args[3..])?;
+
     let sk_origin = StacksPrivateKey::from_hex(sk_origin)?;
 
-    let payload = make_contract_call(contract_address.clone(), contract_name.clone(), function_name.clone(), values)?;
-    let unsigned_tx = make_standard_single_sig_tx(version, chain_id, payload.into(), &StacksPublicKey::from_private(&sk_origin),
-                                                  nonce, fee_rate);
-    
-    let mut unsigned_tx_bytes = vec![];
-    unsigned_tx.consensus_serialize(&mut unsigned_tx_bytes).expect("FATAL: invalid transaction");
-    let signed_tx = sign_transaction_single_sig_standard(
-        &to_hex(&unsigned_tx_bytes), &sk_origin)?;
+    let signed_tx = make_signed_single_sig_tx(version, chain_id, payload, &pc_args, &sk_origin, nonce, fee_rate, sponsored)?;
 
     let mut signed_tx_bytes = vec![];
     signed_tx.consensus_serialize(&mut signed_tx_bytes).expect("FATAL: invalid signed transaction");
     Ok(to_hex(&signed_tx_bytes))
 }
 
-fn handle_token_transfer(args: &[String], version: TransactionVersion, chain_id: u32) -> Result<String, CliError> {
+fn handle_token_transfer(args: &[String], version: TransactionVersion, chain_id: u32, sponsored: bool) -> Result<String, CliError> {
     if args.len() >= 1 && args[0] == "-h" {
         return Err(CliError::Message(format!("USAGE:\n {}", TOKEN_TRANSFER_USAGE)))
     }
+    let (args, pc_args) = parse_post_condition_args(args)?;
     if args.len() < 5 {
         return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", TOKEN_TRANSFER_USAGE)))
     }
     let sk_origin = StacksPrivateKey::from_hex(&args[0])?;
     let fee_rate = args[1].parse()?;
     let nonce = args[2].parse()?;
-    let recipient_address = PrincipalData::parse(&args[3])
-        .map_err(|_e| "Failed to parse recipient")?;
-    let amount = &args[4].parse()?;
-    let memo = {
-        let mut memo = [0; 34];
-        let mut bytes = if args.len() == 6 { args[5].as_bytes().to_vec() } else { vec![] };
-        bytes.resize(34, 0);
-        memo.copy_from_slice(&bytes);
-        TokenTransferMemo(memo)
-    };
+    let payload = parse_token_transfer_payload(&args[3..])?;
 
-    let payload = TransactionPayload::TokenTransfer(recipient_address, *amount, memo);
-    let unsigned_tx = make_standard_single_sig_tx(version, chain_id, payload, &StacksPublicKey::from_private(&sk_origin),
-                                                  nonce, fee_rate);
-    let mut unsigned_tx_bytes = vec![];
-    unsigned_tx.consensus_serialize(&mut unsigned_tx_bytes).expect("FATAL: invalid transaction");
-    let signed_tx = sign_transaction_single_sig_standard(
-        &to_hex(&unsigned_tx_bytes), &sk_origin)?;
+    let signed_tx = make_signed_single_sig_tx(version, chain_id, payload, &pc_args, &sk_origin, nonce, fee_rate, sponsored)?;
 
     let mut signed_tx_bytes = vec![];
     signed_tx.consensus_serialize(&mut signed_tx_bytes).expect("FATAL: invalid signed transaction");
     Ok(to_hex(&signed_tx_bytes))
 }
 
+fn handle_sponsor(args: &[String]) -> Result<String, CliError> {
+    if args.len() >= 1 && args[0] == "-h" {
+        return Err(CliError::Message(format!("USAGE:\n {}", SPONSOR_USAGE)))
+    }
+    if args.len() != 4 {
+        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", SPONSOR_USAGE)))
+    }
+    let origin_tx = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(&args[0])?))?;
+    let sk_sponsor = StacksPrivateKey::from_hex(&args[1])?;
+    let fee_rate = args[2].parse()?;
+    let nonce = args[3].parse()?;
+
+    if !origin_tx.auth.is_sponsored() {
+        return Err("Transaction is not sponsored -- was it generated with --sponsored?".into())
+    }
+
+    let mut spending_condition = TransactionSpendingCondition::new_singlesig_p2pkh(StacksPublicKey::from_private(&sk_sponsor))
+        .expect("Failed to create p2pkh spending condition from public key.");
+    spending_condition.set_nonce(nonce);
+    spending_condition.set_fee_rate(fee_rate);
+
+    let mut tx_signer = StacksTransactionSigner::new_sponsor(&origin_tx, spending_condition)
+        .map_err(|e| CliError::Message(format!("Failed to verify the origin's signature: {:?}", e)))?;
+    tx_signer.sign_sponsor(&sk_sponsor)?;
+
+    let signed_tx = tx_signer.get_tx()
+        .ok_or("TX did not finish signing -- has the origin signed it?")?;
+
+    let mut signed_tx_bytes = vec![];
+    signed_tx.consensus_serialize(&mut signed_tx_bytes).expect("FATAL: invalid signed transaction");
+    Ok(to_hex(&signed_tx_bytes))
+}
+
+/// A multisig transaction which is still being signed, along with the public keys of the
+/// account's signers in signing order (which the transaction itself doesn't carry).
+/// A multisig spending condition only decodes with all of its signatures, so the encoded
+/// transaction requires as many signatures as it has so far, and the number it really requires
+/// is kept alongside it.  The number of signatures required is not part of the sighash.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PartialMultisigTx {
+    tx: String,
+    signatures_required: u16,
+    public_keys: Vec<String>
+}
+
+fn make_multisig_tx(version: TransactionVersion, chain_id: u32, payload: TransactionPayload,
+                    num_sigs: u16, public_keys: &Vec<StacksPublicKey>, nonce: u64, fee_rate: u64) -> Result<StacksTransaction, CliError> {
+    let mut spending_condition = TransactionSpendingCondition::new_multisig_p2sh(num_sigs, public_keys.clone())
+        .ok_or("Failed to create multisig spending condition from public keys")?;
+    spending_condition.set_nonce(nonce);
+    spending_condition.set_fee_rate(fee_rate);
+    let auth = TransactionAuth::Standard(spending_condition);
+    let mut tx = StacksTransaction::new(version, auth, payload);
+    tx.chain_id = chain_id;
+    Ok(tx)
+}
+
+fn encode_partial_multisig_tx(tx: &StacksTransaction, public_keys: &Vec<StacksPublicKey>) -> Result<String, CliError> {
+    let mut partial_tx = tx.clone();
+    let signatures_required = match partial_tx.auth {
+        TransactionAuth::Standard(TransactionSpendingCondition::Multisig(ref mut origin)) => {
+            let signatures_required = origin.signatures_required;
+            origin.signatures_required = origin.fields.iter().filter(|field| field.is_signature()).count() as u16;
+            signatures_required
+        },
+        _ => return Err("Not a multisig transaction".into())
+    };
+
+    let mut tx_bytes = vec![];
+    partial_tx.consensus_serialize(&mut tx_bytes).expect("FATAL: invalid transaction");
+    let partial = PartialMultisigTx {
+        tx: to_hex(&tx_bytes),
+        signatures_required,
+        public_keys: public_keys.iter().map(|pubk| pubk.to_hex()).collect()
+    };
+    Ok(serde_json::to_string_pretty(&partial)?)
+}
+
+/// Load a partially-signed multisig transaction from a file (or stdin, if `path` is `-`), and
+/// check that its public keys belong to the transaction's origin account.
+fn load_partial_multisig_tx(path: &str) -> Result<(StacksTransaction, Vec<StacksPublicKey>), CliError> {
+    let contents = if path == "-" {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        buffer
+    } else {
+        fs::read_to_string(path)?
+    };
+
+    let partial : PartialMultisigTx = serde_json::from_str(&contents)?;
+    let mut tx = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(&partial.tx)?))?;
+    let mut public_keys = vec![];
+    for pubk_hex in partial.public_keys.iter() {
+        public_keys.push(StacksPublicKey::from_hex(pubk_hex)?);
+    }
+
+    match tx.auth {
+        TransactionAuth::Standard(TransactionSpendingCondition::Multisig(ref mut origin)) => {
+            if origin.signatures_required > partial.signatures_required {
+                return Err("Transaction has too many signatures".into());
+            }
+            origin.signatures_required = partial.signatures_required;
+
+            let address = StacksAddress::from_public_keys(0, &origin.hash_mode.to_address_hash_mode(), origin.signatures_required as usize, &public_keys)
+                .ok_or("Failed to generate address from public keys")?;
+            if address.bytes != origin.signer {
+                return Err("Public keys do not match the transaction's multisig account".into());
+            }
+            if origin.fields.len() > public_keys.len() {
+                return Err("Transaction has more signers than public keys".into());
+            }
+            for (field, pubk) in origin.fields.iter().zip(public_keys.iter()) {
+                if let TransactionAuthField::PublicKey(ref field_pubk) = field {
+                    if field_pubk != pubk {
+                        return Err(CliError::Message(format!("Public key {} is out of order", field_pubk.to_hex())));
+                    }
+                }
+            }
+        },
+        _ => return Err("Not a multisig transaction".into())
+    };
+
+    Ok((tx, public_keys))
+}
+
+/// Add the public keys of the signers between the last one in the transaction and the given one
+/// as non-signers, checking that enough signers remain.  Returns the resumed signer.
+fn skip_to_multisig_signer(tx: &StacksTransaction, public_keys: &Vec<StacksPublicKey>, signer_index: usize, signing: bool) -> Result<StacksTransactionSigner, CliError> {
+    let origin = tx.auth.origin();
+    let num_fields = match origin {
+        TransactionSpendingCondition::Multisig(ref origin) => origin.fields.len(),
+        _ => return Err("Not a multisig transaction".into())
+    };
+
+    if signer_index < num_fields {
+        return Err(CliError::Message(format!("Public key {} has already been added to the transaction", public_keys[signer_index].to_hex())));
+    }
+    let num_sigs = origin.num_signatures() as usize + if signing { 1 } else { 0 };
+    if num_sigs > origin.signatures_required() as usize {
+        return Err("Transaction already has all of its signatures".into());
+    }
+    if num_sigs + (public_keys.len() - signer_index - 1) < origin.signatures_required() as usize {
+        return Err("Too few signers would remain to finish signing the transaction".into());
+    }
+
+    let mut tx_signer = StacksTransactionSigner::resume_origin(tx)?;
+    for pubk in public_keys[num_fields..signer_index].iter() {
+        tx_signer.append_origin(pubk)?;
+    }
+    Ok(tx_signer)
+}
+
+fn handle_multisig_create(args: &[String], version: TransactionVersion, chain_id: u32) -> Result<String, CliError> {
+    if args.len() >= 1 && args[0] == "-h" {
+        return Err(CliError::Message(format!("USAGE:\n {}", MULTISIG_CREATE_USAGE)))
+    }
+    let (args, pc_args) = parse_post_condition_args(args)?;
+    if args.len() < 5 {
+        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", MULTISIG_CREATE_USAGE)))
+    }
+    let num_sigs = args[0].parse()?;
+    let mut public_keys = vec![];
+    for pubk_hex in args[1].split(",") {
+        public_keys.push(StacksPublicKey::from_hex(pubk_hex)?);
+    }
+    let fee_rate = args[2].parse()?;
+    let nonce = args[3].parse()?;
+    let method_args = &args[5..];
+
+    let payload = match args[4].as_str() {
+        "publish" if method_args.len() >= 2 => parse_contract_publish_payload(method_args)?,
+        "contract-call" if method_args.len() >= 3 => parse_contract_call_payload(method_args)?,
+        "token-transfer" if method_args.len() >= 2 => parse_token_transfer_payload(method_args)?,
+        "publish" | "contract-call" | "token-transfer" => {
+            return Err(CliError::Message(format!("Incorrect argument count supplied for {}", args[4])))
+        },
+        _ => {
+            return Err(CliError::Message(format!("Unknown transaction method '{}'\n\nUSAGE:\n {}", args[4], MULTISIG_CREATE_USAGE)))
+        }
+    };
+
+    let mut unsigned_tx = make_multisig_tx(version, chain_id, payload, num_sigs, &public_keys, nonce, fee_rate)?;
+    pc_args.apply(&mut unsigned_tx);
+    encode_partial_multisig_tx(&unsigned_tx, &public_keys)
+}
+
+fn handle_multisig_sign(args: &[String]) -> Result<String, CliError> {
+    if args.len() >= 1 && args[0] == "-h" {
+        return Err(CliError::Message(format!("USAGE:\n {}", MULTISIG_SIGN_USAGE)))
+    }
+    if args.len() != 2 {
+        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", MULTISIG_SIGN_USAGE)))
+    }
+    let (tx, public_keys) = load_partial_multisig_tx(&args[0])?;
+    let sk_signer = StacksPrivateKey::from_hex(&args[1])?;
+    let pk_signer = StacksPublicKey::from_private(&sk_signer);
+
+    let signer_index = public_keys.iter().position(|pubk| *pubk == pk_signer)
+        .ok_or("Secret key is not one of the transaction's signers")?;
+
+    let mut tx_signer = skip_to_multisig_signer(&tx, &public_keys, signer_index, true)?;
+    tx_signer.sign_origin(&sk_signer)?;
+    encode_partial_multisig_tx(&tx_signer.get_tx_incomplete(), &public_keys)
+}
+
+fn handle_multisig_append_key(args: &[String]) -> Result<String, CliError> {
+    if args.len() >= 1 && args[0] == "-h" {
+        return Err(CliError::Message(format!("USAGE:\n {}", MULTISIG_APPEND_KEY_USAGE)))
+    }
+    if args.len() != 2 {
+        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", MULTISIG_APPEND_KEY_USAGE)))
+    }
+    let (tx, public_keys) = load_partial_multisig_tx(&args[0])?;
+    let pubk = StacksPublicKey::from_hex(&args[1])?;
+
+    let signer_index = public_keys.iter().position(|signer_pubk| *signer_pubk == pubk)
+        .ok_or("Public key is not one of the transaction's signers")?;
+
+    let mut tx_signer = skip_to_multisig_signer(&tx, &public_keys, signer_index, false)?;
+    tx_signer.append_origin(&pubk)?;
+    encode_partial_multisig_tx(&tx_signer.get_tx_incomplete(), &public_keys)
+}
+
+fn handle_multisig_inspect(args: &[String]) -> Result<String, CliError> {
+    if args.len() >= 1 && args[0] == "-h" {
+        return Err(CliError::Message(format!("USAGE:\n {}", MULTISIG_INSPECT_USAGE)))
+    }
+    if args.len() != 1 {
+        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", MULTISIG_INSPECT_USAGE)))
+    }
+    let (tx, public_keys) = load_partial_multisig_tx(&args[0])?;
+    let origin = match tx.auth.origin() {
+        TransactionSpendingCondition::Multisig(ref origin) => origin,
+        _ => unreachable!("BUG: loaded a partial transaction which is not multisig")
+    };
+
+    let signers : Vec<_> = public_keys.iter().enumerate()
+        .map(|(i, pubk)| {
+            let status = match origin.fields.get(i) {
+                Some(TransactionAuthField::Signature(..)) => "signed",
+                Some(TransactionAuthField::PublicKey(..)) => "not signing",
+                None => "pending"
+            };
+            json!({ "public_key": pubk.to_hex(), "status": status })
+        })
+        .collect();
+
+    let progress = json!({
+        "address": tx.origin_address().to_string(),
+        "signatures_required": origin.signatures_required,
+        "signatures": tx.auth.origin().num_signatures(),
+        "signers": signers
+    });
+    Ok(serde_json::to_string_pretty(&progress)?)
+}
+
+fn handle_multisig_finalize(args: &[String]) -> Result<String, CliError> {
+    if args.len() >= 1 && args[0] == "-h" {
+        return Err(CliError::Message(format!("USAGE:\n {}", MULTISIG_FINALIZE_USAGE)))
+    }
+    if args.len() != 1 {
+        return Err(CliError::Message(format!("Incorrect argument count supplied \n\nUSAGE:\n {}", MULTISIG_FINALIZE_USAGE)))
+    }
+    let (tx, public_keys) = load_partial_multisig_tx(&args[0])?;
+    let origin = tx.auth.origin();
+    if origin.num_signatures() < origin.signatures_required() {
+        return Err(CliError::Message(format!("Transaction has {} of its {} signatures", origin.num_signatures(), origin.signatures_required())));
+    }
+
+    let num_fields = match origin {
+        TransactionSpendingCondition::Multisig(ref origin) => origin.fields.len(),
+        _ => unreachable!("BUG: loaded a partial transaction which is not multisig")
+    };
+
+    let mut tx_signer = StacksTransactionSigner::resume_origin(&tx)?;
+    for pubk in public_keys[num_fields..].iter() {
+        tx_signer.append_origin(pubk)?;
+    }
+    let signed_tx = tx_signer.get_tx()
+        .ok_or("TX did not finish signing")?;
+    signed_tx.verify()?;
+
+    let mut signed_tx_bytes = vec![];
+    signed_tx.consensus_serialize(&mut signed_tx_bytes).expect("FATAL: invalid signed transaction");
+    Ok(to_hex(&signed_tx_bytes))
+}
 
 fn generate_secret_key(args: &[String], version: TransactionVersion) -> Result<String, CliError> {
     if args.len() >= 1 && args[0] == "-h" {
@@ -385,6 +1003,13 @@ fn main_handler(mut argv: Vec<String>) -> Result<String, CliError> {
         TransactionVersion::Mainnet
     };
 
+    let sponsored = if let Some(ix) = argv.iter().position(|x| x == "--sponsored") {
+        argv.remove(ix);
+        true
+    } else {
+        false
+    };
+
     let chain_id = 
         if tx_version == TransactionVersion::Testnet {
             TESTNET_CHAIN_ID
@@ -395,10 +1020,17 @@ fn main_handler(mut argv: Vec<String>) -> Result<String, CliError> {
 
     if let Some((method, args)) = argv.split_first() {
         match method.as_str() {
-            "contract-call" => handle_contract_call(args, tx_version, chain_id),
-            "publish" => handle_contract_publish(args, tx_version, chain_id),
-            "token-transfer" => handle_token_transfer(args, tx_version, chain_id),
+            "contract-call" => handle_contract_call(args, tx_version, chain_id, sponsored),
+            "publish" => handle_contract_publish(args, tx_version, chain_id, sponsored),
+            "token-transfer" => handle_token_transfer(args, tx_version, chain_id, sponsored),
+            "sponsor" => handle_sponsor(args),
+            _ if sponsored => Err("--sponsored is only supported by publish, contract-call and token-transfer".into()),
             "generate-sk" => generate_secret_key(args, tx_version),
+            "multisig-create" => handle_multisig_create(args, tx_version, chain_id),
+            "multisig-sign" => handle_multisig_sign(args),
+            "multisig-append-key" => handle_multisig_append_key(args),
+            "multisig-inspect" => handle_multisig_inspect(args),
+            "multisig-finalize" => handle_multisig_finalize(args),
             _ => Err(CliError::Usage)
         }
     } else {
@@ -445,6 +1077,58 @@ mod test {
 
     }
 
+    #[test]
+    fn publish_with_init() {
+        let publish_args = [
+            "publish",
+            "043ff5004e3d695060fa48ac94c96049b8c14ef441c50a184a6a3875d2a000f3",
+            "1",
+            "0",
+            "foo-contract",
+            "./sample-contracts/tokens.clar",
+            "-e", "owner", "'ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV",
+            "-x", "max-supply", "0100000000000000000000000000000064",
+            "--amount", "1000"];
+
+        let tx_hex = main_handler(to_string_vec(&publish_args)).unwrap();
+        let tx = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(&tx_hex).unwrap())).unwrap();
+        assert_eq!(tx.post_condition_mode, TransactionPostConditionMode::Deny);
+        assert_eq!(tx.post_conditions,
+                   vec![TransactionPostCondition::STX(PostConditionPrincipal::Origin, FungibleConditionCode::SentEq, 1000)]);
+        match tx.payload {
+            TransactionPayload::SmartContract(_, Some(init)) => {
+                assert_eq!(init.amount, 1000);
+                assert_eq!(init.arguments[0].0.as_str(), "owner");
+                assert_eq!(init.arguments[1], (ClarityName::try_from("max-supply".to_string()).unwrap(), Value::UInt(100)));
+            },
+            _ => panic!("Expected a smart contract payload with init arguments")
+        }
+
+        let publish_args = [
+            "publish",
+            "043ff5004e3d695060fa48ac94c96049b8c14ef441c50a184a6a3875d2a000f3",
+            "1",
+            "0",
+            "foo-contract",
+            "./sample-contracts/tokens.clar",
+            "-e", "owner"];
+
+        assert!(format!("{}", main_handler(to_string_vec(&publish_args)).unwrap_err())
+                .contains("publish init arguments"));
+
+        let publish_args = [
+            "publish",
+            "043ff5004e3d695060fa48ac94c96049b8c14ef441c50a184a6a3875d2a000f3",
+            "1",
+            "0",
+            "foo-contract",
+            "./sample-contracts/tokens.clar",
+            "--amount", "-1"];
+
+        assert!(format!("{}", main_handler(to_string_vec(&publish_args)).unwrap_err())
+                .contains("Failed to parse integer"));
+    }
+
     #[test]
     fn simple_token_transfer() {
         let tt_args = [
@@ -492,6 +1176,228 @@ mod test {
                 .contains("Failed to parse recipient"));
     }
 
+    #[test]
+    fn sponsored_token_transfer() {
+        let tt_args = [
+            "token-transfer",
+            "--sponsored",
+            "043ff5004e3d695060fa48ac94c96049b8c14ef441c50a184a6a3875d2a000f3",
+            "0",
+            "3",
+            "ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV",
+            "10"];
+
+        let origin_tx_hex = main_handler(to_string_vec(&tt_args)).unwrap();
+        let origin_tx = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(&origin_tx_hex).unwrap())).unwrap();
+        assert!(origin_tx.auth.is_sponsored());
+        assert!(origin_tx.verify().is_err());
+
+        let sk_sponsor = "6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001";
+        let tx_hex = main_handler(to_string_vec(&["sponsor", &origin_tx_hex, sk_sponsor, "100", "7"])).unwrap();
+        let tx = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(&tx_hex).unwrap())).unwrap();
+        tx.verify().unwrap();
+        assert_eq!(tx.get_fee_rate(), 100);
+        assert_eq!(tx.get_origin_nonce(), 3);
+        assert_eq!(tx.get_sponsor_nonce(), Some(7));
+        assert_eq!(tx.origin_address(), origin_tx.origin_address());
+
+        // standard transactions can't be sponsored
+        let tt_args = [
+            "token-transfer",
+            "043ff5004e3d695060fa48ac94c96049b8c14ef441c50a184a6a3875d2a000f3",
+            "1",
+            "0",
+            "ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV",
+            "10"];
+
+        let standard_tx_hex = main_handler(to_string_vec(&tt_args)).unwrap();
+        assert!(format!("{}", main_handler(to_string_vec(&["sponsor", &standard_tx_hex, sk_sponsor, "100", "7"])).unwrap_err())
+                .contains("not sponsored"));
+
+        assert!(format!("{}", main_handler(to_string_vec(&["generate-sk", "--sponsored"])).unwrap_err())
+                .contains("--sponsored is only supported"));
+    }
+
+    fn write_partial_tx(name: &str, partial_tx: &str) -> String {
+        let path = env::temp_dir().join(format!("blockstack-cli-{}.json", name));
+        fs::write(&path, partial_tx).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn multisig_sign_and_finalize() {
+        let sks = [
+            "6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001",
+            "2a584d899fed1d24e26b524f202763c8ab30260167429f157f1c119f550fa6af01",
+            "d5200dee706ee53ae98a03fba6cf4fdcc5084c30cfa9e1b3462dcdeaa3e0f1d201"];
+        let pks : Vec<String> = sks.iter()
+            .map(|sk| StacksPublicKey::from_private(&StacksPrivateKey::from_hex(sk).unwrap()).to_hex())
+            .collect();
+
+        let create_args = [
+            "multisig-create",
+            "--testnet",
+            "2",
+            &pks.join(","),
+            "1",
+            "0",
+            "token-transfer",
+            "ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV",
+            "10"];
+
+        let partial_tx = main_handler(to_string_vec(&create_args)).unwrap();
+        let path = write_partial_tx("multisig-sign-and-finalize", &partial_tx);
+
+        // the second signer signs first, so the first won't sign
+        let partial_tx = main_handler(to_string_vec(&["multisig-sign", &path, sks[1]])).unwrap();
+        fs::write(&path, &partial_tx).unwrap();
+
+        assert!(format!("{}", main_handler(to_string_vec(&["multisig-sign", &path, sks[0]])).unwrap_err())
+                .contains("already been added"));
+        assert!(format!("{}", main_handler(to_string_vec(&["multisig-finalize", &path])).unwrap_err())
+                .contains("1 of its 2 signatures"));
+
+        let progress : serde_json::Value = serde_json::from_str(&main_handler(to_string_vec(&["multisig-inspect", &path])).unwrap()).unwrap();
+        assert_eq!(progress["signatures"], 1);
+        assert_eq!(progress["signers"][0]["status"], "not signing");
+        assert_eq!(progress["signers"][1]["status"], "signed");
+        assert_eq!(progress["signers"][2]["status"], "pending");
+
+        let partial_tx = main_handler(to_string_vec(&["multisig-sign", &path, sks[2]])).unwrap();
+        fs::write(&path, &partial_tx).unwrap();
+
+        let tx_hex = main_handler(to_string_vec(&["multisig-finalize", &path])).unwrap();
+        let tx = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(&tx_hex).unwrap())).unwrap();
+        tx.verify().unwrap();
+        assert_eq!(tx.auth.origin().num_signatures(), 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn multisig_append_key() {
+        let sks = [
+            "6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001",
+            "2a584d899fed1d24e26b524f202763c8ab30260167429f157f1c119f550fa6af01",
+            "d5200dee706ee53ae98a03fba6cf4fdcc5084c30cfa9e1b3462dcdeaa3e0f1d201"];
+        let pks : Vec<String> = sks.iter()
+            .map(|sk| StacksPublicKey::from_private(&StacksPrivateKey::from_hex(sk).unwrap()).to_hex())
+            .collect();
+
+        let create_args = [
+            "multisig-create",
+            "2",
+            &pks.join(","),
+            "1",
+            "0",
+            "contract-call",
+            "SPJT598WY1RJN792HRKRHRQYFB7RJ5ZCG6J6GEZ4",
+            "foo-contract",
+            "transfer-fookens",
+            "-e",
+            "(+ 1 0)"];
+
+        let partial_tx = main_handler(to_string_vec(&create_args)).unwrap();
+        let path = write_partial_tx("multisig-append-key", &partial_tx);
+
+        let partial_tx = main_handler(to_string_vec(&["multisig-sign", &path, sks[0]])).unwrap();
+        fs::write(&path, &partial_tx).unwrap();
+
+        // with only two of three signers, skipping one more is fine but skipping two is not
+        assert!(format!("{}", main_handler(to_string_vec(&["multisig-append-key", &path, &pks[2]])).unwrap_err())
+                .contains("Too few signers"));
+
+        let partial_tx = main_handler(to_string_vec(&["multisig-append-key", &path, &pks[1]])).unwrap();
+        fs::write(&path, &partial_tx).unwrap();
+
+        let partial_tx = main_handler(to_string_vec(&["multisig-sign", &path, sks[2]])).unwrap();
+        fs::write(&path, &partial_tx).unwrap();
+
+        let tx_hex = main_handler(to_string_vec(&["multisig-finalize", &path])).unwrap();
+        let tx = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(&tx_hex).unwrap())).unwrap();
+        tx.verify().unwrap();
+
+        // keys which aren't the account's
+        let other_sk = StacksPrivateKey::new();
+        let other_pk = StacksPublicKey::from_private(&other_sk);
+        let bad_partial_tx = partial_tx.replace(&pks[2], &other_pk.to_hex());
+        fs::write(&path, &bad_partial_tx).unwrap();
+        assert!(format!("{}", main_handler(to_string_vec(&["multisig-inspect", &path])).unwrap_err())
+                .contains("do not match"));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn post_conditions() {
+        let tt_args = [
+            "token-transfer",
+            "043ff5004e3d695060fa48ac94c96049b8c14ef441c50a184a6a3875d2a000f3",
+            "1",
+            "0",
+            "ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV",
+            "10",
+            "--pc", "origin::sent-le::stx::10",
+            "Memo",
+            "--pc", "ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV.tokens::sent-ge::ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV.tokens::tokens::5",
+            "--pc", "origin::not-sent::ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV.names::names::(tuple (name u1))",
+            "--pc", "origin::sent::ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV.names::owners::'ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV",
+            "--pc", "origin::sent::ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV.names::keys::(tuple (owner 'ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV.names) (tags (list \"a\" \"b\")))",
+            "--pc-mode", "allow"];
+
+        let tx_hex = main_handler(to_string_vec(&tt_args)).unwrap();
+        let tx = StacksTransaction::consensus_deserialize(&mut io::Cursor::new(&hex_bytes(&tx_hex).unwrap())).unwrap();
+        tx.verify().unwrap();
+        assert_eq!(tx.post_condition_mode, TransactionPostConditionMode::Allow);
+        assert_eq!(tx.post_conditions.len(), 5);
+        assert_eq!(tx.post_conditions[0], TransactionPostCondition::STX(PostConditionPrincipal::Origin, FungibleConditionCode::SentLe, 10));
+        assert_eq!(tx.post_conditions[1].to_string(), tt_args[10]);
+        assert_eq!(tx.post_conditions[2].to_string(), "origin::not-sent::ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV.names::names::(tuple (name u1))");
+        // principal-valued assets are displayed as quoted principals
+        assert_eq!(tx.post_conditions[3].to_string(), tt_args[14]);
+        assert_eq!(tx.post_conditions[4].to_string(), tt_args[16]);
+        match tx.payload {
+            TransactionPayload::TokenTransfer(_, 10, memo) => assert_eq!(&memo.0[0..4], b"Memo"),
+            _ => panic!("Expected a token transfer payload")
+        }
+
+        // parsed post-conditions are the same as their displayed form
+        for postcond in tx.post_conditions.iter() {
+            assert_eq!(&parse_post_condition(&postcond.to_string()).unwrap(), postcond);
+        }
+
+        let bad_args = [
+            ("origin::sent::stx::10", "unknown condition code"),
+            ("origin::sent-le::stx", "expected principal::condition-code::asset::amount-or-value"),
+            ("origin::sent-le::btc::10", "expected an asset"),
+            ("SX1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV::sent-le::stx::10", "post-condition principal"),
+            ("origin::sent-le::stx::-1", "Failed to parse integer")];
+
+        for (bad_pc, expected_err) in bad_args.iter() {
+            let tt_args = [
+                "token-transfer",
+                "043ff5004e3d695060fa48ac94c96049b8c14ef441c50a184a6a3875d2a000f3",
+                "1",
+                "0",
+                "ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV",
+                "10",
+                "--pc", bad_pc];
+            assert!(format!("{}", main_handler(to_string_vec(&tt_args)).unwrap_err())
+                    .contains(expected_err));
+        }
+
+        let tt_args = [
+            "token-transfer",
+            "043ff5004e3d695060fa48ac94c96049b8c14ef441c50a184a6a3875d2a000f3",
+            "1",
+            "0",
+            "ST1A14RBKJ289E3DP89QAZE2RRHDPWP5RHMYFRCHV",
+            "10",
+            "--pc-mode", "maybe"];
+        assert!(format!("{}", main_handler(to_string_vec(&tt_args)).unwrap_err())
+                .contains("Unknown post-condition mode"));
+    }
+
     #[test]
     fn simple_cc() {
         let cc_args = [
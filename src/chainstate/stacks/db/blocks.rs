@@ -153,7 +153,9 @@ pub enum MemPoolRejection {
     FailedToValidate(Error),
     FeeTooLow(u64, u64),
     BadNonces(TransactionNonceMismatch),
+    BadSponsorNonce(TransactionNonceMismatch),
     NotEnoughFunds(u128, u128),
+    NotEnoughSponsorFunds(u128, u128),
     NoSuchContract,
     NoSuchPublicFunction,
     BadFunctionArgument(CheckError),
@@ -190,16 +192,33 @@ impl MemPoolRejection {
                      "actual": actual,
                      "principal": principal.to_string(),
                      "is_origin": is_origin}))),
+            BadSponsorNonce(TransactionNonceMismatch {
+                expected, actual, principal, .. }) =>
+                ("BadSponsorNonce",
+                 Some(json!({
+                     "expected": expected,
+                     "actual": actual,
+                     "principal": principal.to_string()}))),
             NotEnoughFunds(expected, actual) => 
                 ("NotEnoughFunds",
                  Some(json!({
                      "expected": format!("0x{}", to_hex(&expected.to_be_bytes())),
                      "actual": format!("0x{}", to_hex(&actual.to_be_bytes()))
                  }))),
+            NotEnoughSponsorFunds(expected, actual) => 
+                ("NotEnoughSponsorFunds",
+                 Some(json!({
+                     "expected": format!("0x{}", to_hex(&expected.to_be_bytes())),
+                     "actual": format!("0x{}", to_hex(&actual.to_be_bytes()))
+                 }))),
             NoSuchContract => ("NoSuchContract", None),
             NoSuchPublicFunction => ("NoSuchPublicFunction", None),
             BadFunctionArgument(e) => ("BadFunctionArgument",
-                                       Some(json!({"message": e.to_string()}))),
+                                       Some(json!({
+                                           "message": e.diagnostic.message,
+                                           "expected": e.diagnostic.expected,
+                                           "found": e.diagnostic.found,
+                                           "suggestion": e.diagnostic.suggestion}))),
             ContractAlreadyExists(id) => ("ContractAlreadyExists",
                                           Some(json!({ "contract_identifier": id.to_string() }))),
             PoisonMicroblocksDoNotConflict => ("PoisonMicroblocksDoNotConflict", None),
@@ -2990,6 +3009,7 @@ impl StacksChainState {
     /// Occurs as a single, atomic transaction against the (marf'ed) headers database and
     /// (un-marf'ed) staging block database, as well as against the chunk store.
     fn process_next_staging_block<'a>(&mut self, burn_tx: &mut BurnDBTx<'a>) -> Result<(Option<(StacksHeaderInfo, Vec<StacksTransactionReceipt>)>, Option<TransactionPayload>), Error> {
+        let tx_index = self.tx_index;
         let (mut chainstate_tx, clarity_instance) = self.chainstate_tx_begin()?;
 
         let blocks_path = chainstate_tx.blocks_tx.get_blocks_path().clone();
@@ -3181,6 +3201,11 @@ impl StacksChainState {
             StacksChainState::set_microblocks_confirmed(&mut chainstate_tx.blocks_tx, &next_staging_block.parent_burn_header_hash, &next_staging_block.parent_anchored_block_hash, last_microblock_seq)?;
         }
         StacksChainState::set_block_processed(&mut chainstate_tx.blocks_tx, Some(burn_tx), &next_chain_tip.burn_header_hash, &next_chain_tip.anchored_header.block_hash(), true)?;
+
+        if tx_index {
+            StacksChainState::index_block_transactions(&mut chainstate_tx.headers_tx, &next_chain_tip, &block, &next_microblocks, &receipts)?;
+        }
+        StacksChainState::index_trait_implementations(&mut chainstate_tx.headers_tx, &next_chain_tip, &receipts)?;
        
         chainstate_tx.commit()
             .map_err(Error::DBError)?;
@@ -3341,7 +3366,7 @@ impl StacksChainState {
 
         // 4: the account nonces must be correct
         let (origin, payer) = StacksChainState::check_transaction_nonces(clarity_connection, &tx)
-            .map_err(|e| MemPoolRejection::BadNonces(e))?;
+            .map_err(MemPoolRejection::from)?;
 
         if !StacksChainState::is_valid_address_version(chainstate_config.mainnet, origin.principal.version())
             || !StacksChainState::is_valid_address_version(chainstate_config.mainnet, payer.principal.version()) {
@@ -3350,6 +3375,10 @@ impl StacksChainState {
 
         // 5: the paying account must have enough funds
         if fee as u128 > payer.stx_balance {
+            if origin != payer {
+                // a sponsor pays the fee on the origin's behalf
+                return Err(MemPoolRejection::NotEnoughSponsorFunds(fee as u128, payer.stx_balance));
+            }
             match &tx.payload {
                 TransactionPayload::TokenTransfer(..) => {
                     // pass: we'll return a total_spent failure below.
@@ -3398,7 +3427,7 @@ impl StacksChainState {
                 function_type.check_args(&mut (), &arg_types)
                     .map_err(|e| MemPoolRejection::BadFunctionArgument(e))?;
             },
-            TransactionPayload::SmartContract(TransactionSmartContract { name, code_body: _ }) => {
+            TransactionPayload::SmartContract(TransactionSmartContract { name, .. }, init) => {
                 let contract_identifier = QualifiedContractIdentifier::new(tx.origin_address().into(), name.clone());
 
                 let exists = clarity_connection.with_analysis_db_readonly(|db| {
@@ -3408,6 +3437,19 @@ impl StacksChainState {
                 if exists {
                     return Err(MemPoolRejection::ContractAlreadyExists(contract_identifier))
                 }
+
+                // got the funds for the contract's initial balance?
+                if let Some(init) = init {
+                    let total_spent = (init.amount as u128) +
+                        if origin == payer {
+                            fee as u128
+                        } else {
+                            0
+                        };
+                    if total_spent > origin.stx_balance {
+                        return Err(MemPoolRejection::NotEnoughFunds(total_spent, origin.stx_balance))
+                    }
+                }
             },
             TransactionPayload::PoisonMicroblock(microblock_header_1, microblock_header_2) => {
                 if microblock_header_1.sequence != microblock_header_2.sequence ||
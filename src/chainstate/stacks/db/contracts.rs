@@ -17,6 +17,10 @@
  along with Blockstack. If not, see <http://www.gnu.org/licenses/>.
 */
 
+use rusqlite::Row;
+use rusqlite::types::ToSql;
+
+use std::cmp;
 use std::io;
 use std::io::prelude::*;
 use std::fmt;
@@ -26,14 +30,19 @@ use std::collections::{HashSet, HashMap};
 use chainstate::stacks::Error;
 use chainstate::stacks::*;
 use chainstate::stacks::db::*;
+use chainstate::stacks::events::StacksTransactionReceipt;
 
 use std::path::{Path, PathBuf};
 
 use util::db::Error as db_error;
 use util::db::{
     DBConn,
+    FromRow,
+    FromColumn,
     query_rows,
-    query_count
+    query_count,
+    u64_to_sql,
+    get_ancestor_block_height
 };
 
 use util::strings::StacksString;
@@ -59,7 +68,8 @@ use vm::ast::build_ast;
 use vm::analysis::run_analysis;
 use vm::types::{
     Value,
-    AssetIdentifier
+    AssetIdentifier,
+    TraitIdentifier
 };
 
 use vm::clarity::{
@@ -73,6 +83,25 @@ use vm::database::ClarityDatabase;
 
 use vm::contracts::Contract;
 
+/// A contract's declared implementation of a trait, as recorded in the trait implementation index
+struct TraitImplementationRow {
+    contract_id: QualifiedContractIdentifier,
+    index_block_hash: StacksBlockId
+}
+
+impl FromRow<TraitImplementationRow> for TraitImplementationRow {
+    fn from_row<'a>(row: &'a Row) -> Result<TraitImplementationRow, db_error> {
+        let contract_id_str : String = row.get("contract_id");
+        let contract_id = QualifiedContractIdentifier::parse(&contract_id_str)
+            .map_err(|_e| db_error::ParseError)?;
+        let index_block_hash = StacksBlockId::from_column(row, "index_block_hash")?;
+        Ok(TraitImplementationRow {
+            contract_id,
+            index_block_hash
+        })
+    }
+}
+
 impl StacksChainState {
     pub fn get_contract<T: ClarityConnection>(clarity_tx: &mut T, contract_id: &QualifiedContractIdentifier) -> Result<Option<Contract>, Error> {
         clarity_tx.with_clarity_db_readonly(|ref mut db| {
@@ -93,4 +122,156 @@ impl StacksChainState {
             }
         }).map_err(Error::ClarityError)
     }
+
+    /// Record the trait implementations declared by the contracts deployed in a newly-appended
+    /// block.  Must be called in the same transaction that stored the block's header.
+    pub fn index_trait_implementations<'a>(headers_tx: &mut StacksDBTx<'a>, tip: &StacksHeaderInfo, receipts: &Vec<StacksTransactionReceipt>) -> Result<(), Error> {
+        let index_block_hash = tip.index_block_hash();
+        let block_height = u64_to_sql(tip.block_height).map_err(Error::DBError)?;
+
+        for receipt in receipts.iter() {
+            if receipt.post_condition_aborted {
+                // the contract was not deployed
+                continue;
+            }
+            if let Some(ref contract_analysis) = receipt.contract_analysis {
+                let contract_id = contract_analysis.contract_identifier.to_string();
+                for trait_identifier in contract_analysis.implemented_traits.iter() {
+                    let args: &[&dyn ToSql] = &[&trait_identifier.to_string(), &contract_id, &index_block_hash, &block_height];
+                    headers_tx.execute("INSERT OR IGNORE INTO trait_implementations (trait_id, contract_id, index_block_hash, block_height) VALUES (?1, ?2, ?3, ?4)", args)
+                        .map_err(|e| Error::DBError(db_error::SqliteError(e)))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the contracts in the fork ending at the given tip which declared an implementation of
+    /// the given trait (via `impl-trait`), in the order they were deployed.  Skips the first
+    /// `offset` of them, and takes at most `max_results`.
+    pub fn get_trait_implementers(&self, tip: &StacksBlockId, trait_id: &TraitIdentifier, offset: usize, max_results: usize) -> Result<Vec<QualifiedContractIdentifier>, Error> {
+        let trait_id = trait_id.to_string();
+
+        // the implementations are read a page at a time, since the ones in other forks are skipped
+        let page_size = cmp::max(offset.saturating_add(max_results), 1) as i64;
+        let mut page_offset : i64 = 0;
+        let mut skipped = 0;
+        let mut implementers = vec![];
+        while implementers.len() < max_results {
+            let sql = "SELECT contract_id, index_block_hash FROM trait_implementations WHERE trait_id = ?1 \
+                       ORDER BY block_height, rowid LIMIT ?2 OFFSET ?3".to_string();
+            let args: &[&dyn ToSql] = &[&trait_id, &page_size, &page_offset];
+            let page = query_rows::<TraitImplementationRow, _>(&self.headers_db, &sql, args).map_err(Error::DBError)?;
+            let page_len = page.len() as i64;
+
+            for row in page.into_iter() {
+                if implementers.len() >= max_results {
+                    break;
+                }
+                if get_ancestor_block_height(&self.headers_state_index, &row.index_block_hash, tip).map_err(Error::DBError)?.is_none() {
+                    continue;
+                }
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                implementers.push(row.contract_id);
+            }
+
+            if page_len < page_size {
+                break;
+            }
+            page_offset += page_len;
+        }
+
+        Ok(implementers)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chainstate::stacks::db::test::*;
+    use chainstate::stacks::db::txindex::test::{advance_tip, make_tx};
+    use chainstate::stacks::index::TrieHash;
+    use vm::analysis::ContractAnalysis;
+    use vm::costs::{ExecutionCost, LimitedCostTracker};
+
+    fn make_deploy_receipt(privk: &StacksPrivateKey, nonce: u64, contract_id: &QualifiedContractIdentifier,
+                           implemented_traits: &[&TraitIdentifier], post_condition_aborted: bool) -> StacksTransactionReceipt {
+        let tx = make_tx(privk, nonce, TransactionPayload::SmartContract(TransactionSmartContract {
+            name: contract_id.name.clone(),
+            code_body: StacksString::from_str("(define-constant hello 1)").unwrap()
+        }, None));
+
+        let mut analysis = ContractAnalysis::new(contract_id.clone(), vec![], LimitedCostTracker::new_max_limit());
+        for trait_identifier in implemented_traits.iter() {
+            analysis.implemented_traits.insert((*trait_identifier).clone());
+        }
+
+        if post_condition_aborted {
+            StacksTransactionReceipt::from_condition_aborted_smart_contract(tx, vec![], 0, analysis, ExecutionCost::zero())
+        } else {
+            StacksTransactionReceipt::from_smart_contract(tx, vec![], 0, analysis, ExecutionCost::zero())
+        }
+    }
+
+    #[test]
+    fn test_index_trait_implementations_by_fork() {
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, "trait-index-by-fork");
+
+        let privk = StacksPrivateKey::from_hex("6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001").unwrap();
+        let addr = TransactionAuth::from_p2pkh(&privk).unwrap().origin().address_testnet();
+        let contract_id = |name: &str| QualifiedContractIdentifier::new(StandardPrincipalData::from(addr.clone()), name.into());
+
+        let trait_1 = TraitIdentifier::new(StandardPrincipalData::from(addr.clone()), "defun".into(), "trait-1".into());
+        let trait_2 = TraitIdentifier::new(StandardPrincipalData::from(addr.clone()), "defun".into(), "trait-2".into());
+
+        let genesis = StacksHeaderInfo::genesis_block_header_info(TrieHash([0u8; 32]));
+        let tip_a = advance_tip(&mut chainstate, &genesis, 1);
+        let tip_b = advance_tip(&mut chainstate, &genesis, 2);
+        let tip_a_2 = advance_tip(&mut chainstate, &tip_a, 3);
+
+        {
+            let mut tx = chainstate.headers_tx_begin().unwrap();
+            StacksChainState::index_trait_implementations(&mut tx, &tip_a, &vec![
+                make_deploy_receipt(&privk, 0, &contract_id("impl-1"), &[&trait_1, &trait_2], false),
+                make_deploy_receipt(&privk, 1, &contract_id("aborted"), &[&trait_1], true),
+                make_deploy_receipt(&privk, 2, &contract_id("no-impl"), &[], false),
+            ]).unwrap();
+            StacksChainState::index_trait_implementations(&mut tx, &tip_a_2, &vec![
+                make_deploy_receipt(&privk, 3, &contract_id("impl-2"), &[&trait_1], false),
+            ]).unwrap();
+            StacksChainState::index_trait_implementations(&mut tx, &tip_b, &vec![
+                make_deploy_receipt(&privk, 0, &contract_id("impl-3"), &[&trait_1], false),
+            ]).unwrap();
+            tx.commit().unwrap();
+        }
+
+        let tip_a_id = tip_a.index_block_hash();
+        let tip_a_2_id = tip_a_2.index_block_hash();
+        let tip_b_id = tip_b.index_block_hash();
+
+        assert_eq!(chainstate.get_trait_implementers(&tip_a_2_id, &trait_1, 0, 10).unwrap(),
+                   vec![contract_id("impl-1"), contract_id("impl-2")]);
+        assert_eq!(chainstate.get_trait_implementers(&tip_a_2_id, &trait_2, 0, 10).unwrap(),
+                   vec![contract_id("impl-1")]);
+        assert_eq!(chainstate.get_trait_implementers(&tip_a_id, &trait_1, 0, 10).unwrap(),
+                   vec![contract_id("impl-1")]);
+        assert_eq!(chainstate.get_trait_implementers(&tip_b_id, &trait_1, 0, 10).unwrap(),
+                   vec![contract_id("impl-3")]);
+        assert_eq!(chainstate.get_trait_implementers(&tip_b_id, &trait_2, 0, 10).unwrap(),
+                   vec![]);
+
+        // paging skips the implementers in other forks
+        assert_eq!(chainstate.get_trait_implementers(&tip_a_2_id, &trait_1, 0, 1).unwrap(),
+                   vec![contract_id("impl-1")]);
+        assert_eq!(chainstate.get_trait_implementers(&tip_a_2_id, &trait_1, 1, 1).unwrap(),
+                   vec![contract_id("impl-2")]);
+        assert_eq!(chainstate.get_trait_implementers(&tip_a_2_id, &trait_1, 2, 1).unwrap(),
+                   vec![]);
+        assert_eq!(chainstate.get_trait_implementers(&tip_a_2_id, &trait_1, 0, 0).unwrap(),
+                   vec![]);
+    }
 }
@@ -22,6 +22,7 @@ pub mod blocks;
 pub mod contracts;
 pub mod headers;
 pub mod transactions;
+pub mod txindex;
 
 use rusqlite::Transaction;
 use rusqlite::Connection;
@@ -116,6 +117,7 @@ pub struct StacksChainState {
     pub blocks_path: String,
     pub clarity_state_index_path: String,
     pub root_path: String,
+    pub tx_index: bool,         // if true, record confirmed transactions in the transaction index
     cached_header_hashes: BlockHeaderCache,
     cached_miner_payments: MinerPaymentCache,
 }
@@ -274,6 +276,10 @@ impl ClarityConnection for ClarityTx<'_> {
     where F: FnOnce(&mut AnalysisDatabase) -> R {
         self.block.with_analysis_db_readonly(to_do)
     }
+
+    fn is_mainnet(&self) -> bool {
+        self.block.is_mainnet()
+    }
 }
 
 impl<'a> ClarityTx<'a> {
@@ -444,6 +450,60 @@ const STACKS_CHAIN_STATE_SQL : &'static [&'static str]= &[
     )"#
 ];
 
+// The transaction index is created when a chain state database is opened, rather than only when
+//   it is instantiated, so that it can be enabled on an existing chain state.  It only records the
+//   blocks processed after that.  The same goes for the trait implementation index, which is
+//   always populated.
+const STACKS_CHAIN_STATE_TX_INDEX_SQL : &'static [&'static str] = &[
+    r#"
+    -- optional index of confirmed transactions.  Only populated if the transaction index is enabled.
+    CREATE TABLE IF NOT EXISTS transactions(
+        txid TEXT NOT NULL,
+        index_block_hash TEXT NOT NULL,         -- anchored block which confirmed this transaction (and the microblock it came in, if any)
+        block_height INTEGER NOT NULL,
+        tx_index INTEGER NOT NULL,              -- position in the anchored block or microblock
+        microblock_hash TEXT,
+        microblock_sequence INTEGER,
+        origin_address TEXT NOT NULL,
+        sponsor_address TEXT,
+        contract_id TEXT,                       -- contract called or deployed
+        status TEXT NOT NULL,
+        result TEXT NOT NULL,                   -- hex-encoded Clarity value
+        tx BLOB NOT NULL,
+
+        PRIMARY KEY(txid,index_block_hash)
+    );
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS transactions_by_origin ON transactions(origin_address);
+    CREATE INDEX IF NOT EXISTS transactions_by_sponsor ON transactions(sponsor_address);
+    CREATE INDEX IF NOT EXISTS transactions_by_contract ON transactions(contract_id);
+    "#,
+    r#"
+    -- accounts whose balance or nonce each confirmed transaction changed.  Populated with the transaction index.
+    CREATE TABLE IF NOT EXISTS account_changes(
+        principal TEXT NOT NULL,
+        index_block_hash TEXT NOT NULL,
+        block_height INTEGER NOT NULL,
+        txid TEXT NOT NULL,
+
+        PRIMARY KEY(principal,index_block_hash,txid)
+    );
+    "#,
+    r#"
+    -- contracts which declared an implementation of a trait (with `impl-trait`), by the block which deployed them.
+    CREATE TABLE IF NOT EXISTS trait_implementations(
+        trait_id TEXT NOT NULL,                 -- defining contract and trait name, as `contract.trait`
+        contract_id TEXT NOT NULL,              -- implementing contract
+        index_block_hash TEXT NOT NULL,
+        block_height INTEGER NOT NULL,
+
+        PRIMARY KEY(trait_id,contract_id,index_block_hash)
+    );
+    CREATE INDEX IF NOT EXISTS trait_implementations_by_height ON trait_implementations(trait_id,block_height);
+    "#,
+];
+
 /// Built-in "system-level" smart contracts that are there from the beginning.
 /// Includes BNS and the miner trust fund.
 #[cfg(test)]
@@ -510,6 +570,10 @@ impl StacksChainState {
             tx.execute(cmd, NO_PARAMS).map_err(|e| Error::DBError(db_error::SqliteError(e)))?;
         }
 
+        for cmd in STACKS_CHAIN_STATE_TX_INDEX_SQL {
+            tx.execute_batch(cmd).map_err(|e| Error::DBError(db_error::SqliteError(e)))?;
+        }
+
         tx.execute("INSERT INTO db_config (version,mainnet,chain_id) VALUES (?1,?2,?3)", &[&CHAINSTATE_VERSION, &(if mainnet { 1 } else { 0 }) as &dyn ToSql, &chain_id as &dyn ToSql])
             .map_err(|e| Error::DBError(db_error::SqliteError(e)))?;
 
@@ -562,6 +626,13 @@ impl StacksChainState {
                 error!("Invalid chain ID: expected {}, got {}", chain_id, db_config.chain_id);
                 return Err(Error::InvalidChainstateDB);
             }
+
+            // migrate chain states which predate the transaction index
+            let tx = tx_begin_immediate(&mut conn)?;
+            for cmd in STACKS_CHAIN_STATE_TX_INDEX_SQL {
+                tx.execute_batch(cmd).map_err(|e| Error::DBError(db_error::SqliteError(e)))?;
+            }
+            tx.commit().map_err(|e| Error::DBError(db_error::SqliteError(e)))?;
         }
 
         Ok(conn)
@@ -637,7 +708,7 @@ impl StacksChainState {
                     TransactionSmartContract {
                         name: ContractName::try_from(STACKS_BOOT_CODE_CONTRACT_NAMES[i].to_string()).expect("FATAL: invalid boot-code contract name"),
                         code_body: StacksString::from_str(&STACKS_BOOT_CODE[i].to_string()).expect("FATAL: invalid boot code body"),
-                    }
+                    }, None
                 );
 
                 let boot_code_smart_contract = StacksTransaction::new(tx_version.clone(), boot_code_auth.clone(), smart_contract);
@@ -654,7 +725,7 @@ impl StacksChainState {
                     TransactionSmartContract {
                         name: ContractName::try_from(additional_boot_code_contract_names[i].clone()).expect("FATAL: invalid additional boot-code contract name"),
                         code_body: StacksString::from_str(&additional_boot_code[i]).expect("FATAL: invalid additional boot code body"),
-                    }
+                    }, None
                 );
 
                 let boot_code_smart_contract = StacksTransaction::new(tx_version.clone(), boot_code_auth.clone(), smart_contract);
@@ -792,7 +863,7 @@ impl StacksChainState {
         let vm_state = MarfedKV::open(&clarity_state_index_root, Some(&StacksBlockHeader::make_index_block_hash(&MINER_BLOCK_BURN_HEADER_HASH, &MINER_BLOCK_HEADER_HASH)))
             .map_err(|e| Error::ClarityError(e.into()))?;
 
-        let clarity_state = ClarityInstance::new(vm_state, block_limit);
+        let clarity_state = ClarityInstance::new(mainnet, vm_state, block_limit);
 
         let mut chainstate = StacksChainState {
             mainnet: mainnet,
@@ -804,6 +875,7 @@ impl StacksChainState {
             blocks_path: blocks_path_root,
             clarity_state_index_path: clarity_state_index_marf,
             root_path: path_str.to_string(),
+            tx_index: false,
             cached_header_hashes: BlockHeaderCache::new(),
             cached_miner_payments: MinerPaymentCache::new(),
         };
@@ -867,6 +939,11 @@ impl StacksChainState {
         Ok((chainstate_tx, clarity_instance))
     }
 
+    /// The execution cost limit of each block processed by this chain state.
+    pub fn block_limit(&self) -> ExecutionCost {
+        self.clarity_state.block_limit().clone()
+    }
+
     pub fn clarity_eval_read_only(&mut self, parent_id_bhh: &StacksBlockId,
                                   contract: &QualifiedContractIdentifier, code: &str) -> Value {
         let result = self.clarity_state.eval_read_only(parent_id_bhh, &self.headers_db, contract, code);
@@ -54,12 +54,21 @@ use vm::types::{
 
 use vm::contexts::{
     AssetMap,
-    AssetMapEntry
+    AssetMapEntry,
+    OwnedEnvironment
 };
 
 use vm::ast::build_ast;
 use vm::analysis::run_analysis;
-use vm::costs::ExecutionCost;
+use vm::costs::{
+    ExecutionCost,
+    LimitedCostTracker
+};
+
+use vm::representations::{
+    ClarityName,
+    SymbolicExpression
+};
 
 use vm::types::{
     Value,
@@ -83,6 +92,39 @@ use vm::database::ClarityDatabase;
 
 use vm::contracts::Contract;
 
+/// Changes to the chain state which are applied before simulating a contract-call.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SimulationOverrides {
+    pub stx_balances: Vec<(PrincipalData, u128)>,
+    pub data_vars: Vec<(QualifiedContractIdentifier, ClarityName, Value)>,
+    /// entries are deleted if no value is given.
+    pub map_entries: Vec<(QualifiedContractIdentifier, ClarityName, Value, Option<Value>)>,
+    pub block_height: Option<u32>,
+}
+
+impl SimulationOverrides {
+    /// Write the overrides to `db`.  Data vars and map entries are checked against their
+    /// declared types.
+    pub fn apply(&self, db: &mut ClarityDatabase) -> Result<(), InterpreterError> {
+        for (principal, balance) in self.stx_balances.iter() {
+            db.set_account_stx_balance(principal, *balance);
+        }
+        for (contract_identifier, var_name, value) in self.data_vars.iter() {
+            db.set_variable(contract_identifier, var_name, value.clone())?;
+        }
+        for (contract_identifier, map_name, key, value) in self.map_entries.iter() {
+            match value {
+                Some(value) => db.set_entry(contract_identifier, map_name, key.clone(), value.clone())?,
+                None => db.delete_entry(contract_identifier, map_name, key)?
+            };
+        }
+        if let Some(block_height) = self.block_height {
+            db.set_simulated_block_height(block_height);
+        }
+        Ok(())
+    }
+}
+
 // make it possible to have a set of Values
 impl std::hash::Hash for Value {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -205,7 +247,11 @@ impl From<TransactionNonceMismatch> for Error {
 
 impl From<TransactionNonceMismatch> for MemPoolRejection {
     fn from(e: TransactionNonceMismatch) -> MemPoolRejection {
-        MemPoolRejection::BadNonces(e)
+        if e.is_origin {
+            MemPoolRejection::BadNonces(e)
+        } else {
+            MemPoolRejection::BadSponsorNonce(e)
+        }
     }
 }
 
@@ -463,6 +509,45 @@ impl StacksChainState {
         return true;
     }
 
+    /// Simulate a contract-call to a public or read-only function from `sender`, after applying
+    /// the given state overrides.  Post-conditions are checked against the resulting asset map
+    /// the way they would be for a mined transaction originated by `sender`.  Nothing is committed,
+    /// including the changes made by the overrides.
+    pub fn simulate_contract_call<T: ClarityConnection>(clarity_tx: &mut T, sender: &PrincipalData, contract_id: &QualifiedContractIdentifier,
+                                                        function: &str, args: &[Value], post_conditions: &Vec<TransactionPostCondition>,
+                                                        post_condition_mode: &TransactionPostConditionMode, overrides: &SimulationOverrides,
+                                                        cost_track: LimitedCostTracker) -> Result<StacksSimulationReceipt, clarity_error> {
+        let args: Vec<_> = args.iter().map(|x| SymbolicExpression::atom_value(x.clone())).collect();
+        let mainnet = clarity_tx.is_mainnet();
+
+        clarity_tx.with_clarity_db_readonly_owned(|mut db| {
+            if let Err(e) = overrides.apply(&mut db) {
+                return (Err(clarity_error::Interpreter(e)), db);
+            }
+
+            let origin_account = StacksAccount {
+                principal: sender.clone(),
+                nonce: db.get_account_nonce(sender),
+                stx_balance: db.get_account_stx_balance(sender)
+            };
+
+            let mut vm_env = OwnedEnvironment::new_cost_limited(mainnet, db, cost_track);
+            let result = vm_env.execute_transaction(sender.clone().into(), contract_id.clone(), function, &args);
+            let execution_cost = vm_env.get_cost_total();
+            let (db, _) = vm_env.destruct()
+                .expect("Failed to recover database reference after executing transaction");
+
+            let receipt = result
+                .map(|(result, asset_map, events)| {
+                    let post_condition_passed = StacksChainState::check_transaction_postconditions(
+                        post_conditions, post_condition_mode, &origin_account, &asset_map);
+                    StacksSimulationReceipt { result, events, asset_map, post_condition_passed, execution_cost }
+                })
+                .map_err(clarity_error::Interpreter);
+            (receipt, db)
+        })
+    }
+
     /// Process a token transfer payload (but pass the transaction that wraps it, in order to do
     /// post-condition checks).
     fn process_transaction_token_transfer(clarity_tx: &mut ClarityTransactionConnection, txid: &Txid,
@@ -591,7 +676,7 @@ impl StacksChainState {
                 let receipt = StacksTransactionReceipt::from_contract_call(tx.clone(), events, result, asset_map.get_stx_burned_total(), total_cost);
                 Ok(receipt)
             },
-            TransactionPayload::SmartContract(ref smart_contract) => {
+            TransactionPayload::SmartContract(ref smart_contract, ref init) => {
                 let issuer_principal = match origin_account.principal {
                     PrincipalData::Standard(ref p) => {
                         p.clone()
@@ -603,6 +688,10 @@ impl StacksChainState {
 
                 let contract_id = QualifiedContractIdentifier::new(issuer_principal, smart_contract.name.clone());
                 let contract_code_str = smart_contract.code_body.to_string();
+                let (init_args, init_amount) = match *init {
+                    Some(ref init) => (&init.arguments[..], init.amount as u128),
+                    None => (&[][..], 0)
+                };
 
                 // can't be instantiated already -- if this fails, then the transaction is invalid
                 // (because this can be checked statically by the miner before mining the block).
@@ -618,7 +707,7 @@ impl StacksChainState {
                 // analysis pass -- if this fails, then the transaction is still accepted, but nothing is stored or processed.
                 // The reason for this is that analyzing the transaction is itself an expensive
                 // operation, and the paying account will need to be debited the fee regardless.
-                let analysis_resp = clarity_tx.analyze_smart_contract(&contract_id, &contract_code_str);
+                let analysis_resp = clarity_tx.analyze_smart_contract_with_args(&contract_id, &contract_code_str, init_args);
                 let (contract_ast, contract_analysis) = match analysis_resp {
                     Ok(x) => x,
                     Err(e) => {
@@ -647,8 +736,9 @@ impl StacksChainState {
 
                 // execution -- if this fails due to a runtime error, then the transaction is still
                 // accepted, but the contract does not materialize (but the sender is out their fee).
-                let initialize_resp = clarity_tx.initialize_smart_contract(
-                    &contract_id, &contract_ast, &contract_code_str,
+                // This includes the origin being unable to fund the contract with its init amount.
+                let initialize_resp = clarity_tx.initialize_funded_smart_contract(
+                    &contract_id, &contract_ast, &contract_code_str, init_args, init_amount,
                     |asset_map, _| {
                         !StacksChainState::check_transaction_postconditions(&tx.post_conditions, &tx.post_condition_mode,
                                                                             origin_account, asset_map) });
@@ -1249,6 +1339,94 @@ pub mod test {
         conn.commit_block();
     }
 
+    #[test]
+    fn process_smart_contract_init_transaction() {
+        let contract = "
+        (define-data-var limit uint max-amount)
+        (define-read-only (get-owner) owner)
+        (define-read-only (get-limit) (var-get limit))";
+
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, "process-smart-contract-init-transaction");
+
+        let privk = StacksPrivateKey::from_hex("6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001").unwrap();
+        let auth = TransactionAuth::from_p2pkh(&privk).unwrap();
+        let addr = auth.origin().address_testnet();
+        let owner = PrincipalData::from(StandardPrincipalData(1, [2u8; 20]));
+
+        let make_tx = |name: &str, amount: u64, nonce: u64| {
+            let init = TransactionContractInit {
+                arguments: vec![(ClarityName::from("owner"), owner.clone().into()),
+                                (ClarityName::from("max-amount"), Value::UInt(1000))],
+                amount
+            };
+            let mut payload = TransactionPayload::new_smart_contract(&name.to_string(), &contract.to_string()).unwrap();
+            if let TransactionPayload::SmartContract(_, ref mut sc_init) = payload {
+                *sc_init = Some(init);
+            }
+
+            let mut tx_contract = StacksTransaction::new(TransactionVersion::Testnet, auth.clone(), payload);
+            tx_contract.chain_id = 0x80000000;
+            tx_contract.post_condition_mode = TransactionPostConditionMode::Allow;
+            tx_contract.set_fee_rate(0);
+            tx_contract.set_origin_nonce(nonce);
+
+            let mut signer = StacksTransactionSigner::new(&tx_contract);
+            signer.sign_origin(&privk).unwrap();
+            signer.get_tx().unwrap()
+        };
+
+        let mut conn = chainstate.block_begin(&FIRST_BURNCHAIN_BLOCK_HASH, &FIRST_STACKS_BLOCK_HASH, &BurnchainHeaderHash([1u8; 32]), &BlockHeaderHash([1u8; 32]));
+
+        conn.connection().as_transaction(
+            |tx| StacksChainState::account_credit(tx, &addr.to_account_principal(), 300));
+
+        let (_fee, receipt) = StacksChainState::process_transaction(&mut conn, &make_tx("hello-world", 200, 0)).unwrap();
+        assert_eq!(receipt.events.len(), 1);
+        let funded_cost = receipt.execution_cost;
+
+        let contract_id = QualifiedContractIdentifier::new(StandardPrincipalData::from(addr.clone()), ContractName::from("hello-world"));
+        assert!(StacksChainState::get_contract(&mut conn, &contract_id).unwrap().is_some());
+
+        // the contract was funded by the origin
+        let account = StacksChainState::get_account(&mut conn, &addr.to_account_principal());
+        assert_eq!(account.nonce, 1);
+        assert_eq!(account.stx_balance, 100);
+        assert_eq!(StacksChainState::get_account(&mut conn, &contract_id.clone().into()).stx_balance, 200);
+
+        // the init arguments are bound as constants
+        let read = |conn: &mut ClarityTx, function: &str| {
+            StacksChainState::simulate_contract_call(conn, &addr.to_account_principal(), &contract_id, function, &[], &vec![],
+                                                     &TransactionPostConditionMode::Allow, &SimulationOverrides::default(),
+                                                     LimitedCostTracker::new_max_limit()).unwrap().result
+        };
+        assert_eq!(read(&mut conn, "get-owner"), owner.clone().into());
+        assert_eq!(read(&mut conn, "get-limit"), Value::UInt(1000));
+
+        // ...and stored alongside the contract's source
+        let init_args = conn.with_clarity_db_readonly(|db| db.get_contract_init_args(&contract_id));
+        assert_eq!(init_args, vec![(ClarityName::from("owner"), owner.clone().into()),
+                                   (ClarityName::from("max-amount"), Value::UInt(1000))]);
+
+        // the origin can't fund a second contract, so it does not materialize
+        let (_fee, _) = StacksChainState::process_transaction(&mut conn, &make_tx("hello-world-2", 200, 1)).unwrap();
+
+        let contract_id = QualifiedContractIdentifier::new(StandardPrincipalData::from(addr.clone()), ContractName::from("hello-world-2"));
+        assert!(StacksChainState::get_contract(&mut conn, &contract_id).unwrap().is_none());
+
+        let account = StacksChainState::get_account(&mut conn, &addr.to_account_principal());
+        assert_eq!(account.nonce, 2);
+        assert_eq!(account.stx_balance, 100);
+
+        // funding a contract costs the same as an stx-transfer?
+        let (_fee, receipt) = StacksChainState::process_transaction(&mut conn, &make_tx("hello-world-3", 0, 2)).unwrap();
+        let contract_id = QualifiedContractIdentifier::new(StandardPrincipalData::from(addr.clone()), ContractName::from("hello-world-3"));
+        assert!(StacksChainState::get_contract(&mut conn, &contract_id).unwrap().is_some());
+        assert_eq!(funded_cost.write_count, receipt.execution_cost.write_count + 1);
+        assert_eq!(funded_cost.read_count, receipt.execution_cost.read_count + 1);
+
+        conn.commit_block();
+    }
+
     #[test]
     fn process_smart_contract_sponsored_transaction() {
         let contract = "
@@ -3287,5 +3465,111 @@ pub mod test {
         assert_eq!(fee, 0);
         if let Error::InvalidFee = err {} else { assert!(false) };
     }
+    #[test]
+    fn simulate_contract_call_with_overrides() {
+        let contract = "
+        (define-data-var open bool false)
+        (define-map credits ((owner principal)) ((amount uint)))
+        (define-public (pay (recipient principal) (amount uint))
+          (begin (asserts! (var-get open) (err u1))
+                 (stx-transfer? amount tx-sender recipient)))
+        (define-read-only (get-credit (owner principal))
+          (default-to u0 (get amount (map-get? credits ((owner owner))))))
+        (define-read-only (get-height) block-height)
+        (define-read-only (get-height-at (block (buff 32))) (at-block block block-height))";
+
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, "simulate-contract-call-with-overrides");
+
+        let privk = StacksPrivateKey::from_hex("6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001").unwrap();
+        let auth = TransactionAuth::from_p2pkh(&privk).unwrap();
+        let addr = auth.origin().address_testnet();
+        let sender = addr.to_account_principal();
+        let recipient = PrincipalData::from(StandardPrincipalData(1, [2u8; 20]));
+
+        let mut tx_contract = StacksTransaction::new(TransactionVersion::Testnet,
+                                                     auth.clone(),
+                                                     TransactionPayload::new_smart_contract(&"hello-world".to_string(), &contract.to_string()).unwrap());
+
+        tx_contract.chain_id = 0x80000000;
+        tx_contract.set_fee_rate(0);
+
+        let mut signer = StacksTransactionSigner::new(&tx_contract);
+        signer.sign_origin(&privk).unwrap();
+
+        let signed_tx = signer.get_tx().unwrap();
+
+        let mut conn = chainstate.block_begin(&FIRST_BURNCHAIN_BLOCK_HASH, &FIRST_STACKS_BLOCK_HASH, &BurnchainHeaderHash([1u8; 32]), &BlockHeaderHash([1u8; 32]));
+        StacksChainState::process_transaction(&mut conn, &signed_tx).unwrap();
+
+        let contract_id = QualifiedContractIdentifier::new(StandardPrincipalData::from(addr.clone()), ContractName::from("hello-world"));
+        let pay_args = [recipient.clone().into(), Value::UInt(100)];
+
+        let simulate = |conn: &mut ClarityTx, function: &str, args: &[Value], post_conditions: &Vec<TransactionPostCondition>, overrides: &SimulationOverrides| {
+            StacksChainState::simulate_contract_call(conn, &sender, &contract_id, function, args, post_conditions,
+                                                     &TransactionPostConditionMode::Deny, overrides, LimitedCostTracker::new_max_limit()).unwrap()
+        };
+
+        // no overrides
+        let receipt = simulate(&mut conn, "pay", &pay_args, &vec![], &SimulationOverrides::default());
+        assert_eq!(receipt.result, Value::error(Value::UInt(1)).unwrap());
+        assert!(receipt.post_condition_passed);
+        assert!(receipt.execution_cost.runtime > 0);
+
+        // open the contract and fund the sender
+        let overrides = SimulationOverrides {
+            stx_balances: vec![(sender.clone(), 1000)],
+            data_vars: vec![(contract_id.clone(), ClarityName::from("open"), Value::Bool(true))],
+            .. SimulationOverrides::default()
+        };
+        let receipt = simulate(&mut conn, "pay", &pay_args, &vec![], &overrides);
+        assert_eq!(receipt.result, Value::okay_true());
+        assert_eq!(receipt.asset_map.get_stx(&sender), Some(100));
+        assert_eq!(receipt.events.len(), 1);
+        // the transfer isn't covered by a post-condition
+        assert!(!receipt.post_condition_passed);
+
+        let post_conditions = vec![TransactionPostCondition::STX(PostConditionPrincipal::Origin, FungibleConditionCode::SentEq, 100)];
+        let receipt = simulate(&mut conn, "pay", &pay_args, &post_conditions, &overrides);
+        assert!(receipt.post_condition_passed);
+
+        let post_conditions = vec![TransactionPostCondition::STX(PostConditionPrincipal::Origin, FungibleConditionCode::SentLt, 100)];
+        let receipt = simulate(&mut conn, "pay", &pay_args, &post_conditions, &overrides);
+        assert!(!receipt.post_condition_passed);
+
+        // nothing was committed
+        assert_eq!(StacksChainState::get_account(&mut conn, &sender).stx_balance, 0);
+        let receipt = simulate(&mut conn, "pay", &pay_args, &vec![], &SimulationOverrides::default());
+        assert_eq!(receipt.result, Value::error(Value::UInt(1)).unwrap());
+
+        // map entries and block height
+        let owner_args = [sender.clone().into()];
+        let key = Value::from(TupleData::from_data(vec![(ClarityName::from("owner"), sender.clone().into())]).unwrap());
+        let value = Value::from(TupleData::from_data(vec![(ClarityName::from("amount"), Value::UInt(5))]).unwrap());
+        let overrides = SimulationOverrides {
+            map_entries: vec![(contract_id.clone(), ClarityName::from("credits"), key, Some(value))],
+            block_height: Some(1000),
+            .. SimulationOverrides::default()
+        };
+        let receipt = simulate(&mut conn, "get-credit", &owner_args, &vec![], &overrides);
+        assert_eq!(receipt.result, Value::UInt(5));
+        let receipt = simulate(&mut conn, "get-height", &[], &vec![], &overrides);
+        assert_eq!(receipt.result, Value::UInt(1000));
+
+        // the simulated block height doesn't carry over into at-block
+        let boot_block = StacksBlockHeader::make_index_block_hash(&FIRST_BURNCHAIN_BLOCK_HASH, &FIRST_STACKS_BLOCK_HASH);
+        let receipt = simulate(&mut conn, "get-height-at", &[Value::buff_from(boot_block.as_bytes().to_vec()).unwrap()], &vec![], &overrides);
+        assert_eq!(receipt.result, Value::UInt(0));
+
+        // overrides are type-checked
+        let overrides = SimulationOverrides {
+            data_vars: vec![(contract_id.clone(), ClarityName::from("open"), Value::UInt(1))],
+            .. SimulationOverrides::default()
+        };
+        assert!(StacksChainState::simulate_contract_call(&mut conn, &sender, &contract_id, "pay", &pay_args, &vec![],
+                                                         &TransactionPostConditionMode::Deny, &overrides, LimitedCostTracker::new_max_limit()).is_err());
+
+        conn.commit_block();
+    }
+
     // TODO: test poison microblock
 }
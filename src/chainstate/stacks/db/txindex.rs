@@ -0,0 +1,748 @@
+/*
+ copyright: (c) 2013-2019 by Blockstack PBC, a public benefit corporation.
+
+ This file is part of Blockstack.
+
+ Blockstack is free software. You may redistribute or modify
+ it under the terms of the GNU General Public License as published by
+ the Free Software Foundation, either version 3 of the License or
+ (at your option) any later version.
+
+ Blockstack is distributed in the hope that it will be useful,
+ but WITHOUT ANY WARRANTY, including without the implied warranty of
+ MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ GNU General Public License for more details.
+
+ You should have received a copy of the GNU General Public License
+ along with Blockstack. If not, see <http://www.gnu.org/licenses/>.
+*/
+
+use rusqlite::Row;
+use rusqlite::types::ToSql;
+
+use std::cmp;
+use std::collections::HashMap;
+
+use burnchains::Txid;
+
+use chainstate::stacks::Error;
+use chainstate::stacks::*;
+use chainstate::stacks::db::*;
+use chainstate::stacks::events::{
+    StacksTransactionReceipt,
+    StacksTransactionEvent,
+    STXEventType
+};
+
+use net::StacksMessageCodec;
+
+use util::db::Error as db_error;
+use util::db::{
+    FromRow,
+    FromColumn,
+    query_rows,
+    query_row_columns,
+    u64_to_sql,
+    get_ancestor_block_height
+};
+
+use vm::types::{
+    PrincipalData,
+    StandardPrincipalData,
+    QualifiedContractIdentifier
+};
+use vm::database::ClaritySerializable;
+use vm::Value;
+
+pub const TX_STATUS_SUCCESS : &'static str = "success";
+pub const TX_STATUS_ABORT_BY_RESPONSE : &'static str = "abort_by_response";
+pub const TX_STATUS_ABORT_BY_POST_CONDITION : &'static str = "abort_by_post_condition";
+
+/// A confirmed transaction, as recorded in the transaction index.
+/// Transactions mined in a microblock are indexed under the anchored block that confirmed the
+/// microblock stream, since that's when they were evaluated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionIndexEntry {
+    pub txid: Txid,
+    pub index_block_hash: StacksBlockId,
+    pub block_height: u64,
+    pub tx_index: u32,                              // position in the anchored block or microblock
+    pub microblock_hash: Option<BlockHeaderHash>,
+    pub microblock_sequence: Option<u16>,
+    pub origin_address: StacksAddress,
+    pub sponsor_address: Option<StacksAddress>,
+    pub contract_id: Option<QualifiedContractIdentifier>,   // contract called or deployed
+    pub status: String,
+    pub result: Value,
+    pub tx: StacksTransaction
+}
+
+impl FromRow<TransactionIndexEntry> for TransactionIndexEntry {
+    fn from_row<'a>(row: &'a Row) -> Result<TransactionIndexEntry, db_error> {
+        let txid = Txid::from_column(row, "txid")?;
+        let index_block_hash = StacksBlockId::from_column(row, "index_block_hash")?;
+        let block_height = u64::from_column(row, "block_height")?;
+        let tx_index : u32 = row.get("tx_index");
+        let microblock_hash : Option<BlockHeaderHash> = row.get("microblock_hash");
+        let microblock_sequence : Option<u16> = row.get("microblock_sequence");
+        let origin_address = StacksAddress::from_column(row, "origin_address")?;
+        let sponsor_address_str : Option<String> = row.get("sponsor_address");
+        let contract_id_str : Option<String> = row.get("contract_id");
+        let status : String = row.get("status");
+        let result_hex : String = row.get("result");
+        let tx_bytes : Vec<u8> = row.get("tx");
+
+        let sponsor_address = match sponsor_address_str {
+            Some(addr_str) => Some(StacksAddress::from_string(&addr_str).ok_or(db_error::ParseError)?),
+            None => None
+        };
+
+        let contract_id = match contract_id_str {
+            Some(id_str) => Some(QualifiedContractIdentifier::parse(&id_str).map_err(|_e| db_error::ParseError)?),
+            None => None
+        };
+
+        let result = Value::try_deserialize_hex_untyped(&result_hex)
+            .map_err(|_e| db_error::ParseError)?;
+
+        let tx = StacksTransaction::consensus_deserialize(&mut &tx_bytes[..])
+            .map_err(|_e| db_error::ParseError)?;
+
+        if tx.txid() != txid {
+            return Err(db_error::ParseError);
+        }
+
+        Ok(TransactionIndexEntry {
+            txid,
+            index_block_hash,
+            block_height,
+            tx_index,
+            microblock_hash,
+            microblock_sequence,
+            origin_address,
+            sponsor_address,
+            contract_id,
+            status,
+            result,
+            tx
+        })
+    }
+}
+
+/// The transactions in a block which changed an account's balance or nonce
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountChange {
+    pub index_block_hash: StacksBlockId,
+    pub block_height: u64,
+    pub txids: Vec<Txid>
+}
+
+struct AccountChangeBlockRow {
+    index_block_hash: StacksBlockId,
+    block_height: u64
+}
+
+impl FromRow<AccountChangeBlockRow> for AccountChangeBlockRow {
+    fn from_row<'a>(row: &'a Row) -> Result<AccountChangeBlockRow, db_error> {
+        let index_block_hash = StacksBlockId::from_column(row, "index_block_hash")?;
+        let block_height = u64::from_column(row, "block_height")?;
+        Ok(AccountChangeBlockRow {
+            index_block_hash,
+            block_height
+        })
+    }
+}
+
+impl StacksChainState {
+    /// Get the status string of a transaction receipt
+    fn get_receipt_status(receipt: &StacksTransactionReceipt) -> &'static str {
+        if receipt.post_condition_aborted {
+            return TX_STATUS_ABORT_BY_POST_CONDITION;
+        }
+        match receipt.result {
+            Value::Response(ref response_data) if !response_data.committed => TX_STATUS_ABORT_BY_RESPONSE,
+            _ => TX_STATUS_SUCCESS
+        }
+    }
+
+    /// Get the contract a transaction calls or deploys, if any
+    fn get_tx_contract_id(tx: &StacksTransaction) -> Option<QualifiedContractIdentifier> {
+        match tx.payload {
+            TransactionPayload::ContractCall(ref contract_call) => Some(contract_call.to_clarity_contract_id()),
+            TransactionPayload::SmartContract(ref smart_contract, _) => {
+                Some(QualifiedContractIdentifier::new(StandardPrincipalData::from(tx.origin_address()), smart_contract.name.clone()))
+            },
+            _ => None
+        }
+    }
+
+    /// Get the accounts whose balance or nonce a transaction changed: its origin and sponsor, and
+    /// the senders and recipients of its STX events.  Matured miner rewards are not among them:
+    /// they are paid out when a later block is processed, outside of any transaction, and are
+    /// credited to the miner-rewards boot contract rather than to the miner's balance.
+    fn get_receipt_principals(receipt: &StacksTransactionReceipt) -> Vec<PrincipalData> {
+        let tx = &receipt.transaction;
+        let mut principals = vec![PrincipalData::from(tx.origin_address())];
+        if let Some(sponsor_address) = tx.sponsor_address() {
+            principals.push(PrincipalData::from(sponsor_address));
+        }
+        for event in receipt.events.iter() {
+            if let StacksTransactionEvent::STXEvent(ref stx_event) = event {
+                match stx_event {
+                    STXEventType::STXTransferEvent(ref event_data) => {
+                        principals.push(event_data.sender.clone());
+                        principals.push(event_data.recipient.clone());
+                    },
+                    STXEventType::STXMintEvent(ref event_data) => principals.push(event_data.recipient.clone()),
+                    STXEventType::STXBurnEvent(ref event_data) => principals.push(event_data.sender.clone())
+                }
+            }
+        }
+        principals
+    }
+
+    /// Record the transactions of a newly-appended block (and the parent microblock stream it
+    /// confirmed) in the transaction index, along with the accounts each one changed.  Must be
+    /// called in the same transaction that stored the block's header.
+    pub fn index_block_transactions<'a>(headers_tx: &mut StacksDBTx<'a>, tip: &StacksHeaderInfo, block: &StacksBlock,
+                                        microblocks: &Vec<StacksMicroblock>, receipts: &Vec<StacksTransactionReceipt>) -> Result<(), Error> {
+        let index_block_hash = tip.index_block_hash();
+        let block_height = u64_to_sql(tip.block_height).map_err(Error::DBError)?;
+
+        let mut locations = HashMap::new();
+        for microblock in microblocks.iter() {
+            let microblock_hash = microblock.block_hash();
+            for (i, tx) in microblock.txs.iter().enumerate() {
+                locations.insert(tx.txid(), (i as u32, Some((microblock_hash.clone(), microblock.header.sequence))));
+            }
+        }
+        for (i, tx) in block.txs.iter().enumerate() {
+            locations.insert(tx.txid(), (i as u32, None));
+        }
+
+        for receipt in receipts.iter() {
+            let tx = &receipt.transaction;
+            let txid = tx.txid();
+            let (tx_index, microblock_opt) = match locations.get(&txid) {
+                Some(location) => location.clone(),
+                None => {
+                    warn!("Transaction {} is not in block {}/{} or its confirmed microblocks", &txid, &tip.burn_header_hash, &block.block_hash());
+                    continue;
+                }
+            };
+
+            let (microblock_hash, microblock_sequence) = match microblock_opt {
+                Some((hash, seq)) => (Some(hash), Some(seq)),
+                None => (None, None)
+            };
+
+            let sponsor_address = tx.sponsor_address().map(|addr| addr.to_string());
+            let contract_id = StacksChainState::get_tx_contract_id(tx).map(|contract_id| contract_id.to_string());
+            let status = StacksChainState::get_receipt_status(receipt);
+            let result = receipt.result.serialize();
+
+            let mut tx_bytes = vec![];
+            tx.consensus_serialize(&mut tx_bytes).map_err(Error::NetError)?;
+
+            let args: &[&dyn ToSql] = &[&txid, &index_block_hash, &block_height, &tx_index, &microblock_hash, &microblock_sequence,
+                                        &tx.origin_address().to_string(), &sponsor_address, &contract_id, &status, &result, &tx_bytes];
+
+            headers_tx.execute("INSERT OR REPLACE INTO transactions \
+                                (txid, index_block_hash, block_height, tx_index, microblock_hash, microblock_sequence, origin_address, sponsor_address, contract_id, status, result, tx) \
+                                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)", args)
+                .map_err(|e| Error::DBError(db_error::SqliteError(e)))?;
+
+            for principal in StacksChainState::get_receipt_principals(receipt).iter() {
+                let args: &[&dyn ToSql] = &[&principal.to_string(), &index_block_hash, &block_height, &txid];
+                headers_tx.execute("INSERT OR IGNORE INTO account_changes (principal, index_block_hash, block_height, txid) VALUES (?1, ?2, ?3, ?4)", args)
+                    .map_err(|e| Error::DBError(db_error::SqliteError(e)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Keep only the indexed transactions that were confirmed in the fork ending at the given tip.
+    /// Takes at most `max_results` entries, in the order given.
+    fn filter_indexed_transactions(&self, tip: &StacksBlockId, entries: Vec<TransactionIndexEntry>, max_results: usize) -> Result<Vec<TransactionIndexEntry>, Error> {
+        // several entries usually share a block, so remember which blocks are in this fork
+        let mut in_fork : HashMap<StacksBlockId, bool> = HashMap::new();
+        let mut ret = vec![];
+        for entry in entries.into_iter() {
+            if ret.len() >= max_results {
+                break;
+            }
+
+            let is_ancestor = match in_fork.get(&entry.index_block_hash) {
+                Some(is_ancestor) => *is_ancestor,
+                None => {
+                    let is_ancestor = get_ancestor_block_height(&self.headers_state_index, &entry.index_block_hash, tip)
+                        .map_err(Error::DBError)?
+                        .is_some();
+                    in_fork.insert(entry.index_block_hash.clone(), is_ancestor);
+                    is_ancestor
+                }
+            };
+
+            if is_ancestor {
+                ret.push(entry);
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Get the blocks in the fork ending at the given tip in which confirmed transactions changed
+    /// an account's balance or nonce, most recent first.  Only blocks below `before_height` are
+    /// considered, if given.  Takes at most `max_results` blocks.
+    pub fn get_account_changes(&self, tip: &StacksBlockId, principal: &PrincipalData, before_height: Option<u64>, max_results: usize) -> Result<Vec<AccountChange>, Error> {
+        let before_height = u64_to_sql(before_height.unwrap_or(i64::max_value() as u64)).map_err(Error::DBError)?;
+        let principal = principal.to_string();
+
+        // the blocks are read a page at a time, since the ones in other forks are skipped
+        let page_size = cmp::max(max_results, 1) as i64;
+        let mut offset : i64 = 0;
+        let mut blocks = vec![];
+        while blocks.len() < max_results {
+            let sql = "SELECT DISTINCT index_block_hash, block_height FROM account_changes WHERE principal = ?1 AND block_height < ?2 \
+                       ORDER BY block_height DESC, index_block_hash LIMIT ?3 OFFSET ?4".to_string();
+            let args: &[&dyn ToSql] = &[&principal, &before_height, &page_size, &offset];
+            let page = query_rows::<AccountChangeBlockRow, _>(&self.headers_db, &sql, args).map_err(Error::DBError)?;
+            let page_len = page.len() as i64;
+
+            for row in page.into_iter() {
+                if blocks.len() >= max_results {
+                    break;
+                }
+                if get_ancestor_block_height(&self.headers_state_index, &row.index_block_hash, tip).map_err(Error::DBError)?.is_some() {
+                    blocks.push(row);
+                }
+            }
+
+            if page_len < page_size {
+                break;
+            }
+            offset += page_len;
+        }
+
+        let mut ret = vec![];
+        for block in blocks.into_iter() {
+            let sql = "SELECT txid FROM account_changes WHERE principal = ?1 AND index_block_hash = ?2 ORDER BY rowid".to_string();
+            let args: &[&dyn ToSql] = &[&principal, &block.index_block_hash];
+            let txids = query_row_columns::<Txid, _>(&self.headers_db, &sql, args, "txid").map_err(Error::DBError)?;
+            ret.push(AccountChange {
+                index_block_hash: block.index_block_hash,
+                block_height: block.block_height,
+                txids
+            });
+        }
+        Ok(ret)
+    }
+
+    /// Look up a confirmed transaction in the fork ending at the given tip.
+    pub fn get_indexed_transaction(&self, tip: &StacksBlockId, txid: &Txid) -> Result<Option<TransactionIndexEntry>, Error> {
+        let sql = "SELECT * FROM transactions WHERE txid = ?1".to_string();
+        let rows = query_rows::<TransactionIndexEntry, _>(&self.headers_db, &sql, &[txid]).map_err(Error::DBError)?;
+        let mut entries = self.filter_indexed_transactions(tip, rows, 1)?;
+        Ok(entries.pop())
+    }
+
+    /// Page through the indexed transactions selected by a query, most recent first, keeping the
+    /// ones confirmed in the fork ending at the given tip.  The query's last two parameters are the
+    /// page size and offset.  A block's transactions are not split across calls: if they would
+    /// not all fit in `max_results`, they are left out, so the next call can start below the last
+    /// block returned.  Only a block with more than `max_results` transactions is cut short.
+    fn query_indexed_transactions(&self, tip: &StacksBlockId, sql: &str, args: &[&dyn ToSql], max_results: usize) -> Result<Vec<TransactionIndexEntry>, Error> {
+        if max_results == 0 {
+            return Ok(vec![]);
+        }
+
+        // one more than asked for, to tell whether the last block is complete
+        let page_size = (max_results + 1) as i64;
+        let mut offset : i64 = 0;
+        let mut entries = vec![];
+        while entries.len() <= max_results {
+            let mut page_args = args.to_vec();
+            page_args.push(&page_size);
+            page_args.push(&offset);
+
+            let page = query_rows::<TransactionIndexEntry, _>(&self.headers_db, sql, &page_args).map_err(Error::DBError)?;
+            let page_len = page.len() as i64;
+
+            let mut page_entries = self.filter_indexed_transactions(tip, page, max_results + 1 - entries.len())?;
+            entries.append(&mut page_entries);
+
+            if page_len < page_size {
+                break;
+            }
+            offset += page_len;
+        }
+
+        if entries.len() > max_results {
+            let next_height = entries[max_results].block_height;
+            if entries[max_results - 1].block_height == next_height && entries[0].block_height != next_height {
+                entries.retain(|entry| entry.block_height != next_height);
+            }
+            else {
+                entries.truncate(max_results);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Get the confirmed transactions sent or sponsored by an account in the fork ending at the
+    /// given tip, most recent first.  Only blocks below `before_height` are considered, if given.
+    /// Takes at most `max_results` transactions.
+    pub fn get_account_transactions(&self, tip: &StacksBlockId, address: &StacksAddress, before_height: Option<u64>, max_results: usize) -> Result<Vec<TransactionIndexEntry>, Error> {
+        let before_height = u64_to_sql(before_height.unwrap_or(i64::max_value() as u64)).map_err(Error::DBError)?;
+        let sql = "SELECT * FROM transactions WHERE (origin_address = ?1 OR sponsor_address = ?1) AND block_height < ?2 \
+                   ORDER BY block_height DESC, index_block_hash, microblock_sequence IS NOT NULL, microblock_sequence DESC, tx_index DESC \
+                   LIMIT ?3 OFFSET ?4";
+        let args: &[&dyn ToSql] = &[&address.to_string(), &before_height];
+        self.query_indexed_transactions(tip, sql, args, max_results)
+    }
+
+    /// Get the confirmed transactions which called or deployed a contract in the fork ending at
+    /// the given tip, most recent first.  Only blocks below `before_height` are considered, if
+    /// given.  Takes at most `max_results` transactions.
+    pub fn get_contract_transactions(&self, tip: &StacksBlockId, contract_id: &QualifiedContractIdentifier, before_height: Option<u64>, max_results: usize) -> Result<Vec<TransactionIndexEntry>, Error> {
+        let before_height = u64_to_sql(before_height.unwrap_or(i64::max_value() as u64)).map_err(Error::DBError)?;
+        let sql = "SELECT * FROM transactions WHERE contract_id = ?1 AND block_height < ?2 \
+                   ORDER BY block_height DESC, index_block_hash, microblock_sequence IS NOT NULL, microblock_sequence DESC, tx_index DESC \
+                   LIMIT ?3 OFFSET ?4";
+        let args: &[&dyn ToSql] = &[&contract_id.to_string(), &before_height];
+        self.query_indexed_transactions(tip, sql, args, max_results)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use burnchains::*;
+    use chainstate::burn::*;
+    use chainstate::stacks::db::test::*;
+    use chainstate::stacks::index::TrieHash;
+    use util::hash::*;
+    use vm::costs::ExecutionCost;
+    use vm::types::ResponseData;
+    use chainstate::stacks::events::STXTransferEventData;
+
+    fn make_miner_payment_schedule(addr: &StacksAddress) -> MinerPaymentSchedule {
+        MinerPaymentSchedule {
+            address: addr.clone(),
+            block_hash: FIRST_STACKS_BLOCK_HASH.clone(),
+            burn_header_hash: FIRST_BURNCHAIN_BLOCK_HASH.clone(),
+            parent_block_hash: FIRST_STACKS_BLOCK_HASH.clone(),
+            parent_burn_header_hash: FIRST_BURNCHAIN_BLOCK_HASH.clone(),
+            coinbase: 0,
+            tx_fees_anchored: 0,
+            tx_fees_streamed: 0,
+            stx_burns: 0,
+            burnchain_commit_burn: 0,
+            burnchain_sortition_burn: 0,
+            fill: 0xffffffffffffffff,
+            miner: true,
+            stacks_block_height: 0,
+            vtxindex: 0
+        }
+    }
+
+    /// Append a dummy block to the given parent, in the burnchain fork identified by burn_byte
+    pub fn advance_tip(chainstate: &mut StacksChainState, parent_header_info: &StacksHeaderInfo, burn_byte: u8) -> StacksHeaderInfo {
+        let mut new_tip = parent_header_info.clone();
+        new_tip.anchored_header.parent_block = parent_header_info.anchored_header.block_hash();
+        new_tip.anchored_header.microblock_pubkey_hash = Hash160([burn_byte; 20]);
+        new_tip.anchored_header.total_work.work = parent_header_info.anchored_header.total_work.work + 1;
+        new_tip.microblock_tail = None;
+        new_tip.block_height = parent_header_info.block_height + 1;
+        new_tip.burn_header_hash = BurnchainHeaderHash([burn_byte; 32]);
+
+        let mut block_reward = make_miner_payment_schedule(&StacksAddress { version: 0, bytes: Hash160([0u8; 20]) });
+        block_reward.parent_burn_header_hash = parent_header_info.burn_header_hash.clone();
+        block_reward.parent_block_hash = parent_header_info.anchored_header.block_hash();
+        block_reward.block_hash = new_tip.anchored_header.block_hash();
+        block_reward.burn_header_hash = new_tip.burn_header_hash.clone();
+
+        let mut tx = chainstate.headers_tx_begin().unwrap();
+        let tip = StacksChainState::advance_tip(&mut tx,
+                                                &parent_header_info.anchored_header,
+                                                &parent_header_info.burn_header_hash,
+                                                &new_tip.anchored_header,
+                                                &new_tip.burn_header_hash,
+                                                new_tip.burn_header_timestamp,
+                                                None,
+                                                &block_reward,
+                                                &vec![]).unwrap();
+        tx.commit().unwrap();
+        tip
+    }
+
+    pub fn make_tx(privk: &StacksPrivateKey, nonce: u64, payload: TransactionPayload) -> StacksTransaction {
+        let auth = TransactionAuth::from_p2pkh(privk).unwrap();
+        let mut tx = StacksTransaction::new(TransactionVersion::Testnet, auth, payload);
+        tx.chain_id = 0x80000000;
+        tx.post_condition_mode = TransactionPostConditionMode::Allow;
+        tx.set_origin_nonce(nonce);
+        tx
+    }
+
+    fn make_receipt(tx: &StacksTransaction, committed: bool, post_condition_aborted: bool) -> StacksTransactionReceipt {
+        StacksTransactionReceipt {
+            transaction: tx.clone(),
+            events: vec![],
+            post_condition_aborted,
+            result: Value::Response(ResponseData { committed, data: Box::new(Value::UInt(1)) }),
+            stx_burned: 0,
+            contract_analysis: None,
+            execution_cost: ExecutionCost::zero()
+        }
+    }
+
+    #[test]
+    fn test_index_transactions_by_fork() {
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, "txindex-by-fork");
+
+        let privk = StacksPrivateKey::from_hex("6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001").unwrap();
+        let addr = TransactionAuth::from_p2pkh(&privk).unwrap().origin().address_testnet();
+        let recv_addr = StacksAddress { version: 1, bytes: Hash160([0xff; 20]) };
+        let contract_id = QualifiedContractIdentifier::new(StandardPrincipalData::from(recv_addr.clone()), "hello-world".into());
+
+        let genesis = StacksHeaderInfo::genesis_block_header_info(TrieHash([0u8; 32]));
+        let tip_a = advance_tip(&mut chainstate, &genesis, 1);
+        let tip_b = advance_tip(&mut chainstate, &genesis, 2);
+
+        let tx_transfer = make_tx(&privk, 0, TransactionPayload::TokenTransfer(recv_addr.clone().into(), 123, TokenTransferMemo([0u8; 34])));
+        let tx_call = make_tx(&privk, 1, TransactionPayload::ContractCall(TransactionContractCall {
+            address: recv_addr.clone(),
+            contract_name: "hello-world".into(),
+            function_name: "hello".into(),
+            function_args: vec![]
+        }));
+        let tx_deploy = make_tx(&privk, 2, TransactionPayload::SmartContract(TransactionSmartContract {
+            name: "hello-world-2".into(),
+            code_body: StacksString::from_str("(define-constant hello 1)").unwrap()
+        }, None));
+
+        // the token transfer was streamed in a microblock
+        let microblock = StacksMicroblock {
+            header: StacksMicroblockHeader {
+                version: 0,
+                sequence: 0,
+                prev_block: tip_a.anchored_header.block_hash(),
+                tx_merkle_root: Sha512Trunc256Sum([0u8; 32]),
+                signature: MessageSignature::empty()
+            },
+            txs: vec![tx_transfer.clone()]
+        };
+        let block = StacksBlock {
+            header: tip_a.anchored_header.clone(),
+            txs: vec![tx_call.clone(), tx_deploy.clone()]
+        };
+        let receipts = vec![
+            make_receipt(&tx_transfer, true, false),
+            make_receipt(&tx_call, false, false),
+            make_receipt(&tx_deploy, true, true),
+        ];
+
+        {
+            let mut tx = chainstate.headers_tx_begin().unwrap();
+            StacksChainState::index_block_transactions(&mut tx, &tip_a, &block, &vec![microblock.clone()], &receipts).unwrap();
+            tx.commit().unwrap();
+        }
+
+        let tip_a_id = tip_a.index_block_hash();
+        let tip_b_id = tip_b.index_block_hash();
+
+        let entry = chainstate.get_indexed_transaction(&tip_a_id, &tx_transfer.txid()).unwrap().unwrap();
+        assert_eq!(entry.tx, tx_transfer);
+        assert_eq!(entry.index_block_hash, tip_a_id);
+        assert_eq!(entry.block_height, 1);
+        assert_eq!(entry.tx_index, 0);
+        assert_eq!(entry.microblock_hash, Some(microblock.block_hash()));
+        assert_eq!(entry.microblock_sequence, Some(0));
+        assert_eq!(entry.origin_address, addr);
+        assert_eq!(entry.sponsor_address, None);
+        assert_eq!(entry.contract_id, None);
+        assert_eq!(entry.status, TX_STATUS_SUCCESS);
+
+        let entry = chainstate.get_indexed_transaction(&tip_a_id, &tx_call.txid()).unwrap().unwrap();
+        assert_eq!(entry.tx_index, 0);
+        assert_eq!(entry.microblock_hash, None);
+        assert_eq!(entry.contract_id, Some(contract_id.clone()));
+        assert_eq!(entry.status, TX_STATUS_ABORT_BY_RESPONSE);
+        assert_eq!(entry.result, Value::Response(ResponseData { committed: false, data: Box::new(Value::UInt(1)) }));
+
+        let entry = chainstate.get_indexed_transaction(&tip_a_id, &tx_deploy.txid()).unwrap().unwrap();
+        assert_eq!(entry.tx_index, 1);
+        assert_eq!(entry.contract_id, Some(QualifiedContractIdentifier::new(StandardPrincipalData::from(addr.clone()), "hello-world-2".into())));
+        assert_eq!(entry.status, TX_STATUS_ABORT_BY_POST_CONDITION);
+
+        // most recent first
+        let txids : Vec<_> = chainstate.get_account_transactions(&tip_a_id, &addr, None, 10).unwrap()
+            .into_iter()
+            .map(|entry| entry.txid)
+            .collect();
+        assert_eq!(txids, vec![tx_deploy.txid(), tx_call.txid(), tx_transfer.txid()]);
+
+        let txids : Vec<_> = chainstate.get_account_transactions(&tip_a_id, &addr, None, 2).unwrap()
+            .into_iter()
+            .map(|entry| entry.txid)
+            .collect();
+        assert_eq!(txids, vec![tx_deploy.txid(), tx_call.txid()]);
+
+        let txids : Vec<_> = chainstate.get_contract_transactions(&tip_a_id, &contract_id, None, 10).unwrap()
+            .into_iter()
+            .map(|entry| entry.txid)
+            .collect();
+        assert_eq!(txids, vec![tx_call.txid()]);
+
+        // none of these transactions were mined in the other fork
+        assert!(chainstate.get_indexed_transaction(&tip_b_id, &tx_transfer.txid()).unwrap().is_none());
+        assert_eq!(chainstate.get_account_transactions(&tip_b_id, &addr, None, 10).unwrap().len(), 0);
+        assert_eq!(chainstate.get_contract_transactions(&tip_b_id, &contract_id, None, 10).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_index_transactions_after_migration() {
+        let chainstate = instantiate_chainstate(false, 0x80000000, "txindex-migration");
+
+        // a chain state from before the transaction index
+        chainstate.headers_db.execute_batch("DROP TABLE transactions; DROP TABLE account_changes").unwrap();
+        let mut chainstate = chainstate.reopen().unwrap();
+
+        let privk = StacksPrivateKey::from_hex("6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001").unwrap();
+        let recv_addr = StacksAddress { version: 1, bytes: Hash160([0xff; 20]) };
+
+        let genesis = StacksHeaderInfo::genesis_block_header_info(TrieHash([0u8; 32]));
+        let tip = advance_tip(&mut chainstate, &genesis, 1);
+
+        let tx_transfer = make_tx(&privk, 0, TransactionPayload::TokenTransfer(recv_addr.clone().into(), 123, TokenTransferMemo([0u8; 34])));
+        let block = StacksBlock {
+            header: tip.anchored_header.clone(),
+            txs: vec![tx_transfer.clone()]
+        };
+
+        {
+            let mut tx = chainstate.headers_tx_begin().unwrap();
+            StacksChainState::index_block_transactions(&mut tx, &tip, &block, &vec![], &vec![make_receipt(&tx_transfer, true, false)]).unwrap();
+            tx.commit().unwrap();
+        }
+
+        let entry = chainstate.get_indexed_transaction(&tip.index_block_hash(), &tx_transfer.txid()).unwrap().unwrap();
+        assert_eq!(entry.tx, tx_transfer);
+
+        let changes = chainstate.get_account_changes(&tip.index_block_hash(), &entry.origin_address.into(), None, 10).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].txids, vec![tx_transfer.txid()]);
+    }
+
+    #[test]
+    fn test_index_account_changes() {
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, "txindex-account-changes");
+
+        let privk = StacksPrivateKey::from_hex("6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001").unwrap();
+        let addr = TransactionAuth::from_p2pkh(&privk).unwrap().origin().address_testnet();
+        let recv_addr = StacksAddress { version: 1, bytes: Hash160([0xff; 20]) };
+        let other_addr = StacksAddress { version: 1, bytes: Hash160([0xee; 20]) };
+
+        let genesis = StacksHeaderInfo::genesis_block_header_info(TrieHash([0u8; 32]));
+        let tip_1 = advance_tip(&mut chainstate, &genesis, 1);
+        let tip_2 = advance_tip(&mut chainstate, &tip_1, 2);
+        let tip_fork = advance_tip(&mut chainstate, &tip_1, 3);
+
+        let tx_transfer = make_tx(&privk, 0, TransactionPayload::TokenTransfer(recv_addr.clone().into(), 123, TokenTransferMemo([0u8; 34])));
+        let tx_deploy = make_tx(&privk, 1, TransactionPayload::SmartContract(TransactionSmartContract {
+            name: "hello-world".into(),
+            code_body: StacksString::from_str("(define-constant hello 1)").unwrap()
+        }, None));
+        let tx_transfer_2 = make_tx(&privk, 2, TransactionPayload::TokenTransfer(recv_addr.clone().into(), 456, TokenTransferMemo([0u8; 34])));
+
+        let mut transfer_receipt = make_receipt(&tx_transfer, true, false);
+        transfer_receipt.events.push(StacksTransactionEvent::STXEvent(STXEventType::STXTransferEvent(STXTransferEventData {
+            sender: addr.clone().into(),
+            recipient: recv_addr.clone().into(),
+            amount: 123
+        })));
+
+        let mut transfer_receipt_2 = make_receipt(&tx_transfer_2, true, false);
+        transfer_receipt_2.events.push(StacksTransactionEvent::STXEvent(STXEventType::STXTransferEvent(STXTransferEventData {
+            sender: addr.clone().into(),
+            recipient: recv_addr.clone().into(),
+            amount: 456
+        })));
+
+        let block_1 = StacksBlock {
+            header: tip_1.anchored_header.clone(),
+            txs: vec![tx_transfer.clone(), tx_deploy.clone()]
+        };
+        let block_2 = StacksBlock {
+            header: tip_2.anchored_header.clone(),
+            txs: vec![tx_transfer_2.clone()]
+        };
+
+        {
+            let mut tx = chainstate.headers_tx_begin().unwrap();
+            StacksChainState::index_block_transactions(&mut tx, &tip_1, &block_1, &vec![], &vec![transfer_receipt, make_receipt(&tx_deploy, true, false)]).unwrap();
+            StacksChainState::index_block_transactions(&mut tx, &tip_2, &block_2, &vec![], &vec![transfer_receipt_2]).unwrap();
+            tx.commit().unwrap();
+        }
+
+        let tip_1_id = tip_1.index_block_hash();
+        let tip_2_id = tip_2.index_block_hash();
+        let tip_fork_id = tip_fork.index_block_hash();
+
+        // the sender paid for every transaction
+        let changes = chainstate.get_account_changes(&tip_2_id, &addr.clone().into(), None, 10).unwrap();
+        assert_eq!(changes, vec![
+            AccountChange { index_block_hash: tip_2_id.clone(), block_height: 2, txids: vec![tx_transfer_2.txid()] },
+            AccountChange { index_block_hash: tip_1_id.clone(), block_height: 1, txids: vec![tx_transfer.txid(), tx_deploy.txid()] },
+        ]);
+
+        // the recipient only received the transfers
+        let changes = chainstate.get_account_changes(&tip_2_id, &recv_addr.clone().into(), None, 10).unwrap();
+        assert_eq!(changes, vec![
+            AccountChange { index_block_hash: tip_2_id.clone(), block_height: 2, txids: vec![tx_transfer_2.txid()] },
+            AccountChange { index_block_hash: tip_1_id.clone(), block_height: 1, txids: vec![tx_transfer.txid()] },
+        ]);
+
+        // paging
+        let changes = chainstate.get_account_changes(&tip_2_id, &recv_addr.clone().into(), None, 1).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].index_block_hash, tip_2_id);
+
+        let changes = chainstate.get_account_changes(&tip_2_id, &recv_addr.clone().into(), Some(2), 10).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].index_block_hash, tip_1_id);
+
+        // the second transfer was not mined in the other fork
+        let changes = chainstate.get_account_changes(&tip_fork_id, &recv_addr.clone().into(), None, 10).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].index_block_hash, tip_1_id);
+
+        // a page of transactions doesn't split a block, so the next page can start below it
+        let txids : Vec<_> = chainstate.get_account_transactions(&tip_2_id, &addr, None, 2).unwrap()
+            .into_iter()
+            .map(|entry| entry.txid)
+            .collect();
+        assert_eq!(txids, vec![tx_transfer_2.txid()]);
+
+        let txids : Vec<_> = chainstate.get_account_transactions(&tip_2_id, &addr, Some(2), 2).unwrap()
+            .into_iter()
+            .map(|entry| entry.txid)
+            .collect();
+        assert_eq!(txids, vec![tx_deploy.txid(), tx_transfer.txid()]);
+
+        let txids : Vec<_> = chainstate.get_account_transactions(&tip_fork_id, &addr, None, 10).unwrap()
+            .into_iter()
+            .map(|entry| entry.txid)
+            .collect();
+        assert_eq!(txids, vec![tx_deploy.txid(), tx_transfer.txid()]);
+
+        // blocks from the other fork fill up the first page
+        let changes = chainstate.get_account_changes(&tip_fork_id, &recv_addr.clone().into(), None, 1).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].index_block_hash, tip_1_id);
+        assert_eq!(changes[0].txids, vec![tx_transfer.txid()]);
+
+        assert_eq!(chainstate.get_account_changes(&tip_2_id, &other_addr.into(), None, 10).unwrap().len(), 0);
+    }
+}
@@ -11,6 +11,7 @@ use vm::types::{
 };
 use vm::costs::ExecutionCost;
 use vm::analysis::ContractAnalysis;
+use vm::contexts::AssetMap;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct StacksTransactionReceipt {
@@ -23,6 +24,16 @@ pub struct StacksTransactionReceipt {
     pub execution_cost: ExecutionCost
 }
 
+/// The outcome of a simulated contract-call.  Nothing in it was committed to the chain state.
+#[derive(Debug, Clone)]
+pub struct StacksSimulationReceipt {
+    pub result: Value,
+    pub events: Vec<StacksTransactionEvent>,
+    pub asset_map: AssetMap,
+    pub post_condition_passed: bool,
+    pub execution_cost: ExecutionCost
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum StacksTransactionEvent {
     SmartContractEvent(SmartContractEventData),
@@ -70,6 +81,12 @@ impl StacksTransactionEvent {
                 "type": "nft_mint_event",
                 "nft_mint_event": event_data.json_serialize()
             }),
+            StacksTransactionEvent::NFTEvent(NFTEventType::NFTBurnEvent(event_data)) => json!({
+                "txid": format!("0x{:?}", txid),
+                "committed": committed,
+                "type": "nft_burn_event",
+                "nft_burn_event": event_data.json_serialize()
+            }),
             StacksTransactionEvent::FTEvent(FTEventType::FTTransferEvent(event_data)) => json!({
                 "txid": format!("0x{:?}", txid),
                 "committed": committed,
@@ -82,6 +99,12 @@ impl StacksTransactionEvent {
                 "type": "ft_mint_event",
                 "ft_mint_event": event_data.json_serialize()
             }),
+            StacksTransactionEvent::FTEvent(FTEventType::FTBurnEvent(event_data)) => json!({
+                "txid": format!("0x{:?}", txid),
+                "committed": committed,
+                "type": "ft_burn_event",
+                "ft_burn_event": event_data.json_serialize()
+            }),
         }
     }
 }
@@ -97,12 +120,14 @@ pub enum STXEventType {
 pub enum NFTEventType {
     NFTTransferEvent(NFTTransferEventData),
     NFTMintEvent(NFTMintEventData),
+    NFTBurnEvent(NFTBurnEventData),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum FTEventType {
     FTTransferEvent(FTTransferEventData),
     FTMintEvent(FTMintEventData),
+    FTBurnEvent(FTBurnEventData),
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -173,6 +198,7 @@ impl NFTTransferEventData {
             "sender": format!("{}",self.sender),
             "recipient": format!("{}",self.recipient),
             "value": self.value,
+            "value_json": self.value.to_json(),
             "raw_value": format!("0x{}", raw_value.join("")),
         })
     }
@@ -197,6 +223,32 @@ impl NFTMintEventData {
             "asset_identifier": format!("{}", self.asset_identifier),
             "recipient": format!("{}",self.recipient),
             "value": self.value,
+            "value_json": self.value.to_json(),
+            "raw_value": format!("0x{}", raw_value.join("")),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NFTBurnEventData {
+    pub asset_identifier: AssetIdentifier,
+    pub sender: PrincipalData,
+    pub value: Value,
+}
+
+impl NFTBurnEventData {
+    pub fn json_serialize(&self) -> serde_json::Value {
+        let raw_value = {
+            let mut bytes = vec![];
+            self.value.consensus_serialize(&mut bytes).unwrap();
+            let formatted_bytes: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            formatted_bytes
+        };
+        json!({
+            "asset_identifier": format!("{}", self.asset_identifier),
+            "sender": format!("{}",self.sender),
+            "value": self.value,
+            "value_json": self.value.to_json(),
             "raw_value": format!("0x{}", raw_value.join("")),
         })
     }
@@ -238,6 +290,23 @@ impl FTMintEventData {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct FTBurnEventData {
+    pub asset_identifier: AssetIdentifier,
+    pub sender: PrincipalData,
+    pub amount: u128,
+}
+
+impl FTBurnEventData {
+    pub fn json_serialize(&self) -> serde_json::Value {
+        json!({
+            "asset_identifier": format!("{}", self.asset_identifier),
+            "sender": format!("{}",self.sender),
+            "amount": format!("{}", self.amount),
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SmartContractEventData {
     pub key: (QualifiedContractIdentifier, String),
@@ -256,6 +325,7 @@ impl SmartContractEventData {
             "contract_identifier": self.key.0.to_string(),
             "topic": self.key.1,
             "value": self.value,
+            "value_json": self.value.to_json(),
             "raw_value": format!("0x{}", raw_value.join("")),
         })
     }
@@ -66,6 +66,8 @@ use net::Error as net_error;
 
 use vm::types::{
     Value,
+    OptionalData,
+    ResponseData,
     PrincipalData,
     StandardPrincipalData,
     QualifiedContractIdentifier
@@ -452,6 +454,15 @@ pub struct TransactionSmartContract {
     pub code_body: StacksString
 }
 
+/// Arguments and funding for a smart contract, supplied when it is instantiated
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionContractInit {
+    /// bound as constants before the contract's own definitions are evaluated
+    pub arguments: Vec<(ClarityName, Value)>,
+    /// microSTX transferred from the origin to the contract principal
+    pub amount: u64
+}
+
 /// A coinbase commits to 32 bytes of control-plane information
 pub struct CoinbasePayload(pub [u8; 32]);
 impl_byte_array_message_codec!(CoinbasePayload, 32);
@@ -471,7 +482,7 @@ pub const TOKEN_TRANSFER_MEMO_LENGTH : usize = 34;      // same as it is in Stac
 pub enum TransactionPayload {
     TokenTransfer(PrincipalData, u64, TokenTransferMemo),
     ContractCall(TransactionContractCall),
-    SmartContract(TransactionSmartContract),
+    SmartContract(TransactionSmartContract, Option<TransactionContractInit>),
     PoisonMicroblock(StacksMicroblockHeader, StacksMicroblockHeader),       // the previous epoch leader sent two microblocks with the same sequence, and this is proof
     Coinbase(CoinbasePayload)
 }
@@ -483,7 +494,8 @@ pub enum TransactionPayloadID {
     SmartContract = 1,
     ContractCall = 2,
     PoisonMicroblock = 3,
-    Coinbase = 4
+    Coinbase = 4,
+    SmartContractInit = 5
 }
 
 /// Encoding of an asset type identifier 
@@ -623,6 +635,107 @@ pub enum TransactionPostConditionMode {
     Deny = 0x02         // deny any other changes not specified
 }
 
+// Human-readable post-conditions, in the form `principal::condition-code::asset::amount-or-value`,
+// e.g. `SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7::sent-le::stx::1000` or
+// `origin::sent::SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7.names::names::u1`
+
+impl fmt::Display for PostConditionPrincipal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PostConditionPrincipal::Origin => write!(f, "origin"),
+            PostConditionPrincipal::Standard(ref addr) => write!(f, "{}", addr),
+            PostConditionPrincipal::Contract(ref addr, ref contract_name) => write!(f, "{}.{}", addr, contract_name.as_str())
+        }
+    }
+}
+
+impl fmt::Display for AssetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}::{}", &self.contract_address, self.contract_name.as_str(), self.asset_name.as_str())
+    }
+}
+
+impl fmt::Display for FungibleConditionCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FungibleConditionCode::SentEq => write!(f, "sent-eq"),
+            FungibleConditionCode::SentGt => write!(f, "sent-gt"),
+            FungibleConditionCode::SentGe => write!(f, "sent-ge"),
+            FungibleConditionCode::SentLt => write!(f, "sent-lt"),
+            FungibleConditionCode::SentLe => write!(f, "sent-le")
+        }
+    }
+}
+
+impl fmt::Display for NonfungibleConditionCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NonfungibleConditionCode::Sent => write!(f, "sent"),
+            NonfungibleConditionCode::NotSent => write!(f, "not-sent")
+        }
+    }
+}
+
+/// Writes `value` as Clarity2 source which evaluates back to it. Unlike `Value`'s Display,
+///   principals are quoted and lists are written as `(list ...)`.
+fn fmt_clarity_literal(value: &Value, f: &mut fmt::Formatter) -> fmt::Result {
+    match value {
+        Value::Principal(principal) => write!(f, "'{}", principal),
+        Value::Optional(OptionalData { data: Some(ref inner) }) => {
+            write!(f, "(some ")?;
+            fmt_clarity_literal(inner, f)?;
+            write!(f, ")")
+        },
+        Value::Response(ResponseData { committed, ref data }) => {
+            write!(f, "({} ", if *committed { "ok" } else { "err" })?;
+            fmt_clarity_literal(data, f)?;
+            write!(f, ")")
+        },
+        Value::Tuple(tuple) => {
+            write!(f, "(tuple")?;
+            for (name, value) in tuple.data_map.iter() {
+                write!(f, " ({} ", name.as_str())?;
+                fmt_clarity_literal(value, f)?;
+                write!(f, ")")?;
+            }
+            write!(f, ")")
+        },
+        Value::List(list) => {
+            write!(f, "(list")?;
+            for value in list.data.iter() {
+                write!(f, " ")?;
+                fmt_clarity_literal(value, f)?;
+            }
+            write!(f, ")")
+        },
+        _ => write!(f, "{}", value)
+    }
+}
+
+impl fmt::Display for TransactionPostCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TransactionPostCondition::STX(ref principal, ref code, ref amount) =>
+                write!(f, "{}::{}::stx::{}", principal, code, amount),
+            TransactionPostCondition::Fungible(ref principal, ref asset_info, ref code, ref amount) =>
+                write!(f, "{}::{}::{}::{}", principal, code, asset_info, amount),
+            TransactionPostCondition::Nonfungible(ref principal, ref asset_info, ref asset_value, ref code) => {
+                write!(f, "{}::{}::{}::", principal, code, asset_info)?;
+                fmt_clarity_literal(asset_value, f)
+            }
+        }
+    }
+}
+
+impl fmt::Display for TransactionPostConditionMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TransactionPostConditionMode::Allow => write!(f, "allow"),
+            TransactionPostConditionMode::Deny => write!(f, "deny")
+        }
+    }
+}
+
 /// Stacks transaction versions
 #[repr(u8)]
 #[derive(Debug, Clone, PartialEq, Copy)]
@@ -918,8 +1031,15 @@ pub mod test {
             }),
             TransactionPayload::SmartContract(TransactionSmartContract {
                 name: ContractName::try_from(hello_contract_name).unwrap(),
-                code_body: StacksString::from_str(hello_contract_body).unwrap(),
-            }),
+                code_body: StacksString::from_str(hello_contract_body).unwrap()
+            }, None),
+            TransactionPayload::SmartContract(TransactionSmartContract {
+                name: ContractName::try_from(hello_contract_name).unwrap(),
+                code_body: StacksString::from_str(hello_contract_body).unwrap()
+            }, Some(TransactionContractInit {
+                arguments: vec![(ClarityName::try_from("hello-arg").unwrap(), Value::UInt(123))],
+                amount: 456
+            })),
             TransactionPayload::Coinbase(CoinbasePayload([0x12; 32])),
             TransactionPayload::PoisonMicroblock(mblock_header_1, mblock_header_2),
         ];
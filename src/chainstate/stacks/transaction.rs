@@ -124,6 +124,46 @@ impl StacksMessageCodec for TransactionSmartContract {
     }
 }
 
+impl StacksMessageCodec for TransactionContractInit {
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
+        write_next(fd, &(self.arguments.len() as u32))?;
+        for (name, value) in self.arguments.iter() {
+            write_next(fd, name)?;
+            write_next(fd, value)?;
+        }
+        write_next(fd, &self.amount)?;
+        Ok(())
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<TransactionContractInit, net_error> {
+        let mut bound_read = BoundReader::from_reader(fd, MAX_MESSAGE_LEN as u64);
+        let num_arguments : u32 = read_next(&mut bound_read)?;
+
+        let mut arguments = vec![];
+        for _i in 0..num_arguments {
+            let name : ClarityName = read_next(&mut bound_read)?;
+            let value : Value = read_next(&mut bound_read)?;
+
+            // argument names are bound as constants, so must be valid Clarity variables
+            if !StacksString::from(name.clone()).is_clarity_variable() {
+                warn!("Invalid init argument name -- not a clarity variable");
+                return Err(net_error::DeserializeError("Failed to parse transaction: invalid init argument name -- not a Clarity variable".to_string()));
+            }
+            if arguments.iter().any(|(existing, _)| *existing == name) {
+                return Err(net_error::DeserializeError(format!("Failed to parse transaction: duplicate init argument '{}'", name.as_str())));
+            }
+
+            arguments.push((name, value));
+        }
+
+        let amount : u64 = read_next(&mut bound_read)?;
+        Ok(TransactionContractInit {
+            arguments,
+            amount
+        })
+    }
+}
+
 impl StacksMessageCodec for TransactionPayload {
     fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), net_error> {
         match *self {
@@ -137,9 +177,18 @@ impl StacksMessageCodec for TransactionPayload {
                 write_next(fd, &(TransactionPayloadID::ContractCall as u8))?;
                 cc.consensus_serialize(fd)?;
             },
-            TransactionPayload::SmartContract(ref sc) => {
-                write_next(fd, &(TransactionPayloadID::SmartContract as u8))?;
-                sc.consensus_serialize(fd)?;
+            TransactionPayload::SmartContract(ref sc, ref init) => {
+                match *init {
+                    None => {
+                        write_next(fd, &(TransactionPayloadID::SmartContract as u8))?;
+                        sc.consensus_serialize(fd)?;
+                    },
+                    Some(ref init) => {
+                        write_next(fd, &(TransactionPayloadID::SmartContractInit as u8))?;
+                        sc.consensus_serialize(fd)?;
+                        init.consensus_serialize(fd)?;
+                    }
+                }
             },
             TransactionPayload::PoisonMicroblock(ref h1, ref h2) => {
                 write_next(fd, &(TransactionPayloadID::PoisonMicroblock as u8))?;
@@ -169,7 +218,12 @@ impl StacksMessageCodec for TransactionPayload {
             }
             x if x == TransactionPayloadID::SmartContract as u8 => {
                 let payload : TransactionSmartContract = read_next(fd)?;
-                TransactionPayload::SmartContract(payload)
+                TransactionPayload::SmartContract(payload, None)
+            }
+            x if x == TransactionPayloadID::SmartContractInit as u8 => {
+                let payload : TransactionSmartContract = read_next(fd)?;
+                let init : TransactionContractInit = read_next(fd)?;
+                TransactionPayload::SmartContract(payload, Some(init))
             }
             x if x == TransactionPayloadID::PoisonMicroblock as u8 => {
                 let h1 : StacksMicroblockHeader = read_next(fd)?;
@@ -228,7 +282,7 @@ impl TransactionPayload {
 
     pub fn new_smart_contract(name: &String, contract: &String) -> Option<TransactionPayload> {
         match (ContractName::try_from((*name).clone()), StacksString::from_string(contract)) {
-            (Ok(s_name), Some(s_body)) => Some(TransactionPayload::SmartContract(TransactionSmartContract { name: s_name, code_body: s_body })),
+            (Ok(s_name), Some(s_body)) => Some(TransactionPayload::SmartContract(TransactionSmartContract { name: s_name, code_body: s_body }, None)),
             (_, _) => None
         }
     }
@@ -475,7 +529,7 @@ impl StacksMessageCodec for StacksTransaction {
 
 impl From<TransactionSmartContract> for TransactionPayload {
     fn from(value: TransactionSmartContract) -> Self {
-        TransactionPayload::SmartContract(value)
+        TransactionPayload::SmartContract(value, None)
     }
 }
 
@@ -762,6 +816,37 @@ impl StacksTransactionSigner {
         })
     }
 
+    /// Resume signing the origin of a partially-signed transaction (e.g. a multisig transaction
+    /// that some of its signers have already signed).  The sighash is recovered from the origin
+    /// signatures the transaction already carries.
+    pub fn resume_origin(tx: &StacksTransaction) -> Result<StacksTransactionSigner, net_error> {
+        let mut sighash = tx.sign_begin();
+        match tx.auth.origin() {
+            TransactionSpendingCondition::Multisig(ref data) => {
+                for field in data.fields.iter() {
+                    if let TransactionAuthField::Signature(ref key_encoding, ref signature) = field {
+                        let (_, next_sighash) = TransactionSpendingCondition::next_verification(&sighash, &TransactionAuthFlags::AuthStandard, data.fee_rate, data.nonce, key_encoding, signature)?;
+                        sighash = next_sighash;
+                    }
+                }
+            },
+            TransactionSpendingCondition::Singlesig(ref data) => {
+                if data.signature != MessageSignature::empty() {
+                    let (_, next_sighash) = TransactionSpendingCondition::next_verification(&sighash, &TransactionAuthFlags::AuthStandard, data.fee_rate, data.nonce, &data.key_encoding, &data.signature)?;
+                    sighash = next_sighash;
+                }
+            }
+        }
+
+        Ok(StacksTransactionSigner {
+            tx: tx.clone(),
+            sighash: sighash,
+            origin_done: false,
+            check_oversign: true,
+            check_overlap: true
+        })
+    }
+
     pub fn resume(&mut self, tx: &StacksTransaction) -> () {
         self.tx = tx.clone()
     }
@@ -1301,9 +1386,9 @@ mod test {
                 TransactionPayload::TokenTransfer(addr.clone(), amount + 1, memo.clone())
             },
             TransactionPayload::ContractCall(_) => {
-                TransactionPayload::SmartContract(TransactionSmartContract { name: ContractName::try_from("corrupt-name").unwrap(), code_body: StacksString::from_str("corrupt body").unwrap() })
+                TransactionPayload::SmartContract(TransactionSmartContract { name: ContractName::try_from("corrupt-name").unwrap(), code_body: StacksString::from_str("corrupt body").unwrap() }, None)
             },
-            TransactionPayload::SmartContract(_) => {
+            TransactionPayload::SmartContract(..) => {
                 TransactionPayload::ContractCall(TransactionContractCall { 
                     address: StacksAddress { version: 1, bytes: Hash160([0xff; 20]) },
                     contract_name: ContractName::try_from("hello-world").unwrap(),
@@ -1449,7 +1534,13 @@ mod test {
 
         let smart_contract = TransactionSmartContract {
             name: ContractName::try_from(hello_contract_name).unwrap(),
-            code_body: StacksString::from_str(hello_contract_body).unwrap(),
+            code_body: StacksString::from_str(hello_contract_body).unwrap()
+        };
+
+        let contract_init = TransactionContractInit {
+            arguments: vec![(ClarityName::try_from("owner").unwrap(), Value::UInt(1)),
+                            (ClarityName::try_from("enabled").unwrap(), Value::Bool(true))],
+            amount: 12345
         };
 
         let mut contract_call_bytes = vec![];
@@ -1472,10 +1563,33 @@ mod test {
         ];
         transaction_smart_contract.append(&mut smart_contract_bytes.clone());
 
+        let mut contract_init_bytes = vec![];
+        (contract_init.arguments.len() as u32).consensus_serialize(&mut contract_init_bytes).unwrap();
+        for (name, value) in contract_init.arguments.iter() {
+            name.consensus_serialize(&mut contract_init_bytes).unwrap();
+            value.consensus_serialize(&mut contract_init_bytes).unwrap();
+        }
+        contract_init.amount.consensus_serialize(&mut contract_init_bytes).unwrap();
+
+        let mut transaction_smart_contract_init = vec![
+            TransactionPayloadID::SmartContractInit as u8
+        ];
+        transaction_smart_contract_init.append(&mut smart_contract_bytes.clone());
+        transaction_smart_contract_init.append(&mut contract_init_bytes.clone());
+
         check_codec_and_corruption::<TransactionContractCall>(&contract_call, &contract_call_bytes);
         check_codec_and_corruption::<TransactionSmartContract>(&smart_contract, &smart_contract_bytes);
         check_codec_and_corruption::<TransactionPayload>(&TransactionPayload::ContractCall(contract_call.clone()), &transaction_contract_call);
-        check_codec_and_corruption::<TransactionPayload>(&TransactionPayload::SmartContract(smart_contract.clone()), &transaction_smart_contract);
+        check_codec_and_corruption::<TransactionPayload>(&TransactionPayload::SmartContract(smart_contract.clone(), None), &transaction_smart_contract);
+        check_codec_and_corruption::<TransactionContractInit>(&contract_init, &contract_init_bytes);
+        check_codec_and_corruption::<TransactionPayload>(&TransactionPayload::SmartContract(smart_contract.clone(), Some(contract_init.clone())), &transaction_smart_contract_init);
+
+        // init argument names may not be repeated
+        let mut duplicate_init = contract_init.clone();
+        duplicate_init.arguments.push((ClarityName::try_from("owner").unwrap(), Value::UInt(2)));
+        let mut duplicate_init_bytes = vec![];
+        duplicate_init.consensus_serialize(&mut duplicate_init_bytes).unwrap();
+        assert!(TransactionContractInit::consensus_deserialize(&mut &duplicate_init_bytes[..]).is_err());
     }
 
     #[test]
@@ -2476,6 +2590,48 @@ mod test {
         }
     } 
     
+    #[test]
+    fn tx_stacks_transaction_sign_verify_standard_p2sh_resumed() {
+        let privk_1 = StacksPrivateKey::from_hex("6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001").unwrap();
+        let privk_2 = StacksPrivateKey::from_hex("2a584d899fed1d24e26b524f202763c8ab30260167429f157f1c119f550fa6af01").unwrap();
+        let privk_3 = StacksPrivateKey::from_hex("d5200dee706ee53ae98a03fba6cf4fdcc5084c30cfa9e1b3462dcdeaa3e0f1d201").unwrap();
+
+        let pubk_1 = StacksPublicKey::from_private(&privk_1);
+        let pubk_2 = StacksPublicKey::from_private(&privk_2);
+        let pubk_3 = StacksPublicKey::from_private(&privk_3);
+
+        let origin_auth = TransactionAuth::Standard(TransactionSpendingCondition::new_multisig_p2sh(2, vec![pubk_1.clone(), pubk_2.clone(), pubk_3.clone()]).unwrap());
+
+        let txs = tx_stacks_transaction_test_txs(&origin_auth);
+
+        for tx in txs {
+            // each signer works from a fresh signer over the last one's output
+            let mut tx_signer = StacksTransactionSigner::resume_origin(&tx).unwrap();
+            tx_signer.append_origin(&pubk_1).unwrap();
+            let partial_tx = tx_signer.get_tx_incomplete();
+
+            let mut tx_signer = StacksTransactionSigner::resume_origin(&partial_tx).unwrap();
+            tx_signer.sign_origin(&privk_2).unwrap();
+            assert!(tx_signer.get_tx().is_none());
+            let partial_tx = tx_signer.get_tx_incomplete();
+
+            let mut tx_signer = StacksTransactionSigner::resume_origin(&partial_tx).unwrap();
+            tx_signer.sign_origin(&privk_3).unwrap();
+            let signed_tx = tx_signer.get_tx().unwrap();
+
+            // same as signing in one go
+            let mut tx_signer = StacksTransactionSigner::new(&tx);
+            tx_signer.append_origin(&pubk_1).unwrap();
+            tx_signer.sign_origin(&privk_2).unwrap();
+            tx_signer.sign_origin(&privk_3).unwrap();
+            let expected_tx = tx_signer.get_tx().unwrap();
+
+            assert_eq!(signed_tx, expected_tx);
+            assert_eq!(signed_tx.auth().origin().num_signatures(), 2);
+            signed_tx.verify().unwrap();
+        }
+    }
+
     #[test]
     fn tx_stacks_transaction_sign_verify_sponsored_p2sh() {
         let origin_privk = StacksPrivateKey::from_hex("807bbe9e471ac976592cc35e3056592ecc0f778ee653fced3b491a122dd8d59701").unwrap();
@@ -6,31 +6,43 @@ use std::env;
 use std::process;
 use std::convert::TryInto;
 use std::path::PathBuf;
+use std::collections::BTreeMap;
 
 use util::log;
 
-use chainstate::burn::BlockHeaderHash;
+use chainstate::burn::{BlockHeaderHash, VRFSeed};
 use chainstate::stacks::index::{MarfTrieId, storage::{TrieFileStorage}};
-use chainstate::stacks::StacksBlockId;
+use chainstate::stacks::{StacksBlockId, StacksAddress, C32_ADDRESS_VERSION_TESTNET_SINGLESIG};
+use burnchains::{Address, BurnchainHeaderHash, Txid};
 
-use rusqlite::{Connection, OpenFlags, NO_PARAMS};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, NO_PARAMS};
 use rusqlite::types::ToSql;
 use rusqlite::Row;
 use rusqlite::Transaction;
 
 use util::db::FromColumn;
+use util::hash::Hash160;
+use util::get_epoch_time_secs;
 
-use vm::ast::{build_ast};
+use vm::ast::{build_ast, ContractAST};
+use vm::ast::errors::{ParseError, ParseErrors};
 use vm::contexts::OwnedEnvironment;
-use vm::database::{ClarityDatabase, SqliteConnection,
-                   MarfedKV, MemoryBackingStore, NULL_HEADER_DB};
+use vm::database::{ClarityDatabase, SqliteConnection, ClarityBackingStore, HeadersDB,
+                   MarfedKV, MemoryBackingStore, ClaritySerializable, NULL_HEADER_DB};
 use vm::errors::{InterpreterResult, RuntimeErrorType, Error};
-use vm::{SymbolicExpression, SymbolicExpressionType, Value, execute as vm_execute};
+use vm::{SymbolicExpression, SymbolicExpressionType, Value, ClarityVersion, execute as vm_execute};
 use vm::analysis;
-use vm::analysis::{errors::CheckResult, AnalysisDatabase, ContractAnalysis};
+use vm::analysis::{errors::{CheckResult, CheckError, CheckErrors}, AnalysisDatabase, ContractAnalysis};
 use vm::analysis::contract_interface_builder::build_contract_interface;
-use vm::types::{QualifiedContractIdentifier, PrincipalData};
-use vm::costs::LimitedCostTracker;
+use vm::analysis::linter::lint;
+#[cfg(feature = "developer-mode")]
+use vm::ast::formatter::format_source;
+use vm::types::{QualifiedContractIdentifier, PrincipalData, FunctionType, FixedFunction, BlockInfoProperty};
+use vm::costs::{LimitedCostTracker, ExecutionCost};
+use vm::costs::trace::{CostTracer, CostDimension};
+use vm::debug::Debugger;
+use vm::diagnostic::Diagnostic;
+use vm::docs::lookup_api_reference;
 
 use address::c32::c32_address;
 
@@ -52,6 +64,9 @@ where command is one of:
 
   initialize         to initialize a local VM state database.
   check              to typecheck a potential contract definition.
+  fmt                to pretty-print a contract definition, preserving its comments.
+  lint               to typecheck a contract definition, and warn about unused bindings,
+                     unreachable branches, unchecked responses and shadowed names.
   launch             to launch a initialize a new contract in the local state database.
   eval               to evaluate (in read-only mode) a program in a given contract context.
   eval_at_chaintip   like `eval`, but does not advance to a new block.
@@ -59,12 +74,59 @@ where command is one of:
                      must be passed eval string via stdin.
   eval_raw           to typecheck and evaluate an expression without a contract or database context.
   repl               to typecheck and evaluate expressions in a stdin/stdout loop.
+                     type `:help` in the repl for its debugger commands.
   execute            to execute a public function of a defined contract.
+  deps               to print the contracts a launched contract depends on (through `contract-call?`
+                     and traits), and their own dependencies, as JSON.
+  serve              to answer JSON-RPC requests on stdin (check, launch, eval, execute, abi and docs),
+                     against a VM state database, or a temporary one if none is given.
+  test               to run the `test-` public functions of contracts, each against a fresh database.
   generate_address   to generate a random Stacks public address for testing purposes.
+  workspace          to advance, checkpoint, restore or fork the blocks of a local VM state database,
+                     and to set the `get-block-info?` properties of its chain tip.
+
+`check` and `lint` accept the trailing flag:
+  --diagnostics=json               print errors as JSON objects, rather than as human readable text.
+
+`fmt` accepts the trailing flag:
+  --write                          rewrite the file in place, rather than printing it to stdout.
+
+`workspace` is invoked as `workspace [vm-state.db] [subcommand]`, where subcommand is one of:
+  status                           print the chain tip, and the `block-height` of the next block.
+  advance (count)                  mine `count` (default 1) empty blocks on the chain tip.
+  checkpoint [name]                name the chain tip, so that it can be restored later.
+  checkpoints                      list the named checkpoints.
+  restore [name]                   make a checkpoint the chain tip. New blocks fork from it.
+  fork [index-block-hash | height] make a prior block the chain tip. New blocks fork from it.
+  set_block_info [property] [value]
+                                   set a `get-block-info?` property (time, header-hash,
+                                   burnchain-header-hash, vrf-seed or miner-address) of the chain tip.
+
+`serve` is invoked as `serve (vm-state.db)`. Requests are read one per line, or framed by a
+  `Content-Length` header as in the language server protocol, and each request's params may
+  include a `cost_limit` object with any of the fields runtime, read_count, read_length,
+  write_count and write_length. Values are returned as Clarity (`value`), hex (`hex`) and
+  JSON (`json`), and `execute` accepts JSON arguments (`json_args`), which are decoded using
+  the argument types of the function.
+
+`eval`, `eval_at_chaintip` and `execute` accept the trailing flags:
+  --cost_trace=<file>              write the cost of each call stack to <file>, in the folded
+                                   stacks format used by flame graph tools, and print a
+                                   per-function cost summary to stderr.
+  --cost_trace_dimension=<dim>     the cost dimension written to the trace: one of runtime (default),
+                                   read_count, read_length, write_count or write_length.
 ", invoked_by);
     panic_test!()
 }
 
+const REPL_HELP_TEXT: &str = "REPL commands:
+  :debug <expr>      evaluate <expr>, stopping the debugger at its first sub-expression.
+  :break <location>  set a breakpoint. See the debugger's `help` command for location formats.
+  :delete <id>       delete a breakpoint.
+  :breakpoints       list the current breakpoints.
+  :help              print this message.
+Any other input is typechecked and evaluated.";
+
 #[cfg_attr(tarpaulin, skip)]
 fn friendly_expect<A,B: std::fmt::Display>(input: Result<A,B>, msg: &str) -> A {
     input.unwrap_or_else(|e| {
@@ -81,6 +143,175 @@ fn friendly_expect_opt<A>(input: Option<A>, msg: &str) -> A {
     })
 }
 
+struct CostTraceOptions {
+    output: String,
+    dimension: CostDimension,
+}
+
+// Strips the cost tracing flags out of the command arguments.
+fn take_cost_trace_options(args: &[String]) -> (Vec<String>, Option<CostTraceOptions>) {
+    let mut output = None;
+    let mut dimension = CostDimension::Runtime;
+    let mut remaining = Vec::new();
+
+    for arg in args.iter() {
+        if arg.starts_with("--cost_trace=") {
+            output = Some(arg["--cost_trace=".len()..].to_string());
+        } else if arg.starts_with("--cost_trace_dimension=") {
+            let name = &arg["--cost_trace_dimension=".len()..];
+            dimension = friendly_expect_opt(CostDimension::parse(name),
+                                            &format!("Unknown cost dimension: {}", name));
+        } else {
+            remaining.push(arg.clone());
+        }
+    }
+
+    (remaining, output.map(|output| CostTraceOptions { output, dimension }))
+}
+
+fn write_cost_trace(tracer: &CostTracer, options: &CostTraceOptions) {
+    let mut file = friendly_expect(fs::File::create(&options.output),
+                                   &format!("Failed to create cost trace file: {}", options.output));
+    friendly_expect(tracer.write_folded_stacks(options.dimension, &mut file),
+                    &format!("Failed to write cost trace file: {}", options.output));
+    friendly_expect(tracer.write_summary(options.dimension, &mut io::stderr()),
+                    "Failed to write cost summary.");
+}
+
+struct TestAccount {
+    principal: PrincipalData,
+    balance: u128,
+}
+
+enum TestOutcome {
+    Passed(Value),
+    Failed(Value),
+    Errored(String),
+}
+
+struct TestResult {
+    contract_identifier: QualifiedContractIdentifier,
+    function_name: String,
+    outcome: TestOutcome,
+    cost: ExecutionCost,
+}
+
+impl TestResult {
+    fn passed(&self) -> bool {
+        match self.outcome {
+            TestOutcome::Passed(_) => true,
+            _ => false
+        }
+    }
+}
+
+// Parses a `<principal>:<balance>` account specification.
+fn parse_test_account(spec: &str) -> TestAccount {
+    let (principal, balance) = match spec.rfind(':') {
+        Some(index) => (&spec[..index], &spec[index+1..]),
+        None => (spec, "0")
+    };
+    let principal = friendly_expect(PrincipalData::parse(principal),
+                                    &format!("Failed to parse principal: {}", principal));
+    let balance = friendly_expect(balance.parse::<u128>(),
+                                  &format!("Failed to parse STX balance: {}", balance));
+    TestAccount { principal, balance }
+}
+
+// Runs a single test function against a fresh database, in which the accounts are funded and
+//   all of the contracts are launched, in order.
+fn run_contract_test(contracts: &[(QualifiedContractIdentifier, String)], accounts: &[TestAccount], sender: &PrincipalData,
+                     contract_identifier: &QualifiedContractIdentifier, function_name: &str) -> TestResult {
+    let mut marf = MemoryBackingStore::new();
+    let mut vm_env = OwnedEnvironment::new_cost_limited(false, marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
+
+    for account in accounts.iter() {
+        vm_env.stx_faucet(&account.principal, account.balance);
+    }
+
+    let mut outcome = None;
+    for (identifier, content) in contracts.iter() {
+        if let Err(e) = vm_env.initialize_contract(identifier.clone(), content) {
+            outcome = Some(TestOutcome::Errored(format!("Failed to launch {}: {}", identifier, e)));
+            break;
+        }
+    }
+
+    let start = vm_env.get_cost_total();
+    let outcome = outcome.unwrap_or_else(|| {
+        match vm_env.execute_transaction(Value::Principal(sender.clone()), contract_identifier.clone(), function_name, &[]) {
+            Ok((Value::Response(data), _, _)) => {
+                if data.committed {
+                    TestOutcome::Passed(*data.data)
+                } else {
+                    TestOutcome::Failed(*data.data)
+                }
+            },
+            Ok((value, _, _)) => TestOutcome::Errored(format!("Expected a response from the test, found: {}", value)),
+            Err(e) => TestOutcome::Errored(e.to_string())
+        }
+    });
+
+    let mut cost = vm_env.get_cost_total();
+    cost.sub(&start).expect("FATAL: cost total decreased while running a test");
+
+    TestResult {
+        contract_identifier: contract_identifier.clone(),
+        function_name: function_name.to_string(),
+        outcome,
+        cost
+    }
+}
+
+fn xml_escape(input: &str) -> String {
+    input.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// Renders the test results as a JUnit XML report, with a test suite per contract.
+fn junit_report(results: &[TestResult]) -> String {
+    let failures = results.iter().filter(|result| !result.passed()).count();
+    let mut report = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites tests=\"{}\" failures=\"{}\">\n",
+                             results.len(), failures);
+
+    let mut contracts: Vec<&QualifiedContractIdentifier> = Vec::new();
+    for result in results.iter() {
+        if !contracts.contains(&&result.contract_identifier) {
+            contracts.push(&result.contract_identifier);
+        }
+    }
+
+    for contract_identifier in contracts.iter() {
+        let suite: Vec<_> = results.iter()
+            .filter(|result| &&result.contract_identifier == contract_identifier)
+            .collect();
+        let suite_name = xml_escape(&contract_identifier.to_string());
+        report.push_str(&format!("  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n", suite_name, suite.len(),
+                                 suite.iter().filter(|result| !result.passed()).count()));
+        for result in suite.iter() {
+            report.push_str(&format!("    <testcase name=\"{}\" classname=\"{}\">\n", xml_escape(&result.function_name), suite_name));
+            match result.outcome {
+                TestOutcome::Passed(_) => {},
+                TestOutcome::Failed(ref value) => {
+                    report.push_str(&format!("      <failure message=\"{}\"/>\n", xml_escape(&format!("(err {})", value))));
+                },
+                TestOutcome::Errored(ref message) => {
+                    report.push_str(&format!("      <error message=\"{}\"/>\n", xml_escape(message)));
+                }
+            }
+            report.push_str(&format!("      <system-out>{}</system-out>\n", xml_escape(&result.cost.to_string())));
+            report.push_str("    </testcase>\n");
+        }
+        report.push_str("  </testsuite>\n");
+    }
+
+    report.push_str("</testsuites>\n");
+    report
+}
+
 struct EvalInput {
     marf_kv: MarfedKV,
     contract_identifier: QualifiedContractIdentifier,
@@ -88,7 +319,7 @@ struct EvalInput {
 }
 
 fn parse(contract_identifier: &QualifiedContractIdentifier, source_code: &str) -> Result<Vec<SymbolicExpression>, Error> {
-    let ast = build_ast(contract_identifier, source_code, &mut ())
+    let ast = build_ast(contract_identifier, source_code, &mut (), ClarityVersion::Clarity1)
         .map_err(|e| RuntimeErrorType::ASTError(e))?;
     Ok(ast.expressions)
 }
@@ -123,6 +354,29 @@ fn create_or_open_db(path: &String) -> Connection {
     conn
 }
 
+// blocks mined by the CLI are 10 minutes apart.
+const CLI_BLOCK_TIME: u64 = 10 * 60;
+
+// store CLI data alongside the MARF database state
+fn get_cli_db_path(db_path: &String) -> String {
+    let mut cli_db_path_buf = PathBuf::from(db_path);
+    cli_db_path_buf.push("cli.sqlite");
+    cli_db_path_buf
+        .to_str()
+        .expect(&format!("FATAL: failed to convert '{}' to a string", db_path))
+        .to_string()
+}
+
+fn create_cli_tables(conn: &Connection) {
+    friendly_expect(conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS cli_chain_tips(id INTEGER PRIMARY KEY AUTOINCREMENT, block_hash TEXT UNIQUE NOT NULL);
+         CREATE TABLE IF NOT EXISTS cli_checkpoints(name TEXT PRIMARY KEY, block_hash TEXT NOT NULL);
+         CREATE TABLE IF NOT EXISTS cli_block_info(block_hash TEXT PRIMARY KEY, time INTEGER NOT NULL,
+                                                   header_hash TEXT, burnchain_header_hash TEXT,
+                                                   vrf_seed TEXT, miner_address TEXT);"),
+                    "FATAL: failed to create CLI tables");
+}
+
 fn get_cli_chain_tip(conn: &Connection) -> StacksBlockId {
     let mut stmt = friendly_expect(conn.prepare("SELECT block_hash FROM cli_chain_tips ORDER BY id DESC LIMIT 1"), "FATAL: could not prepare query");
     let mut rows = friendly_expect(stmt.query(NO_PARAMS), "FATAL: could not fetch rows");
@@ -145,12 +399,19 @@ fn get_cli_chain_tip(conn: &Connection) -> StacksBlockId {
     }
 }
 
+fn get_cli_block_time(conn: &Connection, block_hash: &StacksBlockId) -> Option<u64> {
+    let time: Option<i64> = friendly_expect(
+        conn.query_row("SELECT time FROM cli_block_info WHERE block_hash = ?1", &[block_hash], |row| row.get(0))
+            .optional(),
+        "FATAL: could not read block time");
+    time.map(|x| x as u64)
+}
+
 fn advance_cli_chain_tip(path: &String) -> (StacksBlockId, StacksBlockId) {
     let mut conn = create_or_open_db(path);
     let tx = friendly_expect(conn.transaction(), &format!("FATAL: failed to begin transaction on '{}'", path));
 
-    friendly_expect(tx.execute("CREATE TABLE IF NOT EXISTS cli_chain_tips(id INTEGER PRIMARY KEY AUTOINCREMENT, block_hash TEXT UNIQUE NOT NULL);", NO_PARAMS),
-                    &format!("FATAL: failed to create 'cli_chain_tips' table"));
+    create_cli_tables(&tx);
 
     let parent_block_hash = get_cli_chain_tip(&tx);
 
@@ -161,23 +422,180 @@ fn advance_cli_chain_tip(path: &String) -> (StacksBlockId, StacksBlockId) {
     friendly_expect(tx.execute("INSERT INTO cli_chain_tips (block_hash) VALUES (?1)", &[&next_block_hash]), 
                     &format!("FATAL: failed to store next block hash in '{}'", path));
 
+    // the first block is timestamped with the wall clock, and later blocks follow it.
+    let next_block_time = match get_cli_block_time(&tx, &parent_block_hash) {
+        Some(parent_block_time) => parent_block_time + CLI_BLOCK_TIME,
+        None => get_epoch_time_secs()
+    };
+
+    friendly_expect(tx.execute("INSERT INTO cli_block_info (block_hash, time) VALUES (?1, ?2)",
+                               &[&next_block_hash as &dyn ToSql, &(next_block_time as i64)]),
+                    &format!("FATAL: failed to store next block info in '{}'", path));
+
     friendly_expect(tx.commit(), &format!("FATAL: failed to commit new chain tip to '{}'", path));
 
     (parent_block_hash, next_block_hash)
 }
 
+// forget a block which `advance_cli_chain_tip` mined, restoring its parent as the chain tip.
+fn drop_cli_chain_tip(path: &String, block_hash: &StacksBlockId) {
+    let mut conn = create_or_open_db(path);
+    let tx = friendly_expect(conn.transaction(), &format!("FATAL: failed to begin transaction on '{}'", path));
+
+    friendly_expect(tx.execute("DELETE FROM cli_chain_tips WHERE block_hash = ?1", &[block_hash]),
+                    &format!("FATAL: failed to update chain tip in '{}'", path));
+    friendly_expect(tx.execute("DELETE FROM cli_block_info WHERE block_hash = ?1", &[block_hash]),
+                    &format!("FATAL: failed to update block info in '{}'", path));
+
+    friendly_expect(tx.commit(), &format!("FATAL: failed to commit chain tip to '{}'", path));
+}
+
+// make a block which the CLI already mined the chain tip: it is re-inserted as the
+//   most recent tip, so that the next block forks from it.
+fn set_cli_chain_tip(path: &String, block_hash: &StacksBlockId) {
+    let mut conn = create_or_open_db(path);
+    let tx = friendly_expect(conn.transaction(), &format!("FATAL: failed to begin transaction on '{}'", path));
+
+    create_cli_tables(&tx);
+
+    let known: i64 = friendly_expect(
+        tx.query_row("SELECT COUNT(*) FROM cli_chain_tips WHERE block_hash = ?1", &[block_hash], |row| row.get(0)),
+        "FATAL: could not query chain tips");
+    if known == 0 {
+        eprintln!("No such block in '{}': {}", path, block_hash);
+        panic_test!();
+    }
+
+    friendly_expect(tx.execute("DELETE FROM cli_chain_tips WHERE block_hash = ?1", &[block_hash]),
+                    &format!("FATAL: failed to update chain tip in '{}'", path));
+    friendly_expect(tx.execute("INSERT INTO cli_chain_tips (block_hash) VALUES (?1)", &[block_hash]),
+                    &format!("FATAL: failed to update chain tip in '{}'", path));
+
+    friendly_expect(tx.commit(), &format!("FATAL: failed to commit new chain tip to '{}'", path));
+}
+
+fn set_cli_checkpoint(path: &String, name: &str) -> StacksBlockId {
+    let conn = create_or_open_db(path);
+    create_cli_tables(&conn);
+
+    let chain_tip = get_cli_chain_tip(&conn);
+    friendly_expect(conn.execute("INSERT OR REPLACE INTO cli_checkpoints (name, block_hash) VALUES (?1, ?2)",
+                                 &[&name as &dyn ToSql, &chain_tip]),
+                    &format!("FATAL: failed to store checkpoint in '{}'", path));
+    chain_tip
+}
+
+fn get_cli_checkpoints(path: &String) -> Vec<(String, StacksBlockId)> {
+    let conn = create_or_open_db(path);
+    create_cli_tables(&conn);
+
+    let mut stmt = friendly_expect(conn.prepare("SELECT name, block_hash FROM cli_checkpoints ORDER BY name"), "FATAL: could not prepare query");
+    let mut rows = friendly_expect(stmt.query(NO_PARAMS), "FATAL: could not fetch rows");
+    let mut checkpoints = vec![];
+    while let Some(row_res) = rows.next() {
+        let row = friendly_expect(row_res, "FATAL: could not read checkpoint");
+        let name: String = row.get("name");
+        let block_hash = friendly_expect(StacksBlockId::from_column(&row, "block_hash"), "FATAL: could not parse block hash");
+        checkpoints.push((name, block_hash));
+    }
+    checkpoints
+}
+
+fn set_cli_block_info(path: &String, property: &BlockInfoProperty, value: &str) {
+    let conn = create_or_open_db(path);
+    create_cli_tables(&conn);
+
+    let chain_tip = get_cli_chain_tip(&conn);
+    if chain_tip == StacksBlockId::sentinel() {
+        eprintln!("No blocks in '{}': initialize the database first.", path);
+        panic_test!();
+    }
+
+    // blocks mined before the CLI recorded block info have no row yet.
+    friendly_expect(conn.execute("INSERT OR IGNORE INTO cli_block_info (block_hash, time) VALUES (?1, 0)", &[&chain_tip]),
+                    &format!("FATAL: failed to store block info in '{}'", path));
+
+    let hex_value = if value.starts_with("0x") { &value[2..] } else { value };
+    let (column, value): (&str, Box<dyn ToSql>) = match property {
+        BlockInfoProperty::Time =>
+            ("time", Box::new(friendly_expect(value.parse::<u64>(), "Failed to parse block time.") as i64)),
+        BlockInfoProperty::HeaderHash =>
+            ("header_hash", Box::new(friendly_expect(BlockHeaderHash::from_hex(hex_value), "Failed to parse header hash.").to_hex())),
+        BlockInfoProperty::BurnchainHeaderHash =>
+            ("burnchain_header_hash", Box::new(friendly_expect(BurnchainHeaderHash::from_hex(hex_value), "Failed to parse burnchain header hash.").to_hex())),
+        BlockInfoProperty::VrfSeed =>
+            ("vrf_seed", Box::new(friendly_expect(VRFSeed::from_hex(hex_value), "Failed to parse VRF seed.").to_hex())),
+        BlockInfoProperty::MinerAddress =>
+            ("miner_address", Box::new(friendly_expect_opt(StacksAddress::from_string(value), "Failed to parse miner address.").to_string())),
+        BlockInfoProperty::IdentityHeaderHash => {
+            eprintln!("The id-header-hash of a block is its index block hash, and cannot be set.");
+            panic_test!();
+        }
+    };
+
+    friendly_expect(conn.execute(&format!("UPDATE cli_block_info SET {} = ?1 WHERE block_hash = ?2", column),
+                                 &[&*value, &chain_tip as &dyn ToSql]),
+                    &format!("FATAL: failed to store block info in '{}'", path));
+}
+
+/// Serves the `get-block-info?` properties of the blocks mined by the CLI. Properties which
+///   were not set with `workspace set_block_info` are derived from the index block hash.
+struct CLIHeadersDB {
+    conn: Connection
+}
+
+impl CLIHeadersDB {
+    fn open(db_path: &String) -> CLIHeadersDB {
+        let conn = create_or_open_db(&get_cli_db_path(db_path));
+        create_cli_tables(&conn);
+        CLIHeadersDB { conn }
+    }
+
+    fn get_block_info(&self, id_bhh: &StacksBlockId, column: &str) -> Option<String> {
+        let value: Option<Option<String>> = friendly_expect(
+            self.conn.query_row(&format!("SELECT {} FROM cli_block_info WHERE block_hash = ?1", column), &[id_bhh], |row| row.get(0))
+                .optional(),
+            "FATAL: could not read block info");
+        value.and_then(|x| x)
+    }
+}
+
+impl HeadersDB for CLIHeadersDB {
+    fn get_stacks_block_header_hash_for_block(&self, id_bhh: &StacksBlockId) -> Option<BlockHeaderHash> {
+        match self.get_block_info(id_bhh, "header_hash") {
+            Some(hex) => Some(friendly_expect(BlockHeaderHash::from_hex(&hex), "FATAL: bad header hash in CLI database")),
+            None => Some(BlockHeaderHash(id_bhh.0.clone()))
+        }
+    }
+    fn get_burn_header_hash_for_block(&self, id_bhh: &StacksBlockId) -> Option<BurnchainHeaderHash> {
+        match self.get_block_info(id_bhh, "burnchain_header_hash") {
+            Some(hex) => Some(friendly_expect(BurnchainHeaderHash::from_hex(&hex), "FATAL: bad burnchain header hash in CLI database")),
+            None => Some(BurnchainHeaderHash(id_bhh.0.clone()))
+        }
+    }
+    fn get_vrf_seed_for_block(&self, id_bhh: &StacksBlockId) -> Option<VRFSeed> {
+        match self.get_block_info(id_bhh, "vrf_seed") {
+            Some(hex) => Some(friendly_expect(VRFSeed::from_hex(&hex), "FATAL: bad VRF seed in CLI database")),
+            None => Some(VRFSeed(id_bhh.0.clone()))
+        }
+    }
+    fn get_burn_block_time_for_block(&self, id_bhh: &StacksBlockId) -> Option<u64> {
+        Some(get_cli_block_time(&self.conn, id_bhh).unwrap_or(0))
+    }
+    fn get_miner_address(&self, id_bhh: &StacksBlockId) -> Option<StacksAddress> {
+        match self.get_block_info(id_bhh, "miner_address") {
+            Some(address) => Some(friendly_expect_opt(StacksAddress::from_string(&address), "FATAL: bad miner address in CLI database")),
+            None => Some(StacksAddress { version: C32_ADDRESS_VERSION_TESTNET_SINGLESIG, bytes: Hash160([0; 20]) })
+        }
+    }
+}
+
 // This function is pretty weird! But it helps cut down on
 //   repeating a lot of block initialization for the simulation commands.
 fn in_block<F,R>(db_path: &String, mut marf_kv: MarfedKV, f: F) -> R
 where F: FnOnce(MarfedKV) -> (MarfedKV, R) {
 
-    // store CLI data alongside the MARF database state
-    let mut cli_db_path_buf = PathBuf::from(db_path);
-    cli_db_path_buf.push("cli.sqlite");
-    let cli_db_path = cli_db_path_buf
-        .to_str()
-        .expect(&format!("FATAL: failed to convert '{}' to a string", db_path))
-        .to_string();
+    let cli_db_path = get_cli_db_path(db_path);
 
     // need to load the last block 
     let (from, to) = advance_cli_chain_tip(&cli_db_path);
@@ -192,13 +610,7 @@ where F: FnOnce(MarfedKV) -> (MarfedKV, R) {
 fn at_chaintip<F,R>(db_path: &String, mut marf_kv: MarfedKV, f: F) -> R
 where F: FnOnce(MarfedKV) -> (MarfedKV, R) {
 
-    // store CLI data alongside the MARF database state
-    let mut cli_db_path_buf = PathBuf::from(db_path);
-    cli_db_path_buf.push("cli.sqlite");
-    let cli_db_path = cli_db_path_buf
-        .to_str()
-        .expect(&format!("FATAL: failed to convert '{}' to a string", db_path))
-        .to_string();
+    let cli_db_path = get_cli_db_path(db_path);
 
     let cli_db_conn = create_or_open_db(&cli_db_path);
     let from = get_cli_chain_tip(&cli_db_conn);
@@ -263,6 +675,446 @@ fn get_eval_input(invoked_by: &str, args: &[String]) -> EvalInput {
     }
 }
 
+// JSON-RPC 2.0 error codes returned by `serve`. The server errors are in the range which
+//   the spec reserves for implementation-defined errors.
+const RPC_PARSE_ERROR: i64 = -32700;
+const RPC_INVALID_REQUEST: i64 = -32600;
+const RPC_METHOD_NOT_FOUND: i64 = -32601;
+const RPC_INVALID_PARAMS: i64 = -32602;
+const RPC_CHECK_ERROR: i64 = -32000;
+const RPC_RUNTIME_ERROR: i64 = -32001;
+const RPC_COST_LIMIT_EXCEEDED: i64 = -32002;
+
+struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<serde_json::Value>,
+}
+
+type RpcResult = Result<serde_json::Value, RpcError>;
+
+impl RpcError {
+    fn new(code: i64, message: String) -> RpcError {
+        RpcError { code, message, data: None }
+    }
+
+    fn invalid_params(message: String) -> RpcError {
+        RpcError::new(RPC_INVALID_PARAMS, message)
+    }
+
+    fn from_parse_error(error: &ParseError, contract_identifier: &QualifiedContractIdentifier, source: &str) -> RpcError {
+        let code = match error.err {
+            ParseErrors::CostOverflow | ParseErrors::CostBalanceExceeded(..) | ParseErrors::MemoryBalanceExceeded(..) =>
+                RPC_COST_LIMIT_EXCEEDED,
+            _ => RPC_CHECK_ERROR
+        };
+        RpcError { code, message: error.to_string(),
+                   data: Some(error.diagnostic.to_json(&contract_identifier.to_string(), source)) }
+    }
+
+    fn from_check_error(error: &CheckError, contract_identifier: &QualifiedContractIdentifier, source: &str) -> RpcError {
+        let code = if is_cost_error(&error.err) { RPC_COST_LIMIT_EXCEEDED } else { RPC_CHECK_ERROR };
+        RpcError { code, message: error.to_string(),
+                   data: Some(error.diagnostic.to_json(&contract_identifier.to_string(), source)) }
+    }
+
+    fn from_runtime_error(error: &Error) -> RpcError {
+        let code = match error {
+            Error::Unchecked(ref err) if is_cost_error(err) => RPC_COST_LIMIT_EXCEEDED,
+            _ => RPC_RUNTIME_ERROR
+        };
+        RpcError::new(code, error.to_string())
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut error = json!({ "code": self.code, "message": self.message });
+        if let Some(ref data) = self.data {
+            error["data"] = data.clone();
+        }
+        error
+    }
+}
+
+fn is_cost_error(error: &CheckErrors) -> bool {
+    match error {
+        CheckErrors::CostOverflow | CheckErrors::CostBalanceExceeded(..) | CheckErrors::MemoryBalanceExceeded(..) => true,
+        _ => false
+    }
+}
+
+/// The per-request `cost_limit` parameter. Dimensions which are not given are unlimited.
+#[derive(Deserialize)]
+struct CostLimitParam {
+    runtime: Option<u64>,
+    read_count: Option<u64>,
+    read_length: Option<u64>,
+    write_count: Option<u64>,
+    write_length: Option<u64>,
+}
+
+fn get_str_param<'a>(params: &'a serde_json::Value, name: &str) -> Result<&'a str, RpcError> {
+    params.get(name)
+        .and_then(|param| param.as_str())
+        .ok_or_else(|| RpcError::invalid_params(format!("Expected a string parameter: {}", name)))
+}
+
+fn get_contract_param(params: &serde_json::Value, name: &str) -> Result<QualifiedContractIdentifier, RpcError> {
+    let param = get_str_param(params, name)?;
+    QualifiedContractIdentifier::parse(param)
+        .map_err(|e| RpcError::invalid_params(format!("Failed to parse contract identifier {}: {}", param, e)))
+}
+
+fn get_cost_limit_param(params: &serde_json::Value) -> Result<ExecutionCost, RpcError> {
+    let param: CostLimitParam = match params.get("cost_limit") {
+        Some(param) => serde_json::from_value(param.clone())
+            .map_err(|e| RpcError::invalid_params(format!("Failed to parse cost_limit: {}", e)))?,
+        None => return Ok(ExecutionCost::max_value())
+    };
+    let unlimited = ExecutionCost::max_value();
+    Ok(ExecutionCost {
+        runtime: param.runtime.unwrap_or(unlimited.runtime),
+        read_count: param.read_count.unwrap_or(unlimited.read_count),
+        read_length: param.read_length.unwrap_or(unlimited.read_length),
+        write_count: param.write_count.unwrap_or(unlimited.write_count),
+        write_length: param.write_length.unwrap_or(unlimited.write_length),
+    })
+}
+
+// Parses and type-checks a contract against the analyses in `marf`, within the cost limit.
+//   The analysis is not saved.
+fn analyze_contract(contract_identifier: &QualifiedContractIdentifier, source: &str, marf: &mut MarfedKV,
+                    cost_limit: ExecutionCost) -> Result<(ContractAST, ContractAnalysis), RpcError> {
+    let mut cost_track = LimitedCostTracker::new(cost_limit);
+    let mut ast = build_ast(contract_identifier, source, &mut cost_track, ClarityVersion::Clarity1)
+        .map_err(|e| RpcError::from_parse_error(&e, contract_identifier, source))?;
+    let mut db = AnalysisDatabase::new(marf);
+    let analysis = analysis::run_analysis(contract_identifier, &mut ast.expressions, &mut db, false, cost_track)
+        .map_err(|(e, _)| RpcError::from_check_error(&e, contract_identifier, source))?;
+    Ok((ast, analysis))
+}
+
+enum ServeStore {
+    // a VM state database shared with the other commands: its blocks are mined as in `in_block`.
+    Disk { path: String, headers_db: CLIHeadersDB },
+    // a temporary database, whose blocks are only known to the session.
+    Memory { chain_tip: StacksBlockId },
+}
+
+impl ServeStore {
+    fn headers_db(&self) -> &dyn HeadersDB {
+        match self {
+            ServeStore::Disk { headers_db, .. } => headers_db,
+            ServeStore::Memory { .. } => &NULL_HEADER_DB
+        }
+    }
+}
+
+/// The state of a `serve` session. Each request which changes the state (`launch` and
+///   `execute`) mines a new block, and the other requests are evaluated at the chain tip.
+struct ServeSession {
+    marf: MarfedKV,
+    store: ServeStore,
+}
+
+impl ServeSession {
+    fn open(db_path: &String) -> ServeSession {
+        let marf = friendly_expect(MarfedKV::open(db_path, None), "Failed to open VM database.");
+        let headers_db = CLIHeadersDB::open(db_path);
+        ServeSession { marf, store: ServeStore::Disk { path: db_path.clone(), headers_db } }
+    }
+
+    fn memory() -> ServeSession {
+        let mut marf = MarfedKV::temporary();
+        let genesis = StacksBlockId(rand::thread_rng().gen::<[u8; 32]>());
+        marf.begin(&StacksBlockId::sentinel(), &genesis);
+        marf.as_clarity_db(&NULL_HEADER_DB).initialize();
+        marf.commit_to(&genesis);
+        ServeSession { marf, store: ServeStore::Memory { chain_tip: genesis } }
+    }
+
+    // a failed request leaves no block behind: its changes are rolled back, and the chain tip
+    //   is not advanced.
+    fn in_block<F, R>(&mut self, f: F) -> Result<R, RpcError>
+    where F: FnOnce(&mut MarfedKV, &dyn HeadersDB) -> Result<R, RpcError> {
+        let (from, to) = match self.store {
+            ServeStore::Disk { ref path, .. } => advance_cli_chain_tip(&get_cli_db_path(path)),
+            ServeStore::Memory { ref chain_tip } => (chain_tip.clone(), StacksBlockId(rand::thread_rng().gen::<[u8; 32]>()))
+        };
+        self.marf.begin(&from, &to);
+        let result = f(&mut self.marf, self.store.headers_db());
+        if result.is_ok() {
+            self.marf.commit_to(&to);
+            if let ServeStore::Memory { ref mut chain_tip } = self.store {
+                *chain_tip = to;
+            }
+        } else {
+            self.marf.rollback();
+            if let ServeStore::Disk { ref path, .. } = self.store {
+                drop_cli_chain_tip(&get_cli_db_path(path), &to);
+            }
+        }
+        result
+    }
+
+    fn at_chaintip<F, R>(&mut self, f: F) -> R
+    where F: FnOnce(&mut MarfedKV, &dyn HeadersDB) -> R {
+        let from = match self.store {
+            ServeStore::Disk { ref headers_db, .. } => get_cli_chain_tip(&headers_db.conn),
+            ServeStore::Memory { ref chain_tip } => chain_tip.clone()
+        };
+        self.marf.begin(&from, &StacksBlockId([2u8; 32]));
+        let result = f(&mut self.marf, self.store.headers_db());
+        self.marf.rollback();
+        result
+    }
+
+    fn handle(&mut self, method: &str, params: &serde_json::Value) -> RpcResult {
+        match method {
+            "check" => self.check(params),
+            "launch" => self.launch(params),
+            "eval" => self.eval(params),
+            "execute" => self.execute(params),
+            "abi" => self.abi(params),
+            "docs" => {
+                let name = get_str_param(params, "name")?;
+                lookup_api_reference(name)
+                    .ok_or_else(|| RpcError::invalid_params(format!("No documentation for: {}", name)))
+            },
+            "shutdown" => Ok(serde_json::Value::Null),
+            _ => Err(RpcError::new(RPC_METHOD_NOT_FOUND, format!("Unknown method: {}", method)))
+        }
+    }
+
+    fn check(&mut self, params: &serde_json::Value) -> RpcResult {
+        let source = get_str_param(params, "source")?;
+        let contract_identifier = match params.get("contract_identifier") {
+            Some(_) => get_contract_param(params, "contract_identifier")?,
+            None => QualifiedContractIdentifier::transient()
+        };
+        let cost_limit = get_cost_limit_param(params)?;
+
+        let (_, mut analysis) = self.at_chaintip(|marf, _| analyze_contract(&contract_identifier, source, marf, cost_limit))?;
+        let cost = analysis.take_contract_cost_tracker().get_total();
+        Ok(json!({ "interface": build_contract_interface(&analysis), "cost": cost }))
+    }
+
+    fn launch(&mut self, params: &serde_json::Value) -> RpcResult {
+        let contract_identifier = get_contract_param(params, "contract_identifier")?;
+        let source = get_str_param(params, "source")?;
+        let cost_limit = get_cost_limit_param(params)?;
+
+        self.in_block(|marf, headers_db| {
+            let (ast, mut analysis) = analyze_contract(&contract_identifier, source, marf, cost_limit)?;
+            let (result, cost) = {
+                let db = marf.as_clarity_db(headers_db);
+                let mut vm_env = OwnedEnvironment::new_cost_limited(false, db, analysis.take_contract_cost_tracker());
+                let result = vm_env.initialize_contract_from_ast(contract_identifier.clone(), &ast, source);
+                (result, vm_env.get_cost_total())
+            };
+            result.map_err(|e| RpcError::from_runtime_error(&e))?;
+
+            AnalysisDatabase::new(marf)
+                .execute(|db| db.insert_contract(&contract_identifier, &analysis))
+                .map_err(|e| RpcError::from_check_error(&e, &contract_identifier, source))?;
+            Ok(json!({ "interface": build_contract_interface(&analysis), "cost": cost }))
+        })
+    }
+
+    fn eval(&mut self, params: &serde_json::Value) -> RpcResult {
+        let source = get_str_param(params, "source")?;
+        let contract_identifier = match params.get("contract_identifier") {
+            Some(_) => Some(get_contract_param(params, "contract_identifier")?),
+            None => None
+        };
+        let cost_limit = get_cost_limit_param(params)?;
+
+        self.at_chaintip(|marf, headers_db| {
+            let db = marf.as_clarity_db(headers_db);
+            let mut vm_env = OwnedEnvironment::new_cost_limited(false, db, LimitedCostTracker::new(cost_limit));
+            let result = match contract_identifier {
+                Some(ref contract_identifier) => vm_env.get_exec_environment(None).eval_read_only(contract_identifier, source),
+                None => vm_env.get_exec_environment(None).eval_raw(source)
+            };
+            let cost = vm_env.get_cost_total();
+
+            let value = result.map_err(|e| RpcError::from_runtime_error(&e))?;
+            Ok(json!({ "value": value.to_string(), "hex": format!("0x{}", ClaritySerializable::serialize(&value)),
+                       "json": value.to_json(), "cost": cost }))
+        })
+    }
+
+    fn execute(&mut self, params: &serde_json::Value) -> RpcResult {
+        let contract_identifier = get_contract_param(params, "contract_identifier")?;
+        let function_name = get_str_param(params, "function")?;
+        let sender_in = get_str_param(params, "sender")?;
+        let sender = PrincipalData::parse_standard_principal(sender_in)
+            .map_err(|e| RpcError::invalid_params(format!("Failed to parse sender {}: {}", sender_in, e)))?;
+        let cost_limit = get_cost_limit_param(params)?;
+
+        let mut arguments = vec![];
+        if let Some(args) = params.get("args") {
+            let args = args.as_array()
+                .ok_or_else(|| RpcError::invalid_params("Expected an array of Clarity expressions: args".to_string()))?;
+            for argument in args.iter() {
+                let argument = argument.as_str()
+                    .ok_or_else(|| RpcError::invalid_params(format!("Expected a Clarity expression, found: {}", argument)))?;
+                let value = vm_execute(argument)
+                    .map_err(|e| RpcError::invalid_params(format!("Error parsing argument \"{}\": {}", argument, e)))?
+                    .ok_or_else(|| RpcError::invalid_params(format!("Failed to parse a value from the argument: {}", argument)))?;
+                arguments.push(SymbolicExpression::atom_value(value));
+            }
+        }
+        // JSON arguments are decoded using the argument types of the function.
+        if let Some(args) = params.get("json_args") {
+            let args = args.as_array()
+                .ok_or_else(|| RpcError::invalid_params("Expected an array of JSON values: json_args".to_string()))?;
+            let function_type = self.load_analysis(&contract_identifier)?
+                .get_public_function_type(function_name).cloned()
+                .ok_or_else(|| RpcError::invalid_params(format!("No such public function: {}", function_name)))?;
+            let arg_types = match function_type {
+                FunctionType::Fixed(function) => function.args,
+                _ => return Err(RpcError::invalid_params(format!("No such public function: {}", function_name)))
+            };
+            if arg_types.len() != args.len() {
+                return Err(RpcError::invalid_params(format!("Expected {} JSON arguments, found {}", arg_types.len(), args.len())))
+            }
+            for (argument, arg_type) in args.iter().zip(arg_types.iter()) {
+                let value = Value::try_from_json(argument, &arg_type.signature)
+                    .map_err(|e| RpcError::invalid_params(format!("Error decoding argument {}: {}", arg_type.name.as_str(), e)))?;
+                arguments.push(SymbolicExpression::atom_value(value));
+            }
+        }
+
+        self.in_block(|marf, headers_db| {
+            let db = marf.as_clarity_db(headers_db);
+            let mut vm_env = OwnedEnvironment::new_cost_limited(false, db, LimitedCostTracker::new(cost_limit));
+            let result = vm_env.execute_transaction(Value::Principal(PrincipalData::Standard(sender)), contract_identifier,
+                                                    function_name, &arguments);
+            let cost = vm_env.get_cost_total();
+
+            let (value, _, events) = result.map_err(|e| RpcError::from_runtime_error(&e))?;
+            let committed = match value {
+                Value::Response(ref data) => data.committed,
+                _ => return Err(RpcError::new(RPC_RUNTIME_ERROR, format!("Expected a response from the transaction, found: {}", value)))
+            };
+            // the events are not part of a transaction, so they have no txid.
+            let events: Vec<_> = events.iter()
+                .map(|event| {
+                    let mut event = event.json_serialize(&Txid([0; 32]), committed);
+                    if let Some(event) = event.as_object_mut() {
+                        event.remove("txid");
+                    }
+                    event
+                })
+                .collect();
+            Ok(json!({ "committed": committed, "value": value.to_string(), "hex": format!("0x{}", ClaritySerializable::serialize(&value)),
+                       "json": value.to_json(), "events": events, "cost": cost }))
+        })
+    }
+
+    fn abi(&mut self, params: &serde_json::Value) -> RpcResult {
+        let contract_identifier = get_contract_param(params, "contract_identifier")?;
+        let analysis = self.load_analysis(&contract_identifier)?;
+        Ok(json!(build_contract_interface(&analysis)))
+    }
+
+    fn load_analysis(&mut self, contract_identifier: &QualifiedContractIdentifier) -> Result<ContractAnalysis, RpcError> {
+        let analysis = self.at_chaintip(|marf, _| {
+            let mut db = AnalysisDatabase::new(marf);
+            db.begin();
+            let analysis = db.load_contract(contract_identifier)
+                .map(|analysis| analysis.as_ref().clone());
+            db.roll_back();
+            analysis
+        });
+        analysis.ok_or_else(|| RpcError::invalid_params(format!("No such contract: {}", contract_identifier)))
+    }
+}
+
+// Reads the next message, which is either a line of JSON, or a JSON body framed by a
+//   `Content-Length` header, as in the language server protocol. Returns the message, and
+//   whether it was framed.
+fn read_rpc_message<R: io::BufRead>(input: &mut R) -> io::Result<Option<(String, bool)>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None)
+        }
+        if !line.trim().is_empty() {
+            break
+        }
+    }
+
+    if !line.to_ascii_lowercase().starts_with("content-length:") {
+        return Ok(Some((line.trim().to_string(), false)))
+    }
+    let length = line["content-length:".len()..].trim().parse::<usize>()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    // skip any other headers, up to the blank line which ends them.
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None)
+        }
+        if line.trim().is_empty() {
+            break
+        }
+    }
+
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    let body = String::from_utf8(body)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(Some((body, true)))
+}
+
+fn write_rpc_message<W: Write>(output: &mut W, message: &serde_json::Value, framed: bool) -> io::Result<()> {
+    let body = message.to_string();
+    if framed {
+        write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    } else {
+        writeln!(output, "{}", body)?;
+    }
+    output.flush()
+}
+
+fn rpc_response(id: serde_json::Value, result: RpcResult) -> serde_json::Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error.to_json() })
+    }
+}
+
+// Answers JSON-RPC requests until the input ends, or a `shutdown` request. Requests without
+//   an id are notifications, and are not answered.
+fn serve<R: io::BufRead, W: Write>(session: &mut ServeSession, mut input: R, output: &mut W) -> io::Result<()> {
+    while let Some((message, framed)) = read_rpc_message(&mut input)? {
+        let request: serde_json::Value = match serde_json::from_str(&message) {
+            Ok(request) => request,
+            Err(e) => {
+                let error = RpcError::new(RPC_PARSE_ERROR, format!("Failed to parse request: {}", e));
+                write_rpc_message(output, &rpc_response(serde_json::Value::Null, Err(error)), framed)?;
+                continue;
+            }
+        };
+
+        let method = request.get("method").and_then(|method| method.as_str());
+        let result = match method {
+            Some(method) => session.handle(method, request.get("params").unwrap_or(&serde_json::Value::Null)),
+            None => Err(RpcError::new(RPC_INVALID_REQUEST, "Expected a string method in the request.".to_string()))
+        };
+        if let Some(id) = request.get("id") {
+            write_rpc_message(output, &rpc_response(id.clone(), result), framed)?;
+        }
+        if method == Some("shutdown") {
+            break
+        }
+    }
+    Ok(())
+}
+
 pub fn invoke_command(invoked_by: &str, args: &[String]) {
     if args.len() < 1 {
         print_usage(invoked_by)
@@ -276,8 +1128,9 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
             }
 
             let marf_kv = friendly_expect(MarfedKV::open(&args[1], None), "Failed to open VM database.");
+            let headers_db = CLIHeadersDB::open(&args[1]);
             in_block(&args[1], marf_kv, |mut kv| {
-                { let mut db = kv.as_clarity_db(&NULL_HEADER_DB);
+                { let mut db = kv.as_clarity_db(&headers_db);
                   db.initialize() };
                 (kv, ())
             });
@@ -291,6 +1144,9 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
             println!("{}", addr);
         },
         "check" => {
+            let json_diagnostics = args.iter().any(|arg| arg == "--diagnostics=json");
+            let args: Vec<_> = args.iter().filter(|arg| *arg != "--diagnostics=json").cloned().collect();
+            let args = &args[..];
             if args.len() < 2 {
                 eprintln!("Usage: {} {} [program-file.clar] (vm-state.db)", invoked_by, args[0]);
                 panic_test!();
@@ -301,7 +1157,21 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
             let content: String = friendly_expect(fs::read_to_string(&args[1]),
                                                   &format!("Error reading file: {}", args[1]));
 
-            let mut ast = friendly_expect(parse(&contract_id, &content), "Failed to parse program");
+            let print_diagnostic = |diagnostic: &Diagnostic| {
+                if json_diagnostics {
+                    println!("{}", diagnostic.to_json(&args[1], &content));
+                } else {
+                    println!("{}", diagnostic.render(&args[1], &content));
+                }
+            };
+
+            let mut ast = match build_ast(&contract_id, &content, &mut (), ClarityVersion::Clarity1) {
+                Ok(ast) => ast.expressions,
+                Err(e) => {
+                    print_diagnostic(&e.diagnostic);
+                    panic_test!();
+                }
+            };
 
             let contract_analysis = {
                 if args.len() >= 3 {
@@ -322,7 +1192,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                     run_analysis(&contract_id, &mut ast, &mut db, false)
                 }
             }.unwrap_or_else(|e| {
-                println!("{}", &e.diagnostic);
+                print_diagnostic(&e.diagnostic);
                 panic_test!();
             });
 
@@ -335,9 +1205,191 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                 }
             }
         },
+        #[cfg(feature = "developer-mode")]
+        "fmt" => {
+            let write = args.iter().any(|arg| arg == "--write");
+            let args: Vec<_> = args.iter().filter(|arg| *arg != "--write").cloned().collect();
+            if args.len() < 2 {
+                eprintln!("Usage: {} {} [program-file.clar] (--write)", invoked_by, args[0]);
+                panic_test!();
+            }
+
+            let content: String = friendly_expect(fs::read_to_string(&args[1]),
+                                                  &format!("Error reading file: {}", args[1]));
+            let formatted = format_source(&content).unwrap_or_else(|e| {
+                println!("{}", e.diagnostic.render(&args[1], &content));
+                panic_test!();
+            });
+
+            if write {
+                friendly_expect(fs::write(&args[1], formatted),
+                                &format!("Error writing file: {}", args[1]));
+            } else {
+                print!("{}", formatted);
+            }
+        },
+        "lint" => {
+            let json_diagnostics = args.iter().any(|arg| arg == "--diagnostics=json");
+            let args: Vec<_> = args.iter().filter(|arg| *arg != "--diagnostics=json").cloned().collect();
+            let args = &args[..];
+            if args.len() < 2 {
+                eprintln!("Usage: {} {} [program-file.clar] (vm-state.db)", invoked_by, args[0]);
+                panic_test!();
+            }
+
+            let contract_id = QualifiedContractIdentifier::transient();
+
+            let content: String = friendly_expect(fs::read_to_string(&args[1]),
+                                                  &format!("Error reading file: {}", args[1]));
+
+            let print_diagnostic = |diagnostic: &Diagnostic| {
+                if json_diagnostics {
+                    println!("{}", diagnostic.to_json(&args[1], &content));
+                } else {
+                    println!("{}", diagnostic.render(&args[1], &content));
+                }
+            };
+
+            let mut ast = match build_ast(&contract_id, &content, &mut (), ClarityVersion::Clarity1) {
+                Ok(ast) => ast.expressions,
+                Err(e) => {
+                    print_diagnostic(&e.diagnostic);
+                    panic_test!();
+                }
+            };
+
+            // the linter requires a well-typed contract.
+            let contract_analysis = {
+                if args.len() >= 3 {
+                    let marf_kv = friendly_expect(MarfedKV::open(&args[2], None), "Failed to open VM database.");
+                    at_chaintip(
+                        &args[2],
+                        marf_kv,
+                        |mut marf| {
+                            let result = { let mut db = AnalysisDatabase::new(&mut marf);
+                                           run_analysis(&contract_id, &mut ast, &mut db, false) };
+                            (marf, result)
+                        })
+                } else {
+                    let mut analysis_marf = MemoryBackingStore::new();
+                    let mut db = analysis_marf.as_analysis_db();
+                    run_analysis(&contract_id, &mut ast, &mut db, false)
+                }
+            }.unwrap_or_else(|e| {
+                print_diagnostic(&e.diagnostic);
+                panic_test!();
+            });
+
+            let warnings = lint(&contract_analysis);
+            for warning in warnings.iter() {
+                print_diagnostic(warning);
+            }
+            if warnings.is_empty() && !json_diagnostics {
+                println!("No warnings.");
+            }
+        },
+        "test" => {
+            let mut sender = None;
+            let mut accounts = Vec::new();
+            let mut junit_output = None;
+            let mut contract_files = Vec::new();
+            for arg in args[1..].iter() {
+                if arg.starts_with("--sender=") {
+                    let principal = &arg["--sender=".len()..];
+                    sender = Some(friendly_expect(PrincipalData::parse(principal),
+                                                  &format!("Failed to parse principal: {}", principal)));
+                } else if arg.starts_with("--account=") {
+                    accounts.push(parse_test_account(&arg["--account=".len()..]));
+                } else if arg.starts_with("--junit=") {
+                    junit_output = Some(arg["--junit=".len()..].to_string());
+                } else {
+                    contract_files.push(arg.clone());
+                }
+            }
+
+            if contract_files.is_empty() {
+                eprintln!("Usage: {} {} [contract-file.clar...] (--sender=<principal>) (--account=<principal>:<balance>...) (--junit=<report.xml>)",
+                          invoked_by, args[0]);
+                panic_test!();
+            }
+
+            // the sender defaults to the first funded account, then to the contracts' deployer.
+            let deployer = QualifiedContractIdentifier::transient().issuer;
+            let sender = sender
+                .or_else(|| accounts.first().map(|account| account.principal.clone()))
+                .unwrap_or_else(|| PrincipalData::Standard(deployer.clone()));
+
+            let mut contracts = Vec::new();
+            let mut tests = Vec::new();
+            let mut analysis_marf = MemoryBackingStore::new();
+            let mut analysis_db = analysis_marf.as_analysis_db();
+            // the contracts are never published, so their analyses are only visible to each other
+            //   from inside an open transaction.
+            analysis_db.begin();
+            for contract_file in contract_files.iter() {
+                let contract_name = friendly_expect_opt(PathBuf::from(contract_file).file_stem().and_then(|x| x.to_str()).map(|x| x.to_string()),
+                                                        &format!("Failed to determine a contract name for: {}", contract_file));
+                let contract_identifier = friendly_expect(QualifiedContractIdentifier::parse(&format!("{}.{}", deployer, contract_name)),
+                                                          &format!("Invalid contract name: {}", contract_name));
+                let content = friendly_expect(fs::read_to_string(contract_file),
+                                              &format!("Error reading file: {}", contract_file));
+
+                let mut ast = friendly_expect(parse(&contract_identifier, &content), &format!("Failed to parse {}", contract_file));
+                let analysis = run_analysis(&contract_identifier, &mut ast, &mut analysis_db, true)
+                    .unwrap_or_else(|e| {
+                        println!("{}", e.render(contract_file, &content));
+                        panic_test!();
+                    });
+
+                for (function_name, function_type) in analysis.public_function_types.iter() {
+                    if !function_name.starts_with("test-") {
+                        continue;
+                    }
+                    match function_type {
+                        FunctionType::Fixed(FixedFunction { args, .. }) if args.is_empty() => {
+                            tests.push((contract_identifier.clone(), function_name.to_string()));
+                        },
+                        _ => {
+                            eprintln!("Skipping {}.{}: test functions may not take arguments", contract_name, function_name.as_str());
+                        }
+                    }
+                }
+                contracts.push((contract_identifier, content));
+            }
+            analysis_db.roll_back();
+
+            println!("running {} tests", tests.len());
+            let mut results = Vec::new();
+            for (contract_identifier, function_name) in tests.iter() {
+                let result = run_contract_test(&contracts, &accounts, &sender, contract_identifier, function_name);
+                let status = match result.outcome {
+                    TestOutcome::Passed(ref value) => format!("ok: (ok {})", value),
+                    TestOutcome::Failed(ref value) => format!("FAILED: (err {})", value),
+                    TestOutcome::Errored(ref message) => format!("ERROR: {}", message)
+                };
+                println!("test {}.{} ... {}\n  cost: {}", contract_identifier.name.as_str(), function_name, status, result.cost);
+                results.push(result);
+            }
+
+            if let Some(junit_output) = junit_output {
+                friendly_expect(fs::write(&junit_output, junit_report(&results)),
+                                &format!("Failed to write test report: {}", junit_output));
+            }
+
+            let passed = results.iter().filter(|result| result.passed()).count();
+            let failed = results.len() - passed;
+            println!("\ntest result: {}. {} passed; {} failed", if failed == 0 { "ok" } else { "FAILED" }, passed, failed);
+            if failed > 0 {
+                panic_test!();
+            }
+        },
         "repl" => {
             let mut marf = MemoryBackingStore::new();
-            let mut vm_env = OwnedEnvironment::new_cost_limited(marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
+            let mut vm_env = OwnedEnvironment::new_cost_limited(false, marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
+
+            let debugger = Debugger::new();
+            vm_env.add_eval_hook(Box::new(debugger.clone()));
+
             let mut exec_env = vm_env.get_exec_environment(None);
 
             let mut analysis_marf = MemoryBackingStore::new();
@@ -348,7 +1400,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
             let mut stdout = io::stdout();
 
             loop {
-                let content: String = {
+                let mut content: String = {
                     let mut buffer = String::new();
                     stdout.write(b"> ").unwrap_or_else(|e| {
                         panic!("Failed to write stdout prompt string:\n{}", e);
@@ -365,10 +1417,56 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                     }
                 };
 
+                if content.trim_start().starts_with(':') {
+                    let command = content.trim().to_string();
+                    let mut words = command.splitn(2, char::is_whitespace);
+                    let name = words.next().unwrap_or("");
+                    let argument = words.next().map(|x| x.trim()).unwrap_or("");
+                    match name {
+                        ":debug" => {
+                            debugger.step_into_next();
+                            content = argument.to_string();
+                        },
+                        ":break" => {
+                            match debugger.add_breakpoint(argument) {
+                                Some(breakpoint) => println!("{}", breakpoint),
+                                None => println!("Invalid breakpoint location: {}", argument)
+                            }
+                            continue;
+                        },
+                        ":delete" => {
+                            match argument.parse::<usize>() {
+                                Ok(id) if debugger.delete_breakpoint(id) => println!("Deleted breakpoint {}.", id),
+                                _ => println!("No such breakpoint: {}", argument)
+                            }
+                            continue;
+                        },
+                        ":breakpoints" => {
+                            let breakpoints = debugger.breakpoints();
+                            if breakpoints.is_empty() {
+                                println!("No breakpoints.");
+                            }
+                            for breakpoint in breakpoints.iter() {
+                                println!("{}", breakpoint);
+                            }
+                            continue;
+                        },
+                        ":help" => {
+                            println!("{}", REPL_HELP_TEXT);
+                            continue;
+                        },
+                        _ => {
+                            println!("Unknown command: {}\n{}", name, REPL_HELP_TEXT);
+                            continue;
+                        }
+                    }
+                }
+
                 let mut ast = match parse(&contract_id, &content) {
                     Ok(val) => val,
                     Err(error) => {
                         println!("Parse error:\n{}", error);
+                        debugger.reset();
                         continue;
                     }
                 };
@@ -377,11 +1475,15 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                     Ok(_) => (),
                     Err(error) => {
                         println!("Type check error:\n{}", error);
+                        debugger.reset();
                         continue;
                     } 
                 }
 
-                let eval_result = match exec_env.eval_raw(&content) {
+                let eval_result = exec_env.eval_raw(&content);
+                // don't carry a pending `step` over into the next input.
+                debugger.reset();
+                let eval_result = match eval_result {
                     Ok(val) => val,
                     Err(error) => {
                         println!("Execution error:\n{}", error);
@@ -403,7 +1505,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
             let mut analysis_db = analysis_marf.as_analysis_db();
 
             let mut marf = MemoryBackingStore::new();
-            let mut vm_env = OwnedEnvironment::new_cost_limited(marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
+            let mut vm_env = OwnedEnvironment::new_cost_limited(false, marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
  
             
             let contract_id = QualifiedContractIdentifier::transient(); 
@@ -429,6 +1531,9 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
             }
         },
         "eval" => {
+            let (args, cost_trace) = take_cost_trace_options(args);
+            let args = &args[..];
+            let tracer = CostTracer::new();
             let evalInput = get_eval_input(invoked_by, args);
             let vm_filename = 
                 if args.len() == 3 {
@@ -437,15 +1542,23 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                     &args[3]
                 };
             let marf_kv = friendly_expect(MarfedKV::open(vm_filename, None), "Failed to open VM database.");
+            let headers_db = CLIHeadersDB::open(vm_filename);
             let result = in_block(vm_filename, marf_kv, |mut marf| {
                 let result = {
-                    let db = marf.as_clarity_db(&NULL_HEADER_DB);
-                    let mut vm_env = OwnedEnvironment::new_cost_limited(db, LimitedCostTracker::new_max_limit());
+                    let db = marf.as_clarity_db(&headers_db);
+                    let mut vm_env = OwnedEnvironment::new_cost_limited(false, db, LimitedCostTracker::new_max_limit());
+                    if cost_trace.is_some() {
+                        vm_env.add_cost_tracer(tracer.clone());
+                    }
                     vm_env.get_exec_environment(None)
                         .eval_read_only(&evalInput.contract_identifier, &evalInput.content)
                 };
                 (marf, result)
             });
+
+            if let Some(ref options) = cost_trace {
+                write_cost_trace(&tracer, options);
+            }
         
             match result {
                 Ok(x) => {
@@ -458,6 +1571,9 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
             }
         },
         "eval_at_chaintip" => {
+            let (args, cost_trace) = take_cost_trace_options(args);
+            let args = &args[..];
+            let tracer = CostTracer::new();
             let evalInput = get_eval_input(invoked_by, args);
             let vm_filename = 
                 if args.len() == 3 {
@@ -466,15 +1582,23 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                     &args[3]
                 };
             let marf_kv = friendly_expect(MarfedKV::open(vm_filename, None), "Failed to open VM database.");
+            let headers_db = CLIHeadersDB::open(vm_filename);
             let result = at_chaintip(vm_filename, marf_kv, |mut marf| {
                 let result = {
-                    let db = marf.as_clarity_db(&NULL_HEADER_DB);
-                    let mut vm_env = OwnedEnvironment::new_cost_limited(db, LimitedCostTracker::new_max_limit());
+                    let db = marf.as_clarity_db(&headers_db);
+                    let mut vm_env = OwnedEnvironment::new_cost_limited(false, db, LimitedCostTracker::new_max_limit());
+                    if cost_trace.is_some() {
+                        vm_env.add_cost_tracer(tracer.clone());
+                    }
                     vm_env.get_exec_environment(None)
                         .eval_read_only(&evalInput.contract_identifier, &evalInput.content)
                 };
                 (marf, result)
             });
+
+            if let Some(ref options) = cost_trace {
+                write_cost_trace(&tracer, options);
+            }
         
             match result {
                 Ok(x) => {
@@ -504,10 +1628,11 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
 
             let vm_filename = &args[3];
             let marf_kv = friendly_expect(MarfedKV::open(vm_filename, None), "Failed to open VM database.");
+            let headers_db = CLIHeadersDB::open(vm_filename);
             let result = at_block(chain_tip, marf_kv, |mut marf| {
                 let result = {
-                    let db = marf.as_clarity_db(&NULL_HEADER_DB);
-                    let mut vm_env = OwnedEnvironment::new_cost_limited(db, LimitedCostTracker::new_max_limit());
+                    let db = marf.as_clarity_db(&headers_db);
+                    let mut vm_env = OwnedEnvironment::new_cost_limited(false, db, LimitedCostTracker::new_max_limit());
                     vm_env.get_exec_environment(None)
                         .eval_read_only(&contract_identifier, &content)
                 };
@@ -538,6 +1663,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
 
             let mut ast = friendly_expect(parse(&contract_identifier, &contract_content), "Failed to parse program.");
             let marf_kv = friendly_expect(MarfedKV::open(vm_filename, None), "Failed to open VM database.");
+            let headers_db = CLIHeadersDB::open(vm_filename);
             let result = in_block(
                 vm_filename,
                 marf_kv,
@@ -552,8 +1678,8 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                         Err(e) => (marf, Err(e)),
                         Ok(analysis) => {
                             let result = {
-                                let db = marf.as_clarity_db(&NULL_HEADER_DB);
-                                let mut vm_env = OwnedEnvironment::new_cost_limited(db, LimitedCostTracker::new_max_limit());
+                                let db = marf.as_clarity_db(&headers_db);
+                                let mut vm_env = OwnedEnvironment::new_cost_limited(false, db, LimitedCostTracker::new_max_limit());
                                 vm_env.initialize_contract(contract_identifier, &contract_content)
                             };
                             (marf, Ok((analysis, result)))
@@ -582,18 +1708,61 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                 }
             }
         },
+        "deps" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} {} [contract-identifier] [vm-state.db]", invoked_by, args[0]);
+                panic_test!();
+            }
+            let vm_filename = &args[2];
+
+            let contract_identifier = friendly_expect(QualifiedContractIdentifier::parse(&args[1]), "Failed to parse contract identifier.");
+
+            let marf_kv = friendly_expect(MarfedKV::open(vm_filename, None), "Failed to open VM database.");
+            let result = at_chaintip(
+                vm_filename,
+                marf_kv,
+                |mut marf| {
+                    let result = { let mut db = AnalysisDatabase::new(&mut marf);
+                                   db.begin();
+                                   let result = db.get_dependency_graph(&contract_identifier);
+                                   db.roll_back();
+                                   result };
+                    (marf, result)
+                });
+
+            match result {
+                Ok(graph) => {
+                    let dependencies: Vec<_> = graph.get(&contract_identifier)
+                        .map(|dependencies| dependencies.iter().map(|x| x.to_string()).collect())
+                        .unwrap_or_default();
+                    let graph: BTreeMap<_, Vec<_>> = graph.iter()
+                        .map(|(contract, dependencies)| (contract.to_string(),
+                                                         dependencies.iter().map(|x| x.to_string()).collect()))
+                        .collect();
+                    println!("{}", json!({ "dependencies": dependencies, "graph": graph }));
+                },
+                Err(error) => {
+                    eprintln!("Failed to load contract dependencies: \n{}", error);
+                    panic_test!();
+                }
+            }
+        },
         "execute" => {
+            let (args, cost_trace) = take_cost_trace_options(args);
+            let args = &args[..];
             if args.len() < 5 {
                 eprintln!("Usage: {} {} [vm-state.db] [contract-identifier] [public-function-name] [sender-address] [args...]", invoked_by, args[0]);
                 panic_test!();
             }
             let vm_filename = &args[1];
             let marf_kv = friendly_expect(MarfedKV::open(vm_filename, None), "Failed to open VM database.");
+            let headers_db = CLIHeadersDB::open(vm_filename);
 
             let contract_identifier = friendly_expect(QualifiedContractIdentifier::parse(&args[2]), "Failed to parse contract identifier.");
 
             let tx_name = &args[3];            
             let sender_in = &args[4];
+            let tracer = CostTracer::new();
 
             let sender = {
                 if let Ok(sender) = PrincipalData::parse_standard_principal(sender_in) {
@@ -619,12 +1788,19 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
 
             let result = in_block(vm_filename, marf_kv, |mut marf| {
                 let result = {
-                    let db = marf.as_clarity_db(&NULL_HEADER_DB);
-                    let mut vm_env = OwnedEnvironment::new_cost_limited(db, LimitedCostTracker::new_max_limit());
+                    let db = marf.as_clarity_db(&headers_db);
+                    let mut vm_env = OwnedEnvironment::new_cost_limited(false, db, LimitedCostTracker::new_max_limit());
+                    if cost_trace.is_some() {
+                        vm_env.add_cost_tracer(tracer.clone());
+                    }
                     vm_env.execute_transaction(Value::Principal(sender), contract_identifier, &tx_name, &arguments) };
                 (marf, result)
             });
 
+            if let Some(ref options) = cost_trace {
+                write_cost_trace(&tracer, options);
+            }
+
             match result {
                 Ok((x, _, events)) => {
                     if let Value::Response(data) = x {
@@ -643,6 +1819,105 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) {
                 }
             }
         },
+        "serve" => {
+            let mut session = match args.get(1) {
+                Some(vm_filename) => ServeSession::open(vm_filename),
+                None => ServeSession::memory()
+            };
+            let stdin = io::stdin();
+            friendly_expect(serve(&mut session, stdin.lock(), &mut io::stdout()), "Failed to serve requests.");
+        },
+        "workspace" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} {} [vm-state.db] [status | advance | checkpoint | checkpoints | restore | fork | set_block_info] ...", invoked_by, args[0]);
+                panic_test!();
+            }
+            let vm_filename = &args[1];
+            let marf_kv = friendly_expect(MarfedKV::open(vm_filename, None), "Failed to open VM database.");
+            let cli_db_path = get_cli_db_path(vm_filename);
+
+            match args[2].as_ref() {
+                "status" => {
+                    let chain_tip = get_cli_chain_tip(&create_or_open_db(&cli_db_path));
+                    let next_height = at_chaintip(vm_filename, marf_kv, |mut marf| {
+                        let height = marf.get_current_block_height();
+                        (marf, height)
+                    });
+                    println!("Chain tip: {}\nNext block-height: {}", chain_tip, next_height);
+                },
+                "advance" => {
+                    let count = match args.get(3) {
+                        Some(count) => friendly_expect(count.parse::<u32>(), "Failed to parse block count."),
+                        None => 1
+                    };
+                    let mut marf_kv = marf_kv;
+                    for _ in 0..count {
+                        let (from, to) = advance_cli_chain_tip(&cli_db_path);
+                        marf_kv.begin(&from, &to);
+                        marf_kv.commit_to(&to);
+                    }
+                    println!("Chain tip: {}", get_cli_chain_tip(&create_or_open_db(&cli_db_path)));
+                },
+                "checkpoint" => {
+                    if args.len() != 4 {
+                        eprintln!("Usage: {} {} [vm-state.db] checkpoint [name]", invoked_by, args[0]);
+                        panic_test!();
+                    }
+                    let chain_tip = set_cli_checkpoint(&cli_db_path, &args[3]);
+                    println!("Checkpoint {}: {}", args[3], chain_tip);
+                },
+                "checkpoints" => {
+                    for (name, block_hash) in get_cli_checkpoints(&cli_db_path) {
+                        println!("{} {}", name, block_hash);
+                    }
+                },
+                "restore" => {
+                    if args.len() != 4 {
+                        eprintln!("Usage: {} {} [vm-state.db] restore [name]", invoked_by, args[0]);
+                        panic_test!();
+                    }
+                    let block_hash = friendly_expect_opt(
+                        get_cli_checkpoints(&cli_db_path).into_iter()
+                            .find(|(name, _)| name == &args[3])
+                            .map(|(_, block_hash)| block_hash),
+                        &format!("No such checkpoint: {}", args[3]));
+                    set_cli_chain_tip(&cli_db_path, &block_hash);
+                    println!("Chain tip: {}", block_hash);
+                },
+                "fork" => {
+                    if args.len() != 4 {
+                        eprintln!("Usage: {} {} [vm-state.db] fork [index-block-hash | height]", invoked_by, args[0]);
+                        panic_test!();
+                    }
+                    let block_hash = match args[3].parse::<u32>() {
+                        Ok(height) => {
+                            let block_hash_opt = at_chaintip(vm_filename, marf_kv, |mut marf| {
+                                let block_hash = marf.get_block_at_height(height);
+                                (marf, block_hash)
+                            });
+                            friendly_expect_opt(block_hash_opt, &format!("No block at height {}", height))
+                        },
+                        Err(_) => friendly_expect(StacksBlockId::from_hex(&args[3]), "Failed to parse index block hash.")
+                    };
+                    set_cli_chain_tip(&cli_db_path, &block_hash);
+                    println!("Chain tip: {}", block_hash);
+                },
+                "set_block_info" => {
+                    if args.len() != 5 {
+                        eprintln!("Usage: {} {} [vm-state.db] set_block_info [property] [value]", invoked_by, args[0]);
+                        panic_test!();
+                    }
+                    let property = friendly_expect_opt(BlockInfoProperty::lookup_by_name(&args[3]),
+                                                       &format!("Unknown block info property: {}", args[3]));
+                    set_cli_block_info(&cli_db_path, &property, &args[4]);
+                    println!("Block info updated.");
+                },
+                _ => {
+                    eprintln!("Unknown workspace command: {}", args[2]);
+                    panic_test!();
+                }
+            }
+        },
         _ => {
             print_usage(invoked_by)
         }
@@ -661,7 +1936,18 @@ mod test {
 
         eprintln!("check tokens");
         invoke_command("test", &["check".to_string(), "sample-contracts/tokens.clar".to_string()]);
+
+        eprintln!("check tokens with json diagnostics");
+        invoke_command("test", &["check".to_string(), "sample-contracts/tokens.clar".to_string(), "--diagnostics=json".to_string()]);
         
+        eprintln!("lint tokens");
+        invoke_command("test", &["lint".to_string(), "sample-contracts/tokens.clar".to_string()]);
+
+        if cfg!(feature = "developer-mode") {
+            eprintln!("fmt tokens");
+            invoke_command("test", &["fmt".to_string(), "sample-contracts/tokens.clar".to_string()]);
+        }
+
         eprintln!("check tokens");
         invoke_command("test", &["check".to_string(), "sample-contracts/tokens.clar".to_string(), db_name.clone()]);
         
@@ -676,11 +1962,34 @@ mod test {
         invoke_command("test", &["launch".to_string(), "S1G2081040G2081040G2081040G208105NK8PE5.names".to_string(),
                                  "sample-contracts/names.clar".to_string(), db_name.clone()]);
 
+        eprintln!("deps names");
+        invoke_command("test", &["deps".to_string(), "S1G2081040G2081040G2081040G208105NK8PE5.names".to_string(), db_name.clone()]);
+
         eprintln!("execute tokens");
         invoke_command("test", &["execute".to_string(), db_name.clone(), "S1G2081040G2081040G2081040G208105NK8PE5.tokens".to_string(),
                                  "mint!".to_string(), "SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR".to_string(),
                                  "(+ u900 u100)".to_string()]);
 
+        eprintln!("execute tokens with a cost trace");
+        let trace_name = format!("{}.folded", db_name);
+        invoke_command("test", &["execute".to_string(), db_name.clone(), "S1G2081040G2081040G2081040G208105NK8PE5.tokens".to_string(),
+                                 "mint!".to_string(), "SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR".to_string(),
+                                 "u100".to_string(), format!("--cost_trace={}", trace_name),
+                                 "--cost_trace_dimension=write_count".to_string()]);
+        let trace = fs::read_to_string(&trace_name).unwrap();
+        assert!(trace.lines().all(|line| line.starts_with("S1G2081040G2081040G2081040G208105NK8PE5.tokens:mint!")));
+        assert!(!trace.is_empty());
+
+        eprintln!("test tokens");
+        let junit_name = format!("{}.junit.xml", db_name);
+        invoke_command("test", &["test".to_string(), "sample-contracts/tokens.clar".to_string(),
+                                 "sample-contracts/tokens-test.clar".to_string(),
+                                 "--account=SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR:1000".to_string(),
+                                 format!("--junit={}", junit_name)]);
+        let report = fs::read_to_string(&junit_name).unwrap();
+        assert!(report.contains("<testsuites tests=\"3\" failures=\"0\">"));
+        assert!(report.contains("<testcase name=\"test-stx-balance\" classname=\"S1G2081040G2081040G2081040G208105NK8PE5.tokens-test\">"));
+
         eprintln!("eval tokens");
         invoke_command("test", &["eval".to_string(), "S1G2081040G2081040G2081040G208105NK8PE5.tokens".to_string(), 
                                  "sample-contracts/tokens-mint.clar".to_string(),
@@ -693,4 +2002,175 @@ mod test {
                                  db_name.clone()
                                  ]);
     }
+
+    fn serve_requests(session: &mut ServeSession, requests: &[serde_json::Value]) -> Vec<serde_json::Value> {
+        let input: String = requests.iter().map(|request| format!("{}\n", request)).collect();
+        let mut output = vec![];
+        serve(session, io::Cursor::new(input), &mut output).unwrap();
+        String::from_utf8(output).unwrap().lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_serve() {
+        let tokens_id = "S1G2081040G2081040G2081040G208105NK8PE5.tokens";
+        let tokens = fs::read_to_string("sample-contracts/tokens.clar").unwrap();
+        let mut session = ServeSession::memory();
+
+        let responses = serve_requests(&mut session, &[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "check", "params": { "source": "(define-public (f) (ok (+ 1 u1)))" } }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "launch", "params": { "contract_identifier": tokens_id, "source": tokens } }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "execute",
+                    "params": { "contract_identifier": tokens_id, "function": "mint!",
+                                "sender": "SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR", "args": ["u100"] } }),
+            json!({ "jsonrpc": "2.0", "method": "eval", "params": { "source": "(+ 1 2)" } }),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "eval",
+                    "params": { "contract_identifier": tokens_id, "source": "(get-balance 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)" } }),
+            json!({ "jsonrpc": "2.0", "id": 5, "method": "eval",
+                    "params": { "contract_identifier": tokens_id, "source": "(get-balance 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+                                "cost_limit": { "read_count": 0 } } }),
+            json!({ "jsonrpc": "2.0", "id": 6, "method": "abi", "params": { "contract_identifier": tokens_id } }),
+            json!({ "jsonrpc": "2.0", "id": 7, "method": "docs", "params": { "name": "ft-transfer?" } }),
+            json!({ "jsonrpc": "2.0", "id": 8, "method": "no-such-method" }),
+            json!({ "jsonrpc": "2.0", "id": 9, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "id": 10, "method": "docs", "params": { "name": "ft-transfer?" } }),
+        ]);
+
+        // the notification and the requests after the shutdown are not answered.
+        let ids: Vec<_> = responses.iter().map(|response| response["id"].as_u64().unwrap()).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        assert_eq!(responses[0]["error"]["code"], RPC_CHECK_ERROR);
+        assert_eq!(responses[0]["error"]["data"]["level"], "error");
+        assert!(responses[1]["result"]["interface"]["functions"].as_array().unwrap().len() > 0);
+        assert_eq!(responses[2]["result"]["committed"], true);
+        assert_eq!(responses[2]["result"]["value"], "(ok u100)");
+        assert_eq!(responses[2]["result"]["events"].as_array().unwrap().len(), 0);
+        assert_eq!(responses[3]["result"]["value"], "u10100");
+        assert_eq!(responses[4]["error"]["code"], RPC_COST_LIMIT_EXCEEDED);
+        assert_eq!(responses[5]["result"], responses[1]["result"]["interface"]);
+        assert_eq!(responses[6]["result"]["name"], "ft-transfer?");
+        assert_eq!(responses[7]["error"]["code"], RPC_METHOD_NOT_FOUND);
+        assert_eq!(responses[8]["result"], serde_json::Value::Null);
+
+        // requests framed as in the language server protocol are answered in the same way.
+        let body = json!({ "jsonrpc": "2.0", "id": "eval", "method": "eval", "params": { "source": "(list 1 2)" } }).to_string();
+        let mut output = vec![];
+        serve(&mut session, io::Cursor::new(format!("Content-Length: {}\r\n\r\n{}", body.len(), body)), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let (header, response) = output.split_at(output.find("\r\n\r\n").unwrap() + 4);
+        assert_eq!(header, format!("Content-Length: {}\r\n\r\n", response.len()));
+        let response: serde_json::Value = serde_json::from_str(response).unwrap();
+        assert_eq!(response["id"], "eval");
+        assert_eq!(response["result"]["value"], "(1 2)");
+        assert_eq!(response["result"]["hex"], "0x0b0000000200000000000000000000000000000000010000000000000000000000000000000002");
+        assert_eq!(response["result"]["json"], json!(["1", "2"]));
+
+        // JSON arguments are decoded using the function's argument types.
+        let responses = serve_requests(&mut session, &[
+            json!({ "jsonrpc": "2.0", "id": 11, "method": "execute",
+                    "params": { "contract_identifier": tokens_id, "function": "mint!",
+                                "sender": "SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR", "json_args": ["100"] } }),
+            json!({ "jsonrpc": "2.0", "id": 12, "method": "execute",
+                    "params": { "contract_identifier": tokens_id, "function": "mint!",
+                                "sender": "SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR", "json_args": [true] } }),
+        ]);
+        assert_eq!(responses[0]["result"]["value"], "(ok u100)");
+        assert_eq!(responses[0]["result"]["json"], json!({ "ok": "100" }));
+        assert_eq!(responses[1]["error"]["code"], RPC_INVALID_PARAMS);
+
+        let mut output = vec![];
+        serve(&mut session, io::Cursor::new("not json\n"), &mut output).unwrap();
+        let response: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(response["error"]["code"], RPC_PARSE_ERROR);
+    }
+
+    #[test]
+    fn test_serve_failed_request() {
+        let contract_id = "S1G2081040G2081040G2081040G208105NK8PE5.counter";
+        let failing = "(define-data-var x int 1) (var-set x 2) (/ 1 0)";
+        let requests = [
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "eval", "params": { "source": "block-height" } }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "launch", "params": { "contract_identifier": contract_id, "source": failing } }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "eval", "params": { "source": "block-height" } }),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "launch",
+                    "params": { "contract_identifier": contract_id, "source": "(define-data-var x int 1)" } }),
+            json!({ "jsonrpc": "2.0", "id": 5, "method": "eval", "params": { "source": "block-height" } }),
+        ];
+
+        // the failed launch mines no block, and leaves no contract behind.
+        let mut session = ServeSession::memory();
+        let responses = serve_requests(&mut session, &requests);
+        assert_eq!(responses[1]["error"]["code"], RPC_RUNTIME_ERROR);
+        assert_eq!(responses[2]["result"]["value"], responses[0]["result"]["value"]);
+        assert!(responses[3]["result"]["interface"].is_object());
+        assert_ne!(responses[4]["result"]["value"], responses[0]["result"]["value"]);
+
+        let db_name = format!("/tmp/db_{}", rand::thread_rng().gen::<i32>());
+        invoke_command("test", &["initialize".to_string(), db_name.clone()]);
+        let chain_tip = get_cli_chain_tip(&create_or_open_db(&get_cli_db_path(&db_name)));
+
+        let mut session = ServeSession::open(&db_name);
+        let responses = serve_requests(&mut session, &requests[1..3]);
+        assert_eq!(responses[0]["error"]["code"], RPC_RUNTIME_ERROR);
+        assert_eq!(get_cli_chain_tip(&create_or_open_db(&get_cli_db_path(&db_name))), chain_tip);
+        assert_eq!(eval_at_workspace_tip(&db_name, "block-height"), Value::UInt(1));
+    }
+
+    fn eval_at_workspace_tip(db_name: &String, program: &str) -> Value {
+        let marf_kv = friendly_expect(MarfedKV::open(db_name, None), "Failed to open VM database.");
+        let headers_db = CLIHeadersDB::open(db_name);
+        at_chaintip(db_name, marf_kv, |mut marf| {
+            let result = {
+                let db = marf.as_clarity_db(&headers_db);
+                let mut vm_env = OwnedEnvironment::new(db);
+                vm_env.eval_raw(program).unwrap().0
+            };
+            (marf, result)
+        })
+    }
+
+    fn workspace(db_name: &String, args: &[&str]) {
+        let mut command = vec!["workspace".to_string(), db_name.clone()];
+        command.extend(args.iter().map(|x| x.to_string()));
+        invoke_command("test", &command);
+    }
+
+    #[test]
+    fn test_workspace() {
+        let db_name = format!("/tmp/db_{}", rand::thread_rng().gen::<i32>());
+        invoke_command("test", &["initialize".to_string(), db_name.clone()]);
+
+        workspace(&db_name, &["advance", "2"]);
+        workspace(&db_name, &["status"]);
+        assert_eq!(eval_at_workspace_tip(&db_name, "block-height"), Value::UInt(3));
+        assert_eq!(eval_at_workspace_tip(&db_name, "(- (unwrap-panic (get-block-info? time u2)) (unwrap-panic (get-block-info? time u1)))"),
+                   Value::UInt(600));
+
+        workspace(&db_name, &["set_block_info", "time", "1000"]);
+        workspace(&db_name, &["set_block_info", "miner-address", "SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR"]);
+        assert_eq!(eval_at_workspace_tip(&db_name, "(get-block-info? time u2)"),
+                   Value::some(Value::UInt(1000)).unwrap());
+        assert_eq!(eval_at_workspace_tip(&db_name, "(get-block-info? miner-address u2)"),
+                   Value::some(Value::Principal(PrincipalData::Standard(
+                       PrincipalData::parse_standard_principal("SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR").unwrap()))).unwrap());
+
+        workspace(&db_name, &["checkpoint", "two"]);
+        workspace(&db_name, &["advance"]);
+        assert_eq!(eval_at_workspace_tip(&db_name, "block-height"), Value::UInt(4));
+
+        // blocks mined on a fork follow the time of their own parent.
+        workspace(&db_name, &["fork", "1"]);
+        assert_eq!(eval_at_workspace_tip(&db_name, "block-height"), Value::UInt(2));
+        workspace(&db_name, &["advance"]);
+        assert_eq!(eval_at_workspace_tip(&db_name, "(- (unwrap-panic (get-block-info? time u2)) (unwrap-panic (get-block-info? time u1)))"),
+                   Value::UInt(600));
+
+        workspace(&db_name, &["checkpoints"]);
+        workspace(&db_name, &["restore", "two"]);
+        assert_eq!(eval_at_workspace_tip(&db_name, "block-height"), Value::UInt(3));
+        assert_eq!(eval_at_workspace_tip(&db_name, "(get-block-info? time u2)"),
+                   Value::some(Value::UInt(1000)).unwrap());
+    }
 }
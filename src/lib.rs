@@ -41,6 +41,11 @@ extern crate mio;
 extern crate url;
 extern crate percent_encoding;
 
+#[cfg(feature = "wasm")]
+extern crate wasmi;
+#[cfg(feature = "wasm")]
+extern crate parity_wasm;
+
 #[macro_use] extern crate serde_derive;
 #[macro_use] extern crate serde_json;
 
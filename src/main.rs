@@ -142,6 +142,11 @@ fn main() {
         }).unwrap();
 
         println!("{:#?}", &tx);
+        println!("Post-condition mode: {}", &tx.post_condition_mode);
+        println!("Post-conditions:");
+        for postcond in tx.post_conditions.iter() {
+            println!("  {}", postcond);
+        }
         process::exit(0);
     }
 
@@ -47,8 +47,11 @@ use net::MessageSequence;
 use net::ProtocolFamily;
 use net::HttpRequestMetadata;
 use net::HttpResponseMetadata;
+use net::StacksBlockSelector;
 use net::NeighborAddress;
 use net::CallReadOnlyRequestBody;
+use net::SimulateTransactionRequestBody;
+use net::SimulateTransactionRequest;
 use net::HTTP_PREAMBLE_MAX_ENCODED_SIZE;
 use net::HTTP_PREAMBLE_MAX_NUM_HEADERS;
 use net::MAX_MESSAGE_LEN;
@@ -58,8 +61,9 @@ use net::HTTP_REQUEST_ID_RESERVED;
 use burnchains::{ Txid, Address };
 use chainstate::stacks::{
     StacksAddress, StacksTransaction, StacksBlock, StacksMicroblock, StacksPublicKey,
-    StacksBlockId
+    StacksBlockId, TransactionPostCondition, TransactionPostConditionMode
 };
+use chainstate::stacks::db::transactions::SimulationOverrides;
 
 use util::log;
 use util::hash::hex_bytes;
@@ -70,7 +74,7 @@ use vm::{
     ast::parser::{
         STANDARD_PRINCIPAL_REGEX, PRINCIPAL_DATA_REGEX, CLARITY_NAME_REGEX, CONTRACT_NAME_REGEX
     },
-    types::{ PrincipalData, BOUND_VALUE_SERIALIZATION_HEX },
+    types::{ PrincipalData, QualifiedContractIdentifier, BOUND_VALUE_SERIALIZATION_HEX },
     ClarityName, ContractName, Value
 };
 
@@ -96,20 +100,39 @@ lazy_static! {
     static ref PATH_GETMICROBLOCKS_CONFIRMED : Regex = Regex::new(r#"^/v2/microblocks/confirmed/([0-9a-f]{64})$"#).unwrap();
     static ref PATH_GETMICROBLOCKS_UNCONFIRMED : Regex = Regex::new(r#"^/v2/microblocks/unconfirmed/([0-9a-f]{64})/([0-9]{1,5})$"#).unwrap();
     static ref PATH_POSTTRANSACTION : Regex = Regex::new(r#"^/v2/transactions$"#).unwrap();
+    static ref PATH_GETTRANSACTION : Regex = Regex::new(r#"^/v2/transactions/([0-9a-f]{64})$"#).unwrap();
     static ref PATH_GET_ACCOUNT: Regex = Regex::new(&format!(
         "^/v2/accounts/(?P<principal>{})$", *PRINCIPAL_DATA_REGEX)).unwrap();
+    static ref PATH_GET_ACCOUNT_TRANSACTIONS: Regex = Regex::new(&format!(
+        "^/v2/accounts/(?P<address>{})/transactions$", *STANDARD_PRINCIPAL_REGEX)).unwrap();
+    static ref PATH_GET_ACCOUNT_AT: Regex = Regex::new(&format!(
+        "^/v2/accounts/(?P<principal>{})/at/(?P<block>[0-9a-f]{{64}}|[0-9]{{1,20}})$", *PRINCIPAL_DATA_REGEX)).unwrap();
+    static ref PATH_GET_ACCOUNT_CHANGES: Regex = Regex::new(&format!(
+        "^/v2/accounts/(?P<principal>{})/changes$", *PRINCIPAL_DATA_REGEX)).unwrap();
     static ref PATH_GET_MAP_ENTRY: Regex = Regex::new(&format!(
         "^/v2/map_entry/(?P<address>{})/(?P<contract>{})/(?P<map>{})$",
         *STANDARD_PRINCIPAL_REGEX, *CONTRACT_NAME_REGEX, *CLARITY_NAME_REGEX)).unwrap();
     static ref PATH_POST_CALL_READ_ONLY: Regex = Regex::new(&format!(
         "^/v2/contracts/call-read/(?P<address>{})/(?P<contract>{})/(?P<function>{})$",
         *STANDARD_PRINCIPAL_REGEX, *CONTRACT_NAME_REGEX, *CLARITY_NAME_REGEX)).unwrap();
+    static ref PATH_POST_SIMULATE: Regex = Regex::new(&format!(
+        "^/v2/contracts/simulate/(?P<address>{})/(?P<contract>{})/(?P<function>{})$",
+        *STANDARD_PRINCIPAL_REGEX, *CONTRACT_NAME_REGEX, *CLARITY_NAME_REGEX)).unwrap();
     static ref PATH_GET_CONTRACT_SRC: Regex = Regex::new(&format!(
         "^/v2/contracts/source/(?P<address>{})/(?P<contract>{})$",
         *STANDARD_PRINCIPAL_REGEX, *CONTRACT_NAME_REGEX)).unwrap();
     static ref PATH_GET_CONTRACT_ABI: Regex = Regex::new(&format!(
         "^/v2/contracts/interface/(?P<address>{})/(?P<contract>{})$",
         *STANDARD_PRINCIPAL_REGEX, *CONTRACT_NAME_REGEX)).unwrap();
+    static ref PATH_GET_CONTRACT_DEPENDENCIES: Regex = Regex::new(&format!(
+        "^/v2/contracts/dependencies/(?P<address>{})/(?P<contract>{})$",
+        *STANDARD_PRINCIPAL_REGEX, *CONTRACT_NAME_REGEX)).unwrap();
+    static ref PATH_GET_CONTRACT_TRANSACTIONS: Regex = Regex::new(&format!(
+        "^/v2/contracts/transactions/(?P<address>{})/(?P<contract>{})$",
+        *STANDARD_PRINCIPAL_REGEX, *CONTRACT_NAME_REGEX)).unwrap();
+    static ref PATH_GET_TRAIT_IMPLEMENTERS: Regex = Regex::new(&format!(
+        "^/v2/traits/implementers/(?P<address>{})/(?P<contract>{})/(?P<trait>{})$",
+        *STANDARD_PRINCIPAL_REGEX, *CONTRACT_NAME_REGEX, *CLARITY_NAME_REGEX)).unwrap();
     static ref PATH_GET_TRANSFER_COST: Regex = Regex::new("^/v2/fees/transfer$").unwrap();
     static ref PATH_OPTIONS_WILDCARD: Regex = Regex::new("^/v2/.{0,4096}$").unwrap();
 }
@@ -1166,12 +1189,20 @@ impl HttpRequestType {
             ("GET", &PATH_GETMICROBLOCKS_CONFIRMED, &HttpRequestType::parse_getmicroblocks_confirmed),
             ("GET", &PATH_GETMICROBLOCKS_UNCONFIRMED, &HttpRequestType::parse_getmicroblocks_unconfirmed),
             ("POST", &PATH_POSTTRANSACTION, &HttpRequestType::parse_posttransaction),
+            ("GET", &PATH_GETTRANSACTION, &HttpRequestType::parse_gettransaction),
             ("GET", &PATH_GET_ACCOUNT, &HttpRequestType::parse_get_account),
+            ("GET", &PATH_GET_ACCOUNT_TRANSACTIONS, &HttpRequestType::parse_get_account_transactions),
+            ("GET", &PATH_GET_ACCOUNT_AT, &HttpRequestType::parse_get_account_at),
+            ("GET", &PATH_GET_ACCOUNT_CHANGES, &HttpRequestType::parse_get_account_changes),
             ("POST", &PATH_GET_MAP_ENTRY, &HttpRequestType::parse_get_map_entry),
             ("GET", &PATH_GET_TRANSFER_COST, &HttpRequestType::parse_get_transfer_cost),
             ("GET", &PATH_GET_CONTRACT_SRC, &HttpRequestType::parse_get_contract_source),
             ("GET", &PATH_GET_CONTRACT_ABI, &HttpRequestType::parse_get_contract_abi),
+            ("GET", &PATH_GET_CONTRACT_DEPENDENCIES, &HttpRequestType::parse_get_contract_dependencies),
+            ("GET", &PATH_GET_CONTRACT_TRANSACTIONS, &HttpRequestType::parse_get_contract_transactions),
+            ("GET", &PATH_GET_TRAIT_IMPLEMENTERS, &HttpRequestType::parse_get_trait_implementers),
             ("POST", &PATH_POST_CALL_READ_ONLY, &HttpRequestType::parse_call_read_only),
+            ("POST", &PATH_POST_SIMULATE, &HttpRequestType::parse_simulate_transaction),
             ("OPTIONS", &PATH_OPTIONS_WILDCARD, &HttpRequestType::parse_options_preflight),
         ];
 
@@ -1240,6 +1271,46 @@ impl HttpRequestType {
         !no_proof
     }
 
+    /// get the optional chain tip (`tip=<index block hash>`) from the query string.
+    /// Defaults to the canonical chain tip.
+    fn get_tip_query(query: Option<&str>) -> Result<Option<StacksBlockId>, net_error> {
+        let tip_str = match query {
+            Some(query_string) => form_urlencoded::parse(query_string.as_bytes())
+                .find(|(key, _v)| key == "tip")
+                .map(|(_k, value)| value.to_string()),
+            None => None
+        };
+
+        match tip_str {
+            Some(tip_str) => StacksBlockId::from_hex(&tip_str)
+                .map(Some)
+                .map_err(|_e| net_error::DeserializeError("Failed to parse chain tip".into())),
+            None => Ok(None)
+        }
+    }
+
+    /// get an optional unsigned integer (`<name>=<n>`) from the query string.
+    fn get_u64_query(query: Option<&str>, name: &str) -> Result<Option<u64>, net_error> {
+        let value_str = match query {
+            Some(query_string) => form_urlencoded::parse(query_string.as_bytes())
+                .find(|(key, _v)| key == name)
+                .map(|(_k, value)| value.to_string()),
+            None => None
+        };
+
+        match value_str {
+            Some(value_str) => value_str.parse::<u64>()
+                .map(Some)
+                .map_err(|_e| net_error::DeserializeError(format!("Failed to parse '{}'", name))),
+            None => Ok(None)
+        }
+    }
+
+    /// get the optional block height bound (`before=<height>`) from the query string.
+    fn get_before_query(query: Option<&str>) -> Result<Option<u64>, net_error> {
+        HttpRequestType::get_u64_query(query, "before")
+    }
+
     fn parse_get_account<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
         if preamble.get_content_length() != 0 {
             return Err(net_error::DeserializeError("Invalid Http request: expected 0-length body for GetAccount".to_string()));
@@ -1253,6 +1324,59 @@ impl HttpRequestType {
         Ok(HttpRequestType::GetAccount(HttpRequestMetadata::from_preamble(preamble), principal, with_proof))
     }
 
+    fn parse_get_account_at<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError("Invalid Http request: expected 0-length body for GetAccountAt".to_string()));
+        }
+
+        let principal = PrincipalData::parse(&captures["principal"])
+            .map_err(|_e| net_error::DeserializeError("Failed to parse account principal".into()))?;
+
+        let block_str = &captures["block"];
+        let block = if block_str.len() == 64 {
+            StacksBlockId::from_hex(block_str)
+                .map(StacksBlockSelector::IndexBlockHash)
+                .map_err(|_e| net_error::DeserializeError("Failed to parse index block hash".into()))?
+        }
+        else {
+            block_str.parse::<u64>()
+                .map(StacksBlockSelector::Height)
+                .map_err(|_e| net_error::DeserializeError("Failed to parse block height".into()))?
+        };
+
+        let with_proof = HttpRequestType::get_proof_query(query);
+
+        Ok(HttpRequestType::GetAccountAt(HttpRequestMetadata::from_preamble(preamble), principal, block, with_proof))
+    }
+
+    fn parse_get_account_changes<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError("Invalid Http request: expected 0-length body for GetAccountChanges".to_string()));
+        }
+
+        let principal = PrincipalData::parse(&captures["principal"])
+            .map_err(|_e| net_error::DeserializeError("Failed to parse account principal".into()))?;
+
+        let tip = HttpRequestType::get_tip_query(query)?;
+        let before = HttpRequestType::get_before_query(query)?;
+
+        Ok(HttpRequestType::GetAccountChanges(HttpRequestMetadata::from_preamble(preamble), principal, tip, before))
+    }
+
+    fn parse_get_account_transactions<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError("Invalid Http request: expected 0-length body for GetAccountTransactions".to_string()));
+        }
+
+        let address = StacksAddress::from_string(&captures["address"])
+            .ok_or_else(|| net_error::DeserializeError("Failed to parse account address".into()))?;
+
+        let tip = HttpRequestType::get_tip_query(query)?;
+        let before = HttpRequestType::get_before_query(query)?;
+
+        Ok(HttpRequestType::GetAccountTransactions(HttpRequestMetadata::from_preamble(preamble), address, tip, before))
+    }
+
     fn parse_get_map_entry<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, query: Option<&str>, fd: &mut R) -> Result<HttpRequestType, net_error> {
         let content_len = preamble.get_content_length();
         if !(content_len > 0 && content_len < (BOUND_VALUE_SERIALIZATION_HEX)) {
@@ -1315,6 +1439,92 @@ impl HttpRequestType {
             contract_addr, contract_name, sender, func_name, arguments))
     }
 
+    fn parse_simulate_transaction<R: Read>(protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, _query: Option<&str>, fd: &mut R) -> Result<HttpRequestType, net_error> {
+        let content_len = preamble.get_content_length();
+        if !(content_len > 0 && content_len < protocol.maximum_call_argument_size) {
+            return Err(net_error::DeserializeError("Invalid Http request: invalid body length for SimulateTransaction".to_string()));
+        }
+
+        if preamble.content_type != Some(HttpContentType::JSON) {
+            return Err(net_error::DeserializeError("Invalid content-type: expected application/json".to_string()));
+        }
+
+        let contract_addr =  StacksAddress::from_string(&captures["address"])
+            .ok_or_else(|| net_error::DeserializeError("Failed to parse contract address".into()))?;
+        let contract_name = ContractName::try_from(captures["contract"].to_string())
+            .map_err(|_e| net_error::DeserializeError("Failed to parse contract name".into()))?;
+        let func_name = ClarityName::try_from(captures["function"].to_string())
+            .map_err(|_e| net_error::DeserializeError("Failed to parse function name".into()))?;
+
+        let body: SimulateTransactionRequestBody = serde_json::from_reader(fd)
+            .map_err(|_e| net_error::DeserializeError("Failed to parse JSON body".into()))?;
+
+        let sender = PrincipalData::parse(&body.sender)
+            .map_err(|_e| net_error::DeserializeError("Failed to parse sender principal".into()))?;
+
+        let arguments = body.arguments.into_iter()
+            .map(|hex| Value::try_deserialize_hex_untyped(&hex).ok())
+            .collect::<Option<Vec<Value>>>()
+            .ok_or_else(|| net_error::DeserializeError("Failed to deserialize argument value".into()))?;
+
+        let tip = match body.tip {
+            Some(tip) => Some(StacksBlockId::from_hex(&tip)
+                              .map_err(|_e| net_error::DeserializeError("Failed to parse chain tip".into()))?),
+            None => None
+        };
+
+        let post_conditions = body.post_conditions.into_iter()
+            .map(|hex| hex_bytes(&hex).ok()
+                 .and_then(|bytes| TransactionPostCondition::consensus_deserialize(&mut &bytes[..]).ok()))
+            .collect::<Option<Vec<TransactionPostCondition>>>()
+            .ok_or_else(|| net_error::DeserializeError("Failed to deserialize post-condition".into()))?;
+
+        let post_condition_mode = match body.post_condition_mode.as_ref().map(|mode| mode.as_str()) {
+            None | Some("deny") => TransactionPostConditionMode::Deny,
+            Some("allow") => TransactionPostConditionMode::Allow,
+            Some(_) => return Err(net_error::DeserializeError("Failed to parse post-condition mode".into()))
+        };
+
+        let mut overrides = SimulationOverrides::default();
+        for balance in body.overrides.stx_balances.into_iter() {
+            let principal = PrincipalData::parse(&balance.principal)
+                .map_err(|_e| net_error::DeserializeError("Failed to parse balance principal".into()))?;
+            let amount = u128::from_str(&balance.balance)
+                .map_err(|_e| net_error::DeserializeError("Failed to parse balance".into()))?;
+            overrides.stx_balances.push((principal, amount));
+        }
+        for data_var in body.overrides.data_vars.into_iter() {
+            let contract_identifier = QualifiedContractIdentifier::parse(&data_var.contract)
+                .map_err(|_e| net_error::DeserializeError("Failed to parse contract identifier".into()))?;
+            let var_name = ClarityName::try_from(data_var.name)
+                .map_err(|_e| net_error::DeserializeError("Failed to parse variable name".into()))?;
+            let value = Value::try_deserialize_hex_untyped(&data_var.value)
+                .map_err(|_e| net_error::DeserializeError("Failed to deserialize variable value".into()))?;
+            overrides.data_vars.push((contract_identifier, var_name, value));
+        }
+        for entry in body.overrides.map_entries.into_iter() {
+            let contract_identifier = QualifiedContractIdentifier::parse(&entry.contract)
+                .map_err(|_e| net_error::DeserializeError("Failed to parse contract identifier".into()))?;
+            let map_name = ClarityName::try_from(entry.map)
+                .map_err(|_e| net_error::DeserializeError("Failed to parse map name".into()))?;
+            let key = Value::try_deserialize_hex_untyped(&entry.key)
+                .map_err(|_e| net_error::DeserializeError("Failed to deserialize map key".into()))?;
+            let value = match entry.value {
+                Some(hex) => Some(Value::try_deserialize_hex_untyped(&hex)
+                                  .map_err(|_e| net_error::DeserializeError("Failed to deserialize map value".into()))?),
+                None => None
+            };
+            overrides.map_entries.push((contract_identifier, map_name, key, value));
+        }
+        overrides.block_height = body.overrides.block_height;
+
+        let request = SimulateTransactionRequest { sender, arguments, tip, post_conditions, post_condition_mode, overrides };
+
+        Ok(HttpRequestType::SimulateTransaction(
+            HttpRequestMetadata::from_preamble(preamble),
+            contract_addr, contract_name, func_name, request))
+    }
+
     fn parse_get_contract_arguments(preamble: &HttpRequestPreamble, captures: &Captures) -> Result<(HttpRequestMetadata, StacksAddress, ContractName), net_error> {
         if preamble.get_content_length() != 0 {
             return Err(net_error::DeserializeError("Invalid Http request: expected 0-length body".to_string()));
@@ -1339,6 +1549,27 @@ impl HttpRequestType {
             .map(|(preamble, addr, name)| HttpRequestType::GetContractSrc(preamble, addr, name, with_proof))
     }
 
+    fn parse_get_contract_dependencies<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, _query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
+        HttpRequestType::parse_get_contract_arguments(preamble, captures)
+            .map(|(preamble, addr, name)| HttpRequestType::GetContractDependencies(preamble, addr, name))
+    }
+
+    fn parse_get_contract_transactions<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
+        let tip = HttpRequestType::get_tip_query(query)?;
+        let before = HttpRequestType::get_before_query(query)?;
+        HttpRequestType::parse_get_contract_arguments(preamble, captures)
+            .map(|(preamble, addr, name)| HttpRequestType::GetContractTransactions(preamble, addr, name, tip, before))
+    }
+
+    fn parse_get_trait_implementers<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
+        let trait_name = ClarityName::try_from(captures["trait"].to_string())
+            .map_err(|_e| net_error::DeserializeError("Failed to parse trait name".into()))?;
+        let offset = HttpRequestType::get_u64_query(query, "offset")?;
+        let limit = HttpRequestType::get_u64_query(query, "limit")?;
+        HttpRequestType::parse_get_contract_arguments(preamble, captures)
+            .map(|(preamble, addr, name)| HttpRequestType::GetTraitImplementers(preamble, addr, name, trait_name, offset, limit))
+    }
+
     fn parse_getblock<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, _query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
         if preamble.get_content_length() != 0 {
             return Err(net_error::DeserializeError("Invalid Http request: expected 0-length body for GetBlock".to_string()));
@@ -1355,6 +1586,24 @@ impl HttpRequestType {
         Ok(HttpRequestType::GetBlock(HttpRequestMetadata::from_preamble(preamble), block_hash))
     }
 
+    fn parse_gettransaction<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError("Invalid Http request: expected 0-length body for GetTransaction".to_string()));
+        }
+
+        let txid_str = captures
+            .get(1)
+            .ok_or(net_error::DeserializeError("Failed to match path to txid group".to_string()))?
+            .as_str();
+
+        let txid = Txid::from_hex(txid_str)
+            .map_err(|_e| net_error::DeserializeError("Failed to parse txid".to_string()))?;
+
+        let tip = HttpRequestType::get_tip_query(query)?;
+
+        Ok(HttpRequestType::GetTransaction(HttpRequestMetadata::from_preamble(preamble), txid, tip))
+    }
+
     fn parse_getmicroblocks_indexed<R: Read>(_protocol: &mut StacksHttp, preamble: &HttpRequestPreamble, captures: &Captures, _query: Option<&str>, _fd: &mut R) -> Result<HttpRequestType, net_error> {
         if preamble.get_content_length() != 0 {
             return Err(net_error::DeserializeError("Invalid Http request: expected 0-length body for GetMicroblocksIndexed".to_string()));
@@ -1444,12 +1693,20 @@ impl HttpRequestType {
             HttpRequestType::GetMicroblocksConfirmed(ref md, _) => md,
             HttpRequestType::GetMicroblocksUnconfirmed(ref md, _, _) => md,
             HttpRequestType::PostTransaction(ref md, _) => md,
+            HttpRequestType::GetTransaction(ref md, ..) => md,
             HttpRequestType::GetAccount(ref md, ..) => md,
+            HttpRequestType::GetAccountTransactions(ref md, ..) => md,
+            HttpRequestType::GetAccountAt(ref md, ..) => md,
+            HttpRequestType::GetAccountChanges(ref md, ..) => md,
             HttpRequestType::GetMapEntry(ref md, ..) => md,
             HttpRequestType::GetTransferCost(ref md) => md,
             HttpRequestType::GetContractABI(ref md, ..) => md,
             HttpRequestType::GetContractSrc(ref md, ..) => md,
+            HttpRequestType::GetContractDependencies(ref md, ..) => md,
+            HttpRequestType::GetContractTransactions(ref md, ..) => md,
+            HttpRequestType::GetTraitImplementers(ref md, ..) => md,
             HttpRequestType::CallReadOnlyFunction(ref md, ..) => md,
+            HttpRequestType::SimulateTransaction(ref md, ..) => md,
             HttpRequestType::OptionsPreflight(ref md, ..) => md,
             HttpRequestType::Unmatched(ref md, ..) => md,
         }
@@ -1464,17 +1721,41 @@ impl HttpRequestType {
             HttpRequestType::GetMicroblocksConfirmed(ref mut md, _) => md,
             HttpRequestType::GetMicroblocksUnconfirmed(ref mut md, _, _) => md,
             HttpRequestType::PostTransaction(ref mut md, _) => md,
+            HttpRequestType::GetTransaction(ref mut md, ..) => md,
             HttpRequestType::GetAccount(ref mut md, ..) => md,
+            HttpRequestType::GetAccountTransactions(ref mut md, ..) => md,
+            HttpRequestType::GetAccountAt(ref mut md, ..) => md,
+            HttpRequestType::GetAccountChanges(ref mut md, ..) => md,
             HttpRequestType::GetMapEntry(ref mut md, ..) => md,
             HttpRequestType::GetTransferCost(ref mut md) => md,
             HttpRequestType::GetContractABI(ref mut md, ..) => md,
             HttpRequestType::GetContractSrc(ref mut md, ..) => md,
+            HttpRequestType::GetContractDependencies(ref mut md, ..) => md,
+            HttpRequestType::GetContractTransactions(ref mut md, ..) => md,
+            HttpRequestType::GetTraitImplementers(ref mut md, ..) => md,
             HttpRequestType::CallReadOnlyFunction(ref mut md, ..) => md,
+            HttpRequestType::SimulateTransaction(ref mut md, ..) => md,
             HttpRequestType::OptionsPreflight(ref mut md, ..) => md,
             HttpRequestType::Unmatched(ref mut md, ..) => md,
         }
     }
 
+    /// make the query string for an optional chain tip
+    fn make_tip_query(tip: &Option<StacksBlockId>) -> String {
+        match tip {
+            Some(tip) => format!("?tip={}", tip.to_hex()),
+            None => "".to_string()
+        }
+    }
+
+    fn make_tip_before_query(tip: &Option<StacksBlockId>, before: &Option<u64>) -> String {
+        let mut query = HttpRequestType::make_tip_query(tip);
+        if let Some(before) = before {
+            query.push_str(&format!("{}before={}", if query.len() > 0 { "&" } else { "?" }, before));
+        }
+        query
+    }
+
     pub fn request_path(&self) -> String {
         match self {
             HttpRequestType::GetInfo(_md) => "/v2/info".to_string(),
@@ -1484,8 +1765,21 @@ impl HttpRequestType {
             HttpRequestType::GetMicroblocksConfirmed(_md, block_hash) => format!("/v2/microblocks/confirmed/{}", block_hash.to_hex()),
             HttpRequestType::GetMicroblocksUnconfirmed(_md, block_hash, min_seq) => format!("/v2/microblocks/unconfirmed/{}/{}", block_hash.to_hex(), min_seq),
             HttpRequestType::PostTransaction(_md, _tx) => "/v2/transactions".to_string(),
+            HttpRequestType::GetTransaction(_md, txid, tip) =>
+                format!("/v2/transactions/{}{}", txid.to_hex(), HttpRequestType::make_tip_query(tip)),
             HttpRequestType::GetAccount(_md, principal, _with_proof) => 
                 format!("/v2/accounts/{}", &principal.to_string()[1..]),
+            HttpRequestType::GetAccountTransactions(_md, address, tip, before) =>
+                format!("/v2/accounts/{}/transactions{}", address, HttpRequestType::make_tip_before_query(tip, before)),
+            HttpRequestType::GetAccountAt(_md, principal, block, _with_proof) => {
+                let block_str = match block {
+                    StacksBlockSelector::IndexBlockHash(index_block_hash) => index_block_hash.to_hex(),
+                    StacksBlockSelector::Height(height) => format!("{}", height)
+                };
+                format!("/v2/accounts/{}/at/{}", principal, block_str)
+            },
+            HttpRequestType::GetAccountChanges(_md, principal, tip, before) =>
+                format!("/v2/accounts/{}/changes{}", principal, HttpRequestType::make_tip_before_query(tip, before)),
             HttpRequestType::GetMapEntry(_md, contract_addr, contract_name, map_name, _key, _with_proof) =>
                 format!("/v2/map_entry/{}/{}/{}",
                         contract_addr, contract_name.as_str(), map_name.as_str()),
@@ -1494,9 +1788,25 @@ impl HttpRequestType {
                 format!("/v2/contracts/interface/{}/{}", contract_addr, contract_name.as_str()),
             HttpRequestType::GetContractSrc(_, contract_addr, contract_name, _with_proof) => 
                 format!("/v2/contracts/source/{}/{}", contract_addr, contract_name.as_str()),
+            HttpRequestType::GetContractDependencies(_, contract_addr, contract_name) =>
+                format!("/v2/contracts/dependencies/{}/{}", contract_addr, contract_name.as_str()),
+            HttpRequestType::GetContractTransactions(_, contract_addr, contract_name, tip, before) =>
+                format!("/v2/contracts/transactions/{}/{}{}", contract_addr, contract_name.as_str(), HttpRequestType::make_tip_before_query(tip, before)),
+            HttpRequestType::GetTraitImplementers(_, contract_addr, contract_name, trait_name, offset, limit) => {
+                let mut query = "".to_string();
+                if let Some(offset) = offset {
+                    query.push_str(&format!("?offset={}", offset));
+                }
+                if let Some(limit) = limit {
+                    query.push_str(&format!("{}limit={}", if query.len() > 0 { "&" } else { "?" }, limit));
+                }
+                format!("/v2/traits/implementers/{}/{}/{}{}", contract_addr, contract_name.as_str(), trait_name.as_str(), query)
+            },
             HttpRequestType::CallReadOnlyFunction(_, contract_addr, contract_name, _, func_name, ..) => {
                 format!("/v2/contracts/call-read/{}/{}/{}", contract_addr, contract_name.as_str(), func_name.as_str())
             },
+            HttpRequestType::SimulateTransaction(_, contract_addr, contract_name, func_name, _) =>
+                format!("/v2/contracts/simulate/{}/{}/{}", contract_addr, contract_name.as_str(), func_name.as_str()),
             HttpRequestType::OptionsPreflight(_md, path) => path.to_string(),
             HttpRequestType::Unmatched(_md, path) => path.to_string(),
         }
@@ -1677,14 +1987,15 @@ impl HttpResponseType {
         }
 
         // TODO: make this static somehow
-        let RESPONSE_METHODS : [(&Regex, &dyn Fn(&mut StacksHttp, HttpVersion, &HttpResponsePreamble, &mut R, Option<usize>) -> Result<HttpResponseType, net_error>); 7] = [
+        let RESPONSE_METHODS : [(&Regex, &dyn Fn(&mut StacksHttp, HttpVersion, &HttpResponsePreamble, &mut R, Option<usize>) -> Result<HttpResponseType, net_error>); 8] = [
             (&PATH_GETINFO, &HttpResponseType::parse_peerinfo),
             (&PATH_GETNEIGHBORS, &HttpResponseType::parse_neighbors),
             (&PATH_GETBLOCK, &HttpResponseType::parse_block),
             (&PATH_GETMICROBLOCKS_INDEXED, &HttpResponseType::parse_microblocks),
             (&PATH_GETMICROBLOCKS_CONFIRMED, &HttpResponseType::parse_microblocks),
             (&PATH_GETMICROBLOCKS_UNCONFIRMED, &HttpResponseType::parse_microblocks_unconfirmed),
-            (&PATH_POSTTRANSACTION, &HttpResponseType::parse_txid)
+            (&PATH_POSTTRANSACTION, &HttpResponseType::parse_txid),
+            (&PATH_POST_SIMULATE, &HttpResponseType::parse_simulate_transaction)
         ];
 
         for (regex, parser) in RESPONSE_METHODS.iter() {
@@ -1716,6 +2027,11 @@ impl HttpResponseType {
         Ok(HttpResponseType::Neighbors(HttpResponseMetadata::from_preamble(request_version, preamble), neighbors_data))
     }
 
+    fn parse_simulate_transaction<R: Read>(_protocol: &mut StacksHttp, request_version: HttpVersion, preamble: &HttpResponsePreamble, fd: &mut R, len_hint: Option<usize>) -> Result<HttpResponseType, net_error> {
+        let simulation = HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::SimulateTransaction(HttpResponseMetadata::from_preamble(request_version, preamble), simulation))
+    }
+
     fn parse_block<R: Read>(_protocol: &mut StacksHttp, request_version: HttpVersion, preamble: &HttpResponsePreamble, fd: &mut R, len_hint: Option<usize>) -> Result<HttpResponseType, net_error> {
         let block : StacksBlock = HttpResponseType::parse_bytestream(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
         Ok(HttpResponseType::Block(HttpResponseMetadata::from_preamble(request_version, preamble), block))
@@ -1795,12 +2111,19 @@ impl HttpResponseType {
             HttpResponseType::Microblocks(ref md, _) => md,
             HttpResponseType::MicroblockStream(ref md) => md,
             HttpResponseType::TransactionID(ref md, _) => md,
+            HttpResponseType::GetTransaction(ref md, _) => md,
+            HttpResponseType::GetTransactions(ref md, _) => md,
             HttpResponseType::TokenTransferCost(ref md, _) => md,
             HttpResponseType::GetMapEntry(ref md, _) => md,
             HttpResponseType::GetAccount(ref md, _) => md,
+            HttpResponseType::GetAccountAt(ref md, _) => md,
+            HttpResponseType::GetAccountChanges(ref md, _) => md,
             HttpResponseType::GetContractABI(ref md, _) => md,
             HttpResponseType::GetContractSrc(ref md, _) => md,
+            HttpResponseType::GetContractDependencies(ref md, _) => md,
+            HttpResponseType::GetTraitImplementers(ref md, _) => md,
             HttpResponseType::CallReadOnlyFunction(ref md, _) => md,
+            HttpResponseType::SimulateTransaction(ref md, _) => md,
             HttpResponseType::OptionsPreflight(ref md) => md,
             // errors
             HttpResponseType::BadRequestJSON(ref md, _) => md,
@@ -1866,6 +2189,14 @@ impl HttpResponseType {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, account_data)?;
             },
+            HttpResponseType::GetAccountAt(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            },
+            HttpResponseType::GetAccountChanges(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            },
             HttpResponseType::GetContractABI(ref md, ref data) => {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, data)?;
@@ -1874,6 +2205,22 @@ impl HttpResponseType {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, data)?;
             },
+            HttpResponseType::GetContractDependencies(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            },
+            HttpResponseType::GetTransaction(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            },
+            HttpResponseType::GetTransactions(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            },
+            HttpResponseType::GetTraitImplementers(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            },
             HttpResponseType::TokenTransferCost(ref md, ref cost) => {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, cost)?;
@@ -1882,6 +2229,10 @@ impl HttpResponseType {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, data)?;
             },
+            HttpResponseType::SimulateTransaction(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            },
             HttpResponseType::GetMapEntry(ref md, ref map_data) => {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, map_data)?;
@@ -1994,12 +2345,20 @@ impl MessageSequence for StacksHttpMessage {
                 HttpRequestType::GetMicroblocksConfirmed(_, _) => "HTTP(GetMicroblocksConfirmed)",
                 HttpRequestType::GetMicroblocksUnconfirmed(_, _, _) => "HTTP(GetMicroblocksUnconfirmed)",
                 HttpRequestType::PostTransaction(_, _) => "HTTP(PostTransaction)",
+                HttpRequestType::GetTransaction(..) => "HTTP(GetTransaction)",
                 HttpRequestType::GetAccount(..) => "HTTP(GetAccount)",
+                HttpRequestType::GetAccountTransactions(..) => "HTTP(GetAccountTransactions)",
+                HttpRequestType::GetAccountAt(..) => "HTTP(GetAccountAt)",
+                HttpRequestType::GetAccountChanges(..) => "HTTP(GetAccountChanges)",
                 HttpRequestType::GetMapEntry(..) => "HTTP(GetMapEntry)",
                 HttpRequestType::GetTransferCost(_) => "HTTP(GetTransferCost)",
                 HttpRequestType::GetContractABI(..) => "HTTP(GetContractABI)",
                 HttpRequestType::GetContractSrc(..) => "HTTP(GetContractSrc)",
+                HttpRequestType::GetContractDependencies(..) => "HTTP(GetContractDependencies)",
+                HttpRequestType::GetContractTransactions(..) => "HTTP(GetContractTransactions)",
+                HttpRequestType::GetTraitImplementers(..) => "HTTP(GetTraitImplementers)",
                 HttpRequestType::CallReadOnlyFunction(..) => "HTTP(CallReadOnlyFunction)",
+                HttpRequestType::SimulateTransaction(..) => "HTTP(SimulateTransaction)",
                 HttpRequestType::OptionsPreflight(..) => "HTTP(OptionsPreflight)",
                 HttpRequestType::Unmatched(..) => "HTTP(Unmatched)",
             },
@@ -2007,9 +2366,14 @@ impl MessageSequence for StacksHttpMessage {
                 HttpResponseType::TokenTransferCost(_, _) => "HTTP(TokenTransferCost)",
                 HttpResponseType::GetMapEntry(_, _) => "HTTP(GetMapEntry)",
                 HttpResponseType::GetAccount(_, _) => "HTTP(GetAccount)",
+                HttpResponseType::GetAccountAt(_, _) => "HTTP(GetAccountAt)",
+                HttpResponseType::GetAccountChanges(_, _) => "HTTP(GetAccountChanges)",
                 HttpResponseType::GetContractABI(..) => "HTTP(GetContractABI)",
                 HttpResponseType::GetContractSrc(..) => "HTTP(GetContractSrc)",
+                HttpResponseType::GetContractDependencies(..) => "HTTP(GetContractDependencies)",
+                HttpResponseType::GetTraitImplementers(..) => "HTTP(GetTraitImplementers)",
                 HttpResponseType::CallReadOnlyFunction(..) => "HTTP(CallReadOnlyFunction)",
+                HttpResponseType::SimulateTransaction(..) => "HTTP(SimulateTransaction)",
                 HttpResponseType::PeerInfo(_, _) => "HTTP(PeerInfo)",
                 HttpResponseType::Neighbors(_, _) => "HTTP(Neighbors)",
                 HttpResponseType::Block(_, _) => "HTTP(Block)",
@@ -2017,6 +2381,8 @@ impl MessageSequence for StacksHttpMessage {
                 HttpResponseType::Microblocks(_, _) => "HTTP(Microblocks)",
                 HttpResponseType::MicroblockStream(_) => "HTTP(MicroblockStream)",
                 HttpResponseType::TransactionID(_, _) => "HTTP(Transaction)",
+                HttpResponseType::GetTransaction(..) => "HTTP(GetTransaction)",
+                HttpResponseType::GetTransactions(..) => "HTTP(GetTransactions)",
                 HttpResponseType::OptionsPreflight(_) => "HTTP(OptionsPreflight)",
                 HttpResponseType::BadRequestJSON(..) | HttpResponseType::BadRequest(..) => "HTTP(400)",
                 HttpResponseType::Unauthorized(_, _) => "HTTP(401)",
@@ -3125,11 +3491,24 @@ mod test {
             keep_alive: true,
         };
 
+        let account_addr = StacksAddress { version: 1, bytes: Hash160([6u8; 20]) };
+        let account = PrincipalData::from(account_addr.clone());
+
         let tests = vec![
             HttpRequestType::GetNeighbors(http_request_metadata_ip.clone()),
             HttpRequestType::GetBlock(http_request_metadata_dns.clone(), StacksBlockId([2u8; 32])),
             HttpRequestType::GetMicroblocksIndexed(http_request_metadata_ip.clone(), StacksBlockId([3u8; 32])),
             HttpRequestType::PostTransaction(http_request_metadata_dns.clone(), make_test_transaction()),
+            HttpRequestType::GetTransaction(http_request_metadata_ip.clone(), Txid([4u8; 32]), None),
+            HttpRequestType::GetTransaction(http_request_metadata_ip.clone(), Txid([4u8; 32]), Some(StacksBlockId([5u8; 32]))),
+            HttpRequestType::GetAccountAt(http_request_metadata_ip.clone(), account.clone(), StacksBlockSelector::IndexBlockHash(StacksBlockId([7u8; 32])), true),
+            HttpRequestType::GetAccountAt(http_request_metadata_ip.clone(), account.clone(), StacksBlockSelector::Height(12), true),
+            HttpRequestType::GetAccountChanges(http_request_metadata_ip.clone(), account.clone(), None, None),
+            HttpRequestType::GetAccountChanges(http_request_metadata_ip.clone(), account.clone(), Some(StacksBlockId([5u8; 32])), Some(12)),
+            HttpRequestType::GetTraitImplementers(http_request_metadata_ip.clone(), account_addr.clone(), "hello-world".into(), "trait-1".into(), None, None),
+            HttpRequestType::GetTraitImplementers(http_request_metadata_ip.clone(), account_addr.clone(), "hello-world".into(), "trait-1".into(), Some(10), Some(5)),
+            HttpRequestType::GetAccountTransactions(http_request_metadata_ip.clone(), account_addr.clone(), None, Some(12)),
+            HttpRequestType::GetContractTransactions(http_request_metadata_ip.clone(), account_addr.clone(), "hello-world".into(), Some(StacksBlockId([5u8; 32])), Some(12)),
             HttpRequestType::OptionsPreflight(http_request_metadata_ip.clone(), "/".to_string()),
         ];
 
@@ -3146,6 +3525,16 @@ mod test {
             HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/blocks/{}", StacksBlockId([2u8; 32]).to_hex()), http_request_metadata_dns.peer.hostname(), http_request_metadata_dns.peer.port(), http_request_metadata_dns.keep_alive),
             HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/microblocks/{}", StacksBlockId([3u8; 32]).to_hex()), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
             post_transaction_preamble,
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/transactions/{}", Txid([4u8; 32]).to_hex()), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/transactions/{}?tip={}", Txid([4u8; 32]).to_hex(), StacksBlockId([5u8; 32]).to_hex()), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/accounts/{}/at/{}", &account_addr, StacksBlockId([7u8; 32]).to_hex()), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/accounts/{}/at/12", &account_addr), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/accounts/{}/changes", &account_addr), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/accounts/{}/changes?tip={}&before=12", &account_addr, StacksBlockId([5u8; 32]).to_hex()), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/traits/implementers/{}/hello-world/trait-1", &account_addr), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/traits/implementers/{}/hello-world/trait-1?offset=10&limit=5", &account_addr), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/accounts/{}/transactions?before=12", &account_addr), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
+            HttpRequestPreamble::new(HttpVersion::Http11, "GET".to_string(), format!("/v2/contracts/transactions/{}/hello-world?tip={}&before=12", &account_addr, StacksBlockId([5u8; 32]).to_hex()), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
             HttpRequestPreamble::new(HttpVersion::Http11, "OPTIONS".to_string(), format!("/"), http_request_metadata_ip.peer.hostname(), http_request_metadata_ip.peer.port(), http_request_metadata_ip.keep_alive),
         ];
 
@@ -3154,6 +3543,16 @@ mod test {
             vec![],
             vec![],
             tx_body,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
         ];
 
         for (test, (expected_http_preamble, expected_http_body)) in tests.iter().zip(expected_http_preambles.iter().zip(expected_http_bodies.iter())) {
@@ -43,6 +43,7 @@ use std::net::IpAddr;
 use std::net::Ipv4Addr;
 use std::net::Ipv6Addr;
 use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::io::prelude::*;
 use std::io;
 use std::io::{Read, Write};
@@ -80,9 +81,12 @@ use chainstate::stacks::{
     StacksTransaction,
     StacksPublicKey,
     StacksBlockId,
+    TransactionPostCondition,
+    TransactionPostConditionMode,
     Error as chain_error
 };
 use chainstate::stacks::db::blocks::MemPoolRejection;
+use chainstate::stacks::db::transactions::SimulationOverrides;
 
 use chainstate::stacks::Error as chainstate_error;
 
@@ -92,6 +96,7 @@ use vm::{
     Value,
     types::PrincipalData,
     analysis::contract_interface_builder::ContractInterface,
+    costs::ExecutionCost,
 };
 
 use util::hash::Hash160;
@@ -907,23 +912,93 @@ pub struct MapEntryResponse {
 pub struct ContractSrcResponse {
     pub source: String,
     pub publish_height: u32,
+    /// Constants bound ahead of `source` when the contract was deployed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub init_args: Vec<ContractInitArg>,
     #[serde(rename = "proof")]
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")] 
     pub marf_proof: Option<String>
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractInitArg {
+    pub name: String,
+    /// hex-encoded, serialized Clarity value
+    pub value: String
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CallReadOnlyResponse {
     pub okay: bool,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")] 
     pub result: Option<String>,
+    /// The canonical JSON representation of the result.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")] 
+    pub result_json: Option<serde_json::Value>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")] 
     pub cause: Option<String>
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulateTransactionResponse {
+    pub okay: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")] 
+    pub result: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")] 
+    pub cause: Option<String>,
+    #[serde(default)]
+    pub events: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub asset_map: Vec<serde_json::Value>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")] 
+    pub post_conditions_passed: Option<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")] 
+    pub cost: Option<ExecutionCost>
+}
+
+/// A confirmed transaction from the transaction index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedTransactionResponse {
+    pub txid: String,
+    pub tx: String,
+    pub status: String,
+    pub result: String,
+    pub index_block_hash: String,
+    pub block_height: u64,
+    pub tx_index: u32,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub microblock_hash: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub microblock_sequence: Option<u16>
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedTransactionsResponse {
+    pub transactions: Vec<IndexedTransactionResponse>
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractDependenciesResponse {
+    pub dependencies: Vec<String>,
+    pub graph: BTreeMap<String, Vec<String>>
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraitImplementersResponse {
+    pub implementers: Vec<String>
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AccountEntryResponse {
     pub balance: String,
@@ -936,6 +1011,36 @@ pub struct AccountEntryResponse {
     pub nonce_proof: Option<String>
 }
 
+/// An account's balance and nonce as of a past block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoricalAccountEntryResponse {
+    pub balance: String,
+    pub nonce: u64,
+    #[serde(skip_serializing_if = "Option::is_none")] 
+    #[serde(default)]
+    pub balance_proof: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")] 
+    #[serde(default)]
+    pub nonce_proof: Option<String>,
+    pub index_block_hash: String,
+    pub block_height: u64
+}
+
+/// An account's balance and nonce after a block whose transactions changed them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountChangeResponse {
+    pub index_block_hash: String,
+    pub block_height: u64,
+    pub balance: String,
+    pub nonce: u64,
+    pub txids: Vec<String>
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountChangesResponse {
+    pub changes: Vec<AccountChangeResponse>
+}
+
 /// Request ID to use or expect from non-Stacks HTTP clients.
 /// In particular, if a HTTP response does not contain the x-request-id header, then it's assumed
 /// to be this value.  This is needed to support fetching immutables like block and microblock data
@@ -974,6 +1079,70 @@ pub struct CallReadOnlyRequestBody {
     pub arguments: Vec<String>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct SimulateTransactionRequestBody {
+    pub sender: String,
+    pub arguments: Vec<String>,
+    /// Index block hash of the chain tip to simulate on.  Defaults to the canonical chain tip.
+    #[serde(default)]
+    pub tip: Option<String>,
+    /// Hex-encoded post-conditions.
+    #[serde(default)]
+    pub post_conditions: Vec<String>,
+    /// "allow" or "deny".  Defaults to "deny".
+    #[serde(default)]
+    pub post_condition_mode: Option<String>,
+    #[serde(default)]
+    pub overrides: SimulationOverridesBody,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct SimulationOverridesBody {
+    #[serde(default)]
+    pub stx_balances: Vec<STXBalanceOverrideBody>,
+    #[serde(default)]
+    pub data_vars: Vec<DataVarOverrideBody>,
+    #[serde(default)]
+    pub map_entries: Vec<MapEntryOverrideBody>,
+    #[serde(default)]
+    pub block_height: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct STXBalanceOverrideBody {
+    pub principal: String,
+    /// Decimal amount of microSTX.
+    pub balance: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DataVarOverrideBody {
+    pub contract: String,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MapEntryOverrideBody {
+    pub contract: String,
+    pub map: String,
+    pub key: String,
+    /// The entry is deleted if no value is given.
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// A contract-call to simulate, as parsed from a `SimulateTransactionRequestBody`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulateTransactionRequest {
+    pub sender: PrincipalData,
+    pub arguments: Vec<Value>,
+    pub tip: Option<StacksBlockId>,
+    pub post_conditions: Vec<TransactionPostCondition>,
+    pub post_condition_mode: TransactionPostConditionMode,
+    pub overrides: SimulationOverrides,
+}
+
 /// Items in the NeighborsInfo -- combines NeighborKey and NeighborAddress
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RPCNeighbor {
@@ -1007,6 +1176,14 @@ pub struct RPCNeighborsInfo {
     pub outbound: Vec<RPCNeighbor>,
 }
 
+/// A Stacks block named in a request path, either by its index block hash or by its height in
+/// the canonical fork
+#[derive(Debug, Clone, PartialEq)]
+pub enum StacksBlockSelector {
+    IndexBlockHash(StacksBlockId),
+    Height(u64)
+}
+
 /// All HTTP request paths we support, and the arguments they carry in their paths
 #[derive(Debug, Clone, PartialEq)]
 pub enum HttpRequestType {
@@ -1017,13 +1194,21 @@ pub enum HttpRequestType {
     GetMicroblocksConfirmed(HttpRequestMetadata, StacksBlockId),
     GetMicroblocksUnconfirmed(HttpRequestMetadata, StacksBlockId, u16),
     PostTransaction(HttpRequestMetadata, StacksTransaction),
+    GetTransaction(HttpRequestMetadata, Txid, Option<StacksBlockId>),
     GetAccount(HttpRequestMetadata, PrincipalData, bool),
+    GetAccountTransactions(HttpRequestMetadata, StacksAddress, Option<StacksBlockId>, Option<u64>),
+    GetAccountAt(HttpRequestMetadata, PrincipalData, StacksBlockSelector, bool),
+    GetAccountChanges(HttpRequestMetadata, PrincipalData, Option<StacksBlockId>, Option<u64>),
     GetMapEntry(HttpRequestMetadata, StacksAddress, ContractName, ClarityName, Value, bool),
     CallReadOnlyFunction(HttpRequestMetadata, StacksAddress, ContractName,
                          PrincipalData, ClarityName, Vec<Value>),
     GetTransferCost(HttpRequestMetadata),
     GetContractSrc(HttpRequestMetadata, StacksAddress, ContractName, bool),
     GetContractABI(HttpRequestMetadata, StacksAddress, ContractName),
+    GetContractDependencies(HttpRequestMetadata, StacksAddress, ContractName),
+    GetContractTransactions(HttpRequestMetadata, StacksAddress, ContractName, Option<StacksBlockId>, Option<u64>),
+    GetTraitImplementers(HttpRequestMetadata, StacksAddress, ContractName, ClarityName, Option<u64>, Option<u64>),
+    SimulateTransaction(HttpRequestMetadata, StacksAddress, ContractName, ClarityName, SimulateTransactionRequest),
     OptionsPreflight(HttpRequestMetadata, String),
     Unmatched(HttpRequestMetadata, String),     // catch-all if we can't parse the request
 }
@@ -1092,12 +1277,19 @@ pub enum HttpResponseType {
     Microblocks(HttpResponseMetadata, Vec<StacksMicroblock>),
     MicroblockStream(HttpResponseMetadata),
     TransactionID(HttpResponseMetadata, Txid),
+    GetTransaction(HttpResponseMetadata, IndexedTransactionResponse),
+    GetTransactions(HttpResponseMetadata, IndexedTransactionsResponse),
     TokenTransferCost(HttpResponseMetadata, u64),
     GetMapEntry(HttpResponseMetadata, MapEntryResponse),
     CallReadOnlyFunction(HttpResponseMetadata, CallReadOnlyResponse),
     GetAccount(HttpResponseMetadata, AccountEntryResponse),
+    GetAccountAt(HttpResponseMetadata, HistoricalAccountEntryResponse),
+    GetAccountChanges(HttpResponseMetadata, AccountChangesResponse),
     GetContractABI(HttpResponseMetadata, ContractInterface),
     GetContractSrc(HttpResponseMetadata, ContractSrcResponse),
+    GetContractDependencies(HttpResponseMetadata, ContractDependenciesResponse),
+    GetTraitImplementers(HttpResponseMetadata, TraitImplementersResponse),
+    SimulateTransaction(HttpResponseMetadata, SimulateTransactionResponse),
     OptionsPreflight(HttpResponseMetadata),
     // peer-given error responses
     BadRequest(HttpResponseMetadata, String),
@@ -21,6 +21,7 @@ use std::io::prelude::*;
 use std::io;
 use std::io::{Read, Write, Seek, SeekFrom};
 use std::fmt;
+use std::cmp;
 use std::net::SocketAddr;
 
 use std::collections::HashMap;
@@ -50,7 +51,10 @@ use net::connection::ConnectionOptions;
 use net::db::PeerDB;
 use net::p2p::PeerNetwork;
 use net::{ RPCNeighbor, RPCNeighborsInfo };
-use net::{ MapEntryResponse, AccountEntryResponse, CallReadOnlyResponse, ContractSrcResponse };
+use net::{ MapEntryResponse, AccountEntryResponse, CallReadOnlyResponse, ContractSrcResponse, ContractInitArg, ContractDependenciesResponse, TraitImplementersResponse };
+use net::{ SimulateTransactionRequest, SimulateTransactionResponse };
+use net::{ IndexedTransactionResponse, IndexedTransactionsResponse };
+use net::{ HistoricalAccountEntryResponse, AccountChangeResponse, AccountChangesResponse, StacksBlockSelector };
 use net::p2p::PeerMap;
 use core::mempool::*;
 
@@ -63,7 +67,9 @@ use chainstate::burn::BlockHeaderHash;
 use chainstate::stacks::db::{
     StacksChainState,
     BlockStreamData,
-    blocks::MINIMUM_TX_FEE_RATE_PER_BYTE};
+    blocks::MINIMUM_TX_FEE_RATE_PER_BYTE,
+    StacksHeaderInfo,
+    txindex::TransactionIndexEntry};
 use chainstate::stacks::Error as chain_error;
 use chainstate::stacks::*;
 use burnchains::*;
@@ -72,6 +78,7 @@ use rusqlite::{DatabaseName, NO_PARAMS};
 
 use util::db::Error as db_error;
 use util::db::DBConn;
+use util::db::get_ancestor_block_hash;
 use util::get_epoch_time_secs;
 use util::hash::to_hex;
 use util::hash::Hash160;
@@ -80,6 +87,7 @@ use crate::{version_string};
 
 use vm::{
     clarity::ClarityConnection,
+    contexts::{ AssetMap, AssetMapEntry },
     ClarityName,
     ContractName,
     Value,
@@ -87,7 +95,8 @@ use vm::{
     costs::{ LimitedCostTracker,
              ExecutionCost },
     types::{ PrincipalData,
-             QualifiedContractIdentifier },
+             QualifiedContractIdentifier,
+             TraitIdentifier },
     database::{ ClarityDatabase,
                 MarfedKV,
                 ClaritySerializable,
@@ -99,6 +108,12 @@ use rand::thread_rng;
 
 pub const STREAM_CHUNK_SIZE : u64 = 4096;
 
+/// Most transactions returned when listing an account's or a contract's transactions
+pub const MAX_INDEXED_TRANSACTIONS : usize = 100;
+
+/// Most contracts returned when listing a trait's implementers
+pub const MAX_TRAIT_IMPLEMENTERS : usize = 100;
+
 #[derive(Default)]
 pub struct RPCHandlerArgs <'a> {
     pub exit_at_block_height: Option<&'a u64>,
@@ -441,14 +456,11 @@ impl ConversationHttp {
         response.send(http, fd).map(|_| ())
     }
 
-    /// Handle a GET on an existing account, given the current chain tip.  Optionally supplies a
-    /// MARF proof for each account detail loaded from the chain tip.
-    fn handle_get_account_entry<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
-                                          chainstate: &mut StacksChainState, cur_burn: &BurnchainHeaderHash, cur_block: &BlockHeaderHash,
-                                          account: &PrincipalData, with_proof: bool) -> Result<(), net_error> {
-        let response_metadata = HttpResponseMetadata::from(req);
-
-        let data = chainstate.with_read_only_clarity_tx(cur_burn, cur_block, |clarity_tx| {
+    /// Load an account's balance and nonce as of the given block.  Optionally supplies a MARF
+    /// proof for each account detail.
+    fn load_account_entry(chainstate: &mut StacksChainState, cur_burn: &BurnchainHeaderHash, cur_block: &BlockHeaderHash,
+                          account: &PrincipalData, with_proof: bool) -> AccountEntryResponse {
+        chainstate.with_read_only_clarity_tx(cur_burn, cur_block, |clarity_tx| {
             clarity_tx.with_clarity_db_readonly(|clarity_db| {
                 let key = ClarityDatabase::make_key_for_account_balance(&account);
                 let (balance, balance_proof) = clarity_db.get_with_proof::<u128>(&key)
@@ -472,14 +484,92 @@ impl ConversationHttp {
                 let balance = format!("0x{}", to_hex(&balance.to_be_bytes()));
                 AccountEntryResponse { balance, nonce, balance_proof, nonce_proof }
             })
-        });
+        })
+    }
+
+    /// Handle a GET on an existing account, given the current chain tip.  Optionally supplies a
+    /// MARF proof for each account detail loaded from the chain tip.
+    fn handle_get_account_entry<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
+                                          chainstate: &mut StacksChainState, cur_burn: &BurnchainHeaderHash, cur_block: &BlockHeaderHash,
+                                          account: &PrincipalData, with_proof: bool) -> Result<(), net_error> {
+        let response_metadata = HttpResponseMetadata::from(req);
 
+        let data = ConversationHttp::load_account_entry(chainstate, cur_burn, cur_block, account, with_proof);
         let response = HttpResponseType::GetAccount(
             response_metadata, data);
 
         response.send(http, fd).map(|_| ())
     }
 
+    /// Handle a GET on an account as of a past block.  Optionally supplies a MARF proof for each
+    /// account detail loaded from that block.
+    fn handle_get_account_entry_at<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
+                                             chainstate: &mut StacksChainState, header_info: &StacksHeaderInfo,
+                                             account: &PrincipalData, with_proof: bool) -> Result<(), net_error> {
+        let response_metadata = HttpResponseMetadata::from(req);
+
+        let entry = ConversationHttp::load_account_entry(chainstate, &header_info.burn_header_hash, &header_info.anchored_header.block_hash(), account, with_proof);
+        let data = HistoricalAccountEntryResponse {
+            balance: entry.balance,
+            nonce: entry.nonce,
+            balance_proof: entry.balance_proof,
+            nonce_proof: entry.nonce_proof,
+            index_block_hash: header_info.index_block_hash().to_hex(),
+            block_height: header_info.block_height
+        };
+        let response = HttpResponseType::GetAccountAt(
+            response_metadata, data);
+
+        response.send(http, fd).map(|_| ())
+    }
+
+    /// Handle a GET for the blocks in which an account's balance or nonce changed, given the
+    /// chain tip.  Each change carries the account's balance and nonce as of that block.  Replies
+    /// with a 404 if the transaction index is not enabled.
+    fn handle_get_account_changes<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
+                                            chainstate: &mut StacksChainState, tip: &StacksBlockId,
+                                            account: &PrincipalData, before_height: Option<u64>) -> Result<(), net_error> {
+        let response_metadata = HttpResponseMetadata::from(req);
+
+        if !chainstate.tx_index {
+            let response = HttpResponseType::NotFound(response_metadata, "Transaction index is not enabled".into());
+            return response.send(http, fd).map(|_| ());
+        }
+
+        let account_changes = match chainstate.get_account_changes(tip, account, before_height, MAX_INDEXED_TRANSACTIONS) {
+            Ok(account_changes) => account_changes,
+            Err(e) => {
+                warn!("Failed to query account changes for {}: {:?}", account, &e);
+                let response = HttpResponseType::ServerError(response_metadata, "Failed to query transaction index".into());
+                return response.send(http, fd).map(|_| ());
+            }
+        };
+
+        let mut changes = vec![];
+        for account_change in account_changes.into_iter() {
+            let header_info = match StacksChainState::get_stacks_block_header_info_by_index_block_hash(&chainstate.headers_db, &account_change.index_block_hash)? {
+                Some(header_info) => header_info,
+                None => {
+                    warn!("No header for indexed block {}", account_change.index_block_hash.to_hex());
+                    let response = HttpResponseType::ServerError(response_metadata, "Failed to load Stacks block header".into());
+                    return response.send(http, fd).map(|_| ());
+                }
+            };
+
+            let entry = ConversationHttp::load_account_entry(chainstate, &header_info.burn_header_hash, &header_info.anchored_header.block_hash(), account, false);
+            changes.push(AccountChangeResponse {
+                index_block_hash: account_change.index_block_hash.to_hex(),
+                block_height: account_change.block_height,
+                balance: entry.balance,
+                nonce: entry.nonce,
+                txids: account_change.txids.iter().map(|txid| txid.to_hex()).collect()
+            });
+        }
+
+        let response = HttpResponseType::GetAccountChanges(response_metadata, AccountChangesResponse { changes });
+        response.send(http, fd).map(|_| ())
+    }
+
     /// Handle a GET on a smart contract's data map, given the current chain tip.  Optionally
     /// supplies a MARF proof for the value.
     fn handle_get_map_entry<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
@@ -534,15 +624,107 @@ impl ConversationHttp {
 
         let response = match data {
             Ok(data) => 
-                CallReadOnlyResponse { okay: true, result: Some(format!("0x{}", data.serialize())), cause: None },
+                CallReadOnlyResponse { okay: true, result: Some(format!("0x{}", data.serialize())),
+                                       result_json: Some(data.to_json()), cause: None },
             Err(e) =>
-                CallReadOnlyResponse { okay: false, result: None, cause: Some(e.to_string()) },
+                CallReadOnlyResponse { okay: false, result: None, result_json: None, cause: Some(e.to_string()) },
         };
 
         let response = HttpResponseType::CallReadOnlyFunction(response_metadata, response);
         response.send(http, fd).map(|_| ())
     }
 
+    /// Handle a POST to simulate a contract-call with the given parameters on the given chain tip,
+    /// after applying the requested state overrides.  Nothing is committed.  Returns a
+    /// SimulateTransactionResponse with the result, events, asset transfers, post-condition
+    /// outcome and execution cost of the call.  The call may write to the chain state, so it
+    /// runs under the block limit rather than the read-only call limit.
+    fn handle_simulate_transaction<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
+                                             chainstate: &mut StacksChainState, cur_burn: &BurnchainHeaderHash, cur_block: &BlockHeaderHash,
+                                             contract_addr: &StacksAddress, contract_name: &ContractName, function: &ClarityName,
+                                             simulation: &SimulateTransactionRequest) -> Result<(), net_error> {
+        let response_metadata = HttpResponseMetadata::from(req);
+        let contract_identifier = QualifiedContractIdentifier::new(contract_addr.clone().into(), contract_name.clone());
+
+        let cost_track = LimitedCostTracker::new(chainstate.block_limit());
+
+        let data = chainstate.with_read_only_clarity_tx(cur_burn, cur_block, |clarity_tx| {
+            StacksChainState::simulate_contract_call(clarity_tx, &simulation.sender, &contract_identifier, function.as_str(),
+                                                     &simulation.arguments, &simulation.post_conditions, &simulation.post_condition_mode,
+                                                     &simulation.overrides, cost_track)
+        });
+
+        let response = match data {
+            Ok(receipt) => {
+                // there's no transaction, so there's no txid to report
+                let events = receipt.events.iter()
+                    .map(|event| {
+                        let mut event_json = event.json_serialize(&Txid([0u8; 32]), false);
+                        if let Some(event_obj) = event_json.as_object_mut() {
+                            event_obj.remove("txid");
+                        }
+                        event_json
+                    })
+                    .collect();
+                SimulateTransactionResponse {
+                    okay: true,
+                    result: Some(format!("0x{}", receipt.result.serialize())),
+                    cause: None,
+                    events,
+                    asset_map: ConversationHttp::asset_map_json(receipt.asset_map),
+                    post_conditions_passed: Some(receipt.post_condition_passed),
+                    cost: Some(receipt.execution_cost)
+                }
+            },
+            Err(e) => SimulateTransactionResponse {
+                okay: false,
+                result: None,
+                cause: Some(e.to_string()),
+                events: vec![],
+                asset_map: vec![],
+                post_conditions_passed: None,
+                cost: None
+            }
+        };
+
+        let response = HttpResponseType::SimulateTransaction(response_metadata, response);
+        response.send(http, fd).map(|_| ())
+    }
+
+    /// Describe the assets each principal sent in an asset map.
+    fn asset_map_json(asset_map: AssetMap) -> Vec<serde_json::Value> {
+        let mut principals = vec![];
+        for (principal, assets) in asset_map.to_table().into_iter() {
+            let mut stx_sent = 0;
+            let mut stx_burned = 0;
+            let mut fungible_tokens = serde_json::Map::new();
+            let mut non_fungible_tokens = serde_json::Map::new();
+            for (asset_identifier, entry) in assets.into_iter() {
+                match entry {
+                    AssetMapEntry::STX(amount) => stx_sent = amount,
+                    AssetMapEntry::Burn(amount) => stx_burned = amount,
+                    AssetMapEntry::Token(amount) => {
+                        fungible_tokens.insert(asset_identifier.to_string(), json!(format!("{}", amount)));
+                    },
+                    AssetMapEntry::Asset(values) => {
+                        let values: Vec<_> = values.iter()
+                            .map(|value| format!("0x{}", value.serialize()))
+                            .collect();
+                        non_fungible_tokens.insert(asset_identifier.to_string(), json!(values));
+                    }
+                }
+            }
+            principals.push(json!({
+                "principal": format!("{}", principal),
+                "stx_sent": format!("{}", stx_sent),
+                "stx_burned": format!("{}", stx_burned),
+                "fungible_tokens": fungible_tokens,
+                "non_fungible_tokens": non_fungible_tokens
+            }));
+        }
+        principals
+    }
+
     /// Handle a GET to fetch a contract's source code, given the chain tip.  Optionally returns a
     /// MARF proof as well.
     fn handle_get_contract_src<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
@@ -554,6 +736,9 @@ impl ConversationHttp {
         let data = chainstate.with_read_only_clarity_tx(cur_burn, cur_block, |clarity_tx| {
             clarity_tx.with_clarity_db_readonly(|db| {
                 let source = db.get_contract_src(&contract_identifier)?;
+                let init_args = db.get_contract_init_args(&contract_identifier).into_iter()
+                    .map(|(name, value)| ContractInitArg { name: name.to_string(), value: format!("0x{}", value.serialize()) })
+                    .collect();
                 let contract_commit_key = MarfedKV::make_contract_hash_key(&contract_identifier);
                 let (contract_commit, proof) = db.get_with_proof::<ContractCommitment>(&contract_commit_key)
                     .expect("BUG: obtained source, but couldn't get MARF proof.");
@@ -563,7 +748,7 @@ impl ConversationHttp {
                     None
                 };
                 let publish_height = contract_commit.block_height;
-                Some(ContractSrcResponse { source, publish_height, marf_proof })
+                Some(ContractSrcResponse { source, publish_height, init_args, marf_proof })
             })
         });
 
@@ -589,7 +774,7 @@ impl ConversationHttp {
         let data = chainstate.with_read_only_clarity_tx(cur_burn, cur_block, |clarity_tx| {
             clarity_tx.with_analysis_db_readonly(|db| {
                 let contract = db.load_contract(&contract_identifier)?;
-                contract.contract_interface
+                contract.contract_interface.clone()
             })
         });
 
@@ -601,6 +786,146 @@ impl ConversationHttp {
         response.send(http, fd).map(|_| ())
     }
     
+    /// Handle a GET to fetch the contracts a contract depends on, and their own dependencies,
+    /// given the chain tip.  Like the contract interface, the dependencies are taken from the
+    /// contract analysis data.
+    fn handle_get_contract_dependencies<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
+                                                  chainstate: &mut StacksChainState, cur_burn: &BurnchainHeaderHash, cur_block: &BlockHeaderHash,
+                                                  contract_addr: &StacksAddress, contract_name: &ContractName) -> Result<(), net_error> {
+        let response_metadata = HttpResponseMetadata::from(req);
+        let contract_identifier = QualifiedContractIdentifier::new(contract_addr.clone().into(), contract_name.clone());
+
+        let data = chainstate.with_read_only_clarity_tx(cur_burn, cur_block, |clarity_tx| {
+            clarity_tx.with_analysis_db_readonly(|db| {
+                db.get_dependency_graph(&contract_identifier).ok()
+            })
+        });
+
+        let response = match data {
+            Some(graph) => {
+                let dependencies = graph.get(&contract_identifier)
+                    .map(|dependencies| dependencies.iter().map(|x| x.to_string()).collect())
+                    .unwrap_or_default();
+                let graph = graph.iter()
+                    .map(|(contract, dependencies)| (contract.to_string(),
+                                                     dependencies.iter().map(|x| x.to_string()).collect()))
+                    .collect();
+                HttpResponseType::GetContractDependencies(response_metadata, ContractDependenciesResponse { dependencies, graph })
+            },
+            None => HttpResponseType::NotFound(response_metadata, "No contract analysis data found".into())
+        };
+
+        response.send(http, fd).map(|_| ())
+    }
+
+    /// Handle a GET to list the contracts which implement a trait, given the chain tip.  The
+    /// implementers are indexed as contracts are deployed, so only contracts which declared the
+    /// trait with `impl-trait` are listed.  Lists at most `MAX_TRAIT_IMPLEMENTERS`, starting
+    /// from `offset`.
+    fn handle_get_trait_implementers<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
+                                               chainstate: &mut StacksChainState, tip: &StacksBlockId,
+                                               contract_addr: &StacksAddress, contract_name: &ContractName, trait_name: &ClarityName,
+                                               offset: Option<u64>, limit: Option<u64>) -> Result<(), net_error> {
+        let response_metadata = HttpResponseMetadata::from(req);
+        let trait_identifier = TraitIdentifier::new(contract_addr.clone().into(), contract_name.clone(), trait_name.clone());
+
+        let offset = offset.unwrap_or(0) as usize;
+        let limit = cmp::min(limit.unwrap_or(MAX_TRAIT_IMPLEMENTERS as u64), MAX_TRAIT_IMPLEMENTERS as u64) as usize;
+
+        let implementers = match chainstate.get_trait_implementers(tip, &trait_identifier, offset, limit) {
+            Ok(implementers) => implementers,
+            Err(e) => {
+                warn!("Failed to query implementers of {}: {:?}", &trait_identifier, &e);
+                let response = HttpResponseType::ServerError(response_metadata, "Failed to query trait implementation index".into());
+                return response.send(http, fd).map(|_| ());
+            }
+        };
+
+        let implementers = implementers.iter()
+            .map(|contract_identifier| contract_identifier.to_string())
+            .collect();
+        let response = HttpResponseType::GetTraitImplementers(response_metadata, TraitImplementersResponse { implementers });
+
+        response.send(http, fd).map(|_| ())
+    }
+
+    fn make_indexed_transaction_response(entry: TransactionIndexEntry) -> IndexedTransactionResponse {
+        let mut tx_bytes = vec![];
+        entry.tx.consensus_serialize(&mut tx_bytes)
+            .expect("BUG: failed to serialize an indexed transaction");
+
+        IndexedTransactionResponse {
+            txid: entry.txid.to_hex(),
+            tx: to_hex(&tx_bytes),
+            status: entry.status,
+            result: format!("0x{}", entry.result.serialize()),
+            index_block_hash: entry.index_block_hash.to_hex(),
+            block_height: entry.block_height,
+            tx_index: entry.tx_index,
+            microblock_hash: entry.microblock_hash.map(|hash| hash.to_hex()),
+            microblock_sequence: entry.microblock_sequence
+        }
+    }
+
+    /// Handle a GET for a confirmed transaction, given the chain tip.  Replies with a 404 if the
+    /// transaction index is not enabled, or if the transaction was not confirmed in this fork.
+    fn handle_get_transaction<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
+                                        chainstate: &StacksChainState, tip: &StacksBlockId, txid: &Txid) -> Result<(), net_error> {
+        let response_metadata = HttpResponseMetadata::from(req);
+
+        let response = if !chainstate.tx_index {
+            HttpResponseType::NotFound(response_metadata, "Transaction index is not enabled".into())
+        }
+        else {
+            match chainstate.get_indexed_transaction(tip, txid) {
+                Ok(Some(entry)) => HttpResponseType::GetTransaction(response_metadata, ConversationHttp::make_indexed_transaction_response(entry)),
+                Ok(None) => HttpResponseType::NotFound(response_metadata, format!("No such confirmed transaction {}", txid.to_hex())),
+                Err(e) => {
+                    warn!("Failed to query transaction index for {}: {:?}", txid, &e);
+                    HttpResponseType::ServerError(response_metadata, "Failed to query transaction index".into())
+                }
+            }
+        };
+
+        response.send(http, fd).map(|_| ())
+    }
+
+    /// Handle a GET for the confirmed transactions sent or sponsored by an account, or which
+    /// called or deployed a contract, given the chain tip.  The most recent transactions come first.
+    fn handle_get_indexed_transactions<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType,
+                                                 chainstate: &StacksChainState, tip: &StacksBlockId) -> Result<(), net_error> {
+        let response_metadata = HttpResponseMetadata::from(req);
+
+        let response = if !chainstate.tx_index {
+            HttpResponseType::NotFound(response_metadata, "Transaction index is not enabled".into())
+        }
+        else {
+            let entries = match req {
+                HttpRequestType::GetAccountTransactions(_, ref address, _, ref before_height) => chainstate.get_account_transactions(tip, address, *before_height, MAX_INDEXED_TRANSACTIONS),
+                HttpRequestType::GetContractTransactions(_, ref contract_addr, ref contract_name, _, ref before_height) => {
+                    let contract_identifier = QualifiedContractIdentifier::new(contract_addr.clone().into(), contract_name.clone());
+                    chainstate.get_contract_transactions(tip, &contract_identifier, *before_height, MAX_INDEXED_TRANSACTIONS)
+                },
+                _ => unreachable!("BUG: not a transaction index query")
+            };
+
+            match entries {
+                Ok(entries) => {
+                    let transactions = entries.into_iter()
+                        .map(ConversationHttp::make_indexed_transaction_response)
+                        .collect();
+                    HttpResponseType::GetTransactions(response_metadata, IndexedTransactionsResponse { transactions })
+                },
+                Err(e) => {
+                    warn!("Failed to query transaction index: {:?}", &e);
+                    HttpResponseType::ServerError(response_metadata, "Failed to query transaction index".into())
+                }
+            }
+        };
+
+        response.send(http, fd).map(|_| ())
+    }
+
     /// Handle a GET unconfirmed microblock stream.  Start streaming the reply.
     /// The response's preamble (but not the block data) will be synchronously written to the fd
     /// (so use a fd that can buffer!)
@@ -646,6 +971,60 @@ impl ConversationHttp {
         }
     }
 
+    /// Load up a Stacks block as a chain tip, given its index block hash.  Replies with a 404 if the
+    /// block isn't known to this node.
+    fn handle_load_stacks_block_tip<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType, index_block_hash: &StacksBlockId, chainstate: &StacksChainState) -> Result<Option<(BurnchainHeaderHash, BlockHeaderHash)>, net_error> {
+        match StacksChainState::get_stacks_block_header_info_by_index_block_hash(&chainstate.headers_db, index_block_hash)? {
+            Some(header_info) => Ok(Some((header_info.burn_header_hash, header_info.anchored_header.block_hash()))),
+            None => {
+                let response_metadata = HttpResponseMetadata::from(req);
+                let response = HttpResponseType::NotFound(response_metadata, format!("No such Stacks block {}", index_block_hash.to_hex()));
+                response.send(http, fd).and_then(|_| Ok(None))
+            }
+        }
+    }
+
+    /// Load up a Stacks block named by its index block hash, or by its height in the canonical
+    /// fork.  Replies with a 404 if there is no such block.
+    fn handle_load_stacks_block_selector<W: Write>(http: &mut StacksHttp, fd: &mut W, req: &HttpRequestType, burndb: &BurnDB, chainstate: &StacksChainState,
+                                                   selector: &StacksBlockSelector) -> Result<Option<StacksHeaderInfo>, net_error> {
+        let index_block_hash = match selector {
+            StacksBlockSelector::IndexBlockHash(ref index_block_hash) => Some(index_block_hash.clone()),
+            StacksBlockSelector::Height(height) => {
+                let (burn_block, block) = match ConversationHttp::handle_load_stacks_chain_tip(http, fd, req, burndb, chainstate)? {
+                    Some(tip) => tip,
+                    None => {
+                        return Ok(None);
+                    }
+                };
+                let tip_index_block_hash = StacksBlockHeader::make_index_block_hash(&burn_block, &block);
+                if *height < u32::max_value() as u64 {
+                    get_ancestor_block_hash(&chainstate.headers_state_index, *height, &tip_index_block_hash)?
+                }
+                else {
+                    None
+                }
+            }
+        };
+
+        let header_info = match index_block_hash {
+            Some(ref index_block_hash) => StacksChainState::get_stacks_block_header_info_by_index_block_hash(&chainstate.headers_db, index_block_hash)?,
+            None => None
+        };
+
+        match header_info {
+            Some(header_info) => Ok(Some(header_info)),
+            None => {
+                let response_metadata = HttpResponseMetadata::from(req);
+                let response = match selector {
+                    StacksBlockSelector::IndexBlockHash(ref index_block_hash) => HttpResponseType::NotFound(response_metadata, format!("No such Stacks block {}", index_block_hash.to_hex())),
+                    StacksBlockSelector::Height(height) => HttpResponseType::NotFound(response_metadata, format!("No Stacks block at height {}", height))
+                };
+                response.send(http, fd).and_then(|_| Ok(None))
+            }
+        }
+    }
+
     /// Handle a transaction.  Directly submit it to the mempool so the client can see any
     /// rejection reasons up-front (different from how the peer network handles it).  Indicate
     /// whether or not the transaction was accepted (and thus needs to be forwarded) in the return
@@ -712,6 +1091,47 @@ impl ConversationHttp {
                 }
                 None
             },
+            HttpRequestType::GetAccountAt(ref _md, ref principal, ref selector, ref with_proof) => {
+                if let Some(header_info) = ConversationHttp::handle_load_stacks_block_selector(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate, selector)? {
+                    ConversationHttp::handle_get_account_entry_at(&mut self.connection.protocol, &mut reply, &req, chainstate,
+                                                                  &header_info, principal, *with_proof)?;
+                }
+                None
+            },
+            HttpRequestType::GetAccountChanges(ref _md, ref principal, ref tip_opt, ref before_height) => {
+                let tip = match tip_opt {
+                    Some(ref index_block_hash) => ConversationHttp::handle_load_stacks_block_tip(&mut self.connection.protocol, &mut reply, &req, index_block_hash, chainstate)?,
+                    None => ConversationHttp::handle_load_stacks_chain_tip(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate)?
+                };
+                if let Some((burn_block, block)) = tip {
+                    let index_block_hash = StacksBlockHeader::make_index_block_hash(&burn_block, &block);
+                    ConversationHttp::handle_get_account_changes(&mut self.connection.protocol, &mut reply, &req, chainstate, &index_block_hash,
+                                                                 principal, *before_height)?;
+                }
+                None
+            },
+            HttpRequestType::GetTransaction(ref _md, ref txid, ref tip_opt) => {
+                let tip = match tip_opt {
+                    Some(ref index_block_hash) => ConversationHttp::handle_load_stacks_block_tip(&mut self.connection.protocol, &mut reply, &req, index_block_hash, chainstate)?,
+                    None => ConversationHttp::handle_load_stacks_chain_tip(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate)?
+                };
+                if let Some((burn_block, block)) = tip {
+                    let index_block_hash = StacksBlockHeader::make_index_block_hash(&burn_block, &block);
+                    ConversationHttp::handle_get_transaction(&mut self.connection.protocol, &mut reply, &req, chainstate, &index_block_hash, txid)?;
+                }
+                None
+            },
+            HttpRequestType::GetAccountTransactions(_, _, ref tip_opt, _) | HttpRequestType::GetContractTransactions(_, _, _, ref tip_opt, _) => {
+                let tip = match tip_opt {
+                    Some(ref index_block_hash) => ConversationHttp::handle_load_stacks_block_tip(&mut self.connection.protocol, &mut reply, &req, index_block_hash, chainstate)?,
+                    None => ConversationHttp::handle_load_stacks_chain_tip(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate)?
+                };
+                if let Some((burn_block, block)) = tip {
+                    let index_block_hash = StacksBlockHeader::make_index_block_hash(&burn_block, &block);
+                    ConversationHttp::handle_get_indexed_transactions(&mut self.connection.protocol, &mut reply, &req, chainstate, &index_block_hash)?;
+                }
+                None
+            },
             HttpRequestType::GetMapEntry(ref _md, ref contract_addr, ref contract_name, ref map_name, ref key, ref with_proof) => {
                 if let Some((burn_block, block)) = ConversationHttp::handle_load_stacks_chain_tip(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate)? {
                     ConversationHttp::handle_get_map_entry(&mut self.connection.protocol, &mut reply, &req, chainstate, &burn_block, &block,
@@ -730,6 +1150,21 @@ impl ConversationHttp {
                 }
                 None
             },
+            HttpRequestType::GetContractDependencies(ref _md, ref contract_addr, ref contract_name) => {
+                if let Some((burn_block, block)) = ConversationHttp::handle_load_stacks_chain_tip(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate)? {
+                    ConversationHttp::handle_get_contract_dependencies(&mut self.connection.protocol, &mut reply, &req, chainstate, &burn_block, &block,
+                                                                       contract_addr, contract_name)?;
+                }
+                None
+            },
+            HttpRequestType::GetTraitImplementers(ref _md, ref contract_addr, ref contract_name, ref trait_name, ref offset, ref limit) => {
+                if let Some((burn_block, block)) = ConversationHttp::handle_load_stacks_chain_tip(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate)? {
+                    let index_block_hash = StacksBlockHeader::make_index_block_hash(&burn_block, &block);
+                    ConversationHttp::handle_get_trait_implementers(&mut self.connection.protocol, &mut reply, &req, chainstate, &index_block_hash,
+                                                                    contract_addr, contract_name, trait_name, *offset, *limit)?;
+                }
+                None
+            },
             HttpRequestType::CallReadOnlyFunction(ref _md, ref ctrct_addr, ref ctrct_name, ref as_sender, ref func_name, ref args) => {
                 if let Some((burn_block, block)) = ConversationHttp::handle_load_stacks_chain_tip(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate)? {
                     ConversationHttp::handle_readonly_function_call(
@@ -738,6 +1173,18 @@ impl ConversationHttp {
                 }
                 None
             },
+            HttpRequestType::SimulateTransaction(ref _md, ref ctrct_addr, ref ctrct_name, ref func_name, ref simulation) => {
+                let tip = match simulation.tip {
+                    Some(ref index_block_hash) => ConversationHttp::handle_load_stacks_block_tip(&mut self.connection.protocol, &mut reply, &req, index_block_hash, chainstate)?,
+                    None => ConversationHttp::handle_load_stacks_chain_tip(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate)?
+                };
+                if let Some((burn_block, block)) = tip {
+                    ConversationHttp::handle_simulate_transaction(
+                        &mut self.connection.protocol, &mut reply, &req, chainstate, &burn_block, &block,
+                        ctrct_addr, ctrct_name, func_name, simulation)?;
+                }
+                None
+            },
             HttpRequestType::GetContractSrc(ref _md, ref contract_addr, ref contract_name, ref with_proof) => {
                 if let Some((burn_block, block)) = ConversationHttp::handle_load_stacks_chain_tip(&mut self.connection.protocol, &mut reply, &req, burndb, chainstate)? {
                     ConversationHttp::handle_get_contract_src(&mut self.connection.protocol, &mut reply, &req, chainstate, &burn_block, &block,
@@ -1135,6 +1582,7 @@ mod test {
     use chainstate::stacks::db::StacksChainState;
     use chainstate::stacks::db::BlockStreamData;
     use chainstate::stacks::db::blocks::test::*;
+    use chainstate::stacks::db::test::instantiate_chainstate;
     use chainstate::stacks::Error as chain_error;
     use chainstate::stacks::*;
     use burnchains::*;
@@ -1142,6 +1590,9 @@ mod test {
     use util::pipe::*;
     use util::get_epoch_time_secs;
 
+    use core::{FIRST_BURNCHAIN_BLOCK_HASH, FIRST_STACKS_BLOCK_HASH};
+    use vm::types::StandardPrincipalData;
+
     fn convo_send_recv(sender: &mut ConversationHttp, sender_chainstate: &mut StacksChainState, receiver: &mut ConversationHttp, receiver_chainstate: &mut StacksChainState) -> () {
         let (mut pipe_read, mut pipe_write) = Pipe::new();
         pipe_read.set_nonblocking(true);
@@ -1612,5 +2063,73 @@ mod test {
                     }
                 });
     }
-}
 
+    #[test]
+    fn test_rpc_simulate_transaction_writes() {
+        let contract = "
+        (define-data-var calls uint u0)
+        (define-map paid ((payer principal)) ((amount uint)))
+        (define-public (pay (recipient principal) (amount uint))
+          (begin (var-set calls (+ (var-get calls) u1))
+                 (map-set paid ((payer tx-sender)) ((amount amount)))
+                 (stx-transfer? amount tx-sender recipient)))";
+
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, "test-rpc-simulate-transaction-writes");
+
+        let privk = StacksPrivateKey::from_hex("6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001").unwrap();
+        let auth = TransactionAuth::from_p2pkh(&privk).unwrap();
+        let addr = auth.origin().address_testnet();
+        let sender = addr.to_account_principal();
+        let recipient = PrincipalData::from(StandardPrincipalData(1, [2u8; 20]));
+
+        let mut tx_contract = StacksTransaction::new(TransactionVersion::Testnet, auth,
+                                                     TransactionPayload::new_smart_contract(&"payments".to_string(), &contract.to_string()).unwrap());
+        tx_contract.chain_id = 0x80000000;
+        tx_contract.set_fee_rate(0);
+
+        let mut signer = StacksTransactionSigner::new(&tx_contract);
+        signer.sign_origin(&privk).unwrap();
+        let signed_tx = signer.get_tx().unwrap();
+
+        let burn_block = BurnchainHeaderHash([1u8; 32]);
+        let block = BlockHeaderHash([1u8; 32]);
+        let mut conn = chainstate.block_begin(&FIRST_BURNCHAIN_BLOCK_HASH, &FIRST_STACKS_BLOCK_HASH, &burn_block, &block);
+        StacksChainState::process_transaction(&mut conn, &signed_tx).unwrap();
+        conn.commit_block();
+
+        let simulation = SimulateTransactionRequest {
+            sender: sender.clone(),
+            arguments: vec![recipient.into(), Value::UInt(100)],
+            tip: None,
+            post_conditions: vec![],
+            post_condition_mode: TransactionPostConditionMode::Allow,
+            overrides: SimulationOverrides {
+                stx_balances: vec![(sender, 1000)],
+                .. SimulationOverrides::default()
+            }
+        };
+        let contract_name = ContractName::from("payments");
+        let function = ClarityName::from("pay");
+        let req = HttpRequestType::SimulateTransaction(HttpRequestMetadata::new("127.0.0.1".to_string(), 20443),
+                                                       addr.clone(), contract_name.clone(), function.clone(), simulation.clone());
+
+        // the public function writes, so it must not run under the read-only call limit
+        let mut http = StacksHttp::new();
+        let mut reply = vec![];
+        ConversationHttp::handle_simulate_transaction(&mut http, &mut reply, &req, &mut chainstate, &burn_block, &block,
+                                                      &addr, &contract_name, &function, &simulation).unwrap();
+
+        let mut client_http = StacksHttp::new();
+        client_http.begin_request(HttpVersion::Http11, req.request_path());
+        let (preamble, offset) = client_http.read_preamble(&reply).unwrap();
+        let response = match client_http.stream_payload(&preamble, &mut &reply[offset..]).unwrap() {
+            (Some((StacksHttpMessage::Response(HttpResponseType::SimulateTransaction(_, response)), _)), _) => response,
+            x => panic!("Invalid response: {:?}", &x)
+        };
+
+        assert!(response.okay, "simulation failed: {:?}", response.cause);
+        assert_eq!(Value::try_deserialize_hex_untyped(&response.result.unwrap()[2..]).unwrap(), Value::okay_true());
+        assert_eq!(response.events.len(), 1);
+        assert!(response.cost.unwrap().write_count > 0);
+    }
+}
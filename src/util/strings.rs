@@ -36,6 +36,7 @@ use regex::Regex;
 
 use vm::representations::{ClarityName, ContractName, SymbolicExpression, MAX_STRING_LEN as CLARITY_MAX_STRING_LENGTH};
 use vm::ast::parser::{lex, LexItem, CONTRACT_MIN_NAME_LENGTH, CONTRACT_MAX_NAME_LENGTH};
+use vm::ClarityVersion;
 
 pub use vm::representations::UrlString;
 
@@ -243,7 +244,7 @@ impl StacksString {
 
     pub fn is_clarity_variable(&self) -> bool {
         // must parse to a single Clarity variable
-        match lex(&self.to_string()) {
+        match lex(&self.to_string(), ClarityVersion::latest()) {
             Ok(lexed) => {
                 if lexed.len() != 1 {
                     return false;
@@ -1,4 +1,5 @@
 use std::collections::{HashMap, BTreeMap, BTreeSet};
+use std::rc::Rc;
 
 use vm::types::{TypeSignature, FunctionType, QualifiedContractIdentifier, TraitIdentifier};
 use vm::types::signatures::FunctionSignature;
@@ -8,8 +9,160 @@ use vm::analysis::errors::{CheckError, CheckErrors, CheckResult};
 use vm::analysis::type_checker::{ContractAnalysis};
 use vm::representations::{ClarityName};
 
+// the number of deserialized contract analyses kept by an `AnalysisCache`.
+const ANALYSIS_CACHE_SIZE: usize = 64;
+
 pub struct AnalysisDatabase <'a> {
-    store: RollbackWrapper <'a>
+    store: RollbackWrapper <'a>,
+    cache: AnalysisCache
+}
+
+/// A least-recently-used cache of deserialized contract analyses, so that type checking
+///   contracts which make many calls into the same contracts only deserializes each of them once.
+///   A cache is kept by a Clarity connection, whose chain tip is fixed, and passed to each
+///   `AnalysisDatabase` opened on it.
+///
+/// The cache records which contracts each nested context of the `AnalysisDatabase` stores, and
+///   rolling back a context drops only those contracts.
+pub struct AnalysisCache {
+    slots: Vec<Option<CacheEntry>>,
+    free_slots: Vec<usize>,
+    index: HashMap<QualifiedContractIdentifier, usize>,
+    // the most and least recently used entries.
+    head: Option<usize>,
+    tail: Option<usize>,
+    capacity: usize,
+    // the contracts stored in each open nested context.
+    writes: Vec<Vec<QualifiedContractIdentifier>>
+}
+
+// an entry of the cache's recency list, holding an analysis and the length of its stored form.
+struct CacheEntry {
+    contract_identifier: QualifiedContractIdentifier,
+    contract: Rc<ContractAnalysis>,
+    size: u64,
+    prev: Option<usize>,
+    next: Option<usize>
+}
+
+impl AnalysisCache {
+    pub fn new() -> AnalysisCache {
+        AnalysisCache::with_capacity(ANALYSIS_CACHE_SIZE)
+    }
+
+    pub fn with_capacity(capacity: usize) -> AnalysisCache {
+        AnalysisCache {
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            index: HashMap::new(),
+            head: None,
+            tail: None,
+            capacity,
+            writes: Vec::new()
+        }
+    }
+
+    fn entry(&mut self, slot: usize) -> &mut CacheEntry {
+        self.slots[slot].as_mut()
+            .expect("BUG: analysis cache index points to an empty slot")
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let (prev, next) = {
+            let entry = self.entry(slot);
+            (entry.prev.take(), entry.next.take())
+        };
+        match prev {
+            Some(prev) => self.entry(prev).next = next,
+            None => self.head = next
+        }
+        match next {
+            Some(next) => self.entry(next).prev = prev,
+            None => self.tail = prev
+        }
+    }
+
+    fn push_front(&mut self, slot: usize) {
+        let head = self.head;
+        self.entry(slot).next = head;
+        match head {
+            Some(head) => self.entry(head).prev = Some(slot),
+            None => self.tail = Some(slot)
+        }
+        self.head = Some(slot);
+    }
+
+    fn get(&mut self, contract_identifier: &QualifiedContractIdentifier) -> Option<(Rc<ContractAnalysis>, u64)> {
+        let slot = *self.index.get(contract_identifier)?;
+        self.unlink(slot);
+        self.push_front(slot);
+        let entry = self.entry(slot);
+        Some((entry.contract.clone(), entry.size))
+    }
+
+    fn insert(&mut self, contract_identifier: QualifiedContractIdentifier, contract: Rc<ContractAnalysis>, size: u64) {
+        self.remove(&contract_identifier);
+        if self.capacity == 0 {
+            return
+        }
+        if self.index.len() >= self.capacity {
+            let least_recently_used = self.tail
+                .map(|slot| self.entry(slot).contract_identifier.clone());
+            if let Some(identifier) = least_recently_used {
+                self.remove(&identifier);
+            }
+        }
+
+        let entry = CacheEntry { contract_identifier: contract_identifier.clone(), contract, size, prev: None, next: None };
+        let slot = match self.free_slots.pop() {
+            Some(slot) => {
+                self.slots[slot] = Some(entry);
+                slot
+            },
+            None => {
+                self.slots.push(Some(entry));
+                self.slots.len() - 1
+            }
+        };
+        self.push_front(slot);
+        self.index.insert(contract_identifier, slot);
+    }
+
+    fn remove(&mut self, contract_identifier: &QualifiedContractIdentifier) {
+        if let Some(slot) = self.index.remove(contract_identifier) {
+            self.unlink(slot);
+            self.slots[slot] = None;
+            self.free_slots.push(slot);
+        }
+    }
+
+    /// Opens a nested context, whose stored contracts are dropped if it is rolled back.
+    pub fn nest(&mut self) {
+        self.writes.push(Vec::new());
+    }
+
+    pub fn commit(&mut self) {
+        if let Some(written) = self.writes.pop() {
+            // the writes now belong to the enclosing context, if any.
+            if let Some(parent) = self.writes.last_mut() {
+                parent.extend(written);
+            }
+        }
+    }
+
+    pub fn roll_back(&mut self) {
+        if let Some(written) = self.writes.pop() {
+            for contract_identifier in written.iter() {
+                self.remove(contract_identifier);
+            }
+        }
+    }
+
+    fn record_write(&mut self, contract_identifier: &QualifiedContractIdentifier) {
+        if let Some(written) = self.writes.last_mut() {
+            written.push(contract_identifier.clone());
+        }
+    }
 }
 
 impl ClaritySerializable for ContractAnalysis {
@@ -29,11 +182,17 @@ impl ClarityDeserializable<ContractAnalysis> for ContractAnalysis {
 impl <'a> AnalysisDatabase <'a> {
     pub fn new(store: &'a mut dyn ClarityBackingStore) -> AnalysisDatabase<'a> {
         AnalysisDatabase {
-            store: RollbackWrapper::new(store)
+            store: RollbackWrapper::new(store),
+            cache: AnalysisCache::new()
         }
     }
     pub fn new_with_rollback_wrapper(store: RollbackWrapper<'a>) -> AnalysisDatabase<'a> {
-        AnalysisDatabase { store }
+        AnalysisDatabase { store, cache: AnalysisCache::new() }
+    }
+    /// Opens the database with the cache of a longer-lived connection, which `destroy_with_cache`
+    ///   hands back.
+    pub fn new_with_cache(store: RollbackWrapper<'a>, cache: AnalysisCache) -> AnalysisDatabase<'a> {
+        AnalysisDatabase { store, cache }
     }
 
     pub fn execute <F, T, E> (&mut self, f: F) -> Result<T,E> where F: FnOnce(&mut Self) -> Result<T,E>, {
@@ -49,14 +208,18 @@ impl <'a> AnalysisDatabase <'a> {
 
     pub fn begin(&mut self) {
         self.store.nest();
+        self.cache.nest();
     }
 
     pub fn commit(&mut self) {
         self.store.commit();
+        self.cache.commit();
     }
 
     pub fn roll_back(&mut self) {
         self.store.rollback();
+        // the cache may hold analyses which were only stored in the rolled back context.
+        self.cache.roll_back();
     }
 
     fn storage_key() -> &'static str {
@@ -76,12 +239,25 @@ impl <'a> AnalysisDatabase <'a> {
         self.store.has_metadata_entry(contract_identifier, AnalysisDatabase::storage_key())
     }
 
-    pub fn load_contract(&mut self, contract_identifier: &QualifiedContractIdentifier) -> Option<ContractAnalysis> {
-        self.store.get_metadata(contract_identifier, AnalysisDatabase::storage_key())
+    pub fn load_contract(&mut self, contract_identifier: &QualifiedContractIdentifier) -> Option<Rc<ContractAnalysis>> {
+        self.load_contract_and_size(contract_identifier)
+            .map(|(contract, _)| contract)
+    }
+
+    /// Loads the analysis of a contract, along with the length of its stored form.
+    pub fn load_contract_and_size(&mut self, contract_identifier: &QualifiedContractIdentifier) -> Option<(Rc<ContractAnalysis>, u64)> {
+        if let Some(entry) = self.cache.get(contract_identifier) {
+            return Some(entry)
+        }
+
+        let serialized = self.store.get_metadata(contract_identifier, AnalysisDatabase::storage_key())
             // treat NoSuchContract error thrown by get_metadata as an Option::None --
             //    the analysis will propagate that as a CheckError anyways.
-            .ok()?
-            .map(|x| ContractAnalysis::deserialize(&x))
+            .ok()??;
+        let contract = Rc::new(ContractAnalysis::deserialize(&serialized));
+        let size = serialized.len() as u64;
+        self.cache.insert(contract_identifier.clone(), contract.clone(), size);
+        Some((contract, size))
     }
 
     pub fn insert_contract(&mut self, contract_identifier: &QualifiedContractIdentifier, contract: &ContractAnalysis) -> CheckResult<()> {
@@ -91,6 +267,7 @@ impl <'a> AnalysisDatabase <'a> {
         }
 
         self.store.insert_metadata(contract_identifier, key, &contract.serialize());
+        self.cache.record_write(contract_identifier);
         Ok(())
     }
 
@@ -130,7 +307,29 @@ impl <'a> AnalysisDatabase <'a> {
     pub fn get_implemented_traits(&mut self, contract_identifier: &QualifiedContractIdentifier) -> CheckResult<BTreeSet<TraitIdentifier>> {
         let contract = self.load_contract(contract_identifier)
             .ok_or(CheckErrors::NoSuchContract(contract_identifier.to_string()))?;
-        Ok(contract.implemented_traits)
+        Ok(contract.implemented_traits.clone())
+    }
+
+    pub fn get_contract_dependencies(&mut self, contract_identifier: &QualifiedContractIdentifier) -> CheckResult<BTreeSet<QualifiedContractIdentifier>> {
+        let contract = self.load_contract(contract_identifier)
+            .ok_or(CheckErrors::NoSuchContract(contract_identifier.to_string()))?;
+        Ok(contract.dependencies.clone())
+    }
+
+    /// Returns the dependencies of `contract_identifier` and of each contract it transitively
+    ///   depends on, keyed by contract.
+    pub fn get_dependency_graph(&mut self, contract_identifier: &QualifiedContractIdentifier) -> CheckResult<BTreeMap<QualifiedContractIdentifier, BTreeSet<QualifiedContractIdentifier>>> {
+        let mut graph = BTreeMap::new();
+        let mut pending = vec![contract_identifier.clone()];
+        while let Some(next) = pending.pop() {
+            if graph.contains_key(&next) {
+                continue
+            }
+            let dependencies = self.get_contract_dependencies(&next)?;
+            pending.extend(dependencies.iter().cloned());
+            graph.insert(next, dependencies);
+        }
+        Ok(graph)
     }
 
     pub fn get_map_type(&mut self, contract_identifier: &QualifiedContractIdentifier, map_name: &str) -> CheckResult<(TypeSignature, TypeSignature)> {
@@ -145,4 +344,8 @@ impl <'a> AnalysisDatabase <'a> {
         self.store
     }
 
+    pub fn destroy_with_cache(self) -> (RollbackWrapper<'a>, AnalysisCache) {
+        (self.store, self.cache)
+    }
+
 }
@@ -1,7 +1,8 @@
 use vm::analysis::types::ContractAnalysis;
 use std::collections::{BTreeMap, BTreeSet};
 use vm::{ClarityName};
-use vm::types::{TypeSignature, FunctionArg, TupleTypeSignature, FunctionType, FixedFunction};
+use vm::types::{TypeSignature, FunctionArg, TupleTypeSignature, FunctionType, FixedFunction, StringSubtype};
+use vm::costs::ExecutionCost;
 
 pub fn build_contract_interface(contract_analysis: &ContractAnalysis) -> ContractInterface {
     let mut contract_interface = ContractInterface::new();
@@ -17,6 +18,8 @@ pub fn build_contract_interface(contract_analysis: &ContractAnalysis) -> Contrac
         non_fungible_tokens,
         defined_traits: _,
         implemented_traits: _,
+        dependencies: _,
+        function_cost_bounds,
         expressions: _,
         contract_identifier: _,
         type_map: _,
@@ -27,17 +30,20 @@ pub fn build_contract_interface(contract_analysis: &ContractAnalysis) -> Contrac
     contract_interface.functions.append(
         &mut ContractInterfaceFunction::from_map(
             private_function_types, 
-            ContractInterfaceFunctionAccess::private));
+            ContractInterfaceFunctionAccess::private,
+            function_cost_bounds));
 
     contract_interface.functions.append(
         &mut ContractInterfaceFunction::from_map(
             public_function_types, 
-            ContractInterfaceFunctionAccess::public));
+            ContractInterfaceFunctionAccess::public,
+            function_cost_bounds));
 
     contract_interface.functions.append(
         &mut ContractInterfaceFunction::from_map(
             read_only_function_types, 
-            ContractInterfaceFunctionAccess::read_only));
+            ContractInterfaceFunctionAccess::read_only,
+            function_cost_bounds));
 
     contract_interface.variables.append(
         &mut ContractInterfaceVariable::from_map(
@@ -83,6 +89,8 @@ pub enum ContractInterfaceAtomType {
     bool,
     principal,
     buffer { length: u32 },
+    string_ascii { length: u32 },
+    string_utf8 { length: u32 },
     tuple(Vec<ContractInterfaceTupleEntryType>),
     optional(Box<ContractInterfaceAtomType>),
     response { ok: Box<ContractInterfaceAtomType>, error: Box<ContractInterfaceAtomType> },
@@ -133,6 +141,8 @@ impl ContractInterfaceAtomType {
             PrincipalType => ContractInterfaceAtomType::principal,
             TraitReferenceType(_) => ContractInterfaceAtomType::trait_reference,
             BufferType(len) => ContractInterfaceAtomType::buffer { length: len.into() },
+            StringType(StringSubtype::ASCII(len)) => ContractInterfaceAtomType::string_ascii { length: len.into() },
+            StringType(StringSubtype::UTF8(len)) => ContractInterfaceAtomType::string_utf8 { length: len.into() },
             TupleType(sig) => Self::from_tuple_type(sig),
             ListType(list_data) => {
                 let (type_f, length) = list_data.clone().destruct();
@@ -179,20 +189,34 @@ pub struct ContractInterfaceFunctionOutput {
     pub type_f: ContractInterfaceAtomType,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ContractInterfaceCostBound {
+    bounded(ExecutionCost),
+    unbounded,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContractInterfaceFunction {
     pub name: String,
     pub access: ContractInterfaceFunctionAccess,
     pub args: Vec<ContractInterfaceFunctionArg>,
     pub outputs: ContractInterfaceFunctionOutput,
+    // only public and read-only functions have a cost bound.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_bound: Option<ContractInterfaceCostBound>,
 }
 
 impl ContractInterfaceFunction {
-    pub fn from_map(map: &BTreeMap<ClarityName, FunctionType>, access: ContractInterfaceFunctionAccess) -> Vec<ContractInterfaceFunction> {
+    pub fn from_map(map: &BTreeMap<ClarityName, FunctionType>, access: ContractInterfaceFunctionAccess,
+                    cost_bounds: &BTreeMap<ClarityName, Option<ExecutionCost>>) -> Vec<ContractInterfaceFunction> {
         map.iter().map(|(name, function_type)| {
             ContractInterfaceFunction {
                 name: name.clone().into(),
                 access: access.to_owned(),
+                cost_bound: cost_bounds.get(name).map(|cost_bound| match cost_bound {
+                    Some(cost) => ContractInterfaceCostBound::bounded(cost.clone()),
+                    None => ContractInterfaceCostBound::unbounded
+                }),
                 outputs: ContractInterfaceFunctionOutput { 
                     type_f: match function_type {
                         FunctionType::Fixed(FixedFunction { returns, .. }) => {
@@ -0,0 +1,434 @@
+use std::collections::{HashMap, BTreeMap};
+use vm::representations::{SymbolicExpression, ClarityName};
+use vm::representations::SymbolicExpressionType::{AtomValue, Atom, List, LiteralValue, TraitReference, Field};
+use vm::types::{TypeSignature, FunctionType, FixedFunction, Value, PrincipalData, StringSubtype, QualifiedContractIdentifier};
+use vm::functions::{NativeFunctions, lookup_reserved_functions};
+use vm::functions::define::DefineFunctionsParsed;
+use vm::functions::tuples;
+use vm::functions::tuples::TupleDefinitionType::{Implicit, Explicit};
+use vm::callables::CallableType;
+use vm::variables::NativeVariables;
+use vm::costs::{cost_functions, ExecutionCost, SimpleCostSpecification, LimitedCostTracker};
+use vm::analysis::types::{ContractAnalysis, AnalysisPass};
+use vm::analysis::dependency_collector::DependencyCollector;
+use vm::analysis::type_checker::contexts::TypeMap;
+
+use super::AnalysisDatabase;
+pub use super::errors::{CheckResult, CheckError, CheckErrors};
+
+#[cfg(test)]
+mod tests;
+
+// A cost bound is `None` if the cost of an expression cannot be bounded statically,
+//   e.g., because it dynamically dispatches a call through a trait.
+type CostBound = Option<ExecutionCost>;
+
+/// Computes an upper bound on the cost of every public and read-only function of a
+///   contract, from the cost functions charged by the interpreter and the maximum
+///   sizes of the type-checked expressions. Both branches of every conditional are
+///   assumed to be taken, and every iteration is assumed to run over a sequence of
+///   the maximum length. The bounds do not include the cost of loading the contracts
+///   themselves (`LOAD_CONTRACT`), which depends on their stored size.
+///
+/// Loading the bounds of the contracts called with `contract-call?` is charged to the
+///   analysis cost tracker.
+///
+/// This pass must run after the type checker.
+pub struct CostChecker <'a, 'b, 'c> {
+    db: &'a mut AnalysisDatabase<'b>,
+    contract_analysis: &'c ContractAnalysis,
+    type_map: &'c TypeMap,
+    function_costs: HashMap<ClarityName, CostBound>,
+    callee_costs: HashMap<QualifiedContractIdentifier, BTreeMap<ClarityName, CostBound>>,
+    cost_track: LimitedCostTracker
+}
+
+impl <'a, 'b, 'c> AnalysisPass for CostChecker <'a, 'b, 'c> {
+    fn run_pass(contract_analysis: &mut ContractAnalysis, analysis_db: &mut AnalysisDatabase) -> CheckResult<()> {
+        if contract_analysis.type_map.is_none() {
+            return Ok(())
+        }
+
+        let cost_track = contract_analysis.take_contract_cost_tracker();
+        let (result, cost_track) = {
+            let type_map = contract_analysis.type_map.as_ref()
+                .expect("BUG: type map checked above");
+            let mut command = CostChecker::new(analysis_db, contract_analysis, type_map, cost_track);
+            let result = command.run();
+            (result, command.cost_track)
+        };
+        // replace the cost tracker whether or not the analysis succeeded.
+        contract_analysis.replace_contract_cost_tracker(cost_track);
+        contract_analysis.function_cost_bounds = result?;
+        Ok(())
+    }
+}
+
+fn spec_cost(cost_function: &SimpleCostSpecification, input: u64) -> CostBound {
+    cost_function.compute_cost(input).ok()
+}
+
+fn total<I: IntoIterator<Item = CostBound>>(costs: I) -> CostBound {
+    let mut total = ExecutionCost::zero();
+    for cost in costs {
+        total.add(&cost?).ok()?;
+    }
+    Some(total)
+}
+
+fn max_of(first: CostBound, second: CostBound) -> CostBound {
+    Some(ExecutionCost::max_cost(first?, second?))
+}
+
+fn type_size(type_signature: &TypeSignature) -> u64 {
+    u64::from(type_signature.size())
+}
+
+fn max_sequence_len(type_signature: &TypeSignature) -> u64 {
+    match type_signature {
+        TypeSignature::ListType(list_data) => u64::from(list_data.get_max_len()),
+        TypeSignature::BufferType(len) => u64::from(u32::from(len)),
+        TypeSignature::StringType(subtype) => u64::from(subtype.get_max_len()),
+        _ => 0
+    }
+}
+
+impl <'a, 'b, 'c> CostChecker <'a, 'b, 'c> {
+    fn new(db: &'a mut AnalysisDatabase<'b>, contract_analysis: &'c ContractAnalysis, type_map: &'c TypeMap,
+           cost_track: LimitedCostTracker) -> CostChecker<'a, 'b, 'c> {
+        Self {
+            db,
+            contract_analysis,
+            type_map,
+            function_costs: HashMap::new(),
+            callee_costs: HashMap::new(),
+            cost_track
+        }
+    }
+
+    pub fn run(&mut self) -> CheckResult<BTreeMap<ClarityName, CostBound>> {
+        let contract_analysis = self.contract_analysis;
+        let mut cost_bounds = BTreeMap::new();
+
+        for exp in contract_analysis.expressions.iter() {
+            self.load_callee_costs(exp)?;
+        }
+
+        // definitions are sorted, so functions are always visited before their callers.
+        for exp in contract_analysis.expressions.iter() {
+            use vm::functions::define::DefineFunctionsParsed::*;
+            match DefineFunctionsParsed::try_parse(exp)? {
+                Some(PrivateFunction { signature, body }) => {
+                    self.check_define_function(signature, body)?;
+                },
+                Some(PublicFunction { signature, body }) | Some(ReadOnlyFunction { signature, body }) => {
+                    let (name, cost) = self.check_define_function(signature, body)?;
+                    cost_bounds.insert(name, cost);
+                },
+                _ => {}
+            }
+        }
+
+        Ok(cost_bounds)
+    }
+
+    // Loads the cost bounds of every contract statically called in `expression`, charging
+    //   the analysis cost tracker for reading each contract once.
+    fn load_callee_costs(&mut self, expression: &SymbolicExpression) -> CheckResult<()> {
+        if let List(ref children) = expression.expr {
+            if let Some(contract_identifier) = DependencyCollector::static_contract_call(children) {
+                if !self.callee_costs.contains_key(contract_identifier) {
+                    let (callee, size) = self.db.load_contract_and_size(contract_identifier)
+                        .ok_or_else(|| CheckErrors::NoSuchContract(contract_identifier.to_string()))?;
+                    runtime_cost!(cost_functions::ANALYSIS_FETCH_CONTRACT_ENTRY, &mut self.cost_track, size)?;
+                    self.callee_costs.insert(contract_identifier.clone(), callee.function_cost_bounds.clone());
+                }
+            }
+            for child in children.iter() {
+                self.load_callee_costs(child)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_define_function(&mut self, signature: &[SymbolicExpression], body: &SymbolicExpression) -> CheckResult<(ClarityName, CostBound)> {
+        let function_name = signature.get(0)
+            .ok_or(CheckErrors::DefineFunctionBadSignature)?
+            .match_atom().ok_or(CheckErrors::BadFunctionName)?;
+
+        let cost = self.function_application_cost(function_name, body);
+        self.function_costs.insert(function_name.clone(), cost.clone());
+
+        Ok((function_name.clone(), cost))
+    }
+
+    // The cost of applying a defined function to already evaluated arguments, as charged by
+    //   `DefinedFunction::execute_apply`.
+    fn function_application_cost(&mut self, function_name: &str, body: &SymbolicExpression) -> CostBound {
+        let contract_analysis = self.contract_analysis;
+        let args = match contract_analysis.get_private_function(function_name)
+            .or_else(|| contract_analysis.get_public_function_type(function_name))
+            .or_else(|| contract_analysis.get_read_only_function_type(function_name)) {
+                Some(FunctionType::Fixed(FixedFunction { args, .. })) => args,
+                _ => return None
+            };
+
+        let mut costs = vec![spec_cost(&cost_functions::USER_FUNCTION_APPLICATION, args.len() as u64)];
+        for arg in args.iter() {
+            costs.push(cost_functions::TYPE_CHECK_COST.compute_cost(&arg.signature).ok());
+        }
+        costs.push(self.expression_cost(body, 0));
+        total(costs)
+    }
+
+    fn expression_type_size(&self, expr: &SymbolicExpression) -> u64 {
+        self.type_map.get_type(expr)
+            .map(type_size)
+            .unwrap_or(0)
+    }
+
+    // The cost of constructing the string that `append` or `concat` returns from its arguments,
+    //   which is only charged for strings.
+    fn string_cons_cost(&self, args: &[SymbolicExpression]) -> CostBound {
+        let len = args.iter()
+            .map(|arg| self.type_map.get_type(arg).map(max_sequence_len).unwrap_or(0))
+            .sum();
+        match args.get(0).and_then(|arg| self.type_map.get_type(arg)) {
+            Some(TypeSignature::StringType(StringSubtype::ASCII(_))) => spec_cost(&cost_functions::STRING_ASCII_CONS, len),
+            Some(TypeSignature::StringType(StringSubtype::UTF8(_))) => spec_cost(&cost_functions::STRING_UTF8_CONS, len),
+            _ => Some(ExecutionCost::zero())
+        }
+    }
+
+    // `depth` is the depth of the local context the expression is evaluated in.
+    fn expression_cost(&mut self, expr: &SymbolicExpression, depth: u64) -> CostBound {
+        match expr.expr {
+            AtomValue(_) | LiteralValue(_) | TraitReference(_, _) | Field(_) => Some(ExecutionCost::zero()),
+            Atom(ref name) => {
+                if NativeVariables::lookup_by_name(name).is_some() {
+                    Some(ExecutionCost::zero())
+                } else {
+                    total(vec![spec_cost(&cost_functions::LOOKUP_VARIABLE_DEPTH, depth),
+                               spec_cost(&cost_functions::LOOKUP_VARIABLE_SIZE, self.expression_type_size(expr))])
+                }
+            },
+            List(ref expression) => {
+                let (function_name, args) = expression.split_first()?;
+                let function_name = function_name.match_atom()?;
+
+                let application_cost = match NativeFunctions::lookup_by_name(function_name) {
+                    Some(native_function) => self.native_function_cost(native_function, function_name, args, depth),
+                    None => {
+                        let function_cost = self.function_costs.get(function_name).cloned()?;
+                        total(vec![self.all_args_cost(args, depth), function_cost])
+                    }
+                };
+
+                total(vec![spec_cost(&cost_functions::LOOKUP_FUNCTION, 0), application_cost])
+            }
+        }
+    }
+
+    fn all_args_cost(&mut self, args: &[SymbolicExpression], depth: u64) -> CostBound {
+        let costs: Vec<_> = args.iter().map(|arg| self.expression_cost(arg, depth)).collect();
+        total(costs)
+    }
+
+    // The cost of a tuple argument to the map functions, which may be given in the implicit form.
+    fn tuple_argument_cost(&mut self, arg: &SymbolicExpression, depth: u64) -> CostBound {
+        match tuples::get_definition_type_of_tuple_argument(arg) {
+            Implicit(ref bindings) => total(vec![spec_cost(&cost_functions::TUPLE_CONS, bindings.len() as u64),
+                                                 self.bindings_cost(bindings, depth)]),
+            Explicit => self.expression_cost(arg, depth)
+        }
+    }
+
+    // The cost of evaluating the values of `(name value)` pairs.
+    fn bindings_cost(&mut self, bindings: &[SymbolicExpression], depth: u64) -> CostBound {
+        let mut costs = Vec::new();
+        for binding in bindings.iter() {
+            let value = binding.match_list()
+                .and_then(|pair| pair.get(1))?;
+            costs.push(self.expression_cost(value, depth));
+        }
+        total(costs)
+    }
+
+    // The cost of applying the function named by the first argument of map, filter and fold
+    //   to a single element.
+    fn iterated_function_cost(&mut self, function_name: &SymbolicExpression, arg_count: u64) -> CostBound {
+        let function_name = function_name.match_atom()?;
+        match lookup_reserved_functions(function_name) {
+            Some(CallableType::NativeFunction(_, _, cost_function)) => spec_cost(&cost_function, arg_count),
+            Some(_) => None,
+            None => self.function_costs.get(function_name).cloned()?
+        }
+    }
+
+    fn iteration_cost(&mut self, cost_function: &SimpleCostSpecification, args: &[SymbolicExpression],
+                      arg_count: u64, depth: u64) -> CostBound {
+        let function_name = args.get(0)?;
+        let sequence = args.get(1)?;
+        let iterations = self.type_map.get_type(sequence)
+            .map(max_sequence_len)
+            .unwrap_or(0);
+
+        let mut iteration_cost = self.iterated_function_cost(function_name, arg_count)?;
+        iteration_cost.multiply(iterations).ok()?;
+
+        total(vec![spec_cost(cost_function, 0),
+                   spec_cost(&cost_functions::LOOKUP_FUNCTION, 0),
+                   self.all_args_cost(&args[1..], depth),
+                   Some(iteration_cost)])
+    }
+
+    fn contract_call_cost(&mut self, args: &[SymbolicExpression], depth: u64) -> CostBound {
+        let function_name = args.get(1)?.match_atom()?;
+        let callee_cost = match args[0].expr {
+            LiteralValue(Value::Principal(PrincipalData::Contract(ref contract_identifier))) => {
+                self.callee_costs.get(contract_identifier)?
+                    .get(function_name).cloned()?
+            },
+            // dynamic dispatch: the callee is only known at runtime.
+            _ => None
+        };
+
+        total(vec![spec_cost(&cost_functions::CONTRACT_CALL, 0),
+                   self.all_args_cost(&args[2..], depth),
+                   callee_cost])
+    }
+
+    fn native_function_cost(&mut self, native_function: NativeFunctions, function_name: &str,
+                            args: &[SymbolicExpression], depth: u64) -> CostBound {
+        use vm::functions::NativeFunctions::*;
+        let contract_analysis = self.contract_analysis;
+        match native_function {
+            If => {
+                let condition = self.expression_cost(args.get(0)?, depth);
+                let then_branch = self.expression_cost(args.get(1)?, depth);
+                let else_branch = self.expression_cost(args.get(2)?, depth);
+                total(vec![spec_cost(&cost_functions::IF, 0), condition, max_of(then_branch, else_branch)])
+            },
+            Match => {
+                // (match input some-name some-branch none-branch), or
+                // (match input ok-name ok-branch err-name err-branch)
+                let input = self.expression_cost(args.get(0)?, depth);
+                let first_branch = self.expression_cost(args.get(2)?, depth + 1);
+                let second_branch = self.expression_cost(args.last()?, depth + 1);
+                total(vec![spec_cost(&cost_functions::MATCH, 0), input, max_of(first_branch, second_branch)])
+            },
+            Let => {
+                let bindings = args.get(0)?.match_list()?;
+                let mut costs = vec![spec_cost(&cost_functions::LET, bindings.len() as u64),
+                                     self.bindings_cost(bindings, depth)];
+                for body in args[1..].iter() {
+                    costs.push(self.expression_cost(body, depth + 1));
+                }
+                total(costs)
+            },
+            And => total(vec![spec_cost(&cost_functions::AND, args.len() as u64), self.all_args_cost(args, depth)]),
+            Or => total(vec![spec_cost(&cost_functions::OR, args.len() as u64), self.all_args_cost(args, depth)]),
+            Asserts => total(vec![spec_cost(&cost_functions::ASSERTS, 0), self.all_args_cost(args, depth)]),
+            FetchVar => {
+                let var_type = contract_analysis.get_persisted_variable_type(args.get(0)?.match_atom()?)?;
+                spec_cost(&cost_functions::FETCH_VAR, type_size(var_type))
+            },
+            SetVar => {
+                let var_type = contract_analysis.get_persisted_variable_type(args.get(0)?.match_atom()?)?;
+                total(vec![spec_cost(&cost_functions::SET_VAR, type_size(var_type)),
+                           self.all_args_cost(&args[1..], depth)])
+            },
+            FetchEntry | SetEntry | InsertEntry | DeleteEntry => {
+                let (key_type, value_type) = contract_analysis.get_map_type(args.get(0)?.match_atom()?)?;
+                let (cost_function, input) = match native_function {
+                    FetchEntry => (cost_functions::FETCH_ENTRY, type_size(key_type) + type_size(value_type)),
+                    DeleteEntry => (cost_functions::SET_ENTRY, type_size(key_type)),
+                    _ => (cost_functions::SET_ENTRY, type_size(key_type) + type_size(value_type))
+                };
+                let mut costs = vec![spec_cost(&cost_function, input)];
+                for arg in args[1..].iter() {
+                    costs.push(self.tuple_argument_cost(arg, depth));
+                }
+                total(costs)
+            },
+            TupleCons => total(vec![spec_cost(&cost_functions::TUPLE_CONS, args.len() as u64),
+                                    self.bindings_cost(args, depth)]),
+            TupleGet => {
+                let tuple = args.get(1)?;
+                let fields = match self.type_map.get_type(tuple)? {
+                    TypeSignature::TupleType(tuple_type) => tuple_type.len(),
+                    TypeSignature::OptionalType(inner) => match **inner {
+                        TypeSignature::TupleType(ref tuple_type) => tuple_type.len(),
+                        _ => 0
+                    },
+                    _ => 0
+                };
+                total(vec![spec_cost(&cost_functions::TUPLE_GET, fields), self.expression_cost(tuple, depth)])
+            },
+            ListCons => {
+                let size = args.iter().map(|arg| self.expression_type_size(arg)).sum();
+                total(vec![spec_cost(&cost_functions::LIST_CONS, size), self.all_args_cost(args, depth)])
+            },
+            Map => self.iteration_cost(&cost_functions::MAP, args, 1, depth),
+            Filter => self.iteration_cost(&cost_functions::FILTER, args, 1, depth),
+            Fold => self.iteration_cost(&cost_functions::FILTER, args, 2, depth),
+            Append => {
+                let size = self.expression_type_size(args.get(0)?);
+                total(vec![spec_cost(&cost_functions::APPEND, size), self.string_cons_cost(args), self.all_args_cost(args, depth)])
+            },
+            Concat => {
+                let size = args.iter().map(|arg| self.expression_type_size(arg)).sum();
+                total(vec![spec_cost(&cost_functions::CONCAT, size), self.string_cons_cost(args), self.all_args_cost(args, depth)])
+            },
+            AsMaxLen => total(vec![spec_cost(&cost_functions::AS_MAX_LEN, 0), self.all_args_cost(args, depth)]),
+            ElementAt => total(vec![spec_cost(&cost_functions::ELEMENT_AT, 0), self.all_args_cost(args, depth)]),
+            IndexOf | Slice => {
+                let cost_function = match native_function {
+                    IndexOf => cost_functions::INDEX_OF,
+                    _ => cost_functions::SLICE
+                };
+                let size = self.expression_type_size(args.get(0)?);
+                total(vec![spec_cost(&cost_function, size), self.all_args_cost(args, depth)])
+            },
+            Print => {
+                let size = self.expression_type_size(args.get(0)?);
+                total(vec![spec_cost(&cost_functions::PRINT, size), self.all_args_cost(args, depth)])
+            },
+            ContractCall => self.contract_call_cost(args, depth),
+            AsContract => self.all_args_cost(args, depth),
+            ContractOf => spec_cost(&cost_functions::CONTRACT_OF, 0),
+            PrincipalOf => total(vec![spec_cost(&cost_functions::PRINCIPAL_OF, 0), self.all_args_cost(args, depth)]),
+            ImplementsTrait => total(vec![spec_cost(&cost_functions::IMPLEMENTS_TRAIT, 0), self.all_args_cost(args, depth)]),
+            AtBlock => total(vec![spec_cost(&cost_functions::AT_BLOCK, 0), self.all_args_cost(args, depth)]),
+            GetBlockInfo => total(vec![spec_cost(&cost_functions::BLOCK_INFO, 0), self.all_args_cost(&args[1..], depth)]),
+            GetStxBalance => total(vec![spec_cost(&cost_functions::STX_BALANCE, 0), self.all_args_cost(args, depth)]),
+            StxTransfer | StxBurn => total(vec![spec_cost(&cost_functions::STX_TRANSFER, 0), self.all_args_cost(args, depth)]),
+            MintToken => total(vec![spec_cost(&cost_functions::FT_MINT, 0), self.all_args_cost(&args[1..], depth)]),
+            TransferToken => total(vec![spec_cost(&cost_functions::FT_TRANSFER, 0), self.all_args_cost(&args[1..], depth)]),
+            GetTokenBalance => total(vec![spec_cost(&cost_functions::FT_BALANCE, 0), self.all_args_cost(&args[1..], depth)]),
+            BurnToken => total(vec![spec_cost(&cost_functions::FT_BURN, 0), self.all_args_cost(&args[1..], depth)]),
+            GetTokenSupply => spec_cost(&cost_functions::FT_SUPPLY, 0),
+            MintAsset | TransferAsset | BurnAsset | GetAssetOwner => {
+                let asset_type = contract_analysis.non_fungible_tokens.get(args.get(0)?.match_atom()?)?;
+                let cost_function = match native_function {
+                    MintAsset => cost_functions::NFT_MINT,
+                    TransferAsset => cost_functions::NFT_TRANSFER,
+                    BurnAsset => cost_functions::NFT_BURN,
+                    _ => cost_functions::NFT_OWNER
+                };
+                total(vec![spec_cost(&cost_function, type_size(asset_type)), self.all_args_cost(&args[1..], depth)])
+            },
+            _ => {
+                // the remaining natives evaluate all of their arguments, and then charge their
+                //   cost function for the number of arguments.
+                match lookup_reserved_functions(function_name)? {
+                    CallableType::NativeFunction(_, _, cost_function) => {
+                        total(vec![self.all_args_cost(args, depth), spec_cost(&cost_function, args.len() as u64)])
+                    },
+                    // special functions which are not handled above cannot be bounded.
+                    _ => None
+                }
+            }
+        }
+    }
+}
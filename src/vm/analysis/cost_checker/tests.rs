@@ -0,0 +1,164 @@
+use vm::ast::parse;
+use vm::database::MemoryBackingStore;
+use vm::analysis::{mem_type_check, type_check, run_analysis, CheckResult};
+use vm::analysis::errors::CheckErrors;
+use vm::types::QualifiedContractIdentifier;
+use vm::costs::{ExecutionCost, LimitedCostTracker};
+
+fn cost_bound(contract: &str, function_name: &str) -> Option<ExecutionCost> {
+    let (_, contract_analysis) = mem_type_check(contract).unwrap();
+    contract_analysis.function_cost_bounds.get(function_name).cloned()
+        .expect("No cost bound for function")
+}
+
+#[test]
+fn test_arithmetic_cost_bound() {
+    let contract = "(define-read-only (add (a int) (b int)) (+ a b))";
+    // application: 3 + 2 * 17 (argument type checks)
+    // body: 1 (function lookup) + 2 * 17 (variable lookups) + 3 (addition)
+    assert_eq!(cost_bound(contract, "add"), Some(ExecutionCost::runtime(75)));
+}
+
+#[test]
+fn test_private_functions() {
+    let contract = "(define-private (double (x int)) (* x 2))
+                    (define-read-only (quadruple (y int)) (double (double y)))
+                    (define-read-only (octuple (y int)) (double (quadruple y)))";
+    let quadruple = cost_bound(contract, "quadruple").unwrap();
+    let octuple = cost_bound(contract, "octuple").unwrap();
+    assert!(octuple.runtime > quadruple.runtime);
+
+    // private functions are not reported.
+    let (_, contract_analysis) = mem_type_check(contract).unwrap();
+    assert!(!contract_analysis.function_cost_bounds.contains_key("double"));
+}
+
+#[test]
+fn test_storage_cost_bound() {
+    let contract = "(define-data-var counter int 0)
+                    (define-map balances ((owner principal)) ((balance uint)))
+                    (define-public (increment)
+                      (begin
+                        (var-set counter (+ (var-get counter) 1))
+                        (ok (var-get counter))))
+                    (define-public (credit (amount uint))
+                      (begin
+                        (map-set balances ((owner tx-sender)) ((balance amount)))
+                        (ok amount)))";
+
+    let increment = cost_bound(contract, "increment").unwrap();
+    assert_eq!(increment.read_count, 3);
+    assert_eq!(increment.write_count, 1);
+    assert_eq!(increment.write_length, 17);
+
+    let credit = cost_bound(contract, "credit").unwrap();
+    assert_eq!(credit.read_count, 1);
+    assert_eq!(credit.write_count, 1);
+}
+
+#[test]
+fn test_branches_and_iterations() {
+    let contract = "(define-private (expensive (x int)) (* x x x x x x))
+                    (define-read-only (pick (flag bool))
+                      (if flag (expensive 2) 1))
+                    (define-read-only (expensive-only)
+                      (expensive 2))";
+    // the most expensive branch is assumed to be taken.
+    assert!(cost_bound(contract, "pick").unwrap().runtime > cost_bound(contract, "expensive-only").unwrap().runtime);
+
+    let short = "(define-private (square (x int)) (* x x))
+                 (define-read-only (squares (l (list 10 int))) (map square l))";
+    let long = "(define-private (square (x int)) (* x x))
+                (define-read-only (squares (l (list 20 int))) (map square l))";
+    let short = cost_bound(short, "squares").unwrap();
+    let long = cost_bound(long, "squares").unwrap();
+    // each additional iteration pays the application of `square`: 2 + 17 + 1 + 2 * 17 + 3
+    assert!(long.runtime - short.runtime > 10 * 57);
+}
+
+#[test]
+fn test_string_cons_cost_bound() {
+    let buffers = "(define-read-only (join (a (buff 10)) (b (buff 10))) (concat a b))";
+    let ascii = "(define-read-only (join (a (string-ascii 10)) (b (string-ascii 10))) (concat a b))";
+    let utf8 = "(define-read-only (join (a (string-utf8 10)) (b (string-utf8 10))) (concat a b))";
+
+    // constructing a string also pays for validating each of its (at most 20) characters.
+    let buffers = cost_bound(buffers, "join").unwrap();
+    assert!(cost_bound(ascii, "join").unwrap().runtime >= buffers.runtime + 21);
+    assert!(cost_bound(utf8, "join").unwrap().runtime >= buffers.runtime + 21);
+}
+
+#[test]
+fn test_contract_call_cost_bound() {
+    let callee_src = "(define-public (get-1 (x uint)) (ok u1))";
+    let caller_src = "(define-public (call) (contract-call? .callee get-1 u0))
+                      (define-trait trait-1 ((get-1 (uint) (response uint uint))))
+                      (define-public (dispatch (contract <trait-1>)) (contract-call? contract get-1 u0))";
+
+    let callee_id = QualifiedContractIdentifier::local("callee").unwrap();
+    let caller_id = QualifiedContractIdentifier::local("caller").unwrap();
+    let mut callee = parse(&callee_id, callee_src).unwrap();
+    let mut caller = parse(&caller_id, caller_src).unwrap();
+
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+    let (callee_analysis, caller_analysis) = db.execute(|db| -> CheckResult<_> {
+        let callee_analysis = type_check(&callee_id, &mut callee, db, true)?;
+        let caller_analysis = type_check(&caller_id, &mut caller, db, true)?;
+        Ok((callee_analysis, caller_analysis))
+    }).unwrap();
+
+    let get_1 = callee_analysis.function_cost_bounds["get-1"].clone().unwrap();
+    assert_eq!(get_1, ExecutionCost::runtime(21));
+
+    // the callee's bound, plus the application of `call`, the function lookup and `CONTRACT_CALL`.
+    let call = caller_analysis.function_cost_bounds["call"].clone().unwrap();
+    assert_eq!(call, ExecutionCost::runtime(24));
+
+    // dynamic dispatch cannot be bounded.
+    assert_eq!(caller_analysis.function_cost_bounds["dispatch"], None);
+}
+
+#[test]
+fn test_callee_load_is_charged() {
+    let callee_src = "(define-public (get-1 (x uint)) (ok u1))";
+    let caller_src = "(define-public (call) (contract-call? .callee get-1 u0))
+                      (define-public (call-twice)
+                        (begin (unwrap-panic (contract-call? .callee get-1 u0))
+                               (contract-call? .callee get-1 u1)))";
+
+    let callee_id = QualifiedContractIdentifier::local("callee").unwrap();
+    let caller_id = QualifiedContractIdentifier::local("caller").unwrap();
+    let mut callee = parse(&callee_id, callee_src).unwrap();
+
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+    let (callee_size, total, err) = db.execute(|db| -> CheckResult<_> {
+        type_check(&callee_id, &mut callee, db, true)?;
+        let (_, callee_size) = db.load_contract_and_size(&callee_id).unwrap();
+
+        let mut caller = parse(&caller_id, caller_src).unwrap();
+        let caller_analysis = run_analysis(&caller_id, &mut caller, db, false, LimitedCostTracker::new_max_limit())
+            .map_err(|(e, _)| e)?;
+        let total = caller_analysis.cost_track.as_ref().unwrap().get_total();
+
+        // only the cost checker's read of the callee goes over this budget.
+        let mut limit = ExecutionCost::max_value();
+        limit.read_length = total.read_length - 1;
+        let mut caller = parse(&caller_id, caller_src).unwrap();
+        let err = run_analysis(&caller_id, &mut caller, db, false, LimitedCostTracker::new(limit))
+            .map_err(|(e, _)| e).unwrap_err();
+        Ok((callee_size, total, err))
+    }).unwrap();
+
+    // the type checker reads 3 function entries and stores the analysis; the cost checker
+    //   reads the callee once however many times it is called.
+    assert_eq!(total.read_count, 5);
+    assert!(total.read_length > callee_size);
+
+    // a budget which cannot pay for the read fails the analysis.
+    match err.err {
+        CheckErrors::CostBalanceExceeded(..) => {},
+        e => panic!("Unexpected error: {:?}", e)
+    }
+}
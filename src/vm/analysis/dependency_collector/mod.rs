@@ -0,0 +1,73 @@
+use std::collections::BTreeSet;
+use vm::representations::SymbolicExpression;
+use vm::representations::SymbolicExpressionType::{List, LiteralValue, Field};
+use vm::types::{Value, PrincipalData, QualifiedContractIdentifier};
+use vm::functions::NativeFunctions;
+use vm::analysis::types::{ContractAnalysis, AnalysisPass};
+
+use super::AnalysisDatabase;
+pub use super::errors::{CheckResult, CheckError, CheckErrors};
+
+#[cfg(test)]
+mod tests;
+
+/// Records the contracts which a contract depends on: the contracts it calls with
+///   `contract-call?`, and the contracts defining the traits it uses or implements.
+///   A call which is dynamically dispatched through a trait reference only adds a
+///   dependency on the contract defining the trait.
+///
+/// This pass must run after the type checker, which checks that every dependency exists.
+pub struct DependencyCollector {
+    dependencies: BTreeSet<QualifiedContractIdentifier>
+}
+
+impl AnalysisPass for DependencyCollector {
+    fn run_pass(contract_analysis: &mut ContractAnalysis, _analysis_db: &mut AnalysisDatabase) -> CheckResult<()> {
+        let mut command = DependencyCollector::new();
+        for expression in contract_analysis.expressions.iter() {
+            command.collect(expression);
+        }
+        for dependency in command.dependencies.into_iter() {
+            contract_analysis.add_dependency(dependency);
+        }
+        Ok(())
+    }
+}
+
+impl DependencyCollector {
+    fn new() -> Self {
+        Self {
+            dependencies: BTreeSet::new()
+        }
+    }
+
+    fn collect(&mut self, expression: &SymbolicExpression) {
+        match expression.expr {
+            Field(ref trait_identifier) => {
+                self.dependencies.insert(trait_identifier.contract_identifier.clone());
+            },
+            List(ref children) => {
+                if let Some(contract_identifier) = DependencyCollector::static_contract_call(children) {
+                    self.dependencies.insert(contract_identifier.clone());
+                }
+                for child in children.iter() {
+                    self.collect(child);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Returns the callee of `(contract-call? .contract function ...)`.
+    pub fn static_contract_call(expression: &[SymbolicExpression]) -> Option<&QualifiedContractIdentifier> {
+        let function_name = expression.get(0)?.match_atom()?;
+        match NativeFunctions::lookup_by_name(function_name) {
+            Some(NativeFunctions::ContractCall) => {},
+            _ => return None
+        }
+        match expression.get(1)?.expr {
+            LiteralValue(Value::Principal(PrincipalData::Contract(ref contract_identifier))) => Some(contract_identifier),
+            _ => None
+        }
+    }
+}
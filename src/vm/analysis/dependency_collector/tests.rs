@@ -0,0 +1,134 @@
+use std::collections::BTreeSet;
+use std::rc::Rc;
+use vm::ast::parse;
+use vm::database::{MemoryBackingStore, RollbackWrapper};
+use vm::analysis::{type_check, AnalysisDatabase, AnalysisCache, ContractAnalysis};
+use vm::types::QualifiedContractIdentifier;
+
+fn deploy(db: &mut AnalysisDatabase, name: &str, contract: &str) -> ContractAnalysis {
+    let contract_identifier = QualifiedContractIdentifier::local(name).unwrap();
+    let mut expressions = parse(&contract_identifier, contract).unwrap();
+    db.execute(|db| {
+        db.test_insert_contract_hash(&contract_identifier);
+        type_check(&contract_identifier, &mut expressions, db, true)
+    }).unwrap()
+}
+
+fn contract_set(names: &[&str]) -> BTreeSet<QualifiedContractIdentifier> {
+    names.iter()
+        .map(|name| QualifiedContractIdentifier::local(name).unwrap())
+        .collect()
+}
+
+const TRAITS: &str = "(define-trait token ((balance-of (principal) (response uint uint))))";
+const TOKENS: &str = "(impl-trait .traits.token)
+                      (define-read-only (balance-of (owner principal)) (ok u10))";
+const EXCHANGE: &str = "(use-trait token .traits.token)
+                        (define-public (tokens-balance) (contract-call? .tokens balance-of tx-sender))
+                        (define-public (balance (t <token>)) (contract-call? t balance-of tx-sender))";
+
+#[test]
+fn test_contract_dependencies() {
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    let traits = deploy(&mut db, "traits", TRAITS);
+    assert!(traits.dependencies.is_empty());
+
+    let tokens = deploy(&mut db, "tokens", TOKENS);
+    assert_eq!(tokens.dependencies, contract_set(&["traits"]));
+
+    // the dynamic call through `t` only depends on the contract defining the trait.
+    let exchange = deploy(&mut db, "exchange", EXCHANGE);
+    assert_eq!(exchange.dependencies, contract_set(&["traits", "tokens"]));
+
+    let exchange_id = QualifiedContractIdentifier::local("exchange").unwrap();
+    let dependencies = db.execute(|db| db.get_contract_dependencies(&exchange_id)).unwrap();
+    assert_eq!(dependencies, contract_set(&["traits", "tokens"]));
+}
+
+#[test]
+fn test_dependency_graph() {
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    deploy(&mut db, "traits", TRAITS);
+    deploy(&mut db, "tokens", TOKENS);
+    deploy(&mut db, "exchange", EXCHANGE);
+    deploy(&mut db, "router", "(define-public (route) (contract-call? .exchange tokens-balance))");
+
+    let router_id = QualifiedContractIdentifier::local("router").unwrap();
+    let graph = db.execute(|db| db.get_dependency_graph(&router_id)).unwrap();
+
+    assert_eq!(graph.len(), 4);
+    assert_eq!(graph[&router_id], contract_set(&["exchange"]));
+    assert_eq!(graph[&QualifiedContractIdentifier::local("exchange").unwrap()], contract_set(&["traits", "tokens"]));
+    assert_eq!(graph[&QualifiedContractIdentifier::local("tokens").unwrap()], contract_set(&["traits"]));
+    assert_eq!(graph[&QualifiedContractIdentifier::local("traits").unwrap()], contract_set(&[]));
+
+    let missing_id = QualifiedContractIdentifier::local("missing").unwrap();
+    assert!(db.execute(|db| db.get_dependency_graph(&missing_id)).is_err());
+}
+
+#[test]
+fn test_cached_analyses_rolled_back() {
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+    let traits_id = QualifiedContractIdentifier::local("traits").unwrap();
+
+    db.begin();
+    let traits = deploy(&mut db, "traits", TRAITS);
+    // loaded twice, the second time from the cache.
+    assert_eq!(db.load_contract(&traits_id).unwrap().defined_traits, traits.defined_traits);
+    assert_eq!(db.load_contract(&traits_id).unwrap().defined_traits, traits.defined_traits);
+    db.roll_back();
+
+    db.begin();
+    assert!(db.load_contract(&traits_id).is_none());
+    db.roll_back();
+}
+
+#[test]
+fn test_cache_rollback_keeps_other_analyses() {
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+    let traits_id = QualifiedContractIdentifier::local("traits").unwrap();
+    let tokens_id = QualifiedContractIdentifier::local("tokens").unwrap();
+
+    db.begin();
+    deploy(&mut db, "traits", TRAITS);
+    let traits = db.load_contract(&traits_id).unwrap();
+
+    db.begin();
+    deploy(&mut db, "tokens", TOKENS);
+    assert!(db.load_contract(&tokens_id).is_some());
+    db.roll_back();
+
+    assert!(db.load_contract(&tokens_id).is_none());
+    assert!(Rc::ptr_eq(&traits, &db.load_contract(&traits_id).unwrap()));
+    db.roll_back();
+}
+
+#[test]
+fn test_cache_evicts_least_recently_used() {
+    let mut marf = MemoryBackingStore::new();
+    let mut db = AnalysisDatabase::new_with_cache(RollbackWrapper::new(&mut marf), AnalysisCache::with_capacity(2));
+    let traits_id = QualifiedContractIdentifier::local("traits").unwrap();
+    let tokens_id = QualifiedContractIdentifier::local("tokens").unwrap();
+    let exchange_id = QualifiedContractIdentifier::local("exchange").unwrap();
+
+    db.begin();
+    deploy(&mut db, "traits", TRAITS);
+    deploy(&mut db, "tokens", TOKENS);
+    deploy(&mut db, "exchange", EXCHANGE);
+
+    let traits = db.load_contract(&traits_id).unwrap();
+    let tokens = db.load_contract(&tokens_id).unwrap();
+    // using `traits` again leaves `tokens` as the least recently used.
+    assert!(Rc::ptr_eq(&traits, &db.load_contract(&traits_id).unwrap()));
+    db.load_contract(&exchange_id).unwrap();
+
+    assert!(Rc::ptr_eq(&traits, &db.load_contract(&traits_id).unwrap()));
+    assert!(!Rc::ptr_eq(&tokens, &db.load_contract(&tokens_id).unwrap()));
+    db.roll_back();
+}
@@ -37,6 +37,10 @@ pub enum CheckErrors {
     InvalidTypeDescription,
     UnknownTypeName(String),
 
+    // string literal and value construction
+    InvalidCharactersDetected,
+    InvalidUTF8Encoding,
+
     // union type mismatch
     UnionTypeError(Vec<TypeSignature>, TypeSignature),
     UnionTypeValueError(Vec<TypeSignature>, Value),
@@ -69,6 +73,7 @@ pub enum CheckErrors {
     BadTransferFTArguments,
     BadTransferNFTArguments,
     BadMintFTArguments,
+    BadBurnFTArguments,
 
     // tuples
     BadTupleFieldName,
@@ -112,6 +117,7 @@ pub enum CheckErrors {
     NonFunctionApplication,
     ExpectedListApplication,
     ExpectedListOrBuffer(TypeSignature),
+    ExpectedSequence(TypeSignature),
     MaxLengthOverflow,
 
     // let syntax
@@ -181,6 +187,15 @@ impl CheckError {
         self.diagnostic.spans = exprs.iter().map(|e| e.span.clone()).collect();
         self.expressions.replace(exprs.clone().to_vec());
     }
+
+    /// Renders the error as a rustc style diagnostic against the checked `source`.
+    pub fn render(&self, file_name: &str, source: &str) -> String {
+        self.diagnostic.render(file_name, source)
+    }
+
+    pub fn to_json(&self, file_name: &str, source: &str) -> serde_json::Value {
+        self.diagnostic.to_json(file_name, source)
+    }
 }
 
 impl fmt::Display for CheckErrors {
@@ -286,6 +301,8 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::EmptyTuplesNotAllowed => "tuple types may not be empty".into(),
             CheckErrors::BadSyntaxExpectedListOfPairs => "bad syntax: function expects a list of pairs to bind names, e.g., ((name-0 a) (name-1 b) ...)".into(),
             CheckErrors::UnknownTypeName(name) => format!("failed to parse type: '{}'", name),
+            CheckErrors::InvalidCharactersDetected => format!("invalid characters detected in string-ascii value"),
+            CheckErrors::InvalidUTF8Encoding => format!("invalid utf8 encoding in string-utf8 value"),
             CheckErrors::ValueTooLarge => format!("created a type which was greater than maximum allowed value size"),
             CheckErrors::TypeSignatureTooDeep => "created a type which was deeper than maximum allowed type depth".into(),
             CheckErrors::ExpectedName => format!("expected a name argument to this function"),
@@ -317,6 +334,7 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::BadTransferFTArguments => format!("transfer expects an int amount, from principal, to principal"),
             CheckErrors::BadTransferNFTArguments => format!("transfer expects an asset, from principal, to principal"),
             CheckErrors::BadMintFTArguments => format!("mint expects an int amount and from principal"),
+            CheckErrors::BadBurnFTArguments => format!("burn expects an int amount and from principal"),
             CheckErrors::BadMapName => format!("invalid map name"),
             CheckErrors::NoSuchMap(map_name) => format!("use of unresolved map '{}'", map_name),
             CheckErrors::DefineFunctionBadSignature => format!("invalid function definition"),
@@ -336,6 +354,7 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::NonFunctionApplication => format!("expecting expression of type function"),
             CheckErrors::ExpectedListApplication => format!("expecting expression of type list"),
             CheckErrors::ExpectedListOrBuffer(found_type) => format!("expecting expression of type 'list' or 'buff', found '{}'", found_type),
+            CheckErrors::ExpectedSequence(found_type) => format!("expecting expression of type 'list', 'buff', 'string-ascii' or 'string-utf8', found '{}'", found_type),
             CheckErrors::MaxLengthOverflow => format!("expecting a value <= {}", u32::max_value()),
             CheckErrors::BadLetSyntax => format!("invalid syntax of 'let'"),
             CheckErrors::CircularReference(function_names) => format!("detected interdependent functions ({})", function_names.join(", ")),
@@ -380,6 +399,27 @@ impl DiagnosableError for CheckErrors {
             CheckErrors::BadLetSyntax => Some(format!("'let' syntax example: (let ((supply 1000) (ttl 60)) <next-expression>)")),
             CheckErrors::TraitReferenceUnknown(_) => Some(format!("traits should be either defined, with define-trait, or imported, with use-trait.")),
             CheckErrors::NoSuchBlockInfoProperty(_) => Some(format!("properties available: time, header-hash, burnchain-header-hash, vrf-seed")),
+            CheckErrors::PublicFunctionMustReturnResponse(_) => Some(format!("wrap the returned value with (ok ...) or (err ...)")),
+            CheckErrors::UndefinedVariable(_) => Some(format!("variables should be either bound, with let or as function arguments, or defined, with define-constant.")),
+            CheckErrors::WriteAttemptedInReadOnly => Some(format!("read-only functions may not call functions which write to the chain state; use define-public instead.")),
+            CheckErrors::TypeValueError(TypeSignature::UIntType, Value::Int(_)) |
+            CheckErrors::TypeError(TypeSignature::UIntType, TypeSignature::IntType) => Some(format!("unsigned integer literals are prefixed with 'u', e.g., u10")),
+            _ => None
+        }
+    }
+
+    fn expected_found(&self) -> Option<(String, String)> {
+        match &self {
+            CheckErrors::TypeError(expected_type, found_type) |
+            CheckErrors::TypeLiteralError(expected_type, found_type) => Some((expected_type.to_string(), found_type.to_string())),
+            CheckErrors::TypeValueError(expected_type, found_value) => Some((expected_type.to_string(), found_value.to_string())),
+            CheckErrors::UnionTypeError(expected_types, found_type) => Some((formatted_expected_types(expected_types), found_type.to_string())),
+            CheckErrors::UnionTypeValueError(expected_types, found_value) => Some((formatted_expected_types(expected_types), found_value.to_string())),
+            CheckErrors::PublicFunctionMustReturnResponse(found_type) => Some(("response".into(), found_type.to_string())),
+            CheckErrors::ExpectedOptionalType(found_type) => Some(("optional".into(), found_type.to_string())),
+            CheckErrors::ExpectedResponseType(found_type) => Some(("response".into(), found_type.to_string())),
+            CheckErrors::ExpectedOptionalOrResponseType(found_type) => Some(("optional or response".into(), found_type.to_string())),
+            CheckErrors::IncorrectArgumentCount(expected_count, found_count) => Some((format!("{} arguments", expected_count), format!("{} arguments", found_count))),
             _ => None
         }
     }
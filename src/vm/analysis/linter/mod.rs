@@ -0,0 +1,270 @@
+use vm::analysis::types::ContractAnalysis;
+use vm::analysis::type_checker::contexts::TypeMap;
+use vm::diagnostic::{Diagnostic, DiagnosableError};
+use vm::representations::{SymbolicExpression, SymbolicExpressionType};
+use vm::types::{TypeSignature, Value};
+use vm::is_reserved;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, PartialEq)]
+pub enum LintWarning {
+    UnusedBinding(String),
+    ShadowedReservedName(String),
+    // a name which is already bound by an enclosing function argument, let or match.
+    ShadowedLocalName(String),
+    // the code following an `(asserts! false ...)`.
+    UnreachableCode,
+    // the `then` (true) or `else` (false) branch of an `if` whose condition
+    //   was already asserted.
+    UnreachableBranch(bool),
+    UncheckedResponse(TypeSignature),
+}
+
+impl DiagnosableError for LintWarning {
+    fn message(&self) -> String {
+        match &self {
+            LintWarning::UnusedBinding(name) => format!("unused binding '{}'", name),
+            LintWarning::ShadowedReservedName(name) => format!("binding '{}' shadows a reserved name", name),
+            LintWarning::ShadowedLocalName(name) => format!("binding '{}' shadows an enclosing binding of the same name", name),
+            LintWarning::UnreachableCode => format!("unreachable expression: the preceding asserts! always fails"),
+            LintWarning::UnreachableBranch(true) => format!("unreachable 'then' branch: the negation of its condition was asserted"),
+            LintWarning::UnreachableBranch(false) => format!("unreachable 'else' branch: its condition was asserted"),
+            LintWarning::UncheckedResponse(response_type) => format!("unchecked response of type '{}'", response_type),
+        }
+    }
+
+    fn suggestion(&self) -> Option<String> {
+        match &self {
+            LintWarning::UnusedBinding(_) => Some(format!("remove the binding, or use it.")),
+            LintWarning::ShadowedReservedName(_) => Some(format!("rename the binding: reserved names may not be rebound with let.")),
+            LintWarning::ShadowedLocalName(_) => Some(format!("rename one of the bindings, so that each name refers to a single value.")),
+            LintWarning::UncheckedResponse(_) => Some(format!("handle the response with unwrap!, try!, asserts! or match, so that errors are not ignored.")),
+            _ => None
+        }
+    }
+}
+
+// Natives which do not read or write the chain state: conditions built from these can
+//   be compared across the expressions of a sequence.
+const PURE_FUNCTIONS: &[&str] = &["is-eq", "<", ">", "<=", ">=", "and", "or", "not", "+", "-", "*", "/",
+                                  "mod", "is-some", "is-none", "is-ok", "is-err", "get", "len"];
+
+struct Linter <'a> {
+    type_map: Option<&'a TypeMap>,
+    // the local names bound by the enclosing function arguments, lets and matches.
+    locals: Vec<String>,
+    warnings: Vec<Diagnostic>
+}
+
+/// Flags the suspicious, but well-typed, code of a checked contract: unused bindings, bindings
+///   which shadow reserved or enclosing local names, `if` branches made unreachable by a previous `asserts!`, and responses whose
+///   errors are discarded. The contract must have passed the analysis, so that the
+///   expressions are well-formed and typed.
+pub fn lint(contract_analysis: &ContractAnalysis) -> Vec<Diagnostic> {
+    let mut linter = Linter {
+        type_map: contract_analysis.type_map.as_ref(),
+        locals: Vec::new(),
+        warnings: Vec::new()
+    };
+
+    for expression in contract_analysis.expressions.iter() {
+        linter.check_definition(expression);
+    }
+
+    linter.warnings
+}
+
+fn application<'b>(expression: &'b SymbolicExpression) -> Option<(&'b str, &'b [SymbolicExpression])> {
+    let list = expression.match_list()?;
+    let (function_name, args) = list.split_first()?;
+    Some((function_name.match_atom()?.as_str(), args))
+}
+
+fn references(expression: &SymbolicExpression, name: &str) -> bool {
+    match expression.expr {
+        SymbolicExpressionType::Atom(ref atom) => atom.as_str() == name,
+        SymbolicExpressionType::List(ref list) => list.iter().any(|x| references(x, name)),
+        _ => false
+    }
+}
+
+fn same_expression(a: &SymbolicExpression, b: &SymbolicExpression) -> bool {
+    match (&a.expr, &b.expr) {
+        (SymbolicExpressionType::List(ref a), SymbolicExpressionType::List(ref b)) => {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| same_expression(a, b))
+        },
+        (a, b) => a == b
+    }
+}
+
+fn is_pure(expression: &SymbolicExpression) -> bool {
+    match expression.expr {
+        SymbolicExpressionType::List(_) => {
+            match application(expression) {
+                Some((function_name, args)) => PURE_FUNCTIONS.contains(&function_name) && args.iter().all(is_pure),
+                None => false
+            }
+        },
+        _ => true
+    }
+}
+
+fn is_false(expression: &SymbolicExpression) -> bool {
+    match expression.expr {
+        SymbolicExpressionType::Atom(ref atom) => atom.as_str() == "false",
+        SymbolicExpressionType::AtomValue(Value::Bool(false)) |
+        SymbolicExpressionType::LiteralValue(Value::Bool(false)) => true,
+        _ => false
+    }
+}
+
+impl <'a> Linter <'a> {
+    fn warn(&mut self, warning: LintWarning, expression: &SymbolicExpression) {
+        let mut diagnostic = Diagnostic::warning(&warning);
+        diagnostic.spans = vec![expression.span.clone()];
+        self.warnings.push(diagnostic);
+    }
+
+    // Checks a name bound in `scope`, i.e., the expressions which may refer to it, and adds
+    //   it to the local names. Callers remove the names they bound once their scope ends.
+    fn check_binding(&mut self, name_expression: &SymbolicExpression, scope: &[SymbolicExpression]) {
+        if let Some(name) = name_expression.match_atom() {
+            if is_reserved(name) {
+                self.warn(LintWarning::ShadowedReservedName(name.to_string()), name_expression);
+            } else if self.locals.iter().any(|x| x.as_str() == name.as_str()) {
+                self.warn(LintWarning::ShadowedLocalName(name.to_string()), name_expression);
+            } else if !scope.iter().any(|x| references(x, name)) {
+                self.warn(LintWarning::UnusedBinding(name.to_string()), name_expression);
+            }
+            self.locals.push(name.to_string());
+        }
+    }
+
+    fn check_definition(&mut self, expression: &SymbolicExpression) {
+        match application(expression) {
+            Some(("define-public", args)) | Some(("define-private", args)) | Some(("define-read-only", args)) if args.len() == 2 => {
+                let enclosing = self.locals.len();
+                if let Some(signature) = args[0].match_list() {
+                    for argument in signature.iter().skip(1) {
+                        if let Some(pair) = argument.match_list() {
+                            if let Some(name) = pair.first() {
+                                self.check_binding(name, &args[1..]);
+                            }
+                        }
+                    }
+                }
+                self.check_sequence(&args[1..]);
+                self.locals.truncate(enclosing);
+            },
+            _ => self.check_expression(expression)
+        }
+    }
+
+    fn check_expression(&mut self, expression: &SymbolicExpression) {
+        match application(expression) {
+            Some(("let", args)) if args.len() >= 2 => {
+                let enclosing = self.locals.len();
+                if let Some(bindings) = args[0].match_list() {
+                    for (index, binding) in bindings.iter().enumerate() {
+                        if let Some(pair) = binding.match_list() {
+                            if pair.len() == 2 {
+                                // later bindings are evaluated in the enclosing context,
+                                //   but are checked as well, conservatively.
+                                let mut scope: Vec<_> = bindings[index+1..].to_vec();
+                                scope.extend_from_slice(&args[1..]);
+                                self.check_binding(&pair[0], &scope);
+                                self.check_expression(&pair[1]);
+                            }
+                        }
+                    }
+                }
+                self.check_sequence(&args[1..]);
+                self.locals.truncate(enclosing);
+            },
+            Some(("begin", args)) => {
+                self.check_sequence(args);
+            },
+            Some(("match", args)) if args.len() == 4 || args.len() == 5 => {
+                self.check_expression(&args[0]);
+                // (match input some-name some-branch none-branch), or
+                // (match input ok-name ok-branch err-name err-branch)
+                let enclosing = self.locals.len();
+                self.check_binding(&args[1], &args[2..3]);
+                self.check_expression(&args[2]);
+                self.locals.truncate(enclosing);
+                if args.len() == 5 {
+                    self.check_binding(&args[3], &args[4..5]);
+                }
+                self.check_expression(&args[args.len() - 1]);
+                self.locals.truncate(enclosing);
+            },
+            _ => {
+                if let Some(list) = expression.match_list() {
+                    for item in list.iter() {
+                        self.check_expression(item);
+                    }
+                }
+            }
+        }
+    }
+
+    // Checks the expressions of a sequence, e.g., a function or let body, of which only the
+    //   last value is returned.
+    fn check_sequence(&mut self, expressions: &[SymbolicExpression]) {
+        if let Some((_, intermediate)) = expressions.split_last() {
+            for expression in intermediate.iter() {
+                let response_type = match self.type_map.and_then(|type_map| type_map.get_type(expression)) {
+                    Some(response_type @ TypeSignature::ResponseType(_)) => Some(response_type.clone()),
+                    _ => None
+                };
+                if let Some(response_type) = response_type {
+                    self.warn(LintWarning::UncheckedResponse(response_type), expression);
+                }
+            }
+        }
+
+        let mut asserted: Vec<&SymbolicExpression> = Vec::new();
+        for (index, expression) in expressions.iter().enumerate() {
+            self.check_branches(expression, &asserted);
+            self.check_expression(expression);
+
+            if let Some(("asserts!", args)) = application(expression) {
+                if args.len() == 2 && is_false(&args[0]) {
+                    if let Some(next) = expressions.get(index + 1) {
+                        self.warn(LintWarning::UnreachableCode, next);
+                    }
+                    break;
+                } else if args.len() == 2 && is_pure(&args[0]) {
+                    asserted.push(&args[0]);
+                }
+            }
+        }
+    }
+
+    fn check_branches(&mut self, expression: &SymbolicExpression, asserted: &[&SymbolicExpression]) {
+        if asserted.is_empty() {
+            return;
+        }
+
+        if let Some(("if", args)) = application(expression) {
+            if args.len() == 3 {
+                let condition = &args[0];
+                if asserted.iter().any(|x| same_expression(x, condition)) {
+                    self.warn(LintWarning::UnreachableBranch(false), &args[2]);
+                } else if let Some(("not", negated)) = application(condition) {
+                    if negated.len() == 1 && asserted.iter().any(|x| same_expression(x, &negated[0])) {
+                        self.warn(LintWarning::UnreachableBranch(true), &args[1]);
+                    }
+                }
+            }
+        }
+
+        if let Some(list) = expression.match_list() {
+            for item in list.iter() {
+                self.check_branches(item, asserted);
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use vm::analysis::mem_type_check;
+use vm::analysis::linter::{lint, LintWarning};
+use vm::diagnostic::{Diagnostic, Level};
+use vm::types::TypeSignature;
+
+fn lint_snippet(snippet: &str) -> Vec<Diagnostic> {
+    let (_, contract_analysis) = mem_type_check(snippet).unwrap();
+    lint(&contract_analysis)
+}
+
+fn messages(snippet: &str) -> Vec<String> {
+    lint_snippet(snippet).into_iter().map(|x| x.message).collect()
+}
+
+#[test]
+fn test_unused_bindings() {
+    let snippet = "(define-private (add (x int) (y int))
+                     (let ((z (+ x 1)) (w 2))
+                       z))";
+    assert_eq!(messages(snippet), vec![
+        Diagnostic::warning(&LintWarning::UnusedBinding("y".to_string())).message,
+        Diagnostic::warning(&LintWarning::UnusedBinding("w".to_string())).message]);
+
+    let snippet = "(define-read-only (unwrap-or-zero (x (optional int)))
+                     (match x value 0 0))";
+    assert_eq!(messages(snippet), vec!["unused binding 'value'"]);
+
+    let warnings = lint_snippet(snippet);
+    assert_eq!(warnings[0].level, Level::Warning);
+    if cfg!(feature = "developer-mode") {
+        assert_eq!(warnings[0].spans[0].start_line, 2);
+    }
+}
+
+#[test]
+fn test_shadowed_names() {
+    let snippet = "(define-private (size (list (list 3 int)))
+                     (let ((len (len list))) len))";
+    assert_eq!(messages(snippet), vec!["binding 'list' shadows a reserved name",
+                                       "binding 'len' shadows a reserved name"]);
+
+    let snippet = "(define-private (add (x int) (x int))
+                     x)";
+    assert_eq!(messages(snippet), vec!["binding 'x' shadows an enclosing binding of the same name"]);
+
+    let snippet = "(define-private (add (x int))
+                     (let ((y 1)
+                           (z (let ((y 2)) (+ x y))))
+                       (+ y z)))";
+    assert_eq!(messages(snippet), vec!["binding 'y' shadows an enclosing binding of the same name"]);
+
+    // the bindings of the two branches of a match are separate.
+    let snippet = "(define-private (unwrap-any (x (response int int)))
+                     (match x value value value value))";
+    assert_eq!(messages(snippet), Vec::<String>::new());
+}
+
+#[test]
+fn test_unreachable_branches() {
+    let snippet = "(define-public (withdraw (amount uint))
+                     (begin
+                       (asserts! (> amount u10) (err u1))
+                       (if (> amount u10) (ok amount) (err u2))))";
+    assert_eq!(messages(snippet), vec!["unreachable 'else' branch: its condition was asserted"]);
+
+    let snippet = "(define-public (withdraw (amount uint))
+                     (begin
+                       (asserts! (> amount u10) (err u1))
+                       (if (not (> amount u10)) (err u2) (ok amount))))";
+    assert_eq!(messages(snippet), vec!["unreachable 'then' branch: the negation of its condition was asserted"]);
+
+    // conditions which read the chain state may change between the two expressions.
+    let snippet = "(define-data-var limit uint u10)
+                   (define-public (withdraw (amount uint))
+                     (begin
+                       (asserts! (> amount (var-get limit)) (err u1))
+                       (var-set limit amount)
+                       (if (> amount (var-get limit)) (ok amount) (err u2))))";
+    assert_eq!(messages(snippet), Vec::<String>::new());
+
+    let snippet = "(define-public (fail)
+                     (begin
+                       (asserts! false (err u1))
+                       (ok u1)))";
+    assert_eq!(messages(snippet), vec!["unreachable expression: the preceding asserts! always fails"]);
+}
+
+#[test]
+fn test_unchecked_responses() {
+    let snippet = "(define-private (check (x int))
+                     (if (> x 0) (ok x) (err u1)))
+                   (define-public (run)
+                     (begin
+                       (check 1)
+                       (unwrap! (check 2) (err u2))
+                       (ok 3)))";
+    let response_type = TypeSignature::new_response(TypeSignature::IntType, TypeSignature::UIntType).unwrap();
+    assert_eq!(messages(snippet), vec![
+        Diagnostic::warning(&LintWarning::UncheckedResponse(response_type)).message]);
+}
@@ -3,28 +3,38 @@ pub mod errors;
 pub mod trait_checker;
 pub mod type_checker;
 pub mod read_only_checker;
+pub mod cost_checker;
+pub mod dependency_collector;
 pub mod analysis_db;
 pub mod contract_interface_builder;
+pub mod linter;
 
 pub use self::types::{ContractAnalysis, AnalysisPass};
 use vm::representations::{SymbolicExpression};
 use vm::types::{TypeSignature, QualifiedContractIdentifier};
 use vm::costs::LimitedCostTracker;
+use vm::version::ClarityVersion;
 use vm::database::STORE_CONTRACT_SRC_INTERFACE;
 
 pub use self::errors::{CheckResult, CheckError, CheckErrors};
-pub use self::analysis_db::{AnalysisDatabase};
+pub use self::analysis_db::{AnalysisDatabase, AnalysisCache};
 
 use self::read_only_checker::ReadOnlyChecker;
 use self::trait_checker::TraitChecker;
 use self::type_checker::TypeChecker;
+use self::cost_checker::CostChecker;
+use self::dependency_collector::DependencyCollector;
 use self::contract_interface_builder::build_contract_interface;
 
 pub fn mem_type_check(snippet: &str) -> CheckResult<(Option<TypeSignature>, ContractAnalysis)> {
+    mem_type_check_with_version(snippet, ClarityVersion::Clarity1)
+}
+
+pub fn mem_type_check_with_version(snippet: &str, version: ClarityVersion) -> CheckResult<(Option<TypeSignature>, ContractAnalysis)> {
     use vm::database::MemoryBackingStore;
-    use vm::ast::parse;
+    use vm::ast::build_ast;
     let contract_identifier = QualifiedContractIdentifier::transient();
-    let mut contract = parse(&contract_identifier, snippet).unwrap();
+    let mut contract = build_ast(&contract_identifier, snippet, &mut (), version).unwrap().expressions;
     let mut marf = MemoryBackingStore::new();
     let mut analysis_db = marf.as_analysis_db();
     type_check(&QualifiedContractIdentifier::transient(), &mut contract, &mut analysis_db, false)
@@ -55,6 +65,8 @@ pub fn run_analysis(contract_identifier: &QualifiedContractIdentifier,
         ReadOnlyChecker::run_pass(&mut contract_analysis, db)?;
         TypeChecker::run_pass(&mut contract_analysis, db)?;
         TraitChecker::run_pass(&mut contract_analysis, db)?;
+        DependencyCollector::run_pass(&mut contract_analysis, db)?;
+        CostChecker::run_pass(&mut contract_analysis, db)?;
         if STORE_CONTRACT_SRC_INTERFACE {
             let interface = build_contract_interface(&contract_analysis);
             contract_analysis.contract_interface = Some(interface);
@@ -159,13 +159,16 @@ impl <'a, 'b> ReadOnlyChecker <'a, 'b> {
 
         match function {
             Add | Subtract | Divide | Multiply | CmpGeq | CmpLeq | CmpLess | CmpGreater |
-            Modulo | Power | BitwiseXOR | And | Or | Not | Hash160 | Sha256 | Keccak256 | Equals | If |
+            Modulo | Power | BitwiseXOR | BitwiseAnd | BitwiseOr | BitwiseNot | BitwiseLShift | BitwiseRShift | Sqrti | Log2 |
+            And | Or | Not | Hash160 | Sha256 | Keccak256 | Equals | If |
             Sha512 | Sha512Trunc256 |
             ConsSome | ConsOkay | ConsError | DefaultTo | UnwrapRet | UnwrapErrRet | IsOkay | IsNone | Asserts |
             Unwrap | UnwrapErr | Match | IsErr | IsSome | TryRet |
             ToUInt | ToInt | Append | Concat | AsMaxLen |
-            ContractOf |
-            ListCons | GetBlockInfo | TupleGet | Len | Print | AsContract | Begin | FetchVar | GetStxBalance | GetTokenBalance | GetAssetOwner => {
+            ElementAt | IndexOf | Slice | BuffToIntLe | BuffToIntBe | BuffToUIntLe | BuffToUIntBe | IntToBuffLe | IntToBuffBe |
+            Secp256k1Recover | Secp256k1Verify | PrincipalOf |
+            ContractOf | ImplementsTrait |
+            ListCons | GetBlockInfo | TupleGet | Len | Print | AsContract | Begin | FetchVar | GetStxBalance | GetTokenBalance | GetTokenSupply | GetAssetOwner => {
                 self.check_all_read_only(args)
             },
             AtBlock => {
@@ -192,7 +195,8 @@ impl <'a, 'b> ReadOnlyChecker <'a, 'b> {
                 res
             },
             StxTransfer | StxBurn |
-            SetEntry | DeleteEntry | InsertEntry | SetVar | MintAsset | MintToken | TransferAsset | TransferToken => {
+            SetEntry | DeleteEntry | InsertEntry | SetVar | MintAsset | MintToken | TransferAsset | TransferToken |
+            BurnAsset | BurnToken => {
                 Ok(false)
             },
             Let => {
@@ -2,6 +2,8 @@ use vm::ast::parse;
 use vm::analysis::{AnalysisDatabase, mem_type_check};
 use vm::analysis::errors::CheckErrors;
 use vm::analysis::{ContractAnalysis, type_check};
+use vm::database::{ClaritySerializable, ClarityDeserializable};
+use vm::types::QualifiedContractIdentifier;
 
 mod costs;
 
@@ -25,6 +27,39 @@ fn test_type_error() {
     assert!(format!("{}", err.diagnostic).contains("expecting expression of type 'int', found 'bool'"));
 }
 
+#[test]
+fn test_rendered_diagnostics() {
+    let snippet = "(define-constant a 1)\n(+ a true)";
+    let err = mem_type_check(snippet).unwrap_err();
+
+    let rendered = err.render("test.clar", snippet);
+    assert!(rendered.starts_with("error: expecting expression of type 'int', found 'bool'\n"));
+    assert!(rendered.contains(" = expected: int\n"));
+    assert!(rendered.contains(" = found: bool\n"));
+
+    let json = err.to_json("test.clar", snippet);
+    assert_eq!(json["level"], "error");
+    assert_eq!(json["file"], "test.clar");
+    assert_eq!(json["expected"], "int");
+    assert_eq!(json["found"], "bool");
+    assert_eq!(json["rendered"], rendered.as_str());
+
+    if cfg!(feature = "developer-mode") {
+        assert!(rendered.contains("--> test.clar:2:"));
+        assert!(rendered.contains("2 | (+ a true)\n"));
+        assert!(rendered.contains("^"));
+        assert_eq!(json["spans"][0]["start_line"], 2);
+        assert_eq!(json["spans"][0]["excerpt"], "(+ a true)");
+    } else {
+        assert!(rendered.contains("--> test.clar\n"));
+    }
+
+    let err = mem_type_check("(define-public (f) (+ 1 2))").unwrap_err();
+    let rendered = err.render("test.clar", "(define-public (f) (+ 1 2))");
+    assert!(rendered.contains(" = expected: response\n"));
+    assert!(rendered.contains(" = help: wrap the returned value with (ok ...) or (err ...)\n"));
+}
+
 #[test]
 fn test_union_type_error() {
     let snippet = "(hash160 true)";
@@ -267,3 +302,21 @@ fn test_write_attempt_in_readonly() {
     let err = mem_type_check(snippet).unwrap_err();
     assert!(format!("{}", err.diagnostic).contains("expecting read-only statements, detected a writing operation"));
 }
+
+#[test]
+fn test_deserialize_analysis_without_newer_fields() {
+    let snippet = "(define-read-only (get-one) 1)";
+    let (_, mut contract_analysis) = mem_type_check(snippet).unwrap();
+    assert!(contract_analysis.function_cost_bounds.get("get-one").is_some());
+    contract_analysis.dependencies.insert(QualifiedContractIdentifier::local("other").unwrap());
+
+    // analyses stored by older nodes do not have the newer fields.
+    let mut json: serde_json::Value = serde_json::from_str(&contract_analysis.serialize()).unwrap();
+    json.as_object_mut().unwrap().remove("function_cost_bounds");
+    json.as_object_mut().unwrap().remove("dependencies");
+
+    let stored = ContractAnalysis::deserialize(&json.to_string());
+    assert!(stored.function_cost_bounds.is_empty());
+    assert!(stored.dependencies.is_empty());
+    assert_eq!(stored.public_function_types, contract_analysis.public_function_types);
+}
@@ -1,3 +1,4 @@
+use vm::ClarityVersion;
 use vm::ast::{parse, build_ast};
 use vm::ast::errors::ParseErrors;
 use vm::analysis::errors::CheckErrors;
@@ -269,7 +270,7 @@ fn test_define_map_storing_trait_references() {
 
     let dispatching_contract_id = QualifiedContractIdentifier::local("dispatching-contract").unwrap();
 
-    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut ()).unwrap_err();
+    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut (), ClarityVersion::Clarity1).unwrap_err();
 
     match err.err {
         ParseErrors::TraitReferenceNotAllowed => {},
@@ -289,7 +290,7 @@ fn test_cycle_in_traits_1_contract() {
 
     let dispatching_contract_id = QualifiedContractIdentifier::local("dispatching-contract").unwrap();
 
-    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut ()).unwrap_err();
+    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut (), ClarityVersion::Clarity1).unwrap_err();
     match err.err {
         ParseErrors::CircularReference(_) => {},
         _ => {
@@ -448,7 +449,7 @@ fn test_dynamic_dispatch_collision_trait() {
     let dispatching_contract_id = QualifiedContractIdentifier::local("dispatching-contract").unwrap();
 
     let _contract_defining_trait = parse(&contract_defining_trait_id, contract_defining_trait_src).unwrap();
-    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut ()).unwrap_err();
+    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut (), ClarityVersion::Clarity1).unwrap_err();
     match err.err {
         ParseErrors::NameAlreadyUsed(_) => {},
         _ => {
@@ -469,7 +470,7 @@ fn test_dynamic_dispatch_collision_defined_trait() {
 
     let dispatching_contract_id = QualifiedContractIdentifier::local("dispatching-contract").unwrap();
 
-    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut ()).unwrap_err();
+    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut (), ClarityVersion::Clarity1).unwrap_err();
     match err.err {
         ParseErrors::NameAlreadyUsed(_) => {},
         _ => {
@@ -495,7 +496,7 @@ fn test_dynamic_dispatch_collision_imported_trait() {
     let dispatching_contract_id = QualifiedContractIdentifier::local("dispatching-contract").unwrap();
 
     let _contract_defining_trait = parse(&contract_defining_trait_id, contract_defining_trait_src).unwrap();
-    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut ()).unwrap_err();
+    let err = build_ast(&dispatching_contract_id, dispatching_contract_src, &mut (), ClarityVersion::Clarity1).unwrap_err();
     match err.err {
         ParseErrors::NameAlreadyUsed(_) => {},
         _ => {
@@ -1049,3 +1050,71 @@ fn test_contract_of_wrong_type() {
         }
     }
 }
+
+#[test]
+fn test_implements_trait() {
+    let contract_defining_trait =
+        "(define-trait trait-1 (
+            (get-1 (uint) (response uint uint))))";
+    let checking_contract =
+        "(use-trait trait-2 .defun.trait-1)
+        (define-read-only (check-alias (contract principal))
+            (implements-trait? contract <trait-2>))
+        (define-read-only (check-identifier (contract principal))
+            (implements-trait? contract .defun.trait-1))";
+    let checking_contract_not_trait =
+        "(define-read-only (check (contract principal))
+            (implements-trait? contract .defun))";
+    let checking_contract_int =
+        "(use-trait trait-2 .defun.trait-1)
+        (define-read-only (check (contract int))
+            (implements-trait? contract <trait-2>))";
+    let checking_contract_unknown =
+        "(define-read-only (check (contract principal))
+            (implements-trait? contract .defun.trait-2))";
+    let def_contract_id = QualifiedContractIdentifier::local("defun").unwrap();
+    let check_contract_id = QualifiedContractIdentifier::local("check").unwrap();
+    let mut c_trait = parse(&def_contract_id, contract_defining_trait).unwrap();
+    let mut c_good = parse(&check_contract_id, checking_contract).unwrap();
+    let mut c_not_trait = parse(&check_contract_id, checking_contract_not_trait).unwrap();
+    let mut c_int = parse(&check_contract_id, checking_contract_int).unwrap();
+    let mut c_unknown = parse(&check_contract_id, checking_contract_unknown).unwrap();
+    let mut marf = MemoryBackingStore::new();
+    let mut db = marf.as_analysis_db();
+
+    db.execute(|db| {
+        db.test_insert_contract_hash(&def_contract_id);
+        type_check(&def_contract_id, &mut c_trait, db, true).unwrap();
+        type_check(&check_contract_id, &mut c_good, db, false)
+    }).unwrap();
+
+    let err_not_trait = db.execute(|db| {
+        type_check(&check_contract_id, &mut c_not_trait, db, false)
+    }).unwrap_err();
+    match err_not_trait.err {
+        CheckErrors::ExpectedTraitIdentifier => {},
+        _ => {
+            panic!("{:?}", err_not_trait)
+        }
+    }
+
+    let err_int = db.execute(|db| {
+        type_check(&check_contract_id, &mut c_int, db, false)
+    }).unwrap_err();
+    match err_int.err {
+        CheckErrors::TypeError(_, _) => {},
+        _ => {
+            panic!("{:?}", err_int)
+        }
+    }
+
+    let err_unknown = db.execute(|db| {
+        type_check(&check_contract_id, &mut c_unknown, db, false)
+    }).unwrap_err();
+    match err_unknown.err {
+        CheckErrors::TraitReferenceUnknown(_) => {},
+        _ => {
+            panic!("{:?}", err_unknown)
+        }
+    }
+}
@@ -151,7 +151,18 @@ pub fn check_special_concat(checker: &mut TypeChecker, args: &[SymbolicExpressio
                 return Err(CheckErrors::TypeError(rhs_type.clone(), TypeSignature::max_buffer()).into());
             }
         },
-        _ => Err(CheckErrors::ExpectedListOrBuffer(lhs_type.clone()).into())
+        TypeSignature::StringType(lhs_subtype) => {
+            match rhs_type {
+                TypeSignature::StringType(ref rhs_subtype) if lhs_subtype.is_same_subtype(rhs_subtype) => {
+                    let size: u32 = lhs_subtype.get_max_len().checked_add(rhs_subtype.get_max_len())
+                        .ok_or(CheckErrors::MaxLengthOverflow)?;
+                    let return_type = TypeSignature::StringType(lhs_subtype.with_max_len(size)?);
+                    return Ok(return_type);
+                },
+                _ => return Err(CheckErrors::TypeError(TypeSignature::StringType(lhs_subtype), rhs_type.clone()).into())
+            }
+        },
+        _ => Err(CheckErrors::ExpectedSequence(lhs_type.clone()).into())
     }
 }
 
@@ -174,7 +185,37 @@ pub fn check_special_append(checker: &mut TypeChecker, args: &[SymbolicExpressio
             let return_type = TypeSignature::list_of(list_entry_type, new_len)?;
             return Ok(return_type);
         },
-        _ => Err(CheckErrors::ExpectedListApplication.into())
+        TypeSignature::BufferType(lhs_buff_len) => {
+            // buffers may only be extended by a single byte
+            let rhs_type = checker.type_check(&args[1], context)?;
+            let element_type = TypeSignature::min_buffer();
+
+            analysis_typecheck_cost(checker, &element_type, &rhs_type)?;
+
+            if !element_type.admits_type(&rhs_type) {
+                return Err(CheckErrors::TypeError(element_type, rhs_type).into());
+            }
+            let new_len = u32::from(lhs_buff_len).checked_add(1)
+                .ok_or(CheckErrors::MaxLengthOverflow)?;
+            let return_type = TypeSignature::BufferType(new_len.try_into()?);
+            return Ok(return_type);
+        },
+        TypeSignature::StringType(lhs_subtype) => {
+            // strings may only be extended by a single character of the same subtype
+            let rhs_type = checker.type_check(&args[1], context)?;
+            let element_type = TypeSignature::StringType(lhs_subtype.with_max_len(1)?);
+
+            analysis_typecheck_cost(checker, &element_type, &rhs_type)?;
+
+            if !element_type.admits_type(&rhs_type) {
+                return Err(CheckErrors::TypeError(element_type, rhs_type).into());
+            }
+            let new_len = lhs_subtype.get_max_len().checked_add(1)
+                .ok_or(CheckErrors::MaxLengthOverflow)?;
+            let return_type = TypeSignature::StringType(lhs_subtype.with_max_len(new_len)?);
+            return Ok(return_type);
+        },
+        _ => Err(CheckErrors::ExpectedSequence(lhs_type).into())
     }
 }
 
@@ -206,7 +247,10 @@ pub fn check_special_as_max_len(checker: &mut TypeChecker, args: &[SymbolicExpre
         TypeSignature::BufferType(_) => {
             Ok(TypeSignature::OptionalType(Box::new(TypeSignature::BufferType(BufferLength::try_from(expected_len).unwrap()))))
         },
-        _ => Err(CheckErrors::ExpectedListOrBuffer(iterable).into())
+        TypeSignature::StringType(subtype) => {
+            Ok(TypeSignature::OptionalType(Box::new(TypeSignature::StringType(subtype.with_max_len(expected_len)?))))
+        },
+        _ => Err(CheckErrors::ExpectedSequence(iterable).into())
     }
 }
 
@@ -217,9 +261,75 @@ pub fn check_special_len(checker: &mut TypeChecker, args: &[SymbolicExpression],
     runtime_cost!(cost_functions::ANALYSIS_ITERABLE_FUNC, checker, 1)?;
 
     match collection_type {
-        TypeSignature::ListType(_) | TypeSignature::BufferType(_) => Ok(()),
-        _ => Err(CheckErrors::ExpectedListOrBuffer(collection_type.clone()))
+        TypeSignature::ListType(_) | TypeSignature::BufferType(_) | TypeSignature::StringType(_) => Ok(()),
+        _ => Err(CheckErrors::ExpectedSequence(collection_type.clone()))
     }?;
 
     Ok(TypeSignature::UIntType)
 }
+
+pub fn check_special_element_at(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+
+    runtime_cost!(cost_functions::ANALYSIS_ITERABLE_FUNC, checker, 1)?;
+
+    let sequence_type = checker.type_check(&args[0], context)?;
+    checker.type_check_expects(&args[1], context, &TypeSignature::UIntType)?;
+
+    let element_type = match sequence_type {
+        TypeSignature::ListType(list) => list.destruct().0,
+        TypeSignature::BufferType(_) => TypeSignature::min_buffer(),
+        TypeSignature::StringType(subtype) => TypeSignature::StringType(subtype.with_max_len(1)?),
+        _ => return Err(CheckErrors::ExpectedSequence(sequence_type).into())
+    };
+
+    Ok(TypeSignature::OptionalType(Box::new(element_type)))
+}
+
+pub fn check_special_index_of(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+
+    runtime_cost!(cost_functions::ANALYSIS_ITERABLE_FUNC, checker, 1)?;
+
+    let sequence_type = checker.type_check(&args[0], context)?;
+    let item_type = checker.type_check(&args[1], context)?;
+
+    // buffers and strings are searched for a single element, while list
+    //   entries only need to be comparable with the item (as in is-eq).
+    let element_type = match sequence_type {
+        TypeSignature::ListType(list) => {
+            let (entry_type, _) = list.destruct();
+            analysis_typecheck_cost(checker, &entry_type, &item_type)?;
+            TypeSignature::least_supertype(&entry_type, &item_type)?;
+            None
+        },
+        TypeSignature::BufferType(_) => Some(TypeSignature::min_buffer()),
+        TypeSignature::StringType(subtype) => Some(TypeSignature::StringType(subtype.with_max_len(1)?)),
+        _ => return Err(CheckErrors::ExpectedSequence(sequence_type).into())
+    };
+
+    if let Some(element_type) = element_type {
+        analysis_typecheck_cost(checker, &element_type, &item_type)?;
+        if !element_type.admits_type(&item_type) {
+            return Err(CheckErrors::TypeError(element_type, item_type).into());
+        }
+    }
+
+    Ok(TypeSignature::OptionalType(Box::new(TypeSignature::UIntType)))
+}
+
+pub fn check_special_slice(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(3, args)?;
+
+    runtime_cost!(cost_functions::ANALYSIS_ITERABLE_FUNC, checker, 1)?;
+
+    let sequence_type = checker.type_check(&args[0], context)?;
+    checker.type_check_expects(&args[1], context, &TypeSignature::UIntType)?;
+    checker.type_check_expects(&args[2], context, &TypeSignature::UIntType)?;
+
+    match sequence_type {
+        TypeSignature::ListType(_) | TypeSignature::BufferType(_) | TypeSignature::StringType(_) =>
+            Ok(TypeSignature::OptionalType(Box::new(sequence_type))),
+        _ => Err(CheckErrors::ExpectedSequence(sequence_type).into())
+    }
+}
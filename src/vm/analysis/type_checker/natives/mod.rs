@@ -1,11 +1,12 @@
 use vm::errors::{Error as InterpError, RuntimeErrorType};
 use vm::functions::{NativeFunctions, handle_binding_list};
 use vm::{ClarityName, SymbolicExpression, SymbolicExpressionType};
-use vm::types::{BUFF_32, BUFF_20, BUFF_64, TypeSignature, TupleTypeSignature,
+use vm::representations::TraitDefinition;
+use vm::types::{BUFF_65, BUFF_64, BUFF_33, BUFF_32, BUFF_20, BUFF_16, TypeSignature, TupleTypeSignature,
                 BlockInfoProperty, Value, PrincipalData, MAX_VALUE_SIZE, FunctionArg,
                 FunctionType, FixedFunction, FunctionSignature};
 use super::{TypeChecker, TypingContext, TypeResult, no_type, check_argument_count,
-            check_arguments_at_least};
+            check_arguments_at_least, trait_type_size};
 use vm::analysis::errors::{CheckError, CheckErrors, CheckResult};
 use std::convert::TryFrom;
 
@@ -195,6 +196,21 @@ fn check_special_equals(checker: &mut TypeChecker, args: &[SymbolicExpression],
     Ok(TypeSignature::BoolType)
 }
 
+fn check_special_bit_shift(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+
+    let input_type = checker.type_check(&args[0], context)?;
+    analysis_typecheck_cost(checker, &TypeSignature::IntType, &input_type)?;
+    if input_type != TypeSignature::IntType && input_type != TypeSignature::UIntType {
+        return Err(CheckErrors::UnionTypeError(vec![TypeSignature::IntType, TypeSignature::UIntType],
+                                               input_type).into())
+    }
+
+    checker.type_check_expects(&args[1], context, &TypeSignature::UIntType)?;
+
+    Ok(input_type)
+}
+
 fn check_special_if(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_argument_count(3, args)?;
 
@@ -288,6 +304,36 @@ fn check_contract_of(checker: &mut TypeChecker, args: &[SymbolicExpression], con
     Ok(TypeSignature::PrincipalType)
 }
 
+fn check_implements_trait(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
+    check_argument_count(2, args)?;
+
+    checker.type_check_expects(&args[0], context, &TypeSignature::PrincipalType)?;
+
+    match &args[1].expr {
+        SymbolicExpressionType::TraitReference(name, TraitDefinition::Defined(trait_identifier)) |
+        SymbolicExpressionType::TraitReference(name, TraitDefinition::Imported(trait_identifier)) => {
+            // defined and imported traits are both bound in the contract context, by their own name.
+            checker.contract_context.get_trait(&trait_identifier.name)
+                .ok_or_else(|| CheckErrors::TraitReferenceUnknown(name.to_string()))?;
+        },
+        SymbolicExpressionType::Field(trait_identifier) => {
+            let trait_sig = checker.db.get_defined_trait(&trait_identifier.contract_identifier, &trait_identifier.name)?;
+            match trait_sig {
+                Some(trait_sig) => {
+                    runtime_cost!(cost_functions::ANALYSIS_USE_TRAIT_ENTRY, checker, trait_type_size(&trait_sig)?)?;
+                },
+                None => {
+                    runtime_cost!(cost_functions::ANALYSIS_USE_TRAIT_ENTRY, checker, 1)?;
+                    return Err(CheckErrors::TraitReferenceUnknown(trait_identifier.to_string()).into())
+                }
+            }
+        },
+        _ => return Err(CheckError::new(CheckErrors::ExpectedTraitIdentifier))
+    };
+
+    Ok(TypeSignature::BoolType)
+}
+
 fn check_get_block_info(checker: &mut TypeChecker, args: &[SymbolicExpression], context: &TypingContext) -> TypeResult {
     check_arguments_at_least(2, args)?;
 
@@ -321,6 +367,11 @@ impl TypedNativeFunction {
                 Simple(SimpleNativeFunction(FunctionType::ArithmeticComparison)),
             Modulo | Power | BitwiseXOR =>
                 Simple(SimpleNativeFunction(FunctionType::ArithmeticBinary)),
+            BitwiseAnd | BitwiseOr =>
+                Simple(SimpleNativeFunction(FunctionType::ArithmeticVariadic)),
+            BitwiseNot | Sqrti | Log2 =>
+                Simple(SimpleNativeFunction(FunctionType::ArithmeticUnary)),
+            BitwiseLShift | BitwiseRShift => Special(SpecialNativeFunction(&check_special_bit_shift)),
             And | Or =>
                 Simple(SimpleNativeFunction(FunctionType::Variadic(TypeSignature::BoolType,
                                                                    TypeSignature::BoolType))),
@@ -336,6 +387,23 @@ impl TypedNativeFunction {
                                                 ClarityName::try_from("value".to_owned())
                                                 .expect("FAIL: ClarityName failed to accept default arg name"))],
                     returns: TypeSignature::IntType }))),
+            BuffToIntLe | BuffToIntBe =>
+                Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
+                    args: vec![FunctionArg::new(BUFF_16.clone(),
+                                                ClarityName::try_from("value".to_owned())
+                                                .expect("FAIL: ClarityName failed to accept default arg name"))],
+                    returns: TypeSignature::IntType }))),
+            BuffToUIntLe | BuffToUIntBe =>
+                Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
+                    args: vec![FunctionArg::new(BUFF_16.clone(),
+                                                ClarityName::try_from("value".to_owned())
+                                                .expect("FAIL: ClarityName failed to accept default arg name"))],
+                    returns: TypeSignature::UIntType }))),
+            IntToBuffLe | IntToBuffBe =>
+                Simple(SimpleNativeFunction(FunctionType::UnionArgs(
+                    vec![TypeSignature::IntType,
+                         TypeSignature::UIntType],
+                    BUFF_16.clone()))),
             Not =>
                 Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
                     args: vec![FunctionArg::new(TypeSignature::BoolType, ClarityName::try_from("value".to_owned())
@@ -371,6 +439,39 @@ impl TypedNativeFunction {
                          TypeSignature::UIntType,
                          TypeSignature::IntType],
                     BUFF_32.clone()))),
+            Secp256k1Recover =>
+                Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
+                    args: vec![
+                        FunctionArg::new(BUFF_32.clone(),
+                                         ClarityName::try_from("message-hash".to_owned())
+                                         .expect("FAIL: ClarityName failed to accept default arg name")),
+                        FunctionArg::new(BUFF_65.clone(),
+                                         ClarityName::try_from("signature".to_owned())
+                                         .expect("FAIL: ClarityName failed to accept default arg name")),
+                    ],
+                    returns: TypeSignature::new_response(BUFF_33.clone(), TypeSignature::UIntType).unwrap() }))),
+            Secp256k1Verify =>
+                Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
+                    args: vec![
+                        FunctionArg::new(BUFF_32.clone(),
+                                         ClarityName::try_from("message-hash".to_owned())
+                                         .expect("FAIL: ClarityName failed to accept default arg name")),
+                        FunctionArg::new(BUFF_65.clone(),
+                                         ClarityName::try_from("signature".to_owned())
+                                         .expect("FAIL: ClarityName failed to accept default arg name")),
+                        FunctionArg::new(BUFF_33.clone(),
+                                         ClarityName::try_from("public-key".to_owned())
+                                         .expect("FAIL: ClarityName failed to accept default arg name")),
+                    ],
+                    returns: TypeSignature::BoolType }))),
+            PrincipalOf =>
+                Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
+                    args: vec![
+                        FunctionArg::new(BUFF_33.clone(),
+                                         ClarityName::try_from("public-key".to_owned())
+                                         .expect("FAIL: ClarityName failed to accept default arg name")),
+                    ],
+                    returns: TypeSignature::new_response(TypeSignature::PrincipalType, TypeSignature::UIntType).unwrap() }))),
             GetStxBalance =>
                 Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
                     args: vec![
@@ -410,6 +511,9 @@ impl TypedNativeFunction {
             TransferAsset => Special(SpecialNativeFunction(&assets::check_special_transfer_asset)),
             MintAsset => Special(SpecialNativeFunction(&assets::check_special_mint_asset)),
             MintToken => Special(SpecialNativeFunction(&assets::check_special_mint_token)),
+            GetTokenSupply => Special(SpecialNativeFunction(&assets::check_special_get_token_supply)),
+            BurnAsset => Special(SpecialNativeFunction(&assets::check_special_burn_asset)),
+            BurnToken => Special(SpecialNativeFunction(&assets::check_special_burn_token)),
             Equals => Special(SpecialNativeFunction(&check_special_equals)),
             If => Special(SpecialNativeFunction(&check_special_if)),
             Let => Special(SpecialNativeFunction(&check_special_let)),
@@ -422,6 +526,9 @@ impl TypedNativeFunction {
             Concat => Special(SpecialNativeFunction(&iterables::check_special_concat)),
             AsMaxLen => Special(SpecialNativeFunction(&iterables::check_special_as_max_len)),
             Len => Special(SpecialNativeFunction(&iterables::check_special_len)),
+            ElementAt => Special(SpecialNativeFunction(&iterables::check_special_element_at)),
+            IndexOf => Special(SpecialNativeFunction(&iterables::check_special_index_of)),
+            Slice => Special(SpecialNativeFunction(&iterables::check_special_slice)),
             ListCons => Special(SpecialNativeFunction(&check_special_list_cons)),
             FetchEntry => Special(SpecialNativeFunction(&maps::check_special_fetch_entry)),
             SetEntry => Special(SpecialNativeFunction(&maps::check_special_set_entry)),
@@ -434,6 +541,7 @@ impl TypedNativeFunction {
             AsContract => Special(SpecialNativeFunction(&check_special_as_contract)),
             ContractCall => Special(SpecialNativeFunction(&check_contract_call)),
             ContractOf => Special(SpecialNativeFunction(&check_contract_of)),
+            ImplementsTrait => Special(SpecialNativeFunction(&check_implements_trait)),
             GetBlockInfo => Special(SpecialNativeFunction(&check_get_block_info)),
             ConsSome => Special(SpecialNativeFunction(&options::check_special_some)),
             ConsOkay => Special(SpecialNativeFunction(&options::check_special_okay)),
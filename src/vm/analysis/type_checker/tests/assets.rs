@@ -126,6 +126,15 @@ fn test_bad_asset_usage() {
                        "(ft-transfer? stackaroos u2 u100 tx-sender)",
                        "(ft-transfer? stackaroos true tx-sender tx-sender)",
                        "(ft-transfer? stackaroos u2 tx-sender u100)",
+                       "(ft-burn? stackoos u1 tx-sender)",
+                       "(ft-burn? u1234 u1 tx-sender)",
+                       "(ft-burn? stackaroos u2 u100)",
+                       "(ft-burn? stackaroos true tx-sender)",
+                       "(nft-burn? stackoos 0x61 tx-sender)",
+                       "(nft-burn? stacka-nfts u2 tx-sender)",
+                       "(nft-burn? stacka-nfts 0x61 u2)",
+                       "(ft-get-supply stackoos)",
+                       "(ft-get-supply u1234)",
                        "(define-fungible-token stackaroos true)",
                        "(define-non-fungible-token stackaroos integer)",
                        "(ft-mint? stackaroos 100 tx-sender)",
@@ -176,6 +185,19 @@ fn test_bad_asset_usage() {
                                TypeSignature::BoolType),
         CheckErrors::TypeError(TypeSignature::PrincipalType,
                                TypeSignature::UIntType),
+        CheckErrors::NoSuchFT("stackoos".to_string()),
+        CheckErrors::BadTokenName,
+        CheckErrors::TypeError(TypeSignature::PrincipalType,
+                               TypeSignature::UIntType),
+        CheckErrors::TypeError(TypeSignature::UIntType,
+                               TypeSignature::BoolType),
+        CheckErrors::NoSuchNFT("stackoos".to_string()),
+        CheckErrors::TypeError(buff_type(10),
+                               TypeSignature::UIntType),
+        CheckErrors::TypeError(TypeSignature::PrincipalType,
+                               TypeSignature::UIntType),
+        CheckErrors::NoSuchFT("stackoos".to_string()),
+        CheckErrors::BadTokenName,
         CheckErrors::TypeError(TypeSignature::UIntType,
                                TypeSignature::BoolType),
         CheckErrors::DefineNFTBadSignature.into(),
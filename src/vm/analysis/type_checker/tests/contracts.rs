@@ -252,37 +252,44 @@ fn test_names_tokens_contracts_interface() {
             { "name": "pub-f01",
                 "access": "public",
                 "args": [],
-                "outputs": { "type": { "response": { "ok": "int128", "error": "none" } } }
+                "outputs": { "type": { "response": { "ok": "int128", "error": "none" } } },
+                "cost_bound": { "bounded": { "write_length": 0, "write_count": 0, "read_length": 0, "read_count": 0, "runtime": 3 } }
             },
             { "name": "pub-f02",
                 "access": "public",
                 "args": [],
-                "outputs": { "type": { "response": { "ok": "bool", "error": "none" } } }
+                "outputs": { "type": { "response": { "ok": "bool", "error": "none" } } },
+                "cost_bound": { "bounded": { "write_length": 0, "write_count": 0, "read_length": 0, "read_count": 0, "runtime": 3 } }
             },
             { "name": "pub-f03",
                 "access": "public",
                 "args": [],
-                "outputs": { "type": { "response": { "ok": "none", "error": "bool" } } }
+                "outputs": { "type": { "response": { "ok": "none", "error": "bool" } } },
+                "cost_bound": { "bounded": { "write_length": 0, "write_count": 0, "read_length": 0, "read_count": 0, "runtime": 3 } }
             },
             { "name": "pub-f04",
                 "access": "public",
                 "args": [],
-                "outputs": { "type": { "response": { "ok": "int128", "error": "int128" } } }
+                "outputs": { "type": { "response": { "ok": "int128", "error": "int128" } } },
+                "cost_bound": { "bounded": { "write_length": 0, "write_count": 0, "read_length": 0, "read_count": 0, "runtime": 5 } }
             },
             { "name": "pub-f05",
                 "access": "public",
                 "args": [{ "name": "a1", "type": "int128" }],
-                "outputs": { "type": { "response": { "ok": "bool", "error": "none" } } }
+                "outputs": { "type": { "response": { "ok": "bool", "error": "none" } } },
+                "cost_bound": { "bounded": { "write_length": 0, "write_count": 0, "read_length": 0, "read_count": 0, "runtime": 21 } }
             },
             { "name": "ro-f01",
                 "access": "read_only",
                 "args": [],
-                "outputs": { "type": "int128" }
+                "outputs": { "type": "int128" },
+                "cost_bound": { "bounded": { "write_length": 0, "write_count": 0, "read_length": 0, "read_count": 0, "runtime": 1 } }
             },
             { "name": "ro-f02",
                 "access": "read_only",
                 "args": [{ "name": "a1", "type": "int128" }],
-                "outputs": { "type": "int128" }
+                "outputs": { "type": "int128" },
+                "cost_bound": { "bounded": { "write_length": 0, "write_count": 0, "read_length": 0, "read_count": 0, "runtime": 19 } }
             }
         ],
         "maps": [
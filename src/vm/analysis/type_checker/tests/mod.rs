@@ -1,15 +1,16 @@
+use vm::ClarityVersion;
 use vm::ast::{parse, build_ast};
 use vm::ast::errors::ParseErrors;
 use vm::representations::SymbolicExpression;
 use vm::analysis::type_checker::{TypeResult, TypeChecker, TypingContext};
 use vm::analysis::{AnalysisDatabase};
 use vm::analysis::errors::CheckErrors;
-use vm::analysis::mem_type_check;
+use vm::analysis::{mem_type_check, mem_type_check_with_version};
 use vm::analysis::type_check;
 use vm::analysis::types::ContractAnalysis;
 use vm::contexts::{OwnedEnvironment};
 use vm::types::{Value, PrincipalData, TypeSignature, FunctionType, FixedFunction, BUFF_32, BUFF_64,
-                QualifiedContractIdentifier};
+                QualifiedContractIdentifier, StringSubtype};
 
 use vm::database::MemoryBackingStore;
 use vm::types::TypeSignature::{IntType, BoolType, BufferType, UIntType, PrincipalType};
@@ -22,10 +23,18 @@ fn type_check_helper(exp: &str) -> TypeResult {
     mem_type_check(exp).map(|(type_sig_opt, _)| type_sig_opt.unwrap())
 }
 
+fn type_check_clarity2_helper(exp: &str) -> TypeResult {
+    mem_type_check_with_version(exp, ClarityVersion::Clarity2).map(|(type_sig_opt, _)| type_sig_opt.unwrap())
+}
+
 fn buff_type(size: u32) -> TypeSignature {
     TypeSignature::BufferType(size.try_into().unwrap()).into()
 }
 
+fn ascii_type(size: u32) -> TypeSignature {
+    TypeSignature::StringType(StringSubtype::ASCII(size.try_into().unwrap())).into()
+}
+
 #[test]
 fn test_get_block_info(){
     let good = ["(get-block-info? time u1)",
@@ -92,7 +101,7 @@ fn test_define_trait(){
 
     let contract_identifier = QualifiedContractIdentifier::transient();
     for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
-        let res = build_ast(&contract_identifier, bad_test, &mut ()).unwrap_err();
+        let res = build_ast(&contract_identifier, bad_test, &mut (), ClarityVersion::Clarity1).unwrap_err();
         assert_eq!(expected, &res.err);
     }
 }
@@ -112,7 +121,7 @@ fn test_use_trait(){
 
     let contract_identifier = QualifiedContractIdentifier::transient();
     for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
-        let res = build_ast(&contract_identifier, bad_test, &mut ()).unwrap_err();
+        let res = build_ast(&contract_identifier, bad_test, &mut (), ClarityVersion::Clarity1).unwrap_err();
         assert_eq!(expected, &res.err);
     }
 }
@@ -128,7 +137,7 @@ fn test_impl_trait(){
     
     let contract_identifier = QualifiedContractIdentifier::transient();
     for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
-        let res = build_ast(&contract_identifier, bad_test, &mut ()).unwrap_err();
+        let res = build_ast(&contract_identifier, bad_test, &mut (), ClarityVersion::Clarity1).unwrap_err();
         assert_eq!(expected, &res.err);
     }
 }
@@ -322,7 +331,7 @@ fn test_trait_reference_unknown(){
 
     let contract_identifier = QualifiedContractIdentifier::transient();
     for (bad_test, expected) in bad.iter() {
-        let res = build_ast(&contract_identifier, bad_test, &mut ()).unwrap_err();
+        let res = build_ast(&contract_identifier, bad_test, &mut (), ClarityVersion::Clarity1).unwrap_err();
         assert_eq!(expected, &res.err);
     }
 }
@@ -366,6 +375,40 @@ fn test_simple_arithmetic_checks() {
     }
 }
 
+#[test]
+fn test_bitwise_and_integer_math_checks() {
+    let good = ["(bit-and 1 2 3)",
+                "(bit-or u1 u2)",
+                "(bit-not u1)",
+                "(bit-shift-left 1 u2)",
+                "(bit-shift-right u8 (+ u1 u1))",
+                "(sqrti u4)",
+                "(log2 (bit-or 4 1))"];
+    let expected = ["int", "uint", "uint", "int", "uint", "uint", "int"];
+
+    let bad = ["(bit-and 1 u2)",
+               "(bit-not true)",
+               "(sqrti 1 2)",
+               "(bit-shift-left 1 2)",
+               "(bit-shift-right true u1)",
+               "(bit-shift-left 1)"];
+    let bad_expected = [
+        CheckErrors::TypeError(IntType, UIntType),
+        CheckErrors::UnionTypeError(vec![IntType, UIntType], BoolType),
+        CheckErrors::IncorrectArgumentCount(1, 2),
+        CheckErrors::TypeError(UIntType, IntType),
+        CheckErrors::UnionTypeError(vec![IntType, UIntType], BoolType),
+        CheckErrors::IncorrectArgumentCount(2, 1)];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
+    }
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_simple_hash_checks() {
     let good = ["(hash160 u1)",
@@ -404,6 +447,32 @@ fn test_simple_hash_checks() {
     }
 }
 
+#[test]
+fn test_secp256k1_checks() {
+    let good = ["(secp256k1-recover? (sha256 u1) 0x01)",
+                "(secp256k1-verify (sha256 u1) 0x01 0x02)",
+                "(principal-of? 0x02)"];
+    let expected = ["(response (buff 33) uint)", "bool", "(response principal uint)"];
+
+    let bad = ["(secp256k1-recover? (sha512 u1) 0x01)",
+               "(secp256k1-verify (sha256 u1) 0x01 (sha512 u1))",
+               "(principal-of? u1)",
+               "(secp256k1-verify (sha256 u1) 0x01)"];
+    let bad_expected = [
+        CheckErrors::TypeError(buff_type(32), buff_type(64)),
+        CheckErrors::TypeError(buff_type(33), buff_type(64)),
+        CheckErrors::TypeError(buff_type(33), UIntType),
+        CheckErrors::IncorrectArgumentCount(3, 2)];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
+    }
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_simple_ifs() {
     let good = ["(if (> 1 2) (+ 1 2 3) (- 1 2))",
@@ -564,7 +633,7 @@ fn test_lists() {
         CheckErrors::IncorrectArgumentCount(2, 1),
         CheckErrors::UnionTypeError(vec![IntType, UIntType], BoolType),
         CheckErrors::ExpectedListOrBuffer(UIntType),
-        CheckErrors::ExpectedListOrBuffer(IntType)];
+        CheckErrors::ExpectedSequence(IntType)];
 
     for (good_test, expected) in good.iter().zip(expected.iter()) {
         assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
@@ -615,7 +684,7 @@ fn test_buff() {
         CheckErrors::IncorrectArgumentCount(2, 1),
         CheckErrors::UnionTypeError(vec![IntType, UIntType], BoolType),
         CheckErrors::ExpectedListOrBuffer(UIntType),
-        CheckErrors::ExpectedListOrBuffer(IntType)];
+        CheckErrors::ExpectedSequence(IntType)];
 
     for (good_test, expected) in good.iter().zip(expected.iter()) {
         assert_eq!(expected, &format!("{}", type_check_helper(&good_test).unwrap()));
@@ -631,12 +700,12 @@ fn test_buff_fold() {
     let good = [
         "(define-private (get-len (x (buff 1)) (acc uint)) (+ acc u1))
         (fold get-len \"101010\" u0)",
-        "(define-private (slice (x (buff 1)) (acc (tuple (limit uint) (cursor uint) (data (buff 10)))))
+        "(define-private (take (x (buff 1)) (acc (tuple (limit uint) (cursor uint) (data (buff 10)))))
             (if (< (get cursor acc) (get limit acc))
                 (let ((data (default-to (get data acc) (as-max-len? (concat (get data acc) x) u10))))
                     (tuple (limit (get limit acc)) (cursor (+ u1 (get cursor acc))) (data data)))
                 acc))
-        (fold slice \"0123456789\" (tuple (limit u5) (cursor u0) (data \"\")))"];
+        (fold take \"0123456789\" (tuple (limit u5) (cursor u0) (data \"\")))"];
     let expected = ["uint", "(tuple (cursor uint) (data (buff 10)) (limit uint))"];
 
     for (good_test, expected) in good.iter().zip(expected.iter()) {
@@ -764,6 +833,114 @@ fn test_buff_concat() {
     }
 }
 
+#[test]
+fn test_string_natives() {
+    let good = [
+        "(len \"blockstack\")",
+        "(len u\"stacks \\u{1F600}\")",
+        "(concat \"block\" \"stack\")",
+        "(concat u\"block\" u\"st\\u{e4}ck\")",
+        "(append \"blockstac\" \"k\")",
+        "(append u\"blockstac\" u\"\\u{1F600}\")",
+        "(as-max-len? \"blockstack\" u12)",
+        "(as-max-len? u\"blockstack\" u4)",
+        "(if true \"blockstack\" \"block\")",
+        "(if true u\"block\" u\"blockstack\")"];
+    let expected = [
+        "uint",
+        "uint",
+        "(string-ascii 10)",
+        "(string-utf8 10)",
+        "(string-ascii 10)",
+        "(string-utf8 10)",
+        "(optional (string-ascii 12))",
+        "(optional (string-utf8 4))",
+        "(string-ascii 10)",
+        "(string-utf8 10)"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_clarity2_helper(&good_test).unwrap()));
+    }
+
+    let bad = [
+        "(concat \"block\" u\"stack\")",
+        "(concat \"block\" 0x01)",
+        "(append \"block\" \"stack\")",
+        "(append u\"block\" \"s\")",
+        "(if true \"block\" u\"block\")",
+        "(len true)"];
+    let bad_expected = [
+        CheckErrors::TypeError(ascii_type(5), "(string-utf8 5)".into()),
+        CheckErrors::TypeError(ascii_type(5), buff_type(1)),
+        CheckErrors::TypeError(ascii_type(1), ascii_type(5)),
+        CheckErrors::TypeError("(string-utf8 1)".into(), ascii_type(1)),
+        CheckErrors::IfArmsMustMatch(ascii_type(5), "(string-utf8 5)".into()),
+        CheckErrors::ExpectedSequence(BoolType)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_clarity2_helper(&bad_test).unwrap_err().err);
+    }
+}
+
+#[test]
+fn test_sequence_natives() {
+    let good = [
+        "(element-at (list 1 2 3) u1)",
+        "(element-at 0x0102 u0)",
+        "(element-at u\"blockstack\" u3)",
+        "(index-of (list 1 2 3) 2)",
+        "(index-of \"blockstack\" \"s\")",
+        "(index-of (list) 1)",
+        "(slice (list 1 2 3) u1 u2)",
+        "(slice \"blockstack\" u0 u5)",
+        "(buff-to-int-le 0x0102)",
+        "(buff-to-uint-be 0x0102)",
+        "(int-to-buff-le u1)",
+        "(int-to-buff-be -1)"];
+    let expected = [
+        "(optional int)",
+        "(optional (buff 1))",
+        "(optional (string-utf8 1))",
+        "(optional uint)",
+        "(optional uint)",
+        "(optional uint)",
+        "(optional (list 3 int))",
+        "(optional (string-ascii 10))",
+        "int",
+        "uint",
+        "(buff 16)",
+        "(buff 16)"];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(expected, &format!("{}", type_check_clarity2_helper(&good_test).unwrap()));
+    }
+
+    let bad = [
+        "(element-at (list 1 2 3) 1)",
+        "(element-at 1 u1)",
+        "(index-of (list 1 2 3) true)",
+        "(index-of 0x0102 0x0102)",
+        "(index-of \"blockstack\" u\"s\")",
+        "(slice (list 1 2 3) u1 2)",
+        "(slice true u1 u2)",
+        "(buff-to-int-le 0x0102030405060708090a0b0c0d0e0f1011)",
+        "(int-to-buff-le true)"];
+    let bad_expected = [
+        CheckErrors::TypeError(UIntType, IntType),
+        CheckErrors::ExpectedSequence(IntType),
+        CheckErrors::TypeError(IntType, BoolType),
+        CheckErrors::TypeError(buff_type(1), buff_type(2)),
+        CheckErrors::TypeError(ascii_type(1), "(string-utf8 1)".into()),
+        CheckErrors::TypeError(UIntType, IntType),
+        CheckErrors::ExpectedSequence(BoolType),
+        CheckErrors::TypeError(buff_type(16), buff_type(17)),
+        CheckErrors::UnionTypeError(vec![IntType, UIntType], BoolType)];
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_clarity2_helper(&bad_test).unwrap_err().err);
+    }
+}
+
 #[test]
 fn test_buff_filter() {
     let good = [
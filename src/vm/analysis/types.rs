@@ -28,6 +28,15 @@ pub struct ContractAnalysis {
     pub non_fungible_tokens: BTreeMap<ClarityName, TypeSignature>,
     pub defined_traits: BTreeMap<ClarityName, BTreeMap<ClarityName, FunctionSignature>>,
     pub implemented_traits: BTreeSet<TraitIdentifier>,    
+    /// the contracts this contract calls with `contract-call?`, or whose traits it uses or implements.
+    ///   Analyses stored before the dependencies were recorded have none.
+    #[serde(default)]
+    pub dependencies: BTreeSet<QualifiedContractIdentifier>,
+    /// upper bounds on the cost of the public and read-only functions, or None for the
+    ///   functions whose cost cannot be bounded statically. Analyses stored before the
+    ///   bounds were computed have none.
+    #[serde(default)]
+    pub function_cost_bounds: BTreeMap<ClarityName, Option<ExecutionCost>>,
     pub contract_interface: Option<ContractInterface>,
     #[serde(skip)]
     pub expressions: Vec<SymbolicExpression>,
@@ -52,8 +61,10 @@ impl ContractAnalysis {
             persisted_variable_types: BTreeMap::new(),
             defined_traits: BTreeMap::new(),
             implemented_traits: BTreeSet::new(),
+            dependencies: BTreeSet::new(),
             fungible_tokens: BTreeSet::new(),
             non_fungible_tokens: BTreeMap::new(),
+            function_cost_bounds: BTreeMap::new(),
             cost_track: Some(cost_track)
         }
     }
@@ -108,6 +119,12 @@ impl ContractAnalysis {
         self.implemented_traits.insert(trait_identifier);
     }
 
+    pub fn add_dependency(&mut self, contract_identifier: QualifiedContractIdentifier) {
+        if contract_identifier != self.contract_identifier {
+            self.dependencies.insert(contract_identifier);
+        }
+    }
+
     pub fn get_public_function_type(&self, name: &str) -> Option<&FunctionType> {
         self.public_function_types.get(name)
     }
@@ -1,5 +1,6 @@
 use vm::analysis::{mem_type_check as run_analysis_helper};
 use vm::ast::parser;
+use vm::ClarityVersion;
 use vm::types::QualifiedContractIdentifier;
 use vm::ast::errors::{ParseResult};
 use vm::ast::types::{ContractAST, BuildASTPass};
@@ -10,7 +11,7 @@ use vm::database::MemoryBackingStore;
 
 fn run_scoped_parsing_helper(contract: &str) -> ParseResult<ContractAST> {
     let contract_identifier = QualifiedContractIdentifier::transient();
-    let pre_expressions = parser::parse(contract)?;
+    let pre_expressions = parser::parse(contract, ClarityVersion::Clarity1)?;
     let mut contract_ast = ContractAST::new(contract_identifier.clone(), pre_expressions);
     ExpressionIdentifier::run_pre_expression_pass(&mut contract_ast)?;
     DefinitionSorter::run_pass(&mut contract_ast, &mut ())?;
@@ -24,6 +24,9 @@ pub enum ParseErrors {
     UnknownQuotedValue(String),
     FailedParsingIntValue(String),
     FailedParsingBuffer(String),
+    FailedParsingASCIIString(String),
+    FailedParsingUTF8String(String),
+    InvalidEscaping,
     FailedParsingHexValue(String, String),
     FailedParsingPrincipal(String),
     FailedParsingField(String),
@@ -44,6 +47,7 @@ pub enum ParseErrors {
     TraitReferenceUnknown(String),
     CommaSeparatorUnexpected,
     ColonSeparatorUnexpected,
+    MissingSourceSpan,
 }
 
 #[derive(Debug, PartialEq)]
@@ -136,6 +140,9 @@ impl DiagnosableError for ParseErrors {
             ParseErrors::FailedParsingHexValue(value, x) => format!("Invalid hex-string literal {}: {}", value, x),
             ParseErrors::FailedParsingPrincipal(value) => format!("Invalid principal literal: {}", value),
             ParseErrors::FailedParsingBuffer(value) => format!("Invalid buffer literal: {}", value),
+            ParseErrors::FailedParsingASCIIString(value) => format!("Invalid string-ascii literal: {}", value),
+            ParseErrors::FailedParsingUTF8String(value) => format!("Invalid string-utf8 literal: {}", value),
+            ParseErrors::InvalidEscaping => format!("Invalid escape sequence in string literal"),
             ParseErrors::FailedParsingField(value) => format!("Invalid field literal: {}", value),
             ParseErrors::FailedParsingRemainder(remainder) => format!("Failed to lex input remainder: '{}'", remainder),
             ParseErrors::ClosingParenthesisUnexpected => format!("Tried to close list which isn't open."),
@@ -155,6 +162,7 @@ impl DiagnosableError for ParseErrors {
             ParseErrors::TraitReferenceNotAllowed => format!("trait references can not be stored"),
             ParseErrors::TraitReferenceUnknown(trait_name) => format!("use of undeclared trait <{}>", trait_name),
             ParseErrors::ExpressionStackDepthTooDeep => format!("AST has too deep of an expression nesting. The maximum stack depth is {}", MAX_CALL_STACK_DEPTH),
+            ParseErrors::MissingSourceSpan => format!("Expression does not point into the source text"),
         }
     }
 
@@ -0,0 +1,383 @@
+use vm::ast::errors::{ParseError, ParseErrors, ParseResult};
+use vm::ast::parser::{lex, parse_lexed, LexItem};
+use vm::ClarityVersion;
+use vm::representations::{PreSymbolicExpression, PreSymbolicExpressionType, Span};
+
+/// Lists which fit in this many columns are printed on a single line.
+pub const MAX_LINE_WIDTH: usize = 80;
+
+const INDENT_WIDTH: usize = 2;
+
+struct Comment {
+    text: String,
+    line: u32,
+    column: u32,
+    // whether the comment follows code on its line.
+    trailing: bool
+}
+
+struct Formatter <'a> {
+    lines: Vec<&'a str>,
+    comments: Vec<Comment>,
+    next_comment: usize,
+    output: String,
+    // whether the current output line holds anything besides indentation.
+    line_open: bool,
+    // the last source line which was printed.
+    last_line: u32
+}
+
+/// Pretty-prints a Clarity program, preserving its `;;` comments.
+///
+/// The layout only depends on the parsed program: lists which fit on a line are printed
+///   flat, and longer lists are broken after their first argument, with the remaining
+///   arguments indented by two columns. Blank lines between expressions are kept, but
+///   collapsed into a single blank line.
+pub fn format_source(source: &str) -> ParseResult<String> {
+    let lexed = lex(source, ClarityVersion::latest())?;
+
+    let mut comments = Vec::new();
+    let mut code_line = 0;
+    for (item, line, column) in lexed.iter() {
+        match item {
+            LexItem::Comment(text) => {
+                comments.push(Comment { text: text.trim_end().to_string(), line: *line, column: *column,
+                                        trailing: code_line == *line });
+            },
+            LexItem::Whitespace => {},
+            _ => code_line = *line
+        }
+    }
+
+    let expressions = parse_lexed(lexed)?;
+    format_expressions(source, comments, &expressions)
+}
+
+fn format_expressions(source: &str, comments: Vec<Comment>, expressions: &[PreSymbolicExpression]) -> ParseResult<String> {
+    let lines: Vec<_> = source.lines().collect();
+    for expression in expressions.iter() {
+        check_spans(&lines, expression)?;
+    }
+
+    let mut formatter = Formatter {
+        lines,
+        comments,
+        next_comment: 0,
+        output: String::new(),
+        line_open: false,
+        last_line: 0
+    };
+
+    for expression in expressions.iter() {
+        formatter.begin_element(&expression.span, 0);
+        formatter.format(expression, 0);
+    }
+    formatter.flush_comments(u32::max_value(), u32::max_value(), 0);
+
+    let mut output = formatter.output.trim_end().to_string();
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+// Atoms are printed from their source text, so every expression must point into `lines`.
+//   Expressions only carry spans in developer-mode builds.
+fn check_spans(lines: &[&str], expression: &PreSymbolicExpression) -> ParseResult<()> {
+    let span = &expression.span;
+    if span.start_line == 0 || span.start_column == 0 {
+        return Err(ParseError::new(ParseErrors::MissingSourceSpan))
+    }
+
+    match expression.pre_expr {
+        PreSymbolicExpressionType::List(ref items) | PreSymbolicExpressionType::Tuple(ref items) => {
+            for item in items.iter() {
+                check_spans(lines, item)?;
+            }
+            Ok(())
+        },
+        _ => {
+            let column = span.start_column as usize - 1;
+            match lines.get(span.start_line as usize - 1) {
+                Some(line) if column < line.len() && line.is_char_boundary(column) => Ok(()),
+                _ => Err(ParseError::new(ParseErrors::MissingSourceSpan))
+            }
+        }
+    }
+}
+
+fn is_before(line: u32, column: u32, other_line: u32, other_column: u32) -> bool {
+    (line, column) < (other_line, other_column)
+}
+
+impl <'a> Formatter <'a> {
+    fn column(&self) -> usize {
+        match self.output.rfind('\n') {
+            Some(index) => self.output.len() - index - 1,
+            None => self.output.len()
+        }
+    }
+
+    fn write(&mut self, text: &str) {
+        self.output.push_str(text);
+        self.line_open = true;
+    }
+
+    fn newline(&mut self, indent: usize) {
+        let trimmed = self.output.trim_end_matches(' ').len();
+        self.output.truncate(trimmed);
+        self.output.push('\n');
+        self.output.push_str(&" ".repeat(indent));
+        self.line_open = false;
+    }
+
+    // Starts a new line for an element which begins on `line`, preserving one blank line
+    //   if the source had any before it.
+    fn start_line(&mut self, line: u32, indent: usize) {
+        if self.line_open {
+            self.newline(indent);
+        }
+        if self.last_line > 0 && line > self.last_line + 1 && !self.output.is_empty() {
+            self.newline(indent);
+        }
+    }
+
+    // Prints the comments which appear before the given position. Trailing comments stay on
+    //   the line of the code they follow, if that code was printed last.
+    fn flush_comments(&mut self, line: u32, column: u32, indent: usize) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if !is_before(comment.line, comment.column, line, column) {
+                break;
+            }
+            let (text, comment_line, trailing) = (comment.text.clone(), comment.line, comment.trailing);
+            self.next_comment += 1;
+
+            if trailing && self.line_open && self.last_line == comment_line {
+                self.write(" ");
+            } else {
+                self.start_line(comment_line, indent);
+            }
+            self.write(&text);
+            self.newline(indent);
+            self.last_line = comment_line;
+        }
+    }
+
+    fn has_comments_before(&self, line: u32, column: u32) -> bool {
+        match self.comments.get(self.next_comment) {
+            Some(comment) => is_before(comment.line, comment.column, line, column),
+            None => false
+        }
+    }
+
+    fn begin_element(&mut self, span: &Span, indent: usize) {
+        self.flush_comments(span.start_line, span.start_column, indent);
+        self.start_line(span.start_line, indent);
+    }
+
+    // Returns the source text of an atom, starting at its span, which `check_spans` validated.
+    fn token_text(&self, span: &Span) -> String {
+        let line = self.lines[span.start_line as usize - 1];
+        let token = &line[(span.start_column as usize - 1)..];
+
+        let mut end = 0;
+        let mut in_string = false;
+        let mut escaped = false;
+        for (index, c) in token.char_indices() {
+            end = index + c.len_utf8();
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    break;
+                }
+            } else if c == '"' {
+                in_string = true;
+            } else if c.is_whitespace() || "(){},:;".contains(c) {
+                end = index;
+                break;
+            }
+        }
+        token[..end].to_string()
+    }
+
+    fn flat(&self, expression: &PreSymbolicExpression) -> String {
+        match expression.pre_expr {
+            PreSymbolicExpressionType::List(ref items) => {
+                let items: Vec<_> = items.iter().map(|item| self.flat(item)).collect();
+                format!("({})", items.join(" "))
+            },
+            PreSymbolicExpressionType::Tuple(ref items) => {
+                let pairs: Vec<_> = items.chunks(2)
+                    .map(|pair| pair.iter().map(|item| self.flat(item)).collect::<Vec<_>>().join(": "))
+                    .collect();
+                format!("{{{}}}", pairs.join(", "))
+            },
+            _ => self.token_text(&expression.span)
+        }
+    }
+
+    // Closes a broken list or tuple. If a comment ended the previous line, the closing
+    //   delimiter is aligned with the opening one.
+    fn close(&mut self, delimiter: &str, column: usize) {
+        if !self.line_open {
+            let trimmed = self.output.trim_end_matches(' ').len();
+            self.output.truncate(trimmed);
+            self.output.push_str(&" ".repeat(column));
+        }
+        self.write(delimiter);
+    }
+
+    fn format(&mut self, expression: &PreSymbolicExpression, indent: usize) {
+        let span = &expression.span;
+        match expression.pre_expr {
+            PreSymbolicExpressionType::List(ref items) => {
+                let flat = self.flat(expression);
+                if !self.has_comments_before(span.end_line, span.end_column) &&
+                    self.column() + flat.len() <= MAX_LINE_WIDTH {
+                    self.write(&flat);
+                } else {
+                    self.format_broken_list(items, span);
+                }
+            },
+            PreSymbolicExpressionType::Tuple(ref items) => {
+                let flat = self.flat(expression);
+                if !self.has_comments_before(span.end_line, span.end_column) &&
+                    self.column() + flat.len() <= MAX_LINE_WIDTH {
+                    self.write(&flat);
+                } else {
+                    self.format_broken_tuple(items, span);
+                }
+            },
+            _ => {
+                let text = self.token_text(span);
+                self.write(&text);
+            }
+        }
+        self.last_line = span.end_line;
+        self.flush_comments(span.end_line, span.end_column, indent);
+    }
+
+    fn format_broken_list(&mut self, items: &[PreSymbolicExpression], span: &Span) {
+        let list_column = self.column();
+        self.write("(");
+
+        // applications keep their first argument on the line of the function name, and
+        //   indent the others. Other lists align their items.
+        let (inline, item_indent) = match items.first() {
+            Some(head) if head.match_atom().is_some() => (2, list_column + INDENT_WIDTH),
+            _ => (1, list_column + 1)
+        };
+
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 && index < inline && !self.has_comments_before(item.span.start_line, item.span.start_column) {
+                self.write(" ");
+            } else if index > 0 {
+                self.begin_element(&item.span, item_indent);
+            }
+            self.format(item, item_indent);
+        }
+
+        self.flush_comments(span.end_line, span.end_column, item_indent);
+        self.close(")", list_column);
+    }
+
+    fn format_broken_tuple(&mut self, items: &[PreSymbolicExpression], span: &Span) {
+        let tuple_column = self.column();
+        let item_indent = tuple_column + 1;
+        self.write("{");
+
+        for (index, item) in items.iter().enumerate() {
+            if index % 2 == 1 {
+                self.write(": ");
+            } else if index > 0 {
+                self.write(",");
+                self.begin_element(&item.span, item_indent);
+            }
+            self.format(item, item_indent);
+        }
+
+        self.flush_comments(span.end_line, span.end_column, item_indent);
+        self.close("}", tuple_column);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_layout() {
+        let source = "(define-constant   owner  tx-sender)
+(define-map balances ((account principal)) ((balance uint)))
+
+
+(define-public (transfer (to principal) (amount uint)) (let ((balance (get-balance {account:  tx-sender}))) (if (> amount balance) (err u1) (ok amount))))";
+
+        let expected = "(define-constant owner tx-sender)
+(define-map balances ((account principal)) ((balance uint)))
+
+(define-public (transfer (to principal) (amount uint))
+  (let ((balance (get-balance {account: tx-sender})))
+    (if (> amount balance) (err u1) (ok amount))))
+";
+        assert_eq!(format_source(source).unwrap(), expected);
+        // formatting is idempotent.
+        assert_eq!(format_source(expected).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_comments() {
+        let source = ";; a counter contract
+(define-data-var counter int 0) ;; the current count
+
+;; increments the counter
+(define-public (increment)
+  (begin
+    ;; bump it
+    (var-set counter (+ (var-get counter) 1)) ;; by one
+    (ok (var-get counter))))
+;; end of file";
+
+        let expected = ";; a counter contract
+(define-data-var counter int 0) ;; the current count
+
+;; increments the counter
+(define-public (increment)
+  (begin
+    ;; bump it
+    (var-set counter (+ (var-get counter) 1)) ;; by one
+    (ok (var-get counter))))
+;; end of file
+";
+        assert_eq!(format_source(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_format_missing_spans() {
+        let source = "(ok u1)";
+        let mut expressions = parse_lexed(lex(source, ClarityVersion::latest()).unwrap()).unwrap();
+        if let PreSymbolicExpressionType::List(ref mut items) = expressions[0].pre_expr {
+            items[1].set_span(0, 0, 0, 0);
+        }
+        let err = format_expressions(source, vec![], &expressions).unwrap_err();
+        assert_eq!(err.err, ParseErrors::MissingSourceSpan);
+
+        // a span past the end of the source
+        let mut expressions = parse_lexed(lex(source, ClarityVersion::latest()).unwrap()).unwrap();
+        if let PreSymbolicExpressionType::List(ref mut items) = expressions[0].pre_expr {
+            items[1].set_span(2, 1, 2, 2);
+        }
+        let err = format_expressions(source, vec![], &expressions).unwrap_err();
+        assert_eq!(err.err, ParseErrors::MissingSourceSpan);
+    }
+
+    #[test]
+    fn test_format_literals() {
+        let source = "(list \"a (string) ;; with parens\"   u\"utf8\" 0x0102\n  'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)";
+        let expected = "(list \"a (string) ;; with parens\"\n  u\"utf8\"\n  0x0102\n  'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)\n";
+        assert_eq!(format_source(source).unwrap(), expected);
+    }
+}
@@ -7,11 +7,15 @@ pub mod sugar_expander;
 pub mod types;
 pub mod errors;
 pub mod stack_depth_checker;
+#[cfg(feature = "developer-mode")]
+pub mod formatter;
+
 use vm::errors::{Error, RuntimeErrorType};
 use vm::costs::{cost_functions, CostTracker};
 
-use vm::representations::{SymbolicExpression};
-use vm::types::QualifiedContractIdentifier;
+use vm::representations::{SymbolicExpression, ClarityName};
+use vm::types::{QualifiedContractIdentifier, Value};
+use vm::version::ClarityVersion;
 
 pub use self::types::ContractAST;
 use self::types::BuildASTPass;
@@ -22,15 +26,16 @@ use self::definition_sorter::DefinitionSorter;
 use self::traits_resolver::TraitsResolver;
 use self::stack_depth_checker::StackDepthChecker;
 
-/// Legacy function
+/// Legacy function: parses Clarity1 source.
 pub fn parse(contract_identifier: &QualifiedContractIdentifier, source_code: &str) -> Result<Vec<SymbolicExpression>, Error> {
-    let ast = build_ast(contract_identifier, source_code, &mut ())?;
+    let ast = build_ast(contract_identifier, source_code, &mut (), ClarityVersion::Clarity1)?;
     Ok(ast.expressions)
 }
 
-pub fn build_ast<T: CostTracker>(contract_identifier: &QualifiedContractIdentifier, source_code: &str, cost_track: &mut T) -> ParseResult<ContractAST> {
+pub fn build_ast<T: CostTracker>(contract_identifier: &QualifiedContractIdentifier, source_code: &str, cost_track: &mut T,
+                                 version: ClarityVersion) -> ParseResult<ContractAST> {
     runtime_cost!(cost_functions::AST_PARSE, cost_track, source_code.len() as u64)?;
-    let pre_expressions = parser::parse(source_code)?;
+    let pre_expressions = parser::parse(source_code, version)?;
     let mut contract_ast = ContractAST::new(contract_identifier.clone(), pre_expressions);
     StackDepthChecker::run_pass(&mut contract_ast)?;
     ExpressionIdentifier::run_pre_expression_pass(&mut contract_ast)?;
@@ -41,6 +46,25 @@ pub fn build_ast<T: CostTracker>(contract_identifier: &QualifiedContractIdentifi
     Ok(contract_ast)
 }
 
+/// Prepends a `define-constant` for each of `constants` to a built AST, so that the
+///   contract's own definitions can refer to them. Used to supply contract init arguments.
+pub fn bind_constants(contract_ast: &mut ContractAST, constants: &[(ClarityName, Value)]) -> ParseResult<()> {
+    if constants.len() == 0 {
+        return Ok(())
+    }
+
+    let mut expressions: Vec<_> = constants.iter()
+        .map(|(name, value)| SymbolicExpression::list(Box::new([
+            SymbolicExpression::atom("define-constant".into()),
+            SymbolicExpression::atom(name.clone()),
+            SymbolicExpression::atom_value(value.clone())])))
+        .collect();
+    expressions.append(&mut contract_ast.expressions);
+    contract_ast.expressions = expressions;
+
+    ExpressionIdentifier::run_expression_pass(contract_ast)
+}
+
 #[cfg(test)]
 mod tests {
     use vm::costs::LimitedCostTracker;
@@ -60,7 +84,7 @@ mod tests {
         }
 
         let mut cost_track = LimitedCostTracker::new_max_limit();
-        build_ast(&QualifiedContractIdentifier::transient(), &progn, &mut cost_track).unwrap();
+        build_ast(&QualifiedContractIdentifier::transient(), &progn, &mut cost_track, ClarityVersion::Clarity1).unwrap();
 
         cost_track.get_total().runtime
     }
@@ -75,6 +99,25 @@ mod tests {
         assert!(ratio_8_16 > ratio_4_8);
     }
 
+    #[test]
+    fn test_bind_constants() {
+        let mut cost_track = LimitedCostTracker::new_max_limit();
+        let mut ast = build_ast(&QualifiedContractIdentifier::transient(), "(define-constant b (+ a 1))", &mut cost_track, ClarityVersion::Clarity1).unwrap();
+        bind_constants(&mut ast, &[("a".into(), Value::Int(1))]).unwrap();
+
+        assert_eq!(ast.expressions.len(), 2);
+        assert_eq!(format!("{}", ast.expressions[0]), "( define-constant a 1 )");
+
+        let mut visited = HashMap::new();
+        for expr in ast.expressions.iter() {
+            depth_traverse::<_,_,()>(expr, |x| {
+                assert!(! visited.contains_key(&x.id));
+                visited.insert(x.id, true);
+                Ok(())
+            }).unwrap();
+        }
+    }
+
     #[test]
     fn test_expression_identification_tuples() {
         let progn = "{ a: (+ 1 2 3),
@@ -82,7 +125,7 @@ mod tests {
                        c: 3 }";
 
         let mut cost_track = LimitedCostTracker::new_max_limit();
-        let ast = build_ast(&QualifiedContractIdentifier::transient(), &progn, &mut cost_track).unwrap().expressions;
+        let ast = build_ast(&QualifiedContractIdentifier::transient(), &progn, &mut cost_track, ClarityVersion::Clarity1).unwrap().expressions;
 
         let mut visited = HashMap::new();
 
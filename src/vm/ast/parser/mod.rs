@@ -7,6 +7,7 @@ use vm::ast::errors::{ParseResult, ParseErrors, ParseError};
 use vm::errors::{RuntimeErrorType, InterpreterResult as Result};
 use vm::representations::{PreSymbolicExpression, PreSymbolicExpressionType, ContractName, ClarityName, MAX_STRING_LEN};
 use vm::types::{Value, PrincipalData, TraitIdentifier, QualifiedContractIdentifier};
+use vm::version::ClarityVersion;
 
 pub const CONTRACT_MIN_NAME_LENGTH : usize = 5;
 pub const CONTRACT_MAX_NAME_LENGTH : usize = 40;
@@ -24,15 +25,16 @@ pub enum LexItem {
     Variable(String),
     CommaSeparator,
     ColonSeparator,
+    Comment(String),
     Whitespace
 }
 
 #[derive(Debug)]
 enum TokenType {
-    Whitespace, Comma, Colon,
+    Whitespace, Comment, Comma, Colon,
     LParens, RParens,
     LCurly, RCurly,
-    StringLiteral, HexStringLiteral,
+    StringLiteral, UTF8StringLiteral, HexStringLiteral,
     UIntLiteral, IntLiteral,
     Variable, TraitReferenceLiteral, PrincipalLiteral,
     SugaredContractIdentifierLiteral,
@@ -72,6 +74,45 @@ fn get_value_or_err(input: &str, captures: Captures) -> ParseResult<String> {
     Ok(input[matched.start()..matched.end()].to_string())
 }
 
+/// Replaces the escape sequences of a string literal with the characters
+///   they denote. If `allow_unicode` is set, `\u{...}` escapes are also accepted.
+fn unescape_string_literal(input: &str, allow_unicode: bool) -> ParseResult<String> {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('"') => out.push('"'),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('u') if allow_unicode => {
+                if chars.next() != Some('{') {
+                    return Err(ParseError::new(ParseErrors::InvalidEscaping))
+                }
+                let mut code_point = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(digit) if digit.is_ascii_hexdigit() && code_point.len() < 6 => code_point.push(digit),
+                        _ => return Err(ParseError::new(ParseErrors::InvalidEscaping))
+                    }
+                }
+                let unicode_char = u32::from_str_radix(&code_point, 16).ok()
+                    .and_then(std::char::from_u32)
+                    .ok_or_else(|| ParseError::new(ParseErrors::InvalidEscaping))?;
+                out.push(unicode_char);
+            },
+            _ => return Err(ParseError::new(ParseErrors::InvalidEscaping))
+        }
+    }
+    Ok(out)
+}
+
 fn get_lines_at(input: &str) -> Vec<usize> {
     let mut out: Vec<_> = input.match_indices("\n")
         .map(|(ix, _)| ix)
@@ -90,14 +131,15 @@ lazy_static! {
     pub static ref CLARITY_NAME_REGEX: String = format!(r#"([[:word:]]|[-!?+<>=/*]){{1,{}}}"#, MAX_STRING_LEN); 
 }
 
-pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
+pub fn lex(input: &str, version: ClarityVersion) -> ParseResult<Vec<(LexItem, u32, u32)>> {
     // Aaron: I'd like these to be static, but that'd require using
     //    lazy_static (or just hand implementing that), and I'm not convinced
     //    it's worth either (1) an extern macro, or (2) the complexity of hand implementing.
 
     let lex_matchers: &[LexMatcher] = &[
         LexMatcher::new(r##""(?P<value>((\\")|([[ -~]&&[^"]]))*)""##, TokenType::StringLiteral),
-        LexMatcher::new(";;[ -~]*", TokenType::Whitespace), // ;; comments.
+        LexMatcher::new(r##"u"(?P<value>((\\")|([[ -~]&&[^"]]))*)""##, TokenType::UTF8StringLiteral),
+        LexMatcher::new(";;[ -~]*", TokenType::Comment), // ;; comments.
         LexMatcher::new("[\n]+", TokenType::Whitespace),
         LexMatcher::new("[ \t]+", TokenType::Whitespace),
         LexMatcher::new("[,]", TokenType::Comma),
@@ -107,7 +149,7 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
         LexMatcher::new("[{]", TokenType::LCurly),
         LexMatcher::new("[}]", TokenType::RCurly),
         LexMatcher::new("<(?P<value>([[:word:]]|[-])+)>", TokenType::TraitReferenceLiteral),
-        LexMatcher::new("0x(?P<value>[[:xdigit:]]+)", TokenType::HexStringLiteral),
+        LexMatcher::new("0x(?P<value>[[:xdigit:]]*)", TokenType::HexStringLiteral),
         LexMatcher::new("u(?P<value>[[:digit:]]+)", TokenType::UIntLiteral),
         LexMatcher::new("(?P<value>-?[[:digit:]]+)", TokenType::IntLiteral),
         LexMatcher::new(&format!(r#"'(?P<value>{}(\.)([[:alnum:]]|[-]){{1,{}}})"#,
@@ -131,13 +173,15 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
     let mut column_pos: u32 = 1;
     let mut did_match = true;
     while did_match && munch_index < input.len() {
-        if let Some(next_line_ix) = next_line_break {
-            if munch_index > next_line_ix {
-                next_line_break = line_indices.pop();
-                column_pos = 1;
-                current_line = current_line.checked_add(1)
-                    .ok_or(ParseError::new(ParseErrors::ProgramTooLarge))?;
+        // a single whitespace token may span several line breaks.
+        while let Some(next_line_ix) = next_line_break {
+            if munch_index <= next_line_ix {
+                break;
             }
+            next_line_break = line_indices.pop();
+            column_pos = 1;
+            current_line = current_line.checked_add(1)
+                .ok_or(ParseError::new(ParseErrors::ProgramTooLarge))?;
         }
 
         did_match = false;
@@ -158,6 +202,7 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
                             TokenType::RParens => Ok(()),
                             TokenType::RCurly => Ok(()),
                             TokenType::Whitespace => Ok(()),
+                            TokenType::Comment => Ok(()),
                             TokenType::Comma => Ok(()),
                             TokenType::Colon => Ok(()),
                             _ => Err(ParseError::new(ParseErrors::SeparatorExpected(current_slice[..whole_match.end()].to_string())))
@@ -169,6 +214,7 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
                             TokenType::RParens => Ok(()),
                             TokenType::RCurly => Ok(()),
                             TokenType::Whitespace => Ok(()),
+                            TokenType::Comment => Ok(()),
                             TokenType::Comma => Ok(()),
                             TokenType::Colon => Ok(()),
                             _ => Err(ParseError::new(ParseErrors::SeparatorExpectedAfterColon(
@@ -193,6 +239,10 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
                         context = LexContext::ExpectNothing;
                         Ok(LexItem::Whitespace)
                     },
+                    TokenType::Comment => {
+                        context = LexContext::ExpectNothing;
+                        Ok(LexItem::Comment(current_slice[..whole_match.end()].to_string()))
+                    },
                     TokenType::Comma => {
                         context = LexContext::ExpectNothing;
                         Ok(LexItem::CommaSeparator)
@@ -290,7 +340,8 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
                         }?;
                         Ok(LexItem::LiteralValue(str_value.len(), value))
                     },
-                    TokenType::StringLiteral => {
+                    TokenType::StringLiteral if version < ClarityVersion::Clarity2 => {
+                        // Clarity1 reads "..." as a buffer, and only unescapes quotes and slashes.
                         let str_value = get_value_or_err(current_slice, captures)?;
                         let quote_unescaped = str_value.replace("\\\"","\"");
                         let slash_unescaped = quote_unescaped.replace("\\\\","\\");
@@ -301,6 +352,25 @@ pub fn lex(input: &str) -> ParseResult<Vec<(LexItem, u32, u32)>> {
                         }?;
                         Ok(LexItem::LiteralValue(str_value.len(), value))
                     },
+                    TokenType::StringLiteral => {
+                        let str_value = get_value_or_err(current_slice, captures)?;
+                        let unescaped = unescape_string_literal(&str_value, false)?;
+                        let byte_vec = unescaped.as_bytes().to_vec();
+                        let value = match Value::string_ascii_from_bytes(byte_vec) {
+                            Ok(parsed) => Ok(parsed),
+                            Err(_e) => Err(ParseError::new(ParseErrors::FailedParsingASCIIString(str_value.clone())))
+                        }?;
+                        Ok(LexItem::LiteralValue(str_value.len(), value))
+                    },
+                    TokenType::UTF8StringLiteral => {
+                        let str_value = get_value_or_err(current_slice, captures)?;
+                        let unescaped = unescape_string_literal(&str_value, true)?;
+                        let value = match Value::string_utf8_from_string(&unescaped) {
+                            Ok(parsed) => Ok(parsed),
+                            Err(_e) => Err(ParseError::new(ParseErrors::FailedParsingUTF8String(str_value.clone())))
+                        }?;
+                        Ok(LexItem::LiteralValue(str_value.len(), value))
+                    },
                 }?;
 
                 result.push((token, current_line, column_pos));
@@ -495,7 +565,7 @@ pub fn parse_lexed(mut input: Vec<(LexItem, u32, u32)>) -> ParseResult<Vec<PreSy
                     }
                 };
             },
-            LexItem::Whitespace => ()
+            LexItem::Whitespace | LexItem::Comment(_) => ()
         };
     }
 
@@ -507,8 +577,8 @@ pub fn parse_lexed(mut input: Vec<(LexItem, u32, u32)>) -> ParseResult<Vec<PreSy
     }
 }
 
-pub fn parse(input: &str) -> ParseResult<Vec<PreSymbolicExpression>> {
-    let lexed = lex(input)?;
+pub fn parse(input: &str, version: ClarityVersion) -> ParseResult<Vec<PreSymbolicExpression>> {
+    let lexed = lex(input, version)?;
     parse_lexed(lexed)
 }
 
@@ -520,6 +590,8 @@ mod test {
     use vm::types::{QualifiedContractIdentifier, PrincipalData};
     use vm::ast::errors::{ParseErrors, ParseError};
     use vm::types::{TraitIdentifier};
+    use vm::version::ClarityVersion;
+    use super::{lex, LexItem};
 
     fn make_atom(x: &str, start_line: u32, start_column: u32, end_line: u32, end_column: u32) -> PreSymbolicExpression {
         let mut e = PreSymbolicExpression::atom(x.into());
@@ -545,6 +617,25 @@ mod test {
         e
     }
 
+    #[test]
+    fn test_lex_line_numbers() {
+        // a whitespace token spanning several line breaks advances the line by each of them.
+        let lexed = lex("(a)\n\n\n  (b) ;; c\n\n\t(d)", ClarityVersion::Clarity1).unwrap();
+        let positions: Vec<_> = lexed.iter()
+            .filter(|(item, _, _)| match item { LexItem::LeftParen => true, _ => false })
+            .map(|(_, line, column)| (*line, *column))
+            .collect();
+        assert_eq!(positions, vec![(1, 1), (4, 3), (6, 2)]);
+
+        let comments: Vec<_> = lexed.iter()
+            .filter_map(|(item, line, column)| match item {
+                LexItem::Comment(_) => Some((*line, *column)),
+                _ => None
+            })
+            .collect();
+        assert_eq!(comments, vec![(4, 7)]);
+    }
+
     #[test]
     fn test_parse_let_expression() {
 
@@ -586,7 +677,7 @@ r#"z (let ((x 1) (y 2))
             make_atom("y", 6, 15, 6, 15),
         ];
 
-        let parsed = ast::parser::parse(&input);
+        let parsed = ast::parser::parse(&input, ClarityVersion::Clarity1);
         assert_eq!(Ok(program), parsed, "Should match expected symbolic expression");
 
         let input = "        -1234
@@ -597,7 +688,7 @@ r#"z (let ((x 1) (y 2))
                                 make_atom_value(Value::Int(12), 2, 12, 2, 13),
                                 make_atom_value(Value::Int(34), 2, 15, 2, 16)])) ];
 
-        let parsed = ast::parser::parse(&input);
+        let parsed = ast::parser::parse(&input, ClarityVersion::Clarity1);
         assert_eq!(Ok(program), parsed, "Should match expected symbolic expression");
 
     }
@@ -608,14 +699,45 @@ r#"z (let ((x 1) (y 2))
       let program = vec![ make_tuple(1, 1, 1, 11, Box::new([
                               make_atom("id", 1, 2, 1, 3),
                               make_atom_value(Value::Int(1337), 1, 6, 1, 9)]))];
-      let parsed = ast::parser::parse(&input);
+      let parsed = ast::parser::parse(&input, ClarityVersion::Clarity1);
       assert_eq!(Ok(program), parsed, "Should match expected tuple literal");
     }
 
+    #[test]
+    fn test_parse_string_literals() {
+        let input = r#"("a \"b\" \\ c" u"d\u{e9}f" 0x)"#;
+        let parsed = ast::parser::parse(&input, ClarityVersion::Clarity2).unwrap();
+        let items = match parsed[0].pre_expr {
+            PreSymbolicExpressionType::List(ref items) => items.clone(),
+            _ => panic!("Expected list")
+        };
+        let values: Vec<_> = items.iter().map(|item| match item.pre_expr {
+            PreSymbolicExpressionType::AtomValue(ref value) => value.clone(),
+            _ => panic!("Expected atom value")
+        }).collect();
+
+        assert_eq!(values, vec![
+            Value::string_ascii_from_bytes("a \"b\" \\ c".as_bytes().to_vec()).unwrap(),
+            Value::string_utf8_from_string("déf").unwrap(),
+            Value::buff_from(vec![]).unwrap()]);
+
+        // Clarity1 reads the same literal as a buffer, unescaping only quotes and slashes.
+        let parsed = ast::parser::parse(r#""a \"b\" \\ \n""#, ClarityVersion::Clarity1).unwrap();
+        assert_eq!(parsed[0].match_atom_value(),
+                   Some(&Value::buff_from("a \"b\" \\ \\n".as_bytes().to_vec()).unwrap()));
+
+        assert!(match ast::parser::parse(r#""a \q""#, ClarityVersion::Clarity2).unwrap_err().err {
+            ParseErrors::InvalidEscaping => true, _ => false });
+        assert!(match ast::parser::parse(r#""a \u{e9}""#, ClarityVersion::Clarity2).unwrap_err().err {
+            ParseErrors::InvalidEscaping => true, _ => false });
+        assert!(match ast::parser::parse(r#"u"\u{d800}""#, ClarityVersion::Clarity2).unwrap_err().err {
+            ParseErrors::InvalidEscaping => true, _ => false });
+    }
+
     #[test]
     fn test_parse_contract_principals() {
         let input = "'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR.contract-a";
-        let parsed = ast::parser::parse(&input).unwrap();
+        let parsed = ast::parser::parse(&input, ClarityVersion::Clarity1).unwrap();
 
         let x1 = &parsed[0];
         assert!( match x1.match_atom_value() {
@@ -631,7 +753,7 @@ r#"z (let ((x 1) (y 2))
     #[test]
     fn test_parse_generics() {
         let input = "<a>";
-        let parsed = ast::parser::parse(&input).unwrap();
+        let parsed = ast::parser::parse(&input, ClarityVersion::Clarity1).unwrap();
 
         let x1 = &parsed[0];
         assert!( match x1.match_trait_reference() {
@@ -644,7 +766,7 @@ r#"z (let ((x 1) (y 2))
     fn test_parse_field_identifiers() {
         use vm::types::PrincipalData;
         let input = "'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR.my-contract.my-trait";
-        let parsed = ast::parser::parse(&input).unwrap();
+        let parsed = ast::parser::parse(&input, ClarityVersion::Clarity1).unwrap();
 
         let x1 = &parsed[0];
         assert!( match x1.match_field_identifier() {
@@ -660,7 +782,7 @@ r#"z (let ((x 1) (y 2))
     #[test]
     fn test_parse_sugared_field_identifiers() {
         let input = ".my-contract.my-trait";
-        let parsed = ast::parser::parse(&input).unwrap();
+        let parsed = ast::parser::parse(&input, ClarityVersion::Clarity1).unwrap();
 
         let x1 = &parsed[0];
         assert!( match &x1.pre_expr {
@@ -702,77 +824,77 @@ r#"z (let ((x 1) (y 2))
         // good case
         let function_with_LF = "(define (foo (x y)) \n (+ 1 2 3) \n (- 1 2 3))";
 
-        assert!(match ast::parser::parse(&split_tokens).unwrap_err().err {
+        assert!(match ast::parser::parse(&split_tokens, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::SeparatorExpected(_) => true, _ => false });
 
-        assert!(match ast::parser::parse(&too_much_closure).unwrap_err().err {
+        assert!(match ast::parser::parse(&too_much_closure, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::ClosingParenthesisUnexpected => true, _ => false });
 
-        assert!(match ast::parser::parse(&not_enough_closure).unwrap_err().err {
+        assert!(match ast::parser::parse(&not_enough_closure, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::ClosingParenthesisExpected => true, _ => false });
 
-        assert!(match ast::parser::parse(&middle_hash).unwrap_err().err {
+        assert!(match ast::parser::parse(&middle_hash, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::FailedParsingRemainder(_) => true, _ => false });
 
-        assert!(match ast::parser::parse(&unicode).unwrap_err().err {
+        assert!(match ast::parser::parse(&unicode, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::FailedParsingRemainder(_) => true, _ => false });
 
-        assert!(match ast::parser::parse(&name_with_dot).unwrap_err().err {
+        assert!(match ast::parser::parse(&name_with_dot, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::FailedParsingRemainder(_) => true, _ => false });
 
-        assert!(match ast::parser::parse(&wrong_tuple_literal_close).unwrap_err().err {
+        assert!(match ast::parser::parse(&wrong_tuple_literal_close, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::ClosingTupleLiteralExpected => true, _ => false });
 
-        assert!(match ast::parser::parse(&wrong_list_close).unwrap_err().err {
+        assert!(match ast::parser::parse(&wrong_list_close, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::ClosingParenthesisExpected => true, _ => false });
 
-        assert!(match ast::parser::parse(&extra_tuple_literal_close).unwrap_err().err {
+        assert!(match ast::parser::parse(&extra_tuple_literal_close, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::ClosingTupleLiteralUnexpected => true, _ => false });
 
-        assert!(match ast::parser::parse(&unexpected_comma).unwrap_err().err {
+        assert!(match ast::parser::parse(&unexpected_comma, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::CommaSeparatorUnexpected => true, _ => false });
 
         // { a: b,c: 3 } is legal
-        ast::parser::parse(&tuple_comma_no_space).unwrap();
+        ast::parser::parse(&tuple_comma_no_space, ClarityVersion::Clarity1).unwrap();
 
-        assert!(match ast::parser::parse(&tuple_colon_no_space).unwrap_err().err {
+        assert!(match ast::parser::parse(&tuple_colon_no_space, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::SeparatorExpectedAfterColon(_) => true, _ => false });
 
-        assert!(match ast::parser::parse(&shorthand_tuple).unwrap_err().err {
+        assert!(match ast::parser::parse(&shorthand_tuple, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::TupleColonExpected(_) => true, _ => false });
 
-        assert!(match ast::parser::parse(&shorthand_tuple_dangling_comma).unwrap_err().err {
+        assert!(match ast::parser::parse(&shorthand_tuple_dangling_comma, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::TupleItemExpected(_) => true, _ => false });
 
-        assert!(match ast::parser::parse(&decorative_colon_on_value).unwrap_err().err {
+        assert!(match ast::parser::parse(&decorative_colon_on_value, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::TupleCommaExpected(_) => true, e => 
             { eprintln!("{:?}", e); false } });
 
-        assert!(match ast::parser::parse(&tuple_literal_colon_after_comma).unwrap_err().err {
+        assert!(match ast::parser::parse(&tuple_literal_colon_after_comma, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::TupleItemExpected(_) => true, e => 
             { eprintln!("{:?}", e); false } });
 
-        assert!(match ast::parser::parse(&empty_tuple_literal_comma).unwrap_err().err {
+        assert!(match ast::parser::parse(&empty_tuple_literal_comma, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::TupleItemExpected(_) => true, _ => false });
 
-        assert!(match ast::parser::parse(&empty_tuple_literal_colon).unwrap_err().err {
+        assert!(match ast::parser::parse(&empty_tuple_literal_colon, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::TupleItemExpected(_) => true, _ => false });
 
-        assert!(match ast::parser::parse(&legacy_boolean_literals).unwrap_err().err {
+        assert!(match ast::parser::parse(&legacy_boolean_literals, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::FailedParsingRemainder(_) => true, _ => false });
             
-        assert!(match ast::parser::parse(&function_with_CR).unwrap_err().err {
+        assert!(match ast::parser::parse(&function_with_CR, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::FailedParsingRemainder(_) => true, _ => false });
-        assert!(match ast::parser::parse(&function_with_CRLF).unwrap_err().err {
+        assert!(match ast::parser::parse(&function_with_CRLF, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::FailedParsingRemainder(_) => true, _ => false });
-        assert!(match ast::parser::parse(&function_with_NEL).unwrap_err().err {
+        assert!(match ast::parser::parse(&function_with_NEL, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::FailedParsingRemainder(_) => true, _ => false });
-        assert!(match ast::parser::parse(&function_with_LS).unwrap_err().err {
+        assert!(match ast::parser::parse(&function_with_LS, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::FailedParsingRemainder(_) => true, _ => false });
-        assert!(match ast::parser::parse(&function_with_PS).unwrap_err().err {
+        assert!(match ast::parser::parse(&function_with_PS, ClarityVersion::Clarity1).unwrap_err().err {
             ParseErrors::FailedParsingRemainder(_) => true, _ => false });
 
-        ast::parser::parse(&function_with_LF).unwrap();
+        ast::parser::parse(&function_with_LF, ClarityVersion::Clarity1).unwrap();
     }
 
 }
@@ -128,7 +128,7 @@ impl DefinedFunction {
             }
         }
 
-        let result = eval(&self.body, env, &context);
+        let result = self.eval_body(env, &context);
 
         // if the error wasn't actually an error, but a function return,
         //    pull that out and return it.
@@ -143,6 +143,23 @@ impl DefinedFunction {
         }
     }
 
+    #[cfg(feature = "wasm")]
+    fn eval_body(&self, env: &mut Environment, context: &LocalContext) -> Result<Value> {
+        // compiled code does not invoke the eval hook, so it is only used without one.
+        if env.global_context.eval_hooks.is_empty() {
+            let compiled = env.global_context.wasm_contracts.get(&env.contract_context.contract_identifier).cloned();
+            if let Some(result) = compiled.and_then(|compiled| compiled.execute_function(&self.name, env, context)) {
+                return result
+            }
+        }
+        eval(&self.body, env, context)
+    }
+
+    #[cfg(not(feature = "wasm"))]
+    fn eval_body(&self, env: &mut Environment, context: &LocalContext) -> Result<Value> {
+        eval(&self.body, env, context)
+    }
+
     pub fn check_trait_expectations(&self, 
                                     contract_defining_trait: &ContractContext,
                                     trait_identifier: &TraitIdentifier) -> Result<()> {
@@ -184,6 +201,10 @@ impl DefinedFunction {
     pub fn get_identifier(&self) -> FunctionIdentifier {
         self.identifier.clone()
     }
+
+    pub fn get_body(&self) -> &SymbolicExpression {
+        &self.body
+    }
 }
 
 impl CallableType {
@@ -1,8 +1,8 @@
-use vm::representations::SymbolicExpression;
+use vm::representations::{SymbolicExpression, ClarityName};
 use vm::types::{Value, AssetIdentifier, PrincipalData, QualifiedContractIdentifier, TypeSignature};
 use vm::contexts::{OwnedEnvironment, AssetMap, Environment};
 use vm::database::{MarfedKV, ClarityDatabase, SqliteConnection, HeadersDB, RollbackWrapper, RollbackWrapperPersistedLog};
-use vm::analysis::{AnalysisDatabase};
+use vm::analysis::{AnalysisDatabase, AnalysisCache};
 use vm::errors::{Error as InterpreterError};
 use vm::ast::{ContractAST, errors::ParseError, errors::ParseErrors};
 use vm::analysis::{ContractAnalysis, errors::CheckError, errors::CheckErrors};
@@ -35,6 +35,7 @@ use std::fmt;
 pub struct ClarityInstance {
     datastore: Option<MarfedKV>,
     block_limit: ExecutionCost,
+    mainnet: bool,
 }
 
 ///
@@ -44,7 +45,8 @@ pub struct ClarityBlockConnection<'a> {
     datastore: MarfedKV,
     parent: &'a mut ClarityInstance,
     header_db: &'a dyn HeadersDB,
-    cost_track: Option<LimitedCostTracker>
+    cost_track: Option<LimitedCostTracker>,
+    analysis_cache: Option<AnalysisCache>
 }
 
 ///
@@ -56,13 +58,16 @@ pub struct ClarityTransactionConnection<'a> {
     log: Option<RollbackWrapperPersistedLog>,
     store: &'a mut MarfedKV,
     header_db: &'a dyn HeadersDB,
-    cost_track: &'a mut Option<LimitedCostTracker>
+    cost_track: &'a mut Option<LimitedCostTracker>,
+    analysis_cache: &'a mut Option<AnalysisCache>,
+    mainnet: bool
 }
 
 pub struct ClarityReadOnlyConnection<'a> {
     datastore: MarfedKV,
     parent: &'a mut ClarityInstance,
     header_db: &'a dyn HeadersDB,
+    analysis_cache: Option<AnalysisCache>
 }
 
 #[derive(Debug)]
@@ -160,8 +165,12 @@ impl ClarityBlockConnection<'_> {
 }
 
 impl ClarityInstance {
-    pub fn new(datastore: MarfedKV, block_limit: ExecutionCost) -> ClarityInstance {
-        ClarityInstance { datastore: Some(datastore), block_limit }
+    pub fn new(mainnet: bool, datastore: MarfedKV, block_limit: ExecutionCost) -> ClarityInstance {
+        ClarityInstance { datastore: Some(datastore), block_limit, mainnet }
+    }
+
+    pub fn block_limit(&self) -> &ExecutionCost {
+        &self.block_limit
     }
 
     pub fn begin_block<'a> (&'a mut self, current: &StacksBlockId, next: &StacksBlockId,
@@ -179,7 +188,8 @@ impl ClarityInstance {
             datastore,
             header_db,
             parent: self,
-            cost_track
+            cost_track,
+            analysis_cache: Some(AnalysisCache::new())
         }
     }
 
@@ -195,7 +205,8 @@ impl ClarityInstance {
         ClarityReadOnlyConnection {
             datastore,
             header_db,
-            parent: self
+            parent: self,
+            analysis_cache: Some(AnalysisCache::new())
         }
     }
 
@@ -205,7 +216,7 @@ impl ClarityInstance {
             .set_chain_tip(at_block);
         let clarity_db = self.datastore.as_mut().unwrap()
             .as_clarity_db(header_db);
-        let mut env = OwnedEnvironment::new(clarity_db);
+        let mut env = OwnedEnvironment::new_cost_limited(self.mainnet, clarity_db, LimitedCostTracker::new_max_limit());
         env.eval_read_only(contract, program)
             .map(|(x, _, _)| x)
             .map_err(Error::from)
@@ -225,6 +236,8 @@ pub trait ClarityConnection {
     where F: FnOnce(ClarityDatabase) -> (R, ClarityDatabase);
     fn with_analysis_db_readonly<F, R>(&mut self, to_do: F) -> R
     where F: FnOnce(&mut AnalysisDatabase) -> R;
+    /// Whether the connected chainstate is a mainnet chainstate.
+    fn is_mainnet(&self) -> bool;
 
     fn with_clarity_db_readonly<F, R>(&mut self, to_do: F) -> R
     where F: FnOnce(&mut ClarityDatabase) -> R {
@@ -235,8 +248,9 @@ pub trait ClarityConnection {
 
     fn with_readonly_clarity_env<F, R>(&mut self, sender: PrincipalData, cost_track: LimitedCostTracker, to_do: F) -> Result<R, InterpreterError>
     where F: FnOnce(&mut Environment) -> Result<R, InterpreterError> {
+        let mainnet = self.is_mainnet();
         self.with_clarity_db_readonly_owned(|clarity_db| {
-            let mut vm_env = OwnedEnvironment::new_cost_limited(clarity_db, cost_track);
+            let mut vm_env = OwnedEnvironment::new_cost_limited(mainnet, clarity_db, cost_track);
             let result = vm_env.execute_in_env(sender.into(), to_do)
                 .map(|(result, _, _)| result);
             let (db, _) = vm_env.destruct()
@@ -259,11 +273,18 @@ impl ClarityConnection for ClarityBlockConnection <'_> {
 
     fn with_analysis_db_readonly<F, R>(&mut self, to_do: F) -> R
     where F: FnOnce(&mut AnalysisDatabase) -> R {
-        let mut db = AnalysisDatabase::new(&mut self.datastore);
-        db.begin();
-        let result = to_do(&mut db);
-        db.roll_back();
-        result
+        using!(self.analysis_cache, "analysis cache", |cache| {
+            let mut db = AnalysisDatabase::new_with_cache(RollbackWrapper::new(&mut self.datastore), cache);
+            db.begin();
+            let result = to_do(&mut db);
+            db.roll_back();
+            let (_, cache) = db.destroy_with_cache();
+            (cache, result)
+        })
+    }
+
+    fn is_mainnet(&self) -> bool {
+        self.parent.mainnet
     }
 }
 
@@ -280,11 +301,18 @@ impl ClarityConnection for ClarityReadOnlyConnection <'_> {
 
     fn with_analysis_db_readonly<F, R>(&mut self, to_do: F) -> R
     where F: FnOnce(&mut AnalysisDatabase) -> R {
-        let mut db = AnalysisDatabase::new(&mut self.datastore);
-        db.begin();
-        let result = to_do(&mut db);
-        db.roll_back();
-        result
+        using!(self.analysis_cache, "analysis cache", |cache| {
+            let mut db = AnalysisDatabase::new_with_cache(RollbackWrapper::new(&mut self.datastore), cache);
+            db.begin();
+            let result = to_do(&mut db);
+            db.roll_back();
+            let (_, cache) = db.destroy_with_cache();
+            (cache, result)
+        })
+    }
+
+    fn is_mainnet(&self) -> bool {
+        self.parent.mainnet
     }
 }
 
@@ -353,11 +381,17 @@ impl <'a> ClarityBlockConnection <'a> {
     pub fn start_transaction_processing <'b> (&'b mut self) -> ClarityTransactionConnection <'b> {
         let store = &mut self.datastore;
         let cost_track = &mut self.cost_track;
+        let analysis_cache = &mut self.analysis_cache;
         let header_db = &self.header_db;
+        let mainnet = self.parent.mainnet;
         let mut log = RollbackWrapperPersistedLog::new();
         log.nest();
+        // analyses stored by the transaction are dropped from the cache if it is rolled back.
+        analysis_cache.as_mut()
+            .expect("BUG: Block connection lost analysis cache handle.")
+            .nest();
         ClarityTransactionConnection {
-            store, cost_track, header_db, log: Some(log)
+            store, cost_track, analysis_cache, header_db, mainnet, log: Some(log)
         }
     }
 
@@ -403,6 +437,10 @@ impl ClarityConnection for ClarityTransactionConnection <'_> {
             result
         })
     }
+
+    fn is_mainnet(&self) -> bool {
+        self.mainnet
+    }
 }
 
 impl <'a> Drop for ClarityTransactionConnection<'a> {
@@ -410,6 +448,12 @@ impl <'a> Drop for ClarityTransactionConnection<'a> {
         self.cost_track.as_mut()
             .expect("BUG: Transaction connection lost cost_tracker handle.")
             .reset_memory();
+        // the log is only still held if the transaction was not committed.
+        if self.log.is_some() {
+            self.analysis_cache.as_mut()
+                .expect("BUG: Transaction connection lost analysis cache handle.")
+                .roll_back();
+        }
     }
 }
 
@@ -417,10 +461,13 @@ impl <'a> ClarityTransactionConnection <'a> {
     fn inner_with_analysis_db<F, R>(&mut self, to_do: F) -> R
     where F: FnOnce(&mut AnalysisDatabase) -> R {
         using!(self.log, "log", |log| {
-            let rollback_wrapper = RollbackWrapper::from_persisted_log(self.store, log);
-            let mut db = AnalysisDatabase::new_with_rollback_wrapper(rollback_wrapper);
-            let r = to_do(&mut db);
-            (db.destroy().into(), r)
+            using!(self.analysis_cache, "analysis cache", |cache| {
+                let rollback_wrapper = RollbackWrapper::from_persisted_log(self.store, log);
+                let mut db = AnalysisDatabase::new_with_cache(rollback_wrapper, cache);
+                let r = to_do(&mut db);
+                let (rollback_wrapper, cache) = db.destroy_with_cache();
+                (cache, (rollback_wrapper.into(), r))
+            })
         })
     }
 
@@ -454,9 +501,22 @@ impl <'a> ClarityTransactionConnection <'a> {
     /// Analyze a provided smart contract, but do not write the analysis to the AnalysisDatabase
     pub fn analyze_smart_contract(&mut self, identifier: &QualifiedContractIdentifier, contract_content: &str)
                                   -> Result<(ContractAST, ContractAnalysis), Error> {
+        self.analyze_smart_contract_with_args(identifier, contract_content, &[])
+    }
+
+    /// Analyze a contract whose init arguments are bound as constants ahead of its own definitions.
+    pub fn analyze_smart_contract_with_args(&mut self, identifier: &QualifiedContractIdentifier, contract_content: &str,
+                                            init_args: &[(ClarityName, Value)])
+                                            -> Result<(ContractAST, ContractAnalysis), Error> {
+        // contracts are read as the version of Clarity in effect at the block deploying them.
+        let version = self.with_clarity_db_readonly(|db| db.get_clarity_version());
         using!(self.cost_track, "cost tracker", |mut cost_track| {
             self.inner_with_analysis_db(|db| {
-                let ast_result = ast::build_ast(identifier, contract_content, &mut cost_track);
+                let ast_result = ast::build_ast(identifier, contract_content, &mut cost_track, version)
+                    .and_then(|mut contract_ast| {
+                        ast::bind_constants(&mut contract_ast, init_args)?;
+                        Ok(contract_ast)
+                    });
 
                 let mut contract_ast = match ast_result {
                     Ok(x) => x,
@@ -493,7 +553,7 @@ impl <'a> ClarityTransactionConnection <'a> {
                 // wrap the whole contract-call in a claritydb transaction,
                 //   so we can abort on call_back's boolean retun
                 db.begin();
-                let mut vm_env = OwnedEnvironment::new_cost_limited(db, cost_track);
+                let mut vm_env = OwnedEnvironment::new_cost_limited(self.mainnet, db, cost_track);
                 let result = to_do(&mut vm_env);
                 let (mut db, cost_track) = vm_env.destruct()
                     .expect("Failed to recover database reference after executing transaction");
@@ -588,6 +648,23 @@ impl <'a> ClarityTransactionConnection <'a> {
         }
     }
 
+    /// Initialize a contract analyzed with `init_args`, first funding its principal with `amount` microSTX
+    ///   from the issuer. The init arguments are stored alongside the contract's source.
+    pub fn initialize_funded_smart_contract <F> (&mut self, identifier: &QualifiedContractIdentifier, contract_ast: &ContractAST,
+                                                 contract_str: &str, init_args: &[(ClarityName, Value)], amount: u128,
+                                                 abort_call_back: F) -> Result<(AssetMap, Vec<StacksTransactionEvent>), Error>
+    where F: FnOnce(&AssetMap, &mut ClarityDatabase) -> bool {
+        let (_, asset_map, events, aborted) = self.with_abort_callback(
+            |vm_env| { vm_env.initialize_funded_contract_from_ast(identifier.clone(), contract_ast, contract_str, init_args, amount)
+                       .map_err(Error::from) },
+            abort_call_back)?;
+        if aborted {
+            Err(Error::AbortedByCallback(None, asset_map, events))
+        } else {
+            Ok((asset_map, events))
+        }
+    }
+
     /// Commit the changes from the edit log.
     /// panics if there is more than one open savepoint
     pub fn commit(mut self) {
@@ -598,6 +675,9 @@ impl <'a> ClarityTransactionConnection <'a> {
             panic!("Attempted to commit transaction with {} != 1 rollbacks", rollback_wrapper.depth());
         }
         rollback_wrapper.commit();
+        self.analysis_cache.as_mut()
+            .expect("BUG: Transaction connection lost analysis cache handle.")
+            .commit();
         // now we can reset the memory usage for the edit-log
         self.cost_track.as_mut()
             .expect("BUG: Transaction connection lost cost tracker connection.")
@@ -628,14 +708,16 @@ mod tests {
     use super::*;
     use vm::analysis::errors::CheckErrors;
     use vm::types::{Value, StandardPrincipalData};
+    use vm::version::{ClarityVersion, CLARITY2_ACTIVATION_HEIGHT};
     use vm::database::{NULL_HEADER_DB, ClarityBackingStore, MarfedKV};
     use chainstate::stacks::index::storage::{TrieFileStorage};
     use rusqlite::NO_PARAMS;
+    use std::rc::Rc;
 
     #[test]
     pub fn bad_syntax_test() {
         let marf = MarfedKV::temporary();
-        let mut clarity_instance = ClarityInstance::new(marf, ExecutionCost::max_value());
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
 
         let contract_identifier = QualifiedContractIdentifier::local("foo").unwrap();
 
@@ -662,7 +744,7 @@ mod tests {
     #[test]
     pub fn tx_rollback() {
         let marf = MarfedKV::temporary();
-        let mut clarity_instance = ClarityInstance::new(marf, ExecutionCost::max_value());
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
 
         let contract_identifier = QualifiedContractIdentifier::local("foo").unwrap();
         let contract = "(define-public (foo (x int) (y int)) (ok (+ x y)))";
@@ -717,10 +799,75 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn tx_rollback_drops_cached_analyses() {
+        let marf = MarfedKV::temporary();
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
+
+        let foo = QualifiedContractIdentifier::local("foo").unwrap();
+        let bar = QualifiedContractIdentifier::local("bar").unwrap();
+        let contract = "(define-public (foo (x int) (y int)) (ok (+ x y)))";
+
+        let mut conn = clarity_instance.begin_block(&StacksBlockId::sentinel(),
+                                                    &StacksBlockId([0 as u8; 32]),
+                                                    &NULL_HEADER_DB);
+
+        {
+            let mut tx = conn.start_transaction_processing();
+            let (ct_ast, ct_analysis) = tx.analyze_smart_contract(&foo, &contract).unwrap();
+            tx.initialize_smart_contract(&foo, &ct_ast, &contract, |_,_| false).unwrap();
+            tx.save_analysis(&foo, &ct_analysis).unwrap();
+            tx.commit();
+        }
+
+        // the cache lives on the block connection, across analysis databases.
+        let cached = conn.with_analysis_db_readonly(|db| db.load_contract(&foo)).unwrap();
+        assert!(Rc::ptr_eq(&cached, &conn.with_analysis_db_readonly(|db| db.load_contract(&foo)).unwrap()));
+
+        {
+            let mut tx = conn.start_transaction_processing();
+            let (ct_ast, ct_analysis) = tx.analyze_smart_contract(&bar, &contract).unwrap();
+            tx.initialize_smart_contract(&bar, &ct_ast, &contract, |_,_| false).unwrap();
+            tx.save_analysis(&bar, &ct_analysis).unwrap();
+            assert!(tx.with_analysis_db_readonly(|db| db.load_contract(&bar)).is_some());
+            assert!(tx.with_analysis_db_readonly(|db| db.load_contract(&foo)).is_some());
+        }
+
+        // dropping the transaction only drops the analysis it stored.
+        assert!(conn.with_analysis_db_readonly(|db| db.load_contract(&bar)).is_none());
+        assert!(Rc::ptr_eq(&cached, &conn.with_analysis_db_readonly(|db| db.load_contract(&foo)).unwrap()));
+
+        conn.commit_block();
+    }
+
+    #[test]
+    pub fn test_string_literals_versioned_by_height() {
+        let marf = MarfedKV::temporary();
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
+
+        let contract_identifier = QualifiedContractIdentifier::local("foo").unwrap();
+
+        let mut conn = clarity_instance.begin_block(&StacksBlockId::sentinel(),
+                                                    &StacksBlockId([0 as u8; 32]),
+                                                    &NULL_HEADER_DB);
+
+        // below the Clarity2 activation height, "..." is still a buffer.
+        let contract = "(define-read-only (name) \"abc\")";
+        let (ct_ast, _) = conn.as_transaction(
+            |conn| conn.analyze_smart_contract(&contract_identifier, &contract)).unwrap();
+        assert_eq!(ct_ast.expressions[0].match_list().unwrap()[2].match_literal_value(),
+                   Some(&Value::buff_from(b"abc".to_vec()).unwrap()));
+
+        assert_eq!(ClarityVersion::at_block_height(CLARITY2_ACTIVATION_HEIGHT - 1), ClarityVersion::Clarity1);
+        assert_eq!(ClarityVersion::at_block_height(CLARITY2_ACTIVATION_HEIGHT), ClarityVersion::Clarity2);
+
+        conn.commit_block();
+    }
+
     #[test]
     pub fn simple_test() {
         let marf = MarfedKV::temporary();
-        let mut clarity_instance = ClarityInstance::new(marf, ExecutionCost::max_value());
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
 
         let contract_identifier = QualifiedContractIdentifier::local("foo").unwrap();
 
@@ -752,7 +899,7 @@ mod tests {
     #[test]
     pub fn test_block_roll_back() {
         let marf = MarfedKV::temporary();
-        let mut clarity_instance = ClarityInstance::new(marf, ExecutionCost::max_value());
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
         let contract_identifier = QualifiedContractIdentifier::local("foo").unwrap();
 
         {
@@ -786,7 +933,7 @@ mod tests {
     #[test]
     pub fn test_tx_roll_backs() {
         let marf = MarfedKV::temporary();
-        let mut clarity_instance = ClarityInstance::new(marf, ExecutionCost::max_value());
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
         let contract_identifier = QualifiedContractIdentifier::local("foo").unwrap();
         let sender = StandardPrincipalData::transient().into();
 
@@ -861,7 +1008,7 @@ mod tests {
         use util::hash::Hash160;
 
         let marf = MarfedKV::temporary();
-        let mut clarity_instance = ClarityInstance::new(marf, ExecutionCost::max_value());
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
         let sender = StandardPrincipalData::transient().into();
 
         let spending_cond = TransactionSpendingCondition::Singlesig(SinglesigSpendingCondition {
@@ -880,14 +1027,14 @@ mod tests {
             TransactionAuth::Standard(spending_cond.clone()),
             TransactionPayload::SmartContract(TransactionSmartContract {
                 name: "hello-world".into(),
-                code_body: StacksString::from_str(contract).unwrap() }).into());
+                code_body: StacksString::from_str(contract).unwrap() }, None));
 
         let tx2 = StacksTransaction::new(
             TransactionVersion::Mainnet,
             TransactionAuth::Standard(spending_cond.clone()),
             TransactionPayload::SmartContract(TransactionSmartContract {
                 name: "hello-world".into(),
-                code_body: StacksString::from_str(contract).unwrap() }).into());
+                code_body: StacksString::from_str(contract).unwrap() }, None));
 
         tx1.post_conditions.push(TransactionPostCondition::STX(PostConditionPrincipal::Origin, FungibleConditionCode::SentEq, 100));
 
@@ -944,7 +1091,7 @@ mod tests {
     #[test]
     pub fn test_block_limit() {
         let marf = MarfedKV::temporary();
-        let mut clarity_instance = ClarityInstance::new(marf, ExecutionCost::max_value());
+        let mut clarity_instance = ClarityInstance::new(false, marf, ExecutionCost::max_value());
         let contract_identifier = QualifiedContractIdentifier::local("foo").unwrap();
         let sender = StandardPrincipalData::transient().into();
 
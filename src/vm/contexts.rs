@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet, BTreeMap, BTreeSet};
 use std::fmt;
+use std::mem;
 use std::convert::TryInto;
 
 use vm::errors::{InterpreterError, CheckErrors, RuntimeErrorType, InterpreterResult as Result};
@@ -11,6 +12,7 @@ use vm::representations::{SymbolicExpression, ClarityName, ContractName};
 use vm::contracts::Contract;
 use vm::ast::ContractAST;
 use vm::costs::{CostTracker, ExecutionCost, LimitedCostTracker, cost_functions, CostErrors};
+use vm::costs::trace::CostTracer;
 use vm::ast;
 use vm::{eval, is_reserved};
 
@@ -20,6 +22,11 @@ use chainstate::stacks::StacksBlockId;
 
 use serde::Serialize;
 
+#[cfg(feature = "wasm")]
+use std::rc::Rc;
+#[cfg(feature = "wasm")]
+use vm::wasm::WasmContract;
+
 pub const MAX_CONTEXT_DEPTH: u16 = 256;
 
 // TODO:
@@ -76,7 +83,47 @@ pub struct GlobalContext<'a> {
     pub event_batches: Vec<EventBatch>,
     pub database: ClarityDatabase<'a>,
     read_only: Vec<bool>,
+    // whether the VM runs on mainnet, which selects the address versions of
+    //   principals derived at runtime (e.g., by `principal-of?`).
+    pub mainnet: bool,
     pub cost_track: LimitedCostTracker,
+    pub eval_hooks: Vec<Box<dyn EvalHook>>,
+    #[cfg(feature = "wasm")]
+    pub wasm_contracts: HashMap<QualifiedContractIdentifier, Rc<WasmContract>>,
+    // if set, the functions of each contract are compiled when it is deployed, and registered
+    //   like `OwnedEnvironment::register_wasm_contract` does.
+    #[cfg(feature = "wasm")]
+    pub compile_contracts: bool,
+}
+
+/**
+ An EvalHook is invoked by `vm::eval` before and after the evaluation of every
+   expression, and by `Environment::execute_contract` before and after every call
+   into a contract's public function. Hooks are used by developer tooling (e.g., the
+   clarity-cli debugger), and must not alter the outcome of the evaluation.
+ */
+pub trait EvalHook {
+    fn will_begin_eval(&mut self, env: &mut Environment, context: &LocalContext, expr: &SymbolicExpression);
+    fn did_finish_eval(&mut self, env: &mut Environment, context: &LocalContext, expr: &SymbolicExpression, result: &Result<Value>);
+
+    fn will_begin_contract_call(&mut self, _env: &mut Environment, _contract_identifier: &QualifiedContractIdentifier,
+                                _function_name: &str, _args: &[Value]) {}
+    fn did_finish_contract_call(&mut self, _env: &mut Environment, _contract_identifier: &QualifiedContractIdentifier,
+                                _function_name: &str, _result: &Result<Value>) {}
+}
+
+/// Invokes `f` with every installed eval hook. The hooks are taken out of the global
+///   context while they run, so that they can be handed a mutable reference to the environment.
+pub fn run_eval_hooks<F>(env: &mut Environment, mut f: F)
+where F: FnMut(&mut dyn EvalHook, &mut Environment) {
+    if env.global_context.eval_hooks.is_empty() {
+        return
+    }
+    let mut eval_hooks = mem::replace(&mut env.global_context.eval_hooks, Vec::new());
+    for eval_hook in eval_hooks.iter_mut() {
+        f(eval_hook.as_mut(), env);
+    }
+    env.global_context.eval_hooks = eval_hooks;
 }
 
 #[derive(Serialize, Deserialize)]
@@ -379,20 +426,49 @@ impl <'a> OwnedEnvironment <'a> {
 
     pub fn new(database: ClarityDatabase<'a>) -> OwnedEnvironment <'a> {
         OwnedEnvironment {
-            context: GlobalContext::new(database, LimitedCostTracker::new_max_limit()),
+            context: GlobalContext::new(false, database, LimitedCostTracker::new_max_limit()),
             default_contract: ContractContext::new(QualifiedContractIdentifier::transient()),
             call_stack: CallStack::new()
         }
     }
 
-    pub fn new_cost_limited(database: ClarityDatabase<'a>, cost_tracker: LimitedCostTracker) -> OwnedEnvironment <'a> {
+    pub fn new_cost_limited(mainnet: bool, database: ClarityDatabase<'a>, cost_tracker: LimitedCostTracker) -> OwnedEnvironment <'a> {
         OwnedEnvironment {
-            context: GlobalContext::new(database, cost_tracker),
+            context: GlobalContext::new(mainnet, database, cost_tracker),
             default_contract: ContractContext::new(QualifiedContractIdentifier::transient()),
             call_stack: CallStack::new()
         }
     }
 
+    /// Installs an eval hook. Hooks are invoked in the order in which they were added.
+    pub fn add_eval_hook(&mut self, eval_hook: Box<dyn EvalHook>) {
+        self.context.eval_hooks.push(eval_hook);
+    }
+
+    /// Traces all of the costs charged to this environment from now on.
+    pub fn add_cost_tracer(&mut self, tracer: CostTracer) {
+        self.context.cost_track.set_tracer(tracer.clone());
+        self.add_eval_hook(Box::new(tracer));
+    }
+
+    /// Registers the compiled functions of a deployed contract, which are then executed in place
+    ///   of the interpreter whenever no eval hook is set.
+    #[cfg(feature = "wasm")]
+    pub fn register_wasm_contract(&mut self, contract: WasmContract) -> Result<()> {
+        let contract_identifier = contract.contract_identifier().clone();
+        self.context.database.begin();
+        let deployed = if self.context.database.has_contract(&contract_identifier) {
+            self.context.database.get_contract(&contract_identifier)
+        } else {
+            Err(CheckErrors::NoSuchContract(contract_identifier.to_string()).into())
+        };
+        self.context.database.roll_back();
+
+        contract.check_against(&deployed?.contract_context)?;
+        self.context.wasm_contracts.insert(contract_identifier, Rc::new(contract));
+        Ok(())
+    }
+
     pub fn get_exec_environment <'b> (&'b mut self, sender: Option<Value>) -> Environment<'b,'a> {
         Environment::new(&mut self.context,
                          &self.default_contract,
@@ -435,13 +511,35 @@ impl <'a> OwnedEnvironment <'a> {
                             |exec_env| exec_env.initialize_contract_from_ast(contract_identifier, contract_content, contract_string))
     }
 
+    /// Initializes a contract whose AST has `init_args` bound as constants, first transferring
+    ///   `amount` microSTX from its issuer to the contract principal. Neither happens if either fails.
+    pub fn initialize_funded_contract_from_ast(&mut self,
+                                               contract_identifier: QualifiedContractIdentifier,
+                                               contract_content: &ContractAST,
+                                               contract_string: &str,
+                                               init_args: &[(ClarityName, Value)],
+                                               amount: u128) -> Result<((), AssetMap, Vec<StacksTransactionEvent>)> {
+        self.execute_in_env(Value::from(contract_identifier.issuer.clone()),
+                            |exec_env| {
+                                if amount > 0 {
+                                    runtime_cost!(cost_functions::STX_TRANSFER, exec_env, 0)?;
+                                    let from = PrincipalData::from(contract_identifier.issuer.clone());
+                                    let to = PrincipalData::from(contract_identifier.clone());
+                                    exec_env.transfer_stx(&from, &to, amount)?;
+                                }
+                                exec_env.initialize_contract_from_ast(contract_identifier.clone(), contract_content, contract_string)?;
+                                exec_env.global_context.database.insert_contract_init_args(&contract_identifier, init_args);
+                                Ok(())
+                            })
+    }
+
     pub fn execute_transaction(&mut self, sender: Value, contract_identifier: QualifiedContractIdentifier, 
                                tx_name: &str, args: &[SymbolicExpression]) -> Result<(Value, AssetMap, Vec<StacksTransactionEvent>)> {
         self.execute_in_env(sender, 
                             |exec_env| exec_env.execute_contract(&contract_identifier, tx_name, args, false))
     }
 
-    #[cfg(test)]
+    /// Credits `amount` microSTX to `recipient`, e.g., to fund the principals of a test.
     pub fn stx_faucet(&mut self, recipient: &PrincipalData, amount: u128) {
         self.execute_in_env(recipient.clone().into(),
                             |env| {
@@ -462,6 +560,10 @@ impl <'a> OwnedEnvironment <'a> {
                             |exec_env| exec_env.eval_read_only(contract, program))
     }
 
+    pub fn get_cost_total(&self) -> ExecutionCost {
+        self.context.cost_track.get_total()
+    }
+
     pub fn begin(&mut self) {
         self.context.begin();
     }
@@ -557,7 +659,8 @@ impl <'a,'b> Environment <'a,'b> {
     }
 
     pub fn eval_read_only(&mut self, contract_identifier: &QualifiedContractIdentifier, program: &str) -> Result<Value> {
-        let parsed = ast::build_ast(contract_identifier, program, self)?
+        let version = self.global_context.database.get_clarity_version();
+        let parsed = ast::build_ast(contract_identifier, program, self, version)?
             .expressions;
 
         if parsed.len() < 1 {
@@ -583,7 +686,8 @@ impl <'a,'b> Environment <'a,'b> {
     pub fn eval_raw(&mut self, program: &str) -> Result<Value> {
         let contract_id = QualifiedContractIdentifier::transient();
 
-        let parsed = ast::build_ast(&contract_id, program, self)?
+        let version = self.global_context.database.get_clarity_version();
+        let parsed = ast::build_ast(&contract_id, program, self, version)?
             .expressions;
         if parsed.len() < 1 {
             return Err(RuntimeErrorType::ParseError("Expected a program of at least length 1".to_string()).into())
@@ -629,7 +733,9 @@ impl <'a,'b> Environment <'a,'b> {
                 return Err(CheckErrors::CircularReference(vec![func_identifier.to_string()]).into())
             }
             self.call_stack.insert(&func_identifier, true);
+            run_eval_hooks(self, |eval_hook, env| eval_hook.will_begin_contract_call(env, contract_identifier, tx_name, &args));
             let res = self.execute_function_as_transaction(&func, &args, Some(&contract.contract_context));
+            run_eval_hooks(self, |eval_hook, env| eval_hook.did_finish_contract_call(env, contract_identifier, tx_name, &res));
             self.call_stack.remove(&func_identifier, true)?;
             res
         })
@@ -665,6 +771,9 @@ impl <'a,'b> Environment <'a,'b> {
     pub fn evaluate_at_block(&mut self, bhh: StacksBlockId, closure: &SymbolicExpression, local: &LocalContext) -> Result<Value> {
         self.global_context.begin_read_only();
 
+        // a simulated block height only applies at the chain tip, not at the block in question.
+        let simulated_block_height = self.global_context.database.clear_simulated_block_height();
+
         let result = self.global_context.database.set_block_hash(bhh)
             .and_then(|prior_bhh| {
                 let result = eval(closure, self, local);
@@ -673,13 +782,18 @@ impl <'a,'b> Environment <'a,'b> {
                 result
             });
 
+        if let Some(block_height) = simulated_block_height {
+            self.global_context.database.set_simulated_block_height(block_height);
+        }
+
         self.global_context.roll_back();
 
         result
     }
 
     pub fn initialize_contract(&mut self, contract_identifier: QualifiedContractIdentifier, contract_content: &str) -> Result<()> {
-        let contract_ast = ast::build_ast(&contract_identifier, contract_content, self)?;
+        let version = self.global_context.database.get_clarity_version();
+        let contract_ast = ast::build_ast(&contract_identifier, contract_content, self, version)?;
         self.initialize_contract_from_ast(contract_identifier, &contract_ast, &contract_content)
     }
 
@@ -739,6 +853,25 @@ impl <'a,'b> Environment <'a,'b> {
         Ok(())
     }
 
+    /// Moves `amount` microSTX between two principals, logging the transfer in the asset map and
+    ///   emitting its event. Unlike `stx-transfer?`, the sender need not be the tx-sender.
+    pub fn transfer_stx(&mut self, from: &PrincipalData, to: &PrincipalData, amount: u128) -> Result<()> {
+        let from_bal = self.global_context.database.get_account_stx_balance(from);
+        if from_bal < amount {
+            return Err(RuntimeErrorType::NotEnoughFunds(amount, from_bal).into())
+        }
+
+        let to_bal = self.global_context.database.get_account_stx_balance(to);
+        let final_to_bal = to_bal.checked_add(amount)
+            .ok_or(RuntimeErrorType::ArithmeticOverflow)?;
+
+        self.global_context.database.set_account_stx_balance(from, from_bal - amount);
+        self.global_context.database.set_account_stx_balance(to, final_to_bal);
+
+        self.global_context.log_stx_transfer(from, amount)?;
+        self.register_stx_transfer_event(from.clone(), to.clone(), amount)
+    }
+
     pub fn register_stx_transfer_event(&mut self, sender: PrincipalData, recipient: PrincipalData, amount: u128) -> Result<()> {
         let event_data = STXTransferEventData {
             sender,
@@ -791,6 +924,19 @@ impl <'a,'b> Environment <'a,'b> {
         Ok(())
     }
 
+    pub fn register_nft_burn_event(&mut self, sender: PrincipalData, value: Value, asset_identifier: AssetIdentifier) -> Result<()> {
+        let event_data = NFTBurnEventData {
+            sender,
+            asset_identifier,
+            value
+        };
+
+        if let Some(batch) = self.global_context.event_batches.last_mut() {
+            batch.events.push(StacksTransactionEvent::NFTEvent(NFTEventType::NFTBurnEvent(event_data)));
+        }
+        Ok(())
+    }
+
     pub fn register_ft_transfer_event(&mut self, sender: PrincipalData, recipient: PrincipalData, amount: u128, asset_identifier: AssetIdentifier) -> Result<()> {
         let event_data = FTTransferEventData {
             sender,
@@ -817,17 +963,35 @@ impl <'a,'b> Environment <'a,'b> {
         }
         Ok(())
     }
+
+    pub fn register_ft_burn_event(&mut self, sender: PrincipalData, amount: u128, asset_identifier: AssetIdentifier) -> Result<()> {
+        let event_data = FTBurnEventData {
+            sender,
+            asset_identifier,
+            amount
+        };
+
+        if let Some(batch) = self.global_context.event_batches.last_mut() {
+            batch.events.push(StacksTransactionEvent::FTEvent(FTEventType::FTBurnEvent(event_data)));
+        }
+        Ok(())
+    }
 }
 
 impl <'a> GlobalContext<'a> {
 
     // Instantiate a new Global Context
-    pub fn new(database: ClarityDatabase, cost_track: LimitedCostTracker) -> GlobalContext {
+    pub fn new(mainnet: bool, database: ClarityDatabase, cost_track: LimitedCostTracker) -> GlobalContext {
         GlobalContext {
-            database, cost_track,
+            database, cost_track, mainnet,
             read_only: Vec::new(),
             asset_maps: Vec::new(),
             event_batches: Vec::new(),
+            eval_hooks: Vec::new(),
+            #[cfg(feature = "wasm")]
+            wasm_contracts: HashMap::new(),
+            #[cfg(feature = "wasm")]
+            compile_contracts: WasmContract::compile_by_default(),
         }
     }
 
@@ -1076,6 +1240,10 @@ impl CallStack {
         self.set.contains(function)
     }
 
+    pub fn frames(&self) -> &[FunctionIdentifier] {
+        &self.stack
+    }
+
     pub fn insert(&mut self, function: &FunctionIdentifier, track: bool) {
         self.stack.push(function.clone());
         if track {
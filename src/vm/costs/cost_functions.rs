@@ -104,6 +104,13 @@ def_runtime_cost!(INT_CAST { Constant(1) });
 def_runtime_cost!(MOD { Constant(1) });
 def_runtime_cost!(POW { Constant(1) });
 def_runtime_cost!(XOR { Constant(1) });
+def_runtime_cost!(BITWISE_AND { Linear(1, 1) });
+def_runtime_cost!(BITWISE_OR { Linear(1, 1) });
+def_runtime_cost!(BITWISE_NOT { Constant(1) });
+def_runtime_cost!(BITWISE_LEFT_SHIFT { Constant(1) });
+def_runtime_cost!(BITWISE_RIGHT_SHIFT { Constant(1) });
+def_runtime_cost!(SQRTI { Constant(1) });
+def_runtime_cost!(LOG2 { Constant(1) });
 def_runtime_cost!(NOT { Constant(1) });
 def_runtime_cost!(EQ { Linear(1, 1) });
 def_runtime_cost!(BEGIN { Constant(1) });
@@ -112,6 +119,9 @@ def_runtime_cost!(SHA256 { Constant(1) });
 def_runtime_cost!(SHA512 { Constant(1) });
 def_runtime_cost!(SHA512T256 { Constant(1) });
 def_runtime_cost!(KECCAK256 { Constant(1) });
+def_runtime_cost!(SECP256K1RECOVER { Constant(1) });
+def_runtime_cost!(SECP256K1VERIFY { Constant(1) });
+def_runtime_cost!(PRINCIPAL_OF { Constant(1) });
 def_runtime_cost!(PRINT { Linear(1, 1) });
 def_runtime_cost!(SOME_CONS { Constant(1) });
 def_runtime_cost!(OK_CONS { Constant(1) });
@@ -133,10 +143,25 @@ def_runtime_cost!(AND { Linear(1, 1) });
 def_runtime_cost!(APPEND { Linear(1, 1) });
 def_runtime_cost!(CONCAT { Linear(1, 1) });
 def_runtime_cost!(AS_MAX_LEN { Constant(1) });
+def_runtime_cost!(STRING_ASCII_CONS { Linear(1, 1) });
+def_runtime_cost!(STRING_UTF8_CONS { Linear(1, 1) });
+def_runtime_cost!(ELEMENT_AT { Constant(1) });
+def_runtime_cost!(INDEX_OF { Linear(1, 1) });
+def_runtime_cost!(SLICE { Linear(1, 1) });
+def_runtime_cost!(BUFF_TO_INT { Constant(1) });
+def_runtime_cost!(INT_TO_BUFF { Constant(1) });
 
 def_runtime_cost!(CONTRACT_CALL { Constant(1) });
 def_runtime_cost!(CONTRACT_OF { Constant(1) });
 
+pub const IMPLEMENTS_TRAIT: SimpleCostSpecification = SimpleCostSpecification {
+    write_length: Constant(0),
+    write_count: Constant(0),
+    runtime: Constant(1),
+    read_count: Constant(1),
+    read_length: Constant(1)
+};
+
 pub const AT_BLOCK: SimpleCostSpecification = SimpleCostSpecification {
     write_length: Constant(0),
     write_count: Constant(0),
@@ -266,6 +291,20 @@ pub const FT_BALANCE: SimpleCostSpecification = SimpleCostSpecification {
     read_count: Constant(1),
     read_length: Constant(1) };
 
+pub const FT_SUPPLY: SimpleCostSpecification = SimpleCostSpecification {
+    write_length: Constant(0),
+    write_count: Constant(0),
+    runtime: Constant(1),
+    read_count: Constant(1),
+    read_length: Constant(1) };
+
+pub const FT_BURN: SimpleCostSpecification = SimpleCostSpecification {
+    write_length: Constant(1),
+    write_count: Constant(2),
+    runtime: Constant(1),
+    read_count: Constant(2),
+    read_length: Constant(1) };
+
 pub const NFT_MINT: SimpleCostSpecification = SimpleCostSpecification {
     write_length: Constant(1),
     write_count: Constant(1),
@@ -280,6 +319,13 @@ pub const NFT_TRANSFER: SimpleCostSpecification = SimpleCostSpecification {
     read_count: Constant(1),
     read_length: Constant(1) };
 
+pub const NFT_BURN: SimpleCostSpecification = SimpleCostSpecification {
+    write_length: Constant(1),
+    write_count: Constant(1),
+    runtime: Linear(1, 1),
+    read_count: Constant(1),
+    read_length: Constant(1) };
+
 pub const NFT_OWNER: SimpleCostSpecification = SimpleCostSpecification {
     write_length: Constant(0),
     write_count: Constant(0),
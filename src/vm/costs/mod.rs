@@ -1,10 +1,12 @@
 pub mod cost_functions;
 pub mod constants;
+pub mod trace;
 
 use std::{fmt, cmp};
 use vm::types::TypeSignature;
 use vm::Value;
 use std::convert::TryFrom;
+use vm::costs::trace::CostTracer;
 
 type Result<T> = std::result::Result<T, CostErrors>;
 
@@ -83,7 +85,8 @@ pub struct LimitedCostTracker {
     total: ExecutionCost,
     limit: ExecutionCost,
     memory: u64,
-    memory_limit: u64
+    memory_limit: u64,
+    tracer: Option<CostTracer>
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -96,11 +99,11 @@ pub enum CostErrors {
 impl LimitedCostTracker {
     pub fn new(limit: ExecutionCost) -> LimitedCostTracker {
         LimitedCostTracker { limit, memory_limit: CLARITY_MEMORY_LIMIT,
-                             total: ExecutionCost::zero(), memory: 0 }
+                             total: ExecutionCost::zero(), memory: 0, tracer: None }
     }
     pub fn new_max_limit() -> LimitedCostTracker {
         LimitedCostTracker { limit: ExecutionCost::max_value(), total: ExecutionCost::zero(),
-                             memory: 0, memory_limit: CLARITY_MEMORY_LIMIT }
+                             memory: 0, memory_limit: CLARITY_MEMORY_LIMIT, tracer: None }
     }
     pub fn get_total(&self) -> ExecutionCost {
         self.total.clone()
@@ -109,10 +112,18 @@ impl LimitedCostTracker {
         // used by the miner to "undo" the cost of a transaction when trying to pack a block.
         self.total = total;
     }
+    /// Forwards every cost charged to this tracker to `tracer`, in addition to
+    ///   counting it against the limit.
+    pub fn set_tracer(&mut self, tracer: CostTracer) {
+        self.tracer = Some(tracer);
+    }
 }
 
 fn add_cost(s: &mut LimitedCostTracker, cost: ExecutionCost) -> std::result::Result<(), CostErrors> {
     s.total.add(&cost)?;
+    if let Some(ref mut tracer) = s.tracer {
+        tracer.add_cost(cost)?;
+    }
     if s.total.exceeds(&s.limit) {
         Err(CostErrors::CostBalanceExceeded(s.total.clone(), s.limit.clone()))
     } else {
@@ -0,0 +1,328 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use vm::contexts::{Environment, LocalContext, EvalHook};
+use vm::costs::{ExecutionCost, CostTracker, CostErrors};
+use vm::errors::InterpreterResult as Result;
+use vm::representations::SymbolicExpression;
+use vm::types::{PrincipalData, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CostDimension {
+    Runtime,
+    ReadCount,
+    ReadLength,
+    WriteCount,
+    WriteLength
+}
+
+impl CostDimension {
+    pub fn parse(name: &str) -> Option<CostDimension> {
+        match name {
+            "runtime" => Some(CostDimension::Runtime),
+            "read_count" => Some(CostDimension::ReadCount),
+            "read_length" => Some(CostDimension::ReadLength),
+            "write_count" => Some(CostDimension::WriteCount),
+            "write_length" => Some(CostDimension::WriteLength),
+            _ => None
+        }
+    }
+
+    pub fn of(&self, cost: &ExecutionCost) -> u64 {
+        match self {
+            CostDimension::Runtime => cost.runtime,
+            CostDimension::ReadCount => cost.read_count,
+            CostDimension::ReadLength => cost.read_length,
+            CostDimension::WriteCount => cost.write_count,
+            CostDimension::WriteLength => cost.write_length
+        }
+    }
+}
+
+/// The cost attributed to a single function (native or user-defined) over
+///   all of its invocations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionCost {
+    pub calls: u64,
+    /// the cost incurred by the function itself, excluding the cost of
+    ///   the expressions it evaluated.
+    pub self_cost: ExecutionCost,
+    /// the cost incurred while the function was being evaluated. Nested calls
+    ///   of the same function (e.g., `(+ (+ 1 2) 3)`) are counted once per call.
+    pub total_cost: ExecutionCost
+}
+
+struct TracedExpression {
+    // the function applied by this expression, if it is a function application.
+    label: Option<String>,
+    // the cost incurred so far while evaluating this expression, including the
+    //   cost of the sub-expressions which finished evaluating.
+    cost: ExecutionCost
+}
+
+#[derive(Default)]
+struct TraceState {
+    // the call stack path of the outermost traced expression. Empty while no
+    //   expression is being evaluated.
+    root: Vec<String>,
+    expressions: Vec<TracedExpression>,
+    stacks: BTreeMap<Vec<String>, ExecutionCost>,
+    functions: BTreeMap<String, FunctionCost>
+}
+
+/// A `CostTracker` which records every cost charged to it against the call stack
+///   it was charged in. The tracer is installed alongside an environment's
+///   `LimitedCostTracker`, which forwards all of its costs to the tracer, so tracing
+///   does not alter the costs or limits of the traced execution. The call stack is
+///   followed through the tracer's `EvalHook` implementation.
+///
+/// The tracer is cheaply cloneable: install one clone into an `OwnedEnvironment`
+///   with `add_cost_tracer`, and read the report from another.
+///
+/// Call stack frames are labeled by the function applied: user-defined functions
+///   are labeled `<contract>:<function>`, and native functions by their name.
+///   Costs incurred outside of any evaluated expression (e.g., loading a contract)
+///   are attributed to the `<top-level>` stack.
+#[derive(Clone, Default)]
+pub struct CostTracer {
+    state: Rc<RefCell<TraceState>>
+}
+
+impl fmt::Debug for CostTracer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CostTracer")
+    }
+}
+
+// two tracers are equal when they share the same recorded state, i.e., one is a clone
+//   of the other. A cost tracker with a tracer is never equal to one without.
+impl PartialEq for CostTracer {
+    fn eq(&self, other: &CostTracer) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+fn accumulate(total: &mut ExecutionCost, other: &ExecutionCost) {
+    // traced costs never exceed the tracker's running total, which itself
+    //   cannot overflow.
+    total.add(other).expect("Overflow while accumulating traced costs");
+}
+
+fn function_label(env: &Environment, expr: &SymbolicExpression) -> Option<String> {
+    let list = expr.match_list()?;
+    let function_name = list.first()?.match_atom()?;
+
+    if env.contract_context.functions.contains_key(function_name.as_str()) {
+        return Some(format!("{}:{}", env.contract_context.contract_identifier, function_name.as_str()))
+    }
+
+    if function_name.as_str() == "contract-call?" {
+        if let (Some(contract), Some(called)) = (list.get(1), list.get(2).and_then(|x| x.match_atom())) {
+            if let Some(Value::Principal(PrincipalData::Contract(ref contract_identifier))) = contract.match_literal_value() {
+                return Some(format!("{}:{}", contract_identifier, called.as_str()))
+            }
+        }
+    }
+
+    Some(function_name.to_string())
+}
+
+impl CostTracer {
+    pub fn new() -> CostTracer {
+        CostTracer::default()
+    }
+
+    /// Clears all of the recorded costs.
+    pub fn reset(&self) {
+        *self.state.borrow_mut() = TraceState::default();
+    }
+
+    /// Returns the cost incurred by each call stack, excluding the cost of
+    ///   the function calls made from that stack.
+    pub fn stack_costs(&self) -> Vec<(Vec<String>, ExecutionCost)> {
+        self.state.borrow().stacks.iter()
+            .map(|(stack, cost)| (stack.clone(), cost.clone()))
+            .collect()
+    }
+
+    pub fn function_costs(&self) -> Vec<(String, FunctionCost)> {
+        self.state.borrow().functions.iter()
+            .map(|(name, cost)| (name.clone(), cost.clone()))
+            .collect()
+    }
+
+    /// Writes the recorded costs in the "folded stacks" format consumed by flame
+    ///   graph tools: one `frame;frame;...;frame <cost>` line per call stack.
+    pub fn write_folded_stacks<W: Write>(&self, dimension: CostDimension, output: &mut W) -> io::Result<()> {
+        for (stack, cost) in self.state.borrow().stacks.iter() {
+            let cost = dimension.of(cost);
+            if cost > 0 {
+                if stack.is_empty() {
+                    writeln!(output, "<top-level> {}", cost)?;
+                } else {
+                    writeln!(output, "{} {}", stack.join(";"), cost)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a table of the costs attributed to each function, sorted by
+    ///   their self cost in the given dimension.
+    pub fn write_summary<W: Write>(&self, dimension: CostDimension, output: &mut W) -> io::Result<()> {
+        let mut functions = self.function_costs();
+        functions.sort_by(|(_, a), (_, b)| dimension.of(&b.self_cost).cmp(&dimension.of(&a.self_cost)));
+
+        writeln!(output, "{:<50} {:>8} {:>12} {:>12} {:>12} {:>12} {:>12}",
+                 "function", "calls", "runtime", "read_count", "read_length", "write_count", "write_length")?;
+        for (name, cost) in functions.iter() {
+            let self_cost = &cost.self_cost;
+            writeln!(output, "{:<50} {:>8} {:>12} {:>12} {:>12} {:>12} {:>12}",
+                     name, cost.calls, self_cost.runtime, self_cost.read_count, self_cost.read_length,
+                     self_cost.write_count, self_cost.write_length)?;
+        }
+        Ok(())
+    }
+}
+
+impl TraceState {
+    fn current_stack(&self) -> Vec<String> {
+        let mut stack = self.root.clone();
+        stack.extend(self.expressions.iter().filter_map(|x| x.label.clone()));
+        stack
+    }
+}
+
+impl TraceState {
+    fn function_cost(&mut self, label: String) -> &mut FunctionCost {
+        self.functions.entry(label)
+            .or_insert_with(|| FunctionCost { calls: 0, self_cost: ExecutionCost::zero(), total_cost: ExecutionCost::zero() })
+    }
+}
+
+impl CostTracker for CostTracer {
+    fn add_cost(&mut self, cost: ExecutionCost) -> std::result::Result<(), CostErrors> {
+        let mut state = self.state.borrow_mut();
+        let stack = state.current_stack();
+        accumulate(state.stacks.entry(stack).or_insert_with(ExecutionCost::zero), &cost);
+
+        let label = match state.expressions.last_mut() {
+            Some(expression) => {
+                accumulate(&mut expression.cost, &cost);
+                expression.label.clone()
+            },
+            None => None
+        };
+        if let Some(label) = label {
+            accumulate(&mut state.function_cost(label).self_cost, &cost);
+        }
+        Ok(())
+    }
+    // memory is not traced.
+    fn add_memory(&mut self, _memory: u64) -> std::result::Result<(), CostErrors> {
+        Ok(())
+    }
+    fn drop_memory(&mut self, _memory: u64) {}
+    fn reset_memory(&mut self) {}
+}
+
+impl EvalHook for CostTracer {
+    fn will_begin_eval(&mut self, env: &mut Environment, _context: &LocalContext, expr: &SymbolicExpression) {
+        let label = function_label(env, expr);
+
+        let mut state = self.state.borrow_mut();
+        if state.expressions.is_empty() {
+            state.root = env.call_stack.frames().iter()
+                .map(|frame| frame.to_string())
+                .collect();
+        }
+        state.expressions.push(TracedExpression { label, cost: ExecutionCost::zero() });
+    }
+
+    fn did_finish_eval(&mut self, _env: &mut Environment, _context: &LocalContext, _expr: &SymbolicExpression, _result: &Result<Value>) {
+        let mut state = self.state.borrow_mut();
+        let expression = match state.expressions.pop() {
+            Some(expression) => expression,
+            None => return
+        };
+
+        match state.expressions.last_mut() {
+            Some(parent) => accumulate(&mut parent.cost, &expression.cost),
+            None => state.root.clear()
+        }
+
+        if let Some(label) = expression.label {
+            let function = state.function_cost(label);
+            function.calls += 1;
+            accumulate(&mut function.total_cost, &expression.cost);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use vm::contexts::OwnedEnvironment;
+    use vm::database::MemoryBackingStore;
+    use vm::types::QualifiedContractIdentifier;
+    use vm::tests::symbols_from_values;
+
+    const CONTRACT: &str = "(define-data-var counter int 0)
+(define-private (double (x int))
+  (* x 2))
+(define-public (quadruple (y int))
+  (let ((z (double y)))
+    (var-set counter (+ (var-get counter) 1))
+    (ok (double z))))";
+
+    #[test]
+    fn test_cost_tracing() {
+        let contract_identifier = QualifiedContractIdentifier::local("math").unwrap();
+        let sender = PrincipalData::parse_standard_principal("SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR").unwrap();
+
+        let mut marf = MemoryBackingStore::new();
+        let mut owned_env = OwnedEnvironment::new(marf.as_clarity_db());
+        owned_env.initialize_contract(contract_identifier.clone(), CONTRACT).unwrap();
+
+        let tracer = CostTracer::new();
+        owned_env.add_cost_tracer(tracer.clone());
+        let (result, _, _) = owned_env.execute_transaction(Value::Principal(PrincipalData::Standard(sender)), contract_identifier,
+                                                           "quadruple", &symbols_from_values(vec![Value::Int(3)])).unwrap();
+        assert_eq!(result, Value::okay(Value::Int(12)).unwrap());
+
+        let double = "S1G2081040G2081040G2081040G208105NK8PE5.math:double";
+        let quadruple = "S1G2081040G2081040G2081040G208105NK8PE5.math:quadruple";
+
+        let functions: BTreeMap<_, _> = tracer.function_costs().into_iter().collect();
+        assert_eq!(functions[double].calls, 2);
+        assert_eq!(functions["*"].calls, 2);
+        assert_eq!(functions["var-set"].calls, 1);
+        assert!(functions["*"].self_cost.runtime > 0);
+        assert!(functions["var-set"].self_cost.write_count > 0);
+        assert!(functions["var-get"].self_cost.read_count > 0);
+        assert!(functions[double].total_cost.runtime >= functions[double].self_cost.runtime);
+
+        let mut folded = Vec::new();
+        tracer.write_folded_stacks(CostDimension::Runtime, &mut folded).unwrap();
+        let folded = String::from_utf8(folded).unwrap();
+        assert!(folded.contains(&format!("{};let;{};* ", quadruple, double)));
+        assert!(folded.contains(&format!("{};let;ok;{};* ", quadruple, double)));
+
+        let mut folded = Vec::new();
+        tracer.write_folded_stacks(CostDimension::WriteCount, &mut folded).unwrap();
+        let folded = String::from_utf8(folded).unwrap();
+        assert_eq!(folded, format!("{};let;var-set 1\n", quadruple));
+
+        // loading the contract is charged before its function is evaluated.
+        let mut folded = Vec::new();
+        tracer.write_folded_stacks(CostDimension::ReadCount, &mut folded).unwrap();
+        let folded = String::from_utf8(folded).unwrap();
+        assert!(folded.starts_with("<top-level> "));
+
+        tracer.reset();
+        assert!(tracer.stack_costs().is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use std::convert::TryFrom;
 use rusqlite::OptionalExtension;
 
 use vm::contracts::Contract;
+use vm::version::ClarityVersion;
 use vm::errors::{Error, InterpreterError, RuntimeErrorType, CheckErrors, InterpreterResult as Result, IncomparableError};
 use vm::types::{Value, OptionalData, TypeSignature, TupleTypeSignature, PrincipalData, StandardPrincipalData, QualifiedContractIdentifier, NONE};
 
@@ -17,10 +18,11 @@ use burnchains::BurnchainHeaderHash;
 use util::hash::{Sha256Sum, Sha512Trunc256Sum};
 use vm::database::{MarfedKV, ClarityBackingStore};
 use vm::database::structures::{
-    FungibleTokenMetadata, NonFungibleTokenMetadata, ContractMetadata,
+    FungibleTokenMetadata, NonFungibleTokenMetadata, ContractMetadata, ContractInitArgs,
     DataMapMetadata, DataVariableMetadata, ClaritySerializable, SimmedBlock,
     ClarityDeserializable
 };
+use vm::representations::ClarityName;
 use vm::database::RollbackWrapper;
 use util::db::{DBConn, FromRow};
 use vm::costs::CostOverflowingMath;
@@ -50,6 +52,7 @@ pub enum StoreType {
 pub struct ClarityDatabase<'a> {
     pub store: RollbackWrapper<'a>,
     headers_db: &'a dyn HeadersDB,
+    simulated_block_height: Option<u32>,
 }
 
 pub trait HeadersDB {
@@ -147,12 +150,13 @@ impl <'a> ClarityDatabase <'a> {
     pub fn new(store: &'a mut dyn ClarityBackingStore, headers_db: &'a dyn HeadersDB) -> ClarityDatabase<'a> {
         ClarityDatabase {
             store: RollbackWrapper::new(store),
-            headers_db
+            headers_db,
+            simulated_block_height: None
         }
     }
 
     pub fn new_with_rollback_wrapper(store: RollbackWrapper<'a>, headers_db: &'a dyn HeadersDB) -> ClarityDatabase<'a> {
-        ClarityDatabase { store, headers_db }
+        ClarityDatabase { store, headers_db, simulated_block_height: None }
     }
 
     pub fn initialize(&mut self) {
@@ -223,6 +227,22 @@ impl <'a> ClarityDatabase <'a> {
         self.fetch_metadata(contract_identifier, &key).ok().flatten()
     }
 
+    /// Stores the init arguments a contract was deployed with: they are bound as constants ahead of
+    ///   its source, so the source alone does not describe the contract that was executed.
+    pub fn insert_contract_init_args(&mut self, contract_identifier: &QualifiedContractIdentifier, init_args: &[(ClarityName, Value)]) {
+        if STORE_CONTRACT_SRC_INTERFACE && init_args.len() > 0 {
+            let key = ClarityDatabase::make_metadata_key(StoreType::Contract, "contract-init-args");
+            self.insert_metadata(contract_identifier, &key, &ContractInitArgs { arguments: init_args.to_vec() });
+        }
+    }
+
+    pub fn get_contract_init_args(&mut self, contract_identifier: &QualifiedContractIdentifier) -> Vec<(ClarityName, Value)> {
+        let key = ClarityDatabase::make_metadata_key(StoreType::Contract, "contract-init-args");
+        self.fetch_metadata::<ContractInitArgs>(contract_identifier, &key).ok().flatten()
+            .map(|init_args| init_args.arguments)
+            .unwrap_or_default()
+    }
+
     fn insert_metadata <T: ClaritySerializable> (&mut self, contract_identifier: &QualifiedContractIdentifier, key: &str, data: &T) {
         if self.store.has_metadata_entry(contract_identifier, key) {
             panic!("Metadata entry '{}' already exists for contract: {}", key, contract_identifier);
@@ -294,9 +314,34 @@ impl <'a> ClarityDatabase <'a> {
     }
 
     pub fn get_current_block_height(&mut self) -> u32 {
+        match self.simulated_block_height {
+            Some(block_height) => block_height,
+            None => self.store.get_current_block_height()
+        }
+    }
+
+    /// The version of Clarity that source parsed at the current block height is read as.
+    pub fn get_clarity_version(&mut self) -> ClarityVersion {
+        ClarityVersion::at_block_height(self.get_current_block_height())
+    }
+
+    /// The height of the chain tip this database was opened at, ignoring any simulated block height.
+    ///  Block info lookups must be bounded by this height, since later blocks do not exist.
+    pub fn get_chain_tip_height(&mut self) -> u32 {
         self.store.get_current_block_height()
     }
 
+    /// Make `block-height` evaluate to `block_height`, e.g., to simulate a transaction
+    ///  mined at a later block.  This is never used when processing blocks.
+    pub fn set_simulated_block_height(&mut self, block_height: u32) {
+        self.simulated_block_height = Some(block_height);
+    }
+
+    /// Stop simulating a block height, returning the simulated height if there was one.
+    pub fn clear_simulated_block_height(&mut self) -> Option<u32> {
+        self.simulated_block_height.take()
+    }
+
     pub fn get_block_header_hash(&mut self, block_height: u32) -> BlockHeaderHash {
         let id_bhh = self.get_index_block_header_hash(block_height);
         self.headers_db.get_stacks_block_header_hash_for_block(&id_bhh)
@@ -483,13 +528,16 @@ impl <'a> ClarityDatabase <'a> {
 
 impl <'a> ClarityDatabase <'a> {
     pub fn create_fungible_token(&mut self, contract_identifier: &QualifiedContractIdentifier, token_name: &str, total_supply: &Option<u128>) {
-        let data = FungibleTokenMetadata { total_supply: total_supply.clone() };
+        // uncapped tokens only track their supply from Clarity2: the supply of an uncapped token
+        //   defined earlier cannot be known, since its mints were never counted.
+        let supply_tracked = total_supply.is_none() && self.get_clarity_version() >= ClarityVersion::Clarity2;
+        let data = FungibleTokenMetadata { total_supply: total_supply.clone(), supply_tracked };
 
         let key = ClarityDatabase::make_metadata_key(StoreType::FungibleTokenMeta, token_name);
         self.insert_metadata(contract_identifier, &key, &data);
 
         // total supply _is_ included in the consensus hash
-        if total_supply.is_some() {
+        if data.tracks_supply() {
             let supply_key = ClarityDatabase::make_key_for_trip(contract_identifier, StoreType::CirculatingSupply, token_name);
             self.put(&supply_key, &(0 as u128));
         }
@@ -517,26 +565,57 @@ impl <'a> ClarityDatabase <'a> {
             .ok_or(CheckErrors::NoSuchNFT(token_name.to_string()).into())
     }
 
+    fn get_token_supply(&mut self, key: &str) -> u128 {
+        self.get(key)
+            .expect("ERROR: Clarity VM failed to track token supply.")
+    }
+
     pub fn checked_increase_token_supply(&mut self, contract_identifier: &QualifiedContractIdentifier, token_name: &str, amount: u128) -> Result<()> {
         let descriptor = self.load_ft(contract_identifier, token_name)?;
+        if !descriptor.tracks_supply() {
+            return Ok(())
+        }
 
-        if let Some(total_supply) = descriptor.total_supply {
-            let key = ClarityDatabase::make_key_for_trip(contract_identifier, StoreType::CirculatingSupply, token_name);
-            let current_supply: u128 = self.get(&key)
-                .expect("ERROR: Clarity VM failed to track token supply.");
- 
-            let new_supply = current_supply.checked_add(amount)
-                .ok_or(RuntimeErrorType::ArithmeticOverflow)?;
+        let key = ClarityDatabase::make_key_for_trip(contract_identifier, StoreType::CirculatingSupply, token_name);
+        let current_supply = self.get_token_supply(&key);
+
+        let new_supply = current_supply.checked_add(amount)
+            .ok_or(RuntimeErrorType::ArithmeticOverflow)?;
 
+        if let Some(total_supply) = descriptor.total_supply {
             if new_supply > total_supply {
-                Err(RuntimeErrorType::SupplyOverflow(new_supply, total_supply).into())
-            } else {
-                self.put(&key, &new_supply);
-                Ok(())
+                return Err(RuntimeErrorType::SupplyOverflow(new_supply, total_supply).into())
             }
-        } else {
-            Ok(())
         }
+
+        self.put(&key, &new_supply);
+        Ok(())
+    }
+
+    pub fn checked_decrease_token_supply(&mut self, contract_identifier: &QualifiedContractIdentifier, token_name: &str, amount: u128) -> Result<()> {
+        let descriptor = self.load_ft(contract_identifier, token_name)?;
+        if !descriptor.tracks_supply() {
+            return Ok(())
+        }
+
+        let key = ClarityDatabase::make_key_for_trip(contract_identifier, StoreType::CirculatingSupply, token_name);
+        let current_supply = self.get_token_supply(&key);
+
+        let new_supply = current_supply.checked_sub(amount)
+            .ok_or(RuntimeErrorType::SupplyUnderflow(current_supply, amount))?;
+
+        self.put(&key, &new_supply);
+        Ok(())
+    }
+
+    pub fn get_ft_supply(&mut self, contract_identifier: &QualifiedContractIdentifier, token_name: &str) -> Result<u128> {
+        let descriptor = self.load_ft(contract_identifier, token_name)?;
+        if !descriptor.tracks_supply() {
+            return Err(RuntimeErrorType::UntrackedTokenSupply.into())
+        }
+
+        let key = ClarityDatabase::make_key_for_trip(contract_identifier, StoreType::CirculatingSupply, token_name);
+        Ok(self.get_token_supply(&key))
     }
 
     pub fn get_ft_balance(&mut self, contract_identifier: &QualifiedContractIdentifier, token_name: &str, principal: &PrincipalData) -> Result<u128> {
@@ -566,8 +645,11 @@ impl <'a> ClarityDatabase <'a> {
 
         let key = ClarityDatabase::make_key_for_quad(contract_identifier, StoreType::NonFungibleToken, asset_name, asset.serialize());
 
-        let result = self.get(&key);
-        result.ok_or(RuntimeErrorType::NoSuchToken.into())
+        let result: Option<Option<PrincipalData>> = self.get(&key);
+        match result {
+            Some(Some(owner)) => Ok(owner),
+            _ => Err(RuntimeErrorType::NoSuchToken.into())
+        }
     }
 
     pub fn get_nft_key_type(&mut self, contract_identifier: &QualifiedContractIdentifier, asset_name: &str) -> Result<TypeSignature> {
@@ -583,7 +665,21 @@ impl <'a> ClarityDatabase <'a> {
 
         let key = ClarityDatabase::make_key_for_quad(contract_identifier, StoreType::NonFungibleToken, asset_name, asset.serialize());
 
-        self.put(&key, principal);
+        self.put(&key, &Some(principal.clone()));
+
+        Ok(())
+    }
+
+    pub fn burn_nft(&mut self, contract_identifier: &QualifiedContractIdentifier, asset_name: &str, asset: &Value) -> Result<()> {
+        let descriptor = self.load_nft(contract_identifier, asset_name)?;
+        if !descriptor.key_type.admits(asset) {
+            return Err(CheckErrors::TypeValueError(descriptor.key_type, (*asset).clone()).into())
+        }
+
+        let key = ClarityDatabase::make_key_for_quad(contract_identifier, StoreType::NonFungibleToken, asset_name, asset.serialize());
+
+        let no_owner: Option<PrincipalData> = None;
+        self.put(&key, &no_owner);
 
         Ok(())
     }
@@ -1,6 +1,7 @@
 use vm::contracts::Contract;
+use vm::representations::ClarityName;
 use vm::errors::{Error, InterpreterError, RuntimeErrorType, InterpreterResult as Result, IncomparableError};
-use vm::types::{Value, OptionalData, TypeSignature, TupleTypeSignature, PrincipalData, NONE};
+use vm::types::{Value, OptionalData, TypeSignature, TupleTypeSignature, PrincipalData, QualifiedContractIdentifier, NONE};
 
 pub trait ClaritySerializable {
     fn serialize(&self) -> String;
@@ -28,13 +29,13 @@ macro_rules! clarity_serializable {
         impl ClaritySerializable for $Name {
             fn serialize(&self) -> String {
                 serde_json::to_string(self)
-                    .expect("Failed to serialize vm.Value")
+                    .expect(concat!("Failed to serialize ", stringify!($Name)))
             }
         }
         impl ClarityDeserializable<$Name> for $Name {
             fn deserialize(json: &str) -> Self {
                 serde_json::from_str(json)
-                    .expect("Failed to serialize vm.Value")
+                    .expect(concat!("Failed to deserialize ", stringify!($Name)))
             }
         }
     }
@@ -42,7 +43,17 @@ macro_rules! clarity_serializable {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FungibleTokenMetadata {
-    pub total_supply: Option<u128>
+    pub total_supply: Option<u128>,
+    // whether an uncapped token stores its circulating supply: only uncapped tokens defined
+    //   from Clarity2 do. Metadata stored before this field existed reads as `false`.
+    #[serde(default)]
+    pub supply_tracked: bool
+}
+
+impl FungibleTokenMetadata {
+    pub fn tracks_supply(&self) -> bool {
+        self.total_supply.is_some() || self.supply_tracked
+    }
 }
 
 clarity_serializable!(FungibleTokenMetadata);
@@ -69,6 +80,13 @@ pub struct DataVariableMetadata {
 
 clarity_serializable!(DataVariableMetadata);
 
+#[derive(Serialize, Deserialize)]
+pub struct ContractInitArgs {
+    pub arguments: Vec<(ClarityName, Value)>
+}
+
+clarity_serializable!(ContractInitArgs);
+
 #[derive(Serialize, Deserialize)]
 pub struct ContractMetadata {
     pub contract: Contract
@@ -89,7 +107,24 @@ pub struct SimmedBlock {
 clarity_serializable!(SimmedBlock);
 
 clarity_serializable!(PrincipalData);
+clarity_serializable!(QualifiedContractIdentifier);
 clarity_serializable!(i128);
 clarity_serializable!(u128);
 clarity_serializable!(u64);
 clarity_serializable!(Contract);
+
+// non-fungible token owners are stored as optional principals, so that burned tokens
+//   can be recorded as `None`: a `Some` serializes exactly like the bare principal.
+impl ClaritySerializable for Option<PrincipalData> {
+    fn serialize(&self) -> String {
+        serde_json::to_string(self)
+            .expect("Failed to serialize token owner")
+    }
+}
+
+impl ClarityDeserializable<Option<PrincipalData>> for Option<PrincipalData> {
+    fn deserialize(json: &str) -> Self {
+        serde_json::from_str(json)
+            .expect("Failed to deserialize token owner")
+    }
+}
@@ -0,0 +1,552 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::rc::Rc;
+
+use vm::contexts::{Environment, LocalContext, EvalHook};
+use vm::errors::InterpreterResult as Result;
+use vm::representations::SymbolicExpression;
+use vm::types::{QualifiedContractIdentifier, Value};
+
+const HELP_TEXT: &str = "Debugger commands:
+  s, step               evaluate the next expression, stepping into sub-expressions.
+  n, next               evaluate the current expression, stopping at the next one at the same depth.
+  c, continue           continue evaluation until the next breakpoint.
+  p, print [name]       print the local variables, or the value bound to `name`.
+  bt, backtrace         print the current call stack.
+  b, break [location]   set a breakpoint (see below).
+  d, delete [id]        delete a breakpoint.
+  i, breakpoints        list the current breakpoints.
+  h, help               print this message.
+
+Breakpoint locations are one of:
+  function              the body of a function named `function` in any contract.
+  contract.function     the body of `function` in `contract`.
+  line                  the first expression on `line` in any contract.
+  contract:line         the first expression on `line` in `contract`.
+where `contract` is either a fully qualified contract identifier or a contract name.
+";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreakpointLocation {
+    Function(String),
+    Line(u32)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breakpoint {
+    pub id: usize,
+    // either a fully qualified contract identifier or a bare contract name.
+    //   breakpoints without a contract match every contract.
+    pub contract: Option<String>,
+    pub location: BreakpointLocation
+}
+
+impl Breakpoint {
+    fn parse(id: usize, spec: &str) -> Option<Breakpoint> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return None
+        }
+
+        let (contract, location) =
+            if let Ok(line) = spec.parse::<u32>() {
+                (None, BreakpointLocation::Line(line))
+            } else if let Some(split) = spec.rfind(':') {
+                let line = spec[split+1..].parse::<u32>().ok()?;
+                (Some(spec[..split].to_string()), BreakpointLocation::Line(line))
+            } else if let Some(split) = spec.rfind('.') {
+                (Some(spec[..split].to_string()), BreakpointLocation::Function(spec[split+1..].to_string()))
+            } else {
+                (None, BreakpointLocation::Function(spec.to_string()))
+            };
+
+        if let Some(ref contract) = contract {
+            if contract.is_empty() {
+                return None
+            }
+        }
+
+        Some(Breakpoint { id, contract, location })
+    }
+
+    fn matches_contract(&self, contract_identifier: &QualifiedContractIdentifier) -> bool {
+        match self.contract {
+            Some(ref contract) => *contract == contract_identifier.to_string() || contract.as_str() == &*contract_identifier.name,
+            None => true
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Breakpoint {} at ", self.id)?;
+        match (&self.contract, &self.location) {
+            (Some(contract), BreakpointLocation::Function(name)) => write!(f, "function {}.{}", contract, name),
+            (None, BreakpointLocation::Function(name)) => write!(f, "function {}", name),
+            (Some(contract), BreakpointLocation::Line(line)) => write!(f, "line {}:{}", contract, line),
+            (None, BreakpointLocation::Line(line)) => write!(f, "line {}", line),
+        }
+    }
+}
+
+enum StepMode {
+    Continue,
+    Step,
+    // stop at the next expression whose evaluation depth is at most the given depth.
+    Next(usize)
+}
+
+struct DebugState {
+    breakpoints: Vec<Breakpoint>,
+    next_breakpoint_id: usize,
+    step_mode: StepMode,
+    // the lines of the expressions currently being evaluated, innermost last.
+    eval_lines: Vec<Option<u32>>,
+    // the id and depth of the expression the debugger last stopped at.
+    stopped_at: Option<(u64, usize)>,
+    // `None` reads commands from stdin.
+    input: Option<Box<dyn BufRead>>,
+    output: Box<dyn Write>
+}
+
+/// An interactive debugger, driven by the `EvalHook` interface. Clones of a
+///   `Debugger` share their breakpoints and stepping state, so the handle installed
+///   in a `GlobalContext` can be controlled from outside of the evaluation.
+#[derive(Clone)]
+pub struct Debugger {
+    state: Rc<RefCell<DebugState>>
+}
+
+#[cfg(feature = "developer-mode")]
+fn expression_position(expr: &SymbolicExpression) -> Option<(u32, u32)> {
+    if expr.span.start_line == 0 {
+        None
+    } else {
+        Some((expr.span.start_line, expr.span.start_column))
+    }
+}
+
+// without developer-mode, expressions do not carry any span information,
+//   so line breakpoints can never be hit.
+#[cfg(not(feature = "developer-mode"))]
+fn expression_position(_expr: &SymbolicExpression) -> Option<(u32, u32)> {
+    None
+}
+
+// If `expr` is the body of the user function at the top of the call stack, returns
+//   the name of that function.
+fn entered_function_name(env: &Environment, expr: &SymbolicExpression) -> Option<String> {
+    let frame = env.call_stack.frames().last()?.to_string();
+    let name = frame.rsplit(':').next()?;
+    let function = env.contract_context.lookup_function(name)?;
+    if function.get_identifier().to_string() == frame && function.get_body().id == expr.id {
+        Some(name.to_string())
+    } else {
+        None
+    }
+}
+
+impl DebugState {
+    fn is_stepping(&self) -> bool {
+        match self.step_mode {
+            StepMode::Continue => false,
+            StepMode::Step | StepMode::Next(_) => true
+        }
+    }
+
+    fn hit_breakpoint(&self, env: &Environment, expr: &SymbolicExpression, line: Option<u32>, parent_line: Option<u32>) -> Option<usize> {
+        let contract_identifier = &env.contract_context.contract_identifier;
+        let mut entered_function = None;
+
+        for breakpoint in self.breakpoints.iter() {
+            if !breakpoint.matches_contract(contract_identifier) {
+                continue
+            }
+            let is_hit = match breakpoint.location {
+                BreakpointLocation::Line(breakpoint_line) => {
+                    // only stop at the outermost expression on a line.
+                    line == Some(breakpoint_line) && parent_line != line
+                },
+                BreakpointLocation::Function(ref name) => {
+                    if entered_function.is_none() {
+                        entered_function = Some(entered_function_name(env, expr));
+                    }
+                    entered_function.as_ref()
+                        .map(|entered| entered.as_ref() == Some(name))
+                        .unwrap_or(false)
+                }
+            };
+            if is_hit {
+                return Some(breakpoint.id)
+            }
+        }
+        None
+    }
+
+    fn add_breakpoint(&mut self, spec: &str) -> Option<Breakpoint> {
+        let breakpoint = Breakpoint::parse(self.next_breakpoint_id, spec)?;
+        self.next_breakpoint_id += 1;
+        self.breakpoints.push(breakpoint.clone());
+        Some(breakpoint)
+    }
+
+    fn delete_breakpoint(&mut self, id: usize) -> bool {
+        let count = self.breakpoints.len();
+        self.breakpoints.retain(|breakpoint| breakpoint.id != id);
+        count != self.breakpoints.len()
+    }
+
+    fn read_command(&mut self) -> Option<String> {
+        let mut buffer = String::new();
+        let read = match self.input {
+            Some(ref mut input) => input.read_line(&mut buffer),
+            None => io::stdin().read_line(&mut buffer)
+        };
+        match read {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(buffer)
+        }
+    }
+
+    fn print_locals(&mut self, env: &Environment, context: &LocalContext, name: Option<&str>) -> io::Result<()> {
+        if let Some(name) = name {
+            return match context.lookup_variable(name).or_else(|| env.contract_context.lookup_variable(name)) {
+                Some(value) => writeln!(self.output, "{} = {}", name, value),
+                None => writeln!(self.output, "No variable named '{}' in the current context.", name)
+            }
+        }
+
+        // walk from the innermost context outwards, skipping shadowed bindings.
+        let mut printed = HashSet::new();
+        let mut current = Some(context);
+        while let Some(local) = current {
+            let mut names: Vec<_> = local.variables.keys().collect();
+            names.sort();
+            for name in names {
+                if printed.insert(name.as_str()) {
+                    writeln!(self.output, "{} = {}", name.as_str(), local.variables[name])?;
+                }
+            }
+            current = local.parent;
+        }
+
+        if printed.is_empty() {
+            writeln!(self.output, "No local variables.")?;
+        }
+        Ok(())
+    }
+
+    fn print_backtrace(&mut self, env: &Environment) -> io::Result<()> {
+        let frames = env.call_stack.frames();
+        if frames.is_empty() {
+            return writeln!(self.output, "The call stack is empty.")
+        }
+        for (depth, frame) in frames.iter().rev().enumerate() {
+            writeln!(self.output, "#{} {}", depth, frame)?;
+        }
+        Ok(())
+    }
+
+    fn print_breakpoints(&mut self) -> io::Result<()> {
+        if self.breakpoints.is_empty() {
+            return writeln!(self.output, "No breakpoints.")
+        }
+        for breakpoint in self.breakpoints.iter() {
+            writeln!(self.output, "{}", breakpoint)?;
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self, env: &Environment, context: &LocalContext, expr: &SymbolicExpression, breakpoint: Option<usize>) -> io::Result<()> {
+        let depth = self.eval_lines.len();
+        self.stopped_at = Some((expr.id, depth));
+
+        if let Some(breakpoint) = breakpoint {
+            writeln!(self.output, "Breakpoint {} hit.", breakpoint)?;
+        }
+        let contract_identifier = &env.contract_context.contract_identifier;
+        match expression_position(expr) {
+            Some((line, column)) => writeln!(self.output, "{}:{}:{}: {}", contract_identifier, line, column, expr)?,
+            None => writeln!(self.output, "{}: {}", contract_identifier, expr)?
+        }
+
+        loop {
+            write!(self.output, "(debug) ")?;
+            self.output.flush()?;
+
+            let command = match self.read_command() {
+                Some(command) => command,
+                None => {
+                    // the input was closed: let the evaluation run to completion.
+                    self.step_mode = StepMode::Continue;
+                    return Ok(())
+                }
+            };
+
+            let mut words = command.trim().splitn(2, char::is_whitespace);
+            let name = words.next().filter(|x| !x.is_empty());
+            let argument = words.next().map(|x| x.trim()).filter(|x| !x.is_empty());
+            match name {
+                Some("s") | Some("step") => {
+                    self.step_mode = StepMode::Step;
+                    return Ok(())
+                },
+                Some("n") | Some("next") => {
+                    self.step_mode = StepMode::Next(depth);
+                    return Ok(())
+                },
+                Some("c") | Some("continue") => {
+                    self.step_mode = StepMode::Continue;
+                    return Ok(())
+                },
+                Some("p") | Some("print") => {
+                    self.print_locals(env, context, argument)?;
+                },
+                Some("bt") | Some("backtrace") => {
+                    self.print_backtrace(env)?;
+                },
+                Some("b") | Some("break") => {
+                    match self.add_breakpoint(argument.unwrap_or("")) {
+                        Some(breakpoint) => writeln!(self.output, "{}", breakpoint)?,
+                        None => writeln!(self.output, "Invalid breakpoint location. Type 'help' for usage.")?
+                    }
+                },
+                Some("d") | Some("delete") => {
+                    match argument.and_then(|x| x.parse::<usize>().ok()) {
+                        Some(id) if self.delete_breakpoint(id) => writeln!(self.output, "Deleted breakpoint {}.", id)?,
+                        _ => writeln!(self.output, "No such breakpoint.")?
+                    }
+                },
+                Some("i") | Some("breakpoints") => {
+                    self.print_breakpoints()?;
+                },
+                Some("h") | Some("help") => {
+                    write!(self.output, "{}", HELP_TEXT)?;
+                },
+                None => {},
+                Some(unknown) => {
+                    writeln!(self.output, "Unknown command '{}'. Type 'help' for a list of commands.", unknown)?;
+                }
+            }
+        }
+    }
+}
+
+impl Debugger {
+    /// Creates a debugger which reads its commands from stdin and writes to stdout.
+    pub fn new() -> Debugger {
+        Debugger::with_io(None, Box::new(io::stdout()))
+    }
+
+    pub fn with_io(input: Option<Box<dyn BufRead>>, output: Box<dyn Write>) -> Debugger {
+        Debugger {
+            state: Rc::new(RefCell::new(DebugState {
+                breakpoints: Vec::new(),
+                next_breakpoint_id: 1,
+                step_mode: StepMode::Continue,
+                eval_lines: Vec::new(),
+                stopped_at: None,
+                input, output
+            }))
+        }
+    }
+
+    /// Parses and adds a breakpoint, returning `None` if the location is malformed.
+    pub fn add_breakpoint(&self, spec: &str) -> Option<Breakpoint> {
+        self.state.borrow_mut().add_breakpoint(spec)
+    }
+
+    pub fn delete_breakpoint(&self, id: usize) -> bool {
+        self.state.borrow_mut().delete_breakpoint(id)
+    }
+
+    pub fn breakpoints(&self) -> Vec<Breakpoint> {
+        self.state.borrow().breakpoints.clone()
+    }
+
+    /// Stop at the next evaluated expression, regardless of the breakpoints.
+    pub fn step_into_next(&self) {
+        self.state.borrow_mut().step_mode = StepMode::Step;
+    }
+
+    /// Resume evaluation without stopping until the next breakpoint. Used to reset
+    ///   the stepping state after an evaluation is aborted.
+    pub fn reset(&self) {
+        let mut state = self.state.borrow_mut();
+        state.step_mode = StepMode::Continue;
+        state.eval_lines.clear();
+        state.stopped_at = None;
+    }
+}
+
+impl EvalHook for Debugger {
+    fn will_begin_eval(&mut self, env: &mut Environment, context: &LocalContext, expr: &SymbolicExpression) {
+        let mut state = self.state.borrow_mut();
+
+        let line = expression_position(expr).map(|(line, _)| line);
+        let parent_line = state.eval_lines.last().and_then(|x| *x);
+        state.eval_lines.push(line);
+        let depth = state.eval_lines.len();
+
+        let breakpoint = state.hit_breakpoint(env, expr, line, parent_line);
+        let should_stop = breakpoint.is_some() || match state.step_mode {
+            StepMode::Continue => false,
+            StepMode::Step => true,
+            StepMode::Next(stop_depth) => depth <= stop_depth
+        };
+
+        if should_stop {
+            // failing to write to the debugger's output must not affect the evaluation.
+            state.stop(env, context, expr, breakpoint)
+                .unwrap_or_else(|e| eprintln!("Debugger I/O error: {}", e));
+        }
+    }
+
+    fn did_finish_eval(&mut self, _env: &mut Environment, _context: &LocalContext, expr: &SymbolicExpression, result: &Result<Value>) {
+        let mut state = self.state.borrow_mut();
+        let depth = state.eval_lines.len();
+        state.eval_lines.pop();
+
+        if state.stopped_at == Some((expr.id, depth)) {
+            state.stopped_at = None;
+            let written = match result {
+                Ok(value) => writeln!(state.output, "=> {}", value),
+                Err(error) => writeln!(state.output, "=> error: {}", error)
+            };
+            written.unwrap_or_else(|e| eprintln!("Debugger I/O error: {}", e));
+        }
+    }
+
+    fn will_begin_contract_call(&mut self, _env: &mut Environment, contract_identifier: &QualifiedContractIdentifier,
+                                function_name: &str, args: &[Value]) {
+        let mut state = self.state.borrow_mut();
+        if state.is_stepping() {
+            let args: Vec<_> = args.iter().map(|arg| arg.to_string()).collect();
+            writeln!(state.output, "Calling {}.{} with ({})", contract_identifier, function_name, args.join(" "))
+                .unwrap_or_else(|e| eprintln!("Debugger I/O error: {}", e));
+        }
+    }
+
+    fn did_finish_contract_call(&mut self, _env: &mut Environment, contract_identifier: &QualifiedContractIdentifier,
+                                function_name: &str, result: &Result<Value>) {
+        let mut state = self.state.borrow_mut();
+        if state.is_stepping() {
+            let written = match result {
+                Ok(value) => writeln!(state.output, "{}.{} returned {}", contract_identifier, function_name, value),
+                Err(error) => writeln!(state.output, "{}.{} failed: {}", contract_identifier, function_name, error)
+            };
+            written.unwrap_or_else(|e| eprintln!("Debugger I/O error: {}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+    use vm::contexts::OwnedEnvironment;
+    use vm::database::MemoryBackingStore;
+    use vm::types::PrincipalData;
+    use vm::tests::symbols_from_values;
+
+    #[derive(Clone)]
+    struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    const CONTRACT: &str = "(define-private (double (x int))
+  (* x 2))
+(define-public (quadruple (y int))
+  (let ((z (double y)))
+    (ok (double z))))";
+
+    fn run_debugged(breakpoints: &[&str], commands: &str, step_first: bool) -> String {
+        let output = SharedOutput(Rc::new(RefCell::new(Vec::new())));
+        let debugger = Debugger::with_io(Some(Box::new(Cursor::new(commands.as_bytes().to_vec()))),
+                                         Box::new(output.clone()));
+        for breakpoint in breakpoints.iter() {
+            debugger.add_breakpoint(breakpoint).unwrap();
+        }
+
+        let contract_identifier = QualifiedContractIdentifier::local("math").unwrap();
+        let sender = PrincipalData::parse_standard_principal("SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR").unwrap();
+
+        let mut marf = MemoryBackingStore::new();
+        let mut owned_env = OwnedEnvironment::new(marf.as_clarity_db());
+        owned_env.initialize_contract(contract_identifier.clone(), CONTRACT).unwrap();
+
+        owned_env.add_eval_hook(Box::new(debugger.clone()));
+        if step_first {
+            debugger.step_into_next();
+        }
+        let (result, _, _) = owned_env.execute_transaction(Value::Principal(PrincipalData::Standard(sender)), contract_identifier,
+                                                           "quadruple", &symbols_from_values(vec![Value::Int(3)])).unwrap();
+        assert_eq!(result, Value::okay(Value::Int(12)).unwrap());
+
+        let output = output.0.borrow().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_parse_breakpoints() {
+        let expected = [
+            ("double", None, BreakpointLocation::Function("double".into())),
+            ("math.double", Some("math"), BreakpointLocation::Function("double".into())),
+            ("S1G2081040G2081040G2081040G208105NK8PE5.math.double", Some("S1G2081040G2081040G2081040G208105NK8PE5.math"),
+             BreakpointLocation::Function("double".into())),
+            ("4", None, BreakpointLocation::Line(4)),
+            ("math:4", Some("math"), BreakpointLocation::Line(4)) ];
+
+        for (spec, contract, location) in expected.iter() {
+            let breakpoint = Breakpoint::parse(1, spec).unwrap();
+            assert_eq!(breakpoint.contract, contract.map(|x| x.to_string()));
+            assert_eq!(&breakpoint.location, location);
+        }
+
+        assert!(Breakpoint::parse(1, "").is_none());
+        assert!(Breakpoint::parse(1, "math:").is_none());
+        assert!(Breakpoint::parse(1, ".double").is_none());
+    }
+
+    #[test]
+    fn test_function_breakpoints() {
+        let output = run_debugged(&["math.double"], "p\nbt\nc\np x\nd 1\nc\n", false);
+
+        assert_eq!(output.matches("Breakpoint 1 hit.").count(), 2);
+        assert!(output.contains("x = 3\n"));
+        assert!(output.contains("x = 6\n"));
+        assert!(output.contains("#0 S1G2081040G2081040G2081040G208105NK8PE5.math:double\n"));
+        assert!(output.contains("#1 _native_:special_let\n"));
+        assert!(output.contains("Deleted breakpoint 1."));
+    }
+
+    #[test]
+    fn test_stepping() {
+        // `next` over the let-binding of `z` stops at the body of the let.
+        let output = run_debugged(&[], "s\nn\np\nc\n", true);
+
+        assert!(output.contains("Calling S1G2081040G2081040G2081040G208105NK8PE5.math.quadruple with (3)\n"));
+        assert!(output.contains("=> 6\n"));
+        assert!(output.contains("z = 6\n"));
+        assert!(output.contains("y = 3\n"));
+        assert!(!output.contains("Breakpoint"));
+    }
+
+    #[cfg(feature = "developer-mode")]
+    #[test]
+    fn test_line_breakpoints() {
+        let output = run_debugged(&["math:5"], "c\n", false);
+
+        assert_eq!(output.matches("Breakpoint 1 hit.").count(), 1);
+        assert!(output.contains("S1G2081040G2081040G2081040G208105NK8PE5.math:5:5: ( ok ( double z ) )"));
+    }
+}
@@ -1,5 +1,5 @@
 use vm::representations::{Span};
-use std::fmt;
+use std::{fmt, cmp};
 
 /// In a near future, we can go further in our static analysis and provide different levels 
 /// of diagnostics, such as warnings, hints, best practices, etc.
@@ -8,11 +8,16 @@ use std::fmt;
 #[derive(PartialEq)]
 pub enum Level {
     Error,
+    Warning,
 }
 
 pub trait DiagnosableError {
     fn message(&self) -> String;
     fn suggestion(&self) -> Option<String>;
+    /// The expected and found types of a type mismatch, if the error is one.
+    fn expected_found(&self) -> Option<(String, String)> {
+        None
+    }
 }
 
 #[derive(Debug)]
@@ -23,18 +28,122 @@ pub struct Diagnostic {
     pub message: String,
     pub spans: Vec<Span>,
     pub suggestion: Option<String>,
+    pub expected: Option<String>,
+    pub found: Option<String>,
 }
 
 impl Diagnostic {
 
     pub fn err(error: &dyn DiagnosableError) -> Diagnostic {
+        let (expected, found) = match error.expected_found() {
+            Some((expected, found)) => (Some(expected), Some(found)),
+            None => (None, None)
+        };
         Diagnostic {
             spans: vec![],
             level: Level::Error,
             message: error.message(),
             suggestion: error.suggestion(),
+            expected,
+            found
         }
     }
+
+    pub fn warning(error: &dyn DiagnosableError) -> Diagnostic {
+        Diagnostic {
+            level: Level::Warning,
+            ..Diagnostic::err(error)
+        }
+    }
+
+    fn level_name(&self) -> &'static str {
+        match self.level {
+            Level::Error => "error",
+            Level::Warning => "warning"
+        }
+    }
+
+    // spans are zeroed when the developer-mode feature is disabled.
+    fn located_spans(&self) -> Vec<&Span> {
+        self.spans.iter().filter(|span| span.start_line > 0).collect()
+    }
+
+    /// Renders the diagnostic in the style of rustc: the message, followed by the
+    ///   location and an underlined excerpt of `source` for each span, then the
+    ///   expected and found types and the suggestion, if any.
+    pub fn render(&self, file_name: &str, source: &str) -> String {
+        let lines: Vec<&str> = source.lines().collect();
+        let spans = self.located_spans();
+        let gutter = spans.iter()
+            .map(|span| span.start_line.to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        let mut output = format!("{}: {}\n", self.level_name(), self.message);
+        if spans.is_empty() {
+            output.push_str(&format!("{:w$}--> {}\n", "", file_name, w = gutter));
+        }
+
+        for span in spans.iter() {
+            output.push_str(&format!("{:w$}--> {}:{}:{}\n", "", file_name, span.start_line, span.start_column, w = gutter));
+            output.push_str(&format!("{:w$} |\n", "", w = gutter));
+
+            if let Some(line) = lines.get(span.start_line as usize - 1) {
+                let start = span.start_column as usize;
+                let end = if span.end_line == span.start_line {
+                    span.end_column as usize
+                } else {
+                    line.chars().count()
+                };
+                // keep the tabs of the excerpt, so that the underline stays aligned.
+                let indent: String = line.chars().take(start.saturating_sub(1))
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect();
+                let underline = "^".repeat(cmp::max(end + 1, start + 1) - start);
+
+                output.push_str(&format!("{:>w$} | {}\n", span.start_line, line, w = gutter));
+                output.push_str(&format!("{:w$} | {}{}\n", "", indent, underline, w = gutter));
+            }
+        }
+
+        if let (Some(expected), Some(found)) = (&self.expected, &self.found) {
+            output.push_str(&format!("{:w$} = expected: {}\n", "", expected, w = gutter));
+            output.push_str(&format!("{:w$} = found: {}\n", "", found, w = gutter));
+        }
+        if let Some(suggestion) = &self.suggestion {
+            output.push_str(&format!("{:w$} = help: {}\n", "", suggestion, w = gutter));
+        }
+        output
+    }
+
+    /// Renders the diagnostic as a JSON object, with the excerpt of `source`
+    ///   covered by each span, and the human readable rendering under `rendered`.
+    pub fn to_json(&self, file_name: &str, source: &str) -> serde_json::Value {
+        let lines: Vec<&str> = source.lines().collect();
+        let spans: Vec<_> = self.located_spans().iter()
+            .map(|span| {
+                let excerpt = lines.get(span.start_line as usize - 1).cloned().unwrap_or("");
+                json!({
+                    "start_line": span.start_line,
+                    "start_column": span.start_column,
+                    "end_line": span.end_line,
+                    "end_column": span.end_column,
+                    "excerpt": excerpt
+                })
+            })
+            .collect();
+
+        json!({
+            "level": self.level_name(),
+            "message": self.message,
+            "file": file_name,
+            "spans": spans,
+            "expected": self.expected,
+            "found": self.found,
+            "suggestion": self.suggestion,
+            "rendered": self.render(file_name, source)
+        })
+    }
 }
 
 impl fmt::Display for Diagnostic {
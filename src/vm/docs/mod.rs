@@ -191,6 +191,86 @@ const XOR_API: SimpleFunctionAPI = SimpleFunctionAPI {
 "
 };
 
+const BITWISE_AND_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(bit-and i1 i2...)",
+    description: "Returns the result of bitwise and'ing a variable number of integer inputs.",
+    example: "(bit-and 24 16) ;; Returns 16
+(bit-and 28 24 -1) ;; Returns 24
+(bit-and u7 u6) ;; Returns u6
+"
+};
+
+const BITWISE_OR_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(bit-or i1 i2...)",
+    description: "Returns the result of bitwise inclusive or'ing a variable number of integer inputs.",
+    example: "(bit-or 4 8) ;; Returns 12
+(bit-or 1 2 4) ;; Returns 7
+(bit-or u4 u3) ;; Returns u7
+"
+};
+
+const BITWISE_NOT_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(bit-not i1)",
+    description: "Returns the one's complement of `i1`, i.e., the result of flipping every bit of `i1`.",
+    example: "(bit-not 3) ;; Returns -4
+(bit-not -128) ;; Returns 127
+(bit-not u128) ;; Returns u340282366920938463463374607431768211327
+"
+};
+
+const BITWISE_LEFT_SHIFT_API: SpecialAPI = SpecialAPI {
+    input_type: "int, uint | uint, uint",
+    output_type: "int | uint",
+    signature: "(bit-shift-left i1 shamt)",
+    description: "Shifts all the bits in `i1` to the left by the number of places given by `shamt`, taken
+modulo 128. Bits shifted past the most significant bit are discarded, so this function never
+throws an overflow error: in particular, shifting a bit into the highest position of an `int` produces a
+negative value.",
+    example: "(bit-shift-left 2 u1) ;; Returns 4
+(bit-shift-left u16 u2) ;; Returns u64
+(bit-shift-left 1 u127) ;; Returns -170141183460469231731687303715884105728
+(bit-shift-left 1 u128) ;; Returns 1
+"
+};
+
+const BITWISE_RIGHT_SHIFT_API: SpecialAPI = SpecialAPI {
+    input_type: "int, uint | uint, uint",
+    output_type: "int | uint",
+    signature: "(bit-shift-right i1 shamt)",
+    description: "Shifts all the bits in `i1` to the right by the number of places given by `shamt`, taken
+modulo 128. Bits shifted past the least significant bit are discarded. For an `int` input, the shift is
+arithmetic: the sign bit is copied into the vacated positions.",
+    example: "(bit-shift-right 8 u2) ;; Returns 2
+(bit-shift-right -64 u2) ;; Returns -16
+(bit-shift-right u128 u130) ;; Returns u32
+"
+};
+
+const SQRTI_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(sqrti i1)",
+    description: "Returns the largest integer that is less than or equal to the square root of `i1`.
+If `i1` is a negative integer, throws a runtime error.",
+    example: "(sqrti 11) ;; Returns 3
+(sqrti u1000000) ;; Returns u1000
+(sqrti 0) ;; Returns 0
+"
+};
+
+const LOG2_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(log2 i1)",
+    description: "Returns the base 2 logarithm of `i1`, rounded down to the nearest integer.
+If `i1` is zero or a negative integer, throws a runtime error.",
+    example: "(log2 8) ;; Returns 3
+(log2 1000) ;; Returns 9
+(log2 u1) ;; Returns u0
+"
+};
+
 const AND_API: SimpleFunctionAPI = SimpleFunctionAPI {
     name: None,
     signature: "(and b1 b2 ...)",
@@ -273,13 +353,14 @@ fn make_for_simple_native(api: &SimpleFunctionAPI, function: &NativeFunctions, n
                     in_types.join(" | ")
                 },
                 FunctionType::ArithmeticVariadic => "int, ... | uint, ...".to_string(),
+                FunctionType::ArithmeticUnary => "int | uint".to_string(),
                 FunctionType::ArithmeticBinary | FunctionType::ArithmeticComparison => "int, int | uint, uint".to_string(),
             };
             let output_type = match function_type {
                 FunctionType::Variadic(_, ref out_type) => format!("{}", out_type),
                 FunctionType::Fixed(FixedFunction{ ref returns, .. }) => format!("{}", returns),
                 FunctionType::UnionArgs(_, ref out_type) => format!("{}", out_type),
-                FunctionType::ArithmeticVariadic | FunctionType::ArithmeticBinary => "int | uint".to_string(),
+                FunctionType::ArithmeticVariadic | FunctionType::ArithmeticUnary | FunctionType::ArithmeticBinary => "int | uint".to_string(),
                 FunctionType::ArithmeticComparison => "bool".to_string(),
             };
             (input_type, output_type)
@@ -387,54 +468,214 @@ has to be a literal function name.",
 (fold * (list 2 2 2) 0) ;; Returns 0
 ;; calculates (- 11 (- 7 (- 3 2)))
 (fold - (list 3 7 11) 2) ;; Returns 5 
-(fold concat \"cdef\" \"ab\")   ;; Returns 0x666564636162
-                                ;; hex form of \"fedcab\"  
-(fold concat (list \"cd\" \"ef\") \"ab\")   ;; Returns 0x656663646162
-                                            ;; hex form of \"efcdab\""
+(fold concat 0x63646566 0x6162)   ;; Returns 0x666564636162
+(fold concat (list \"cd\" \"ef\") \"ab\")   ;; Returns \"efcdab\""
 };
 
 const CONCAT_API: SpecialAPI = SpecialAPI {
-    input_type: "(buff, buff)|(list, list)",
-    output_type: "buff|list",
-    signature: "(concat buff-a buff-b)",
-    description: "The `concat` function takes two buffers or two lists with the same entry type,
-and returns a concatenated buffer or list of the same entry type, with max_len = max_len_a + max_len_b.",
-    example: "(concat \"hello \" \"world\") ;; Returns 0x68656c6c6f20776f726c64
-                                            ;; hex form of \"hello world\""
+    input_type: "(buff, buff)|(list, list)|(string-ascii, string-ascii)|(string-utf8, string-utf8)",
+    output_type: "buff|list|string-ascii|string-utf8",
+    signature: "(concat sequence-a sequence-b)",
+    description: "The `concat` function takes two buffers, two strings of the same kind, or two lists with the same entry type,
+and returns a concatenated buffer, string or list of the same entry type, with max_len = max_len_a + max_len_b.",
+    example: "(concat \"hello \" \"world\") ;; Returns \"hello world\"
+(concat 0x0102 0x0304) ;; Returns 0x01020304
+"
 };
 
 const APPEND_API: SpecialAPI = SpecialAPI {
-    input_type: "list A, A",
-    output_type: "list",
+    input_type: "list A, A|buff, (buff 1)|string-ascii, (string-ascii 1)|string-utf8, (string-utf8 1)",
+    output_type: "list|buff|string-ascii|string-utf8",
     signature: "(append (list 1 2 3 4) 5)",
     description: "The `append` function takes a list and another value with the same entry type,
-or a buffer and another buffer of length 1 and outputs a buffer or a list of the same type with max_len += 1.",
-    example: "(append (list 1 2 3 4) 5) ;; Returns (1 2 3 4 5)"
+or a buffer or string and another buffer or string of the same kind with length 1, and outputs a buffer,
+string or list of the same type with max_len += 1.",
+    example: "(append (list 1 2 3 4) 5) ;; Returns (1 2 3 4 5)
+(append \"blockstac\" \"k\") ;; Returns \"blockstack\"
+(append 0x0102 0x03) ;; Returns 0x010203"
 };
 
 const ASSERTS_MAX_LEN_API: SpecialAPI = SpecialAPI {
-    input_type: "buff|list, uint",
-    output_type: "(optional buff|list)",
-    signature: "(as-max-len? buffer u10)",
-    description: "The `as-max-len?` function takes a length N (must be a literal) and a buffer or list argument, which must be typed as a list
-or buffer of length M and outputs that same list or buffer, but typed with max length N.
+    input_type: "buff|list|string-ascii|string-utf8, uint",
+    output_type: "(optional buff|list|string-ascii|string-utf8)",
+    signature: "(as-max-len? sequence u10)",
+    description: "The `as-max-len?` function takes a length N (must be a literal) and a buffer, string or list argument, which must be typed as a list,
+string or buffer of length M and outputs that same list, string or buffer, but typed with max length N.
 
 This function returns an optional type with the resulting iterable. If the input iterable is less than
 or equal to the supplied max-len, it returns `(some <iterable>)`, otherwise it returns `none`.",
     example: "(as-max-len? (list 2 2 2) u3) ;; Returns (some (2 2 2))
-(as-max-len? (list 1 2 3) u2) ;; Returns none"
+(as-max-len? (list 1 2 3) u2) ;; Returns none
+(as-max-len? \"blockstack\" u12) ;; Returns (some \"blockstack\")"
 };
 
 const LEN_API: SpecialAPI = SpecialAPI {
-    input_type: "buff|list",
+    input_type: "buff|list|string-ascii|string-utf8",
     output_type: "uint",
-    signature: "(len buffer)",
-    description: "The `len` function returns the length of a given buffer or list.",
+    signature: "(len sequence)",
+    description: "The `len` function returns the length of a given buffer, string or list. The length
+of a `string-utf8` is its number of characters, not its number of encoded bytes.",
     example: "(len \"blockstack\") ;; Returns u10
+(len u\"stacks \\u{1F600}\") ;; Returns u8
+(len 0x0102) ;; Returns u2
 (len (list 1 2 3 4 5)) ;; Returns u5
 "
 };
 
+const ELEMENT_AT_API: SpecialAPI = SpecialAPI {
+    input_type: "buff|list A|string-ascii|string-utf8, uint",
+    output_type: "(optional buff|A|string-ascii|string-utf8)",
+    signature: "(element-at sequence index)",
+    description: "The `element-at` function returns the element at `index` in the provided buffer, string or list.
+Elements of a buffer or string are returned as a buffer or string of length 1. If `index` is out of bounds,
+this function returns `none`.",
+    example: "(element-at \"blockstack\" u5) ;; Returns (some \"s\")
+(element-at (list 1 2 3 4 5) u5) ;; Returns none
+(element-at (list 1 2 3 4 5) u1) ;; Returns (some 2)
+(element-at 0x0102 u0) ;; Returns (some 0x01)
+"
+};
+
+const INDEX_OF_API: SpecialAPI = SpecialAPI {
+    input_type: "buff|list A|string-ascii|string-utf8, buff|A|string-ascii|string-utf8",
+    output_type: "(optional uint)",
+    signature: "(index-of sequence item)",
+    description: "The `index-of` function returns the index of the first occurrence of `item` in the provided buffer,
+string or list, applying the same equality as `is-eq`. When searching a buffer or string, `item` must be a
+buffer or string of the same kind with length 1. If `item` is not found, this function returns `none`.",
+    example: "(index-of \"blockstack\" \"b\") ;; Returns (some u0)
+(index-of \"blockstack\" \"k\") ;; Returns (some u4)
+(index-of \"blockstack\" \"z\") ;; Returns none
+(index-of (list 1 2 3 4 5) 6) ;; Returns none
+(index-of 0x010203 0x03) ;; Returns (some u2)
+"
+};
+
+const SLICE_API: SpecialAPI = SpecialAPI {
+    input_type: "buff|list|string-ascii|string-utf8, uint, uint",
+    output_type: "(optional buff|list|string-ascii|string-utf8)",
+    signature: "(slice sequence left-position right-position)",
+    description: "The `slice` function returns the elements of the provided buffer, string or list starting at
+`left-position` (inclusive) and ending at `right-position` (exclusive). The result is typed with the same
+max length as the input sequence. If `left-position` is greater than `right-position`, or `right-position`
+is greater than the length of the sequence, this function returns `none`.",
+    example: "(slice \"blockstack\" u5 u10) ;; Returns (some \"stack\")
+(slice (list 1 2 3 4 5) u5 u9) ;; Returns none
+(slice (list 1 2 3 4 5) u3 u4) ;; Returns (some (4))
+(slice 0x010203 u1 u1) ;; Returns (some 0x)
+"
+};
+
+const BUFF_TO_INT_LE_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(buff-to-int-le buffer)",
+    description: "Converts a buffer of at most 16 bytes into an `int`, reading the buffer as a little-endian
+two's complement integer. Buffers shorter than 16 bytes are not an error: they are read as if they were
+padded with zero bytes on their most significant side, so the sign is not extended and `0xff` converts
+to 255 rather than -1. Sign-sensitive callers should pass a full 16 byte buffer.",
+    example: "(buff-to-int-le 0x01) ;; Returns 1
+(buff-to-int-le 0x01000000000000000000000000000000) ;; Returns 1
+(buff-to-int-le 0xffffffffffffffffffffffffffffffff) ;; Returns -1
+(buff-to-int-le 0x) ;; Returns 0
+"
+};
+
+const BUFF_TO_INT_BE_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(buff-to-int-be buffer)",
+    description: "Converts a buffer of at most 16 bytes into an `int`, reading the buffer as a big-endian
+two's complement integer. Buffers shorter than 16 bytes are not an error: they are read as if they were
+padded with zero bytes on their most significant side, so the sign is not extended and `0xff` converts
+to 255 rather than -1. Sign-sensitive callers should pass a full 16 byte buffer.",
+    example: "(buff-to-int-be 0x01) ;; Returns 1
+(buff-to-int-be 0x00000000000000000000000000000001) ;; Returns 1
+(buff-to-int-be 0xffffffffffffffffffffffffffffffff) ;; Returns -1
+(buff-to-int-be 0xff) ;; Returns 255
+"
+};
+
+const BUFF_TO_UINT_LE_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(buff-to-uint-le buffer)",
+    description: "Converts a buffer of at most 16 bytes into a `uint`, reading the buffer as a little-endian
+unsigned integer. Buffers shorter than 16 bytes are not an error: they are read as if they were
+padded with zero bytes on their most significant side.",
+    example: "(buff-to-uint-le 0x0001) ;; Returns u256
+(buff-to-uint-le 0x01000000000000000000000000000000) ;; Returns u1
+(buff-to-uint-le 0x) ;; Returns u0
+"
+};
+
+const BUFF_TO_UINT_BE_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(buff-to-uint-be buffer)",
+    description: "Converts a buffer of at most 16 bytes into a `uint`, reading the buffer as a big-endian
+unsigned integer. Buffers shorter than 16 bytes are not an error: they are read as if they were
+padded with zero bytes on their most significant side.",
+    example: "(buff-to-uint-be 0x0100) ;; Returns u256
+(buff-to-uint-be 0x00000000000000000000000000000001) ;; Returns u1
+(buff-to-uint-be 0x) ;; Returns u0
+"
+};
+
+const INT_TO_BUFF_LE_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(int-to-buff-le value)",
+    description: "Converts an `int` or `uint` into its 16 byte little-endian encoding. `int` values
+are encoded in two's complement.",
+    example: "(int-to-buff-le 1) ;; Returns 0x01000000000000000000000000000000
+(int-to-buff-le u256) ;; Returns 0x00010000000000000000000000000000
+(int-to-buff-le -1) ;; Returns 0xffffffffffffffffffffffffffffffff
+"
+};
+
+const INT_TO_BUFF_BE_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(int-to-buff-be value)",
+    description: "Converts an `int` or `uint` into its 16 byte big-endian encoding. `int` values
+are encoded in two's complement.",
+    example: "(int-to-buff-be 1) ;; Returns 0x00000000000000000000000000000001
+(int-to-buff-be u256) ;; Returns 0x00000000000000000000000000000100
+(int-to-buff-be -1) ;; Returns 0xffffffffffffffffffffffffffffffff
+"
+};
+
+const SECP256K1RECOVER_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(secp256k1-recover? message-hash signature)",
+    description: "The `secp256k1-recover?` function recovers the compressed public key which produced the 65 byte `signature`
+over the 32 byte `message-hash`. The signature is encoded as a one byte recovery id followed by the 64 byte compact
+(r, s) signature, which is the encoding used for Stacks transaction signatures. It returns `(ok public-key)` on success,
+`(err u1)` if the signature could not be decoded and `(err u2)` if no public key could be recovered.",
+    example: "(secp256k1-recover? 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10
+ 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae) ;; Returns (ok 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)"
+};
+
+const SECP256K1VERIFY_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    signature: "(secp256k1-verify message-hash signature public-key)",
+    description: "The `secp256k1-verify` function checks that the 65 byte `signature` over the 32 byte `message-hash` was
+produced by the compressed `public-key`, using the same signature encoding as `secp256k1-recover?`. Signatures with a
+high S value are rejected. It returns `true` if the signature is valid, and `false` otherwise.",
+    example: "(secp256k1-verify 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10
+ 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae
+ 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110) ;; Returns true
+(secp256k1-verify 0x0000000000000000000000000000000000000000000000000000000000000000
+ 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae
+ 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110) ;; Returns false"
+};
+
+const PRINCIPAL_OF_API: SpecialAPI = SpecialAPI {
+    input_type: "(buff 33)",
+    output_type: "(response principal uint)",
+    signature: "(principal-of? public-key)",
+    description: "The `principal-of?` function returns the single-signature principal derived from the compressed
+`public-key`. The principal is constructed with the mainnet address version when the VM is running on mainnet,
+and with the testnet address version otherwise. It returns `(ok principal)` on success, and `(err u1)` if
+`public-key` is not a valid public key.",
+    example: "(principal-of? 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110) ;; Returns (ok ST1AW6EKPGT61SQ9FNVDS17RKNWT8ZP582VF9HSCP)"
+};
+
 const LIST_API: SpecialAPI = SpecialAPI {
     input_type: "A, ...",
     output_type: "(list A)",
@@ -470,7 +711,7 @@ const FETCH_ENTRY_API: SpecialAPI = SpecialAPI {
 The value is looked up using `key-tuple`.
 If there is no value associated with that key in the data map, the function returns a `none` option. Otherwise,
 it returns `(some value)`.",
-    example: "(define-map names-map ((name (buff 10))) ((id int)))
+    example: "(define-map names-map ((name (string-ascii 10))) ((id int)))
 (map-set names-map { name: \"blockstack\" } { id: 1337 })
 (map-get? names-map (tuple (name \"blockstack\"))) ;; Returns (some (tuple (id 1337)))
 (map-get? names-map ((name \"blockstack\"))) ;; Same command, using a shorthand for constructing the tuple
@@ -487,7 +728,7 @@ with the key, the function overwrites that existing association.
 
 Note: the `value-tuple` requires 1 additional byte for storage in the materialized blockchain state,
 and therefore the maximum size of a value that may be inserted into a map is MAX_CLARITY_VALUE - 1.",
-    example: "(define-map names-map ((name (buff 10))) ((id int)))
+    example: "(define-map names-map ((name (string-ascii 10))) ((id int)))
 (map-set names-map { name: \"blockstack\" } { id: 1337 }) ;; Returns true
 (map-set names-map ((name \"blockstack\")) ((id 1337))) ;; Same command, using a shorthand for constructing the tuple
 ",
@@ -504,7 +745,7 @@ this key in the data map, the function returns `false`.
 
 Note: the `value-tuple` requires 1 additional byte for storage in the materialized blockchain state,
 and therefore the maximum size of a value that may be inserted into a map is MAX_CLARITY_VALUE - 1.",
-    example: "(define-map names-map ((name (buff 10))) ((id int)))
+    example: "(define-map names-map ((name (string-ascii 10))) ((id int)))
 (map-insert names-map { name: \"blockstack\" } { id: 1337 }) ;; Returns true
 (map-insert names-map { name: \"blockstack\" } { id: 1337 }) ;; Returns false
 (map-insert names-map ((name \"blockstack\")) ((id 1337))) ;; Same command, using a shorthand for constructing the tuple
@@ -518,7 +759,7 @@ const DELETE_ENTRY_API: SpecialAPI = SpecialAPI {
     description: "The `map-delete` function removes the value associated with the input key for
 the given map. If an item exists and is removed, the function returns `true`.
 If a value did not exist for this key in the data map, the function returns `false`.",
-    example: "(define-map names-map ((name (buff 10))) ((id int)))
+    example: "(define-map names-map ((name (string-ascii 10))) ((id int)))
 (map-insert names-map { name: \"blockstack\" } { id: 1337 }) ;; Returns true
 (map-delete names-map { name: \"blockstack\" }) ;; Returns true
 (map-delete names-map { name: \"blockstack\" }) ;; Returns false
@@ -544,7 +785,7 @@ const TUPLE_GET_API: SpecialAPI = SpecialAPI {
     description: "The `get` function fetches the value associated with a given key from the supplied typed tuple.
 If an `Optional` value is supplied as the inputted tuple, `get` returns an `Optional` type of the specified key in
 the tuple. If the supplied option is a `(none)` option, get returns `(none)`.",
-    example: "(define-map names-map ((name (buff 12))) ((id int)))
+    example: "(define-map names-map ((name (string-ascii 12))) ((id int)))
 (map-insert names-map { name: \"blockstack\" } { id: 1337 }) ;; Returns true
 (get id (tuple (name \"blockstack\") (id 1337))) ;; Returns 1337
 (get id (map-get? names-map (tuple (name \"blockstack\")))) ;; Returns (some 1337)
@@ -629,6 +870,22 @@ const CONTRACT_OF_API: SpecialAPI = SpecialAPI {
 "
 };
 
+const IMPLEMENTS_TRAIT_API: SpecialAPI = SpecialAPI {
+    input_type: "principal, Trait",
+    output_type: "bool",
+    signature: "(implements-trait? contract-principal trait-identifier)",
+    description: "The `implements-trait?` function returns `true` if the contract identified by `contract-principal`
+declared an implementation of the trait, using `impl-trait`, when it was deployed. The trait may be given either
+as a trait reference (e.g., `<token-trait>`) or as a trait identifier (e.g., `.token-a.token-trait`).
+Standard principals never implement traits.",
+    example: "
+(define-trait token-trait ((get-balance (principal) (response uint uint))))
+(impl-trait .docs-test.token-trait)
+(implements-trait? .docs-test <token-trait>) ;; Returns true
+(implements-trait? 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR .docs-test.token-trait) ;; Returns false
+"
+};
+
 const AT_BLOCK: SpecialAPI = SpecialAPI {
     input_type: "(buff 32), A",
     output_type: "A",
@@ -679,9 +936,9 @@ option. If the argument is a response type, and the argument is an `(ok ...)` re
  the inner value of the `ok`. If the supplied argument is either an `(err ...)` or a `(none)` value,
 `unwrap!` _returns_ `thrown-value` from the current function and exits the current control-flow.",
     example: "
-(define-map names-map ((name (buff 12))) ((id int)))
+(define-map names-map ((name (string-ascii 12))) ((id int)))
 (map-set names-map { name: \"blockstack\" } { id: 1337 })
-(define-private (get-name-or-err (name (buff 12)))
+(define-private (get-name-or-err (name (string-ascii 12)))
   (let ((raw-name (unwrap! (map-get? names-map { name: name }) (err 1))))
        (ok raw-name)))
 
@@ -699,7 +956,7 @@ option. If the argument is a response type, and the argument is an `(ok ...)` re
  the inner value of the `ok`. If the supplied argument is either an `(err ...)` or a `none` value,
 `try!` _returns_ either `none` or the `(err ...)` value from the current function and exits the current control-flow.",
     example: "
-(define-map names-map ((name (buff 12))) ((id int)))
+(define-map names-map ((name (string-ascii 12))) ((id int)))
 (map-set names-map { name: \"blockstack\" } { id: 1337 })
 (try! (map-get? names-map { name: \"blockstack\" })) ;; Returns (tuple (id 1337))
 (define-private (checked-even (x int))
@@ -723,7 +980,7 @@ option. If the argument is a response type, and the argument is an `(ok ...)` re
  the inner value of the `ok`. If the supplied argument is either an `(err ...)` or a `(none)` value,
 `unwrap` throws a runtime error, aborting any further processing of the current transaction.",
     example: "
-(define-map names-map ((name (buff 12))) ((id int)))
+(define-map names-map ((name (string-ascii 12))) ((id int)))
 (map-set names-map { name: \"blockstack\" } { id: 1337 })
 (unwrap-panic (map-get? names-map { name: \"blockstack\" })) ;; Returns (tuple (id 1337))
 (unwrap-panic (map-get? names-map { name: \"non-existant\" })) ;; Throws a runtime exception
@@ -792,7 +1049,7 @@ is untyped, you should use `unwrap-panic` or `unwrap-err-panic` instead of `matc
 (add-10 (some 5)) ;; returns 15
 (add-10 none) ;; returns 10
 
-(define-private (add-or-pass-err (x (response int (buff 10))) (to-add int))
+(define-private (add-or-pass-err (x (response int (string-ascii 10))) (to-add int))
   (match x
    value (+ to-add value)
    err-value (err err-value)))
@@ -808,7 +1065,7 @@ const DEFAULT_TO_API: SpecialAPI = SpecialAPI {
 a `(some ...)` option, it returns the inner value of the option. If the second argument is a `(none)` value,
 `default-to` it returns the value of `default-value`.",
     example: "
-(define-map names-map ((name (buff 12))) ((id int)))
+(define-map names-map ((name (string-ascii 12))) ((id int)))
 (map-set names-map { name: \"blockstack\" } { id: 1337 })
 (default-to 0 (get id (map-get? names-map (tuple (name \"blockstack\"))))) ;; Returns 1337
 (default-to 0 (get id (map-get? names-map (tuple (name \"non-existant\"))))) ;; Returns 0
@@ -861,7 +1118,7 @@ const IS_NONE_API: SpecialAPI = SpecialAPI {
     description: "`is-none` tests a supplied option value, returning `true` if the option value is `(none)`,
 and `false` if it is a `(some ...)`.",
     example: "
-(define-map names-map ((name (buff 12))) ((id int)))
+(define-map names-map ((name (string-ascii 12))) ((id int)))
 (map-set names-map { name: \"blockstack\" } { id: 1337 })
 (is-none (get id (map-get? names-map { name: \"blockstack\" }))) ;; Returns false
 (is-none (get id (map-get? names-map { name: \"non-existant\" }))) ;; Returns true"
@@ -884,7 +1141,7 @@ const IS_SOME_API: SpecialAPI = SpecialAPI {
     description: "`is-some` tests a supplied option value, returning `true` if the option value is `(some ...)`,
 and `false` if it is a `none`.",
     example: "
-(define-map names-map ((name (buff 12))) ((id int)))
+(define-map names-map ((name (string-ascii 12))) ((id int)))
 (map-set names-map { name: \"blockstack\" } { id: 1337 })
 (is-some (get id (map-get? names-map { name: \"blockstack\" }))) ;; Returns true
 (is-some (get id (map-get? names-map { name: \"non-existant\" }))) ;; Returns false"
@@ -948,7 +1205,7 @@ definition (i.e., you cannot put a define statement in the middle of a function
 
 Assets defined using `define-non-fungible-token` may be used in `nft-transfer?`, `nft-mint?`, and `nft-get-owner?` functions",
     example: "
-(define-non-fungible-token names (buff 50))
+(define-non-fungible-token names (string-ascii 50))
 "
 };
 
@@ -1169,7 +1426,7 @@ If an asset identified by `asset-identifier` _already exists_, this function wil
 Otherwise, on successfuly mint, it returns `(ok true)`.
 ",
     example: "
-(define-non-fungible-token stackaroo (buff 40))
+(define-non-fungible-token stackaroo (string-ascii 40))
 (nft-mint? stackaroo \"Roo\" 'SPAXYA5XS51713FDTQ8H94EJ4V579CXMTRNBZKSF) ;; returns (ok true)
 "
 };
@@ -1182,7 +1439,7 @@ const GET_OWNER: SpecialAPI = SpecialAPI {
 The asset type must have been defined using `define-non-fungible-token`, and the supplied `asset-identifier` must be of the same type specified in
 that definition.",
     example: "
-(define-non-fungible-token stackaroo (buff 40))
+(define-non-fungible-token stackaroo (string-ascii 40))
 (nft-mint? stackaroo \"Roo\" 'SPAXYA5XS51713FDTQ8H94EJ4V579CXMTRNBZKSF)
 (nft-get-owner? stackaroo \"Roo\") ;; Returns (some SPAXYA5XS51713FDTQ8H94EJ4V579CXMTRNBZKSF)
 (nft-get-owner? stackaroo \"Too\") ;; Returns none
@@ -1241,7 +1498,7 @@ one of the following error codes:
 `(err u3)` -- asset identified by asset-identifier does not exist
 ",
     example: "
-(define-non-fungible-token stackaroo (buff 40))
+(define-non-fungible-token stackaroo (string-ascii 40))
 (nft-mint? stackaroo \"Roo\" 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)
 (nft-transfer? stackaroo \"Roo\" 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SPAXYA5XS51713FDTQ8H94EJ4V579CXMTRNBZKSF) ;; returns (ok true)
 (nft-transfer? stackaroo \"Roo\" 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SPAXYA5XS51713FDTQ8H94EJ4V579CXMTRNBZKSF) ;; returns (err u1)
@@ -1249,6 +1506,65 @@ one of the following error codes:
 "
 };
 
+const GET_SUPPLY: SpecialAPI = SpecialAPI {
+    input_type: "TokenName",
+    output_type: "uint",
+    signature: "(ft-get-supply token-name)",
+    description: "`ft-get-supply` returns `token-name` circulating supply: the amount of tokens minted and not
+yet burned. The token type must have been defined using `define-fungible-token`. Tokens defined without a
+total supply before Clarity2 do not track their supply: reading it is a runtime error.",
+    example: "
+(define-fungible-token stackaroo u1000000)
+(ft-mint? stackaroo u100 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)
+(ft-get-supply stackaroo) ;; returns u100
+"
+};
+
+const BURN_TOKEN: SpecialAPI = SpecialAPI {
+    input_type: "TokenName, uint, principal",
+    output_type: "(response bool uint)",
+    signature: "(ft-burn? token-name amount sender)",
+    description: "`ft-burn?` is used to decrease the token balance for the `sender` principal for a token
+type defined using `define-fungible-token`. The decreased token balance is _not_ transfered to another principal, but
+rather destroyed, reducing the circulating supply.
+
+This function returns (ok true) if the burn is successful. In the event of an unsuccessful burn it returns
+one of the following error codes:
+
+`(err u1)` -- `sender` does not have enough balance to burn
+`(err u3)` -- amount to burn is non-positive
+",
+    example: "
+(define-fungible-token stackaroo)
+(ft-mint? stackaroo u100 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)
+(ft-burn? stackaroo u50 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR) ;; returns (ok true)
+(ft-burn? stackaroo u60 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR) ;; returns (err u1)
+"
+};
+
+const BURN_ASSET: SpecialAPI = SpecialAPI {
+    input_type: "AssetName, A, principal",
+    output_type: "(response bool uint)",
+    signature: "(nft-burn? asset-class asset-identifier sender)",
+    description: "`nft-burn?` is used to destroy an asset identified by `asset-identifier`, owned by `sender`.
+The `asset-class` must have been defined by `define-non-fungible-token` and `asset-identifier`
+must be of the type specified in that definition.
+
+This function returns (ok true) if the burn is successful. In the event of an unsuccessful burn it returns
+one of the following error codes:
+
+`(err u1)` -- `sender` does not own the asset
+`(err u3)` -- asset identified by asset-identifier does not exist
+",
+    example: "
+(define-non-fungible-token stackaroo (string-ascii 40))
+(nft-mint? stackaroo \"Roo\" 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)
+(nft-burn? stackaroo \"Roo\" 'SPAXYA5XS51713FDTQ8H94EJ4V579CXMTRNBZKSF) ;; returns (err u1)
+(nft-burn? stackaroo \"Roo\" 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR) ;; returns (ok true)
+(nft-burn? stackaroo \"Roo\" 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR) ;; returns (err u3)
+"
+};
+
 const STX_GET_BALANCE: SimpleFunctionAPI = SimpleFunctionAPI {
     name: None,
     signature: "(stx-get-balance owner)",
@@ -1323,6 +1639,13 @@ fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
         Modulo => make_for_simple_native(&MOD_API, &Modulo, name),
         Power => make_for_simple_native(&POW_API, &Power, name),
         BitwiseXOR => make_for_simple_native(&XOR_API, &BitwiseXOR, name),
+        BitwiseAnd => make_for_simple_native(&BITWISE_AND_API, &BitwiseAnd, name),
+        BitwiseOr => make_for_simple_native(&BITWISE_OR_API, &BitwiseOr, name),
+        BitwiseNot => make_for_simple_native(&BITWISE_NOT_API, &BitwiseNot, name),
+        BitwiseLShift => make_for_special(&BITWISE_LEFT_SHIFT_API, name),
+        BitwiseRShift => make_for_special(&BITWISE_RIGHT_SHIFT_API, name),
+        Sqrti => make_for_simple_native(&SQRTI_API, &Sqrti, name),
+        Log2 => make_for_simple_native(&LOG2_API, &Log2, name),
         And => make_for_simple_native(&AND_API, &And, name),
         Or => make_for_simple_native(&OR_API, &Or, name),
         Not => make_for_simple_native(&NOT_API, &Not, name),
@@ -1338,6 +1661,15 @@ fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
         Concat => make_for_special(&CONCAT_API, name),
         AsMaxLen => make_for_special(&ASSERTS_MAX_LEN_API, name),
         Len => make_for_special(&LEN_API, name),
+        ElementAt => make_for_special(&ELEMENT_AT_API, name),
+        IndexOf => make_for_special(&INDEX_OF_API, name),
+        Slice => make_for_special(&SLICE_API, name),
+        BuffToIntLe => make_for_simple_native(&BUFF_TO_INT_LE_API, &BuffToIntLe, name),
+        BuffToIntBe => make_for_simple_native(&BUFF_TO_INT_BE_API, &BuffToIntBe, name),
+        BuffToUIntLe => make_for_simple_native(&BUFF_TO_UINT_LE_API, &BuffToUIntLe, name),
+        BuffToUIntBe => make_for_simple_native(&BUFF_TO_UINT_BE_API, &BuffToUIntBe, name),
+        IntToBuffLe => make_for_simple_native(&INT_TO_BUFF_LE_API, &IntToBuffLe, name),
+        IntToBuffBe => make_for_simple_native(&INT_TO_BUFF_BE_API, &IntToBuffBe, name),
         ListCons => make_for_special(&LIST_API, name),
         FetchEntry => make_for_special(&FETCH_ENTRY_API, name),
         SetEntry => make_for_special(&SET_ENTRY_API, name),
@@ -1351,9 +1683,13 @@ fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
         Sha512 => make_for_special(&SHA512_API, name),
         Sha512Trunc256 => make_for_special(&SHA512T256_API, name),
         Keccak256 => make_for_special(&KECCAK256_API, name),
+        Secp256k1Recover => make_for_simple_native(&SECP256K1RECOVER_API, &Secp256k1Recover, name),
+        Secp256k1Verify => make_for_simple_native(&SECP256K1VERIFY_API, &Secp256k1Verify, name),
+        PrincipalOf => make_for_special(&PRINCIPAL_OF_API, name),
         Print => make_for_special(&PRINT_API, name),
         ContractCall => make_for_special(&CONTRACT_CALL_API, name),
         ContractOf => make_for_special(&CONTRACT_OF_API, name),
+        ImplementsTrait => make_for_special(&IMPLEMENTS_TRAIT_API, name),
         AsContract => make_for_special(&AS_CONTRACT_API, name),
         GetBlockInfo => make_for_special(&GET_BLOCK_INFO_API, name),
         ConsOkay => make_for_special(&CONS_OK_API, name),
@@ -1377,6 +1713,9 @@ fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
         GetAssetOwner => make_for_special(&GET_OWNER, name),
         TransferToken => make_for_special(&TOKEN_TRANSFER, name),
         TransferAsset => make_for_special(&ASSET_TRANSFER, name),
+        GetTokenSupply => make_for_special(&GET_SUPPLY, name),
+        BurnToken => make_for_special(&BURN_TOKEN, name),
+        BurnAsset => make_for_special(&BURN_ASSET, name),
         AtBlock => make_for_special(&AT_BLOCK, name),
         GetStxBalance => make_for_simple_native(&STX_GET_BALANCE, &GetStxBalance, name),
         StxTransfer => make_for_simple_native(&STX_TRANSFER, &StxTransfer, name),
@@ -1462,15 +1801,29 @@ pub fn make_json_api_reference() -> String {
             .expect("Failed to serialize documentation"))
 }
 
+/// Returns the reference of the native function, define form or keyword named `name`.
+pub fn lookup_api_reference(name: &str) -> Option<serde_json::Value> {
+    let api_out = make_all_api_reference();
+    if let Some(function) = api_out.functions.iter().find(|x| x.name == name) {
+        return Some(serde_json::to_value(function)
+                    .expect("Failed to serialize documentation"))
+    }
+    api_out.keywords.iter()
+        .find(|x| x.name == name)
+        .map(|keyword| serde_json::to_value(keyword)
+             .expect("Failed to serialize documentation"))
+}
+
 #[cfg(test)]
 mod test {
     use super::make_json_api_reference;
     use super::make_all_api_reference;
+    use super::lookup_api_reference;
     use chainstate::stacks::{StacksAddress, StacksBlockId, index::MarfTrieId};
     use chainstate::burn::{BlockHeaderHash, VRFSeed};
     use burnchains::BurnchainHeaderHash;
 
-    use vm::{ execute, ast, eval_all, Value, QualifiedContractIdentifier, ContractContext,
+    use vm::{ execute, ast, eval_all, ClarityVersion, Value, QualifiedContractIdentifier, ContractContext,
               database::{ MarfedKV, HeadersDB },
               LimitedCostTracker, GlobalContext, Error, contexts::OwnedEnvironment };
 
@@ -1521,7 +1874,7 @@ mod test {
         let conn = marf.as_clarity_db(&DOC_HEADER_DB);
         let contract_id = QualifiedContractIdentifier::local("docs-test").unwrap();
         let mut contract_context = ContractContext::new(contract_id.clone());
-        let mut global_context = GlobalContext::new(conn, LimitedCostTracker::new_max_limit());
+        let mut global_context = GlobalContext::new(false, conn, LimitedCostTracker::new_max_limit());
 
         global_context.execute(|g| {
             for segment in segments.iter() {
@@ -1535,7 +1888,7 @@ mod test {
                 eprintln!("{}", segment);
 
                 let result = {
-                    let parsed = ast::build_ast(&contract_id, segment, &mut ()).unwrap()
+                    let parsed = ast::build_ast(&contract_id, segment, &mut (), ClarityVersion::latest()).unwrap()
                         .expressions;
                     eval_all(&parsed, &mut contract_context, g).unwrap()
                 };
@@ -1558,6 +1911,14 @@ mod test {
         make_json_api_reference();
     }
 
+    #[test]
+    fn test_lookup_api_reference() {
+        assert_eq!(lookup_api_reference("map-get?").unwrap()["signature"], "(map-get? map-name key-tuple)");
+        assert_eq!(lookup_api_reference("define-public").unwrap()["name"], "define-public");
+        assert_eq!(lookup_api_reference("tx-sender").unwrap()["output_type"], "principal");
+        assert!(lookup_api_reference("no-such-function").is_none());
+    }
+
     #[test]
     fn test_examples() {
         let apis = make_all_api_reference();
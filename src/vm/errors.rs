@@ -54,6 +54,8 @@ pub enum RuntimeErrorType {
     ArithmeticOverflow,
     ArithmeticUnderflow,
     SupplyOverflow(u128, u128),
+    SupplyUnderflow(u128, u128),
+    UntrackedTokenSupply,
     DivisionByZero,
     // error in parsing types
     ParseError(String),
@@ -66,6 +68,8 @@ pub enum RuntimeErrorType {
     ValueTooLarge,
     BadBlockHeight(String),
     TransferNonPositiveAmount,
+    // (required, available)
+    NotEnoughFunds(u128, u128),
     NoSuchToken,
     NotImplemented,
     NoSenderInContext,
@@ -31,6 +31,36 @@ macro_rules! type_force_binary_arithmetic { ($function: ident, $x: expr, $y: exp
 }
 }}
 
+// This macro checks the type of the single argument and then dispatches the evaluation
+//   to the correct arithmetic type handler.
+macro_rules! type_force_unary_arithmetic { ($function: ident, $x: expr) => {
+{
+    match $x {
+        Value::Int(x) => I128Ops::$function(x),
+        Value::UInt(x) => U128Ops::$function(x),
+        x => Err(CheckErrors::UnionTypeValueError(vec![TypeSignature::IntType, TypeSignature::UIntType],
+                                                  x).into())
+    }
+}
+}}
+
+// This macro checks that the shift amount is a uint, and then dispatches the evaluation
+//   on the type of the shifted argument.
+macro_rules! type_force_shift_arithmetic { ($function: ident, $x: expr, $shift: expr) => {
+{
+    let shift = match $shift {
+        Value::UInt(shift) => shift,
+        shift => return Err(CheckErrors::TypeValueError(TypeSignature::UIntType, shift).into())
+    };
+    match $x {
+        Value::Int(x) => I128Ops::$function(x, shift),
+        Value::UInt(x) => U128Ops::$function(x, shift),
+        x => Err(CheckErrors::UnionTypeValueError(vec![TypeSignature::IntType, TypeSignature::UIntType],
+                                                  x).into())
+    }
+}
+}}
+
 // This macro checks the type of the first argument and then dispatches the evaluation
 //   to the correct arithmetic type handler (after deconstructing the Clarity Values into
 //   the corresponding Rust integer type.
@@ -74,6 +104,41 @@ macro_rules! make_arithmetic_ops { ($struct_name: ident, $type:ty) => {
         fn xor(x: $type, y: $type) -> InterpreterResult<Value> {
             Self::make_value(x ^ y)
         }
+        fn bitwise_and(args: &[$type]) -> InterpreterResult<Value> {
+            let result = args.iter()
+                .fold(!0, |acc: $type, x: &$type| { acc & *x });
+            Self::make_value(result)
+        }
+        fn bitwise_or(args: &[$type]) -> InterpreterResult<Value> {
+            let result = args.iter()
+                .fold(0, |acc: $type, x: &$type| { acc | *x });
+            Self::make_value(result)
+        }
+        fn bitwise_not(x: $type) -> InterpreterResult<Value> {
+            Self::make_value(!x)
+        }
+        // shift amounts are taken modulo 128, and bits shifted past either end are discarded.
+        //   right shifts of signed integers are arithmetic (i.e., they preserve the sign).
+        fn bitwise_left_shift(x: $type, shift: u128) -> InterpreterResult<Value> {
+            Self::make_value(x.wrapping_shl((shift % 128) as u32))
+        }
+        fn bitwise_right_shift(x: $type, shift: u128) -> InterpreterResult<Value> {
+            Self::make_value(x.wrapping_shr((shift % 128) as u32))
+        }
+        #[allow(unused_comparisons)]
+        fn sqrti(x: $type) -> InterpreterResult<Value> {
+            if x < 0 {
+                return Err(RuntimeErrorType::Arithmetic("sqrti must be passed a non-negative integer".to_string()).into())
+            }
+            Self::make_value(integer_sqrt(x as u128) as $type)
+        }
+        #[allow(unused_comparisons)]
+        fn log2(x: $type) -> InterpreterResult<Value> {
+            if x <= 0 {
+                return Err(RuntimeErrorType::Arithmetic("log2 must be passed a positive integer".to_string()).into())
+            }
+            Self::make_value((127 - (x as u128).leading_zeros()) as $type)
+        }
         fn leq(x: $type, y: $type) -> InterpreterResult<Value> {
             Ok(Value::Bool(x <= y))
         }
@@ -139,12 +204,57 @@ macro_rules! make_arithmetic_ops { ($struct_name: ident, $type:ty) => {
     }
 }}
 
+// Computes floor(sqrt(n)) one bit at a time, which avoids any floating point
+//   conversions (and their loss of precision on large inputs).
+fn integer_sqrt(n: u128) -> u128 {
+    let mut remainder = n;
+    let mut result = 0;
+    let mut bit = 1u128 << 126;
+
+    while bit > remainder {
+        bit >>= 2;
+    }
+
+    while bit != 0 {
+        if remainder >= result + bit {
+            remainder -= result + bit;
+            result = (result >> 1) + bit;
+        } else {
+            result >>= 1;
+        }
+        bit >>= 2;
+    }
+
+    result
+}
+
 make_arithmetic_ops!(U128Ops, u128);
 make_arithmetic_ops!(I128Ops, i128);
 
 pub fn native_xor(a: Value, b: Value) -> InterpreterResult<Value> {
     type_force_binary_arithmetic!(xor, a, b)
 }
+pub fn native_bitwise_and(mut args: Vec<Value>) -> InterpreterResult<Value> {
+    type_force_variadic_arithmetic!(bitwise_and, args)
+}
+pub fn native_bitwise_or(mut args: Vec<Value>) -> InterpreterResult<Value> {
+    type_force_variadic_arithmetic!(bitwise_or, args)
+}
+pub fn native_bitwise_not(a: Value) -> InterpreterResult<Value> {
+    type_force_unary_arithmetic!(bitwise_not, a)
+}
+pub fn native_bitwise_left_shift(a: Value, shift: Value) -> InterpreterResult<Value> {
+    type_force_shift_arithmetic!(bitwise_left_shift, a, shift)
+}
+pub fn native_bitwise_right_shift(a: Value, shift: Value) -> InterpreterResult<Value> {
+    type_force_shift_arithmetic!(bitwise_right_shift, a, shift)
+}
+pub fn native_sqrti(a: Value) -> InterpreterResult<Value> {
+    type_force_unary_arithmetic!(sqrti, a)
+}
+pub fn native_log2(a: Value) -> InterpreterResult<Value> {
+    type_force_unary_arithmetic!(log2, a)
+}
 pub fn native_geq(a: Value, b: Value) -> InterpreterResult<Value> {
     type_force_binary_arithmetic!(geq, a, b)
 }
@@ -2,7 +2,7 @@ use vm::functions::tuples;
 use vm::functions::tuples::TupleDefinitionType::{Implicit, Explicit};
 
 use vm::types::{Value, OptionalData, BuffData, PrincipalData, BlockInfoProperty, TypeSignature, AssetIdentifier};
-use vm::representations::{SymbolicExpression};
+use vm::representations::{SymbolicExpression, ClarityName};
 use vm::errors::{Error, InterpreterError, CheckErrors, RuntimeErrorType, InterpreterResult as Result, check_argument_count};
 use vm::{eval, LocalContext, Environment};
 use vm::costs::{cost_functions, CostTracker};
@@ -12,6 +12,8 @@ enum MintAssetErrorCodes { ALREADY_EXIST = 1 }
 enum MintTokenErrorCodes { NON_POSITIVE_AMOUNT = 1 }
 enum TransferAssetErrorCodes { NOT_OWNED_BY = 1, SENDER_IS_RECIPIENT = 2, DOES_NOT_EXIST = 3 }
 enum TransferTokenErrorCodes { NOT_ENOUGH_BALANCE = 1, SENDER_IS_RECIPIENT = 2, NON_POSITIVE_AMOUNT = 3 }
+enum BurnAssetErrorCodes { NOT_OWNED_BY = 1, DOES_NOT_EXIST = 3 }
+enum BurnTokenErrorCodes { NOT_ENOUGH_BALANCE = 1, NON_POSITIVE_AMOUNT = 3 }
 enum StxErrorCodes { NOT_ENOUGH_BALANCE = 1, SENDER_IS_RECIPIENT = 2, NON_POSITIVE_AMOUNT = 3, SENDER_IS_NOT_TX_SENDER = 4 }
 
 macro_rules! clarity_ecode {
@@ -29,6 +31,10 @@ pub fn special_stx_balance(args: &[SymbolicExpression],
 
     let owner = eval(&args[0], env, context)?;
 
+    stx_balance(owner, env)
+}
+
+pub fn stx_balance(owner: Value, env: &mut Environment) -> Result<Value> {
     if let Value::Principal(ref principal) = owner {
         let balance = env.global_context.database.get_account_stx_balance(&principal);
         Ok(Value::UInt(balance))
@@ -48,6 +54,10 @@ pub fn special_stx_transfer(args: &[SymbolicExpression],
     let from_val   = eval(&args[1], env, context)?;
     let to_val     = eval(&args[2], env, context)?;
 
+    stx_transfer(amount_val, from_val, to_val, env)
+}
+
+pub fn stx_transfer(amount_val: Value, from_val: Value, to_val: Value, env: &mut Environment) -> Result<Value> {
     if let (Value::Principal(ref from), Value::Principal(ref to), Value::UInt(amount)) = (&from_val, to_val, amount_val) {
         if amount <= 0 {
             return clarity_ecode!(StxErrorCodes::NON_POSITIVE_AMOUNT)
@@ -99,6 +109,10 @@ pub fn special_stx_burn(args: &[SymbolicExpression],
     let amount_val = eval(&args[0], env, context)?;
     let from_val   = eval(&args[1], env, context)?;
 
+    stx_burn(amount_val, from_val, env)
+}
+
+pub fn stx_burn(amount_val: Value, from_val: Value, env: &mut Environment) -> Result<Value> {
     if let (Value::Principal(ref from), Value::UInt(amount)) = (&from_val, amount_val) {
         if amount <= 0 {
             return clarity_ecode!(StxErrorCodes::NON_POSITIVE_AMOUNT)
@@ -144,6 +158,10 @@ pub fn special_mint_token(args: &[SymbolicExpression],
     let amount = eval(&args[1], env, context)?;
     let to =     eval(&args[2], env, context)?;
 
+    mint_token(token_name, amount, to, env)
+}
+
+pub fn mint_token(token_name: &ClarityName, amount: Value, to: Value, env: &mut Environment) -> Result<Value> {
     if let (Value::UInt(amount),
             Value::Principal(ref to_principal)) = (amount, to) {
         if amount <= 0 {
@@ -186,6 +204,10 @@ pub fn special_mint_asset(args: &[SymbolicExpression],
     let asset =  eval(&args[1], env, context)?;
     let to    =  eval(&args[2], env, context)?;
 
+    mint_asset(asset_name, asset, to, env)
+}
+
+pub fn mint_asset(asset_name: &ClarityName, asset: Value, to: Value, env: &mut Environment) -> Result<Value> {
     let expected_asset_type = env.global_context.database.get_nft_key_type(&env.contract_context.contract_identifier, asset_name)?;
 
     runtime_cost!(cost_functions::NFT_MINT, env, expected_asset_type.size())?;
@@ -230,6 +252,10 @@ pub fn special_transfer_asset(args: &[SymbolicExpression],
     let from  =  eval(&args[2], env, context)?;
     let to    =  eval(&args[3], env, context)?;
 
+    transfer_asset(asset_name, asset, from, to, env)
+}
+
+pub fn transfer_asset(asset_name: &ClarityName, asset: Value, from: Value, to: Value, env: &mut Environment) -> Result<Value> {
     let expected_asset_type = env.global_context.database.get_nft_key_type(&env.contract_context.contract_identifier, asset_name)?;
 
     runtime_cost!(cost_functions::NFT_TRANSFER, env, expected_asset_type.size())?;
@@ -291,6 +317,10 @@ pub fn special_transfer_token(args: &[SymbolicExpression],
     let from =   eval(&args[2], env, context)?;
     let to =     eval(&args[3], env, context)?;
 
+    transfer_token(token_name, amount, from, to, env)
+}
+
+pub fn transfer_token(token_name: &ClarityName, amount: Value, from: Value, to: Value, env: &mut Environment) -> Result<Value> {
     if let (Value::UInt(amount),
             Value::Principal(ref from_principal),
             Value::Principal(ref to_principal)) = (amount, from, to) {
@@ -349,6 +379,10 @@ pub fn special_get_balance(args: &[SymbolicExpression],
 
     let owner = eval(&args[1], env, context)?;
 
+    get_balance(token_name, owner, env)
+}
+
+pub fn get_balance(token_name: &ClarityName, owner: Value, env: &mut Environment) -> Result<Value> {
     if let Value::Principal(ref principal) = owner {
         let balance = env.global_context.database.get_ft_balance(&env.contract_context.contract_identifier, token_name, principal)?;
         Ok(Value::UInt(balance))
@@ -358,6 +392,134 @@ pub fn special_get_balance(args: &[SymbolicExpression],
 
 }
 
+pub fn special_get_supply(args: &[SymbolicExpression],
+                          env: &mut Environment,
+                          _context: &LocalContext) -> Result<Value> {
+    check_argument_count(1, args)?;
+
+    runtime_cost!(cost_functions::FT_SUPPLY, env, 0)?;
+
+    let token_name = args[0].match_atom()
+        .ok_or(CheckErrors::BadTokenName)?;
+
+    get_supply(token_name, env)
+}
+
+pub fn get_supply(token_name: &ClarityName, env: &mut Environment) -> Result<Value> {
+    let supply = env.global_context.database.get_ft_supply(&env.contract_context.contract_identifier, token_name)?;
+    Ok(Value::UInt(supply))
+}
+
+pub fn special_burn_token(args: &[SymbolicExpression],
+                          env: &mut Environment,
+                          context: &LocalContext) -> Result<Value> {
+    check_argument_count(3, args)?;
+
+    runtime_cost!(cost_functions::FT_BURN, env, 0)?;
+
+    let token_name = args[0].match_atom()
+        .ok_or(CheckErrors::BadTokenName)?;
+
+    let amount = eval(&args[1], env, context)?;
+    let from =   eval(&args[2], env, context)?;
+
+    burn_token(token_name, amount, from, env)
+}
+
+pub fn burn_token(token_name: &ClarityName, amount: Value, from: Value, env: &mut Environment) -> Result<Value> {
+    if let (Value::UInt(amount),
+            Value::Principal(ref from_principal)) = (amount, from) {
+        if amount <= 0 {
+            return clarity_ecode!(BurnTokenErrorCodes::NON_POSITIVE_AMOUNT)
+        }
+
+        let from_bal = env.global_context.database.get_ft_balance(&env.contract_context.contract_identifier, token_name, from_principal)?;
+
+        if from_bal < amount {
+            return clarity_ecode!(BurnTokenErrorCodes::NOT_ENOUGH_BALANCE)
+        }
+
+        let final_from_bal = from_bal - amount;
+
+        env.global_context.database.checked_decrease_token_supply(
+            &env.contract_context.contract_identifier, token_name, amount)?;
+
+        env.add_memory(TypeSignature::PrincipalType.size() as u64)?;
+        env.add_memory(TypeSignature::UIntType.size() as u64)?;
+
+        env.global_context.database.set_ft_balance(&env.contract_context.contract_identifier, token_name, from_principal, final_from_bal)?;
+
+        // burned tokens leave the sender's possession: post-conditions treat them as sent.
+        env.global_context.log_token_transfer(from_principal, &env.contract_context.contract_identifier, token_name, amount)?;
+
+        let asset_identifier = AssetIdentifier {
+            contract_identifier: env.contract_context.contract_identifier.clone(),
+            asset_name: token_name.clone()
+        };
+        env.register_ft_burn_event(from_principal.clone(), amount, asset_identifier)?;
+
+        Ok(Value::okay_true())
+    } else {
+        Err(CheckErrors::BadBurnFTArguments.into())
+    }
+}
+
+pub fn special_burn_asset(args: &[SymbolicExpression],
+                          env: &mut Environment,
+                          context: &LocalContext) -> Result<Value> {
+    check_argument_count(3, args)?;
+
+    let asset_name = args[0].match_atom()
+        .ok_or(CheckErrors::BadTokenName)?;
+
+    let asset = eval(&args[1], env, context)?;
+    let from  = eval(&args[2], env, context)?;
+
+    burn_asset(asset_name, asset, from, env)
+}
+
+pub fn burn_asset(asset_name: &ClarityName, asset: Value, from: Value, env: &mut Environment) -> Result<Value> {
+    let expected_asset_type = env.global_context.database.get_nft_key_type(&env.contract_context.contract_identifier, asset_name)?;
+
+    runtime_cost!(cost_functions::NFT_BURN, env, expected_asset_type.size())?;
+
+    if !expected_asset_type.admits(&asset) {
+        return Err(CheckErrors::TypeValueError(expected_asset_type, asset).into())
+    }
+
+    if let Value::Principal(ref from_principal) = from {
+        let current_owner = match env.global_context.database.get_nft_owner(&env.contract_context.contract_identifier, asset_name, &asset) {
+            Ok(owner) => Ok(owner),
+            Err(Error::Runtime(RuntimeErrorType::NoSuchToken, _)) => {
+                return clarity_ecode!(BurnAssetErrorCodes::DOES_NOT_EXIST)
+            },
+            Err(e) => Err(e)
+        }?;
+
+        if current_owner != *from_principal {
+            return clarity_ecode!(BurnAssetErrorCodes::NOT_OWNED_BY)
+        }
+
+        env.add_memory(TypeSignature::PrincipalType.size() as u64)?;
+        env.add_memory(expected_asset_type.size() as u64)?;
+
+        env.global_context.database.burn_nft(&env.contract_context.contract_identifier, asset_name, &asset)?;
+
+        // burned assets leave the sender's possession: post-conditions treat them as sent.
+        env.global_context.log_asset_transfer(from_principal, &env.contract_context.contract_identifier, asset_name, asset.clone());
+
+        let asset_identifier = AssetIdentifier {
+            contract_identifier: env.contract_context.contract_identifier.clone(),
+            asset_name: asset_name.clone()
+        };
+        env.register_nft_burn_event(from_principal.clone(), asset, asset_identifier)?;
+
+        Ok(Value::okay_true())
+    } else {
+        Err(CheckErrors::TypeValueError(TypeSignature::PrincipalType, from).into())
+    }
+}
+
 pub fn special_get_owner(args: &[SymbolicExpression],
                          env: &mut Environment,
                          context: &LocalContext) -> Result<Value> {
@@ -367,6 +529,11 @@ pub fn special_get_owner(args: &[SymbolicExpression],
         .ok_or(CheckErrors::BadTokenName)?;
 
     let asset = eval(&args[1], env, context)?;
+
+    get_owner(asset_name, asset, env)
+}
+
+pub fn get_owner(asset_name: &ClarityName, asset: Value, env: &mut Environment) -> Result<Value> {
     let expected_asset_type = env.global_context.database.get_nft_key_type(&env.contract_context.contract_identifier, asset_name)?;
 
     runtime_cost!(cost_functions::NFT_OWNER, env, expected_asset_type.size())?;
@@ -0,0 +1,63 @@
+use vm::types::{Value, TypeSignature, BUFF_16};
+use vm::errors::{CheckErrors, InterpreterResult as Result};
+
+enum EndianDirection {
+    LittleEndian,
+    BigEndian
+}
+
+// Reads a buffer of at most 16 bytes into a fixed width array. Shorter
+//   buffers are accepted and silently zero-padded on their most significant
+//   side (so 0xff reads as 255 even for the signed conversions). This is
+//   documented behavior of the buff-to-(u)int natives, not an oversight.
+fn buff_to_bytes(input: Value, direction: EndianDirection) -> Result<[u8; 16]> {
+    let buff = match input {
+        Value::Buffer(ref buff) if buff.data.len() <= 16 => &buff.data,
+        _ => return Err(CheckErrors::TypeValueError(BUFF_16.clone(), input).into())
+    };
+
+    let mut bytes = [0u8; 16];
+    match direction {
+        EndianDirection::LittleEndian => bytes[..buff.len()].copy_from_slice(buff),
+        EndianDirection::BigEndian => bytes[16 - buff.len()..].copy_from_slice(buff)
+    }
+    Ok(bytes)
+}
+
+pub fn native_buff_to_int_le(input: Value) -> Result<Value> {
+    let bytes = buff_to_bytes(input, EndianDirection::LittleEndian)?;
+    Ok(Value::Int(i128::from_le_bytes(bytes)))
+}
+
+pub fn native_buff_to_int_be(input: Value) -> Result<Value> {
+    let bytes = buff_to_bytes(input, EndianDirection::BigEndian)?;
+    Ok(Value::Int(i128::from_be_bytes(bytes)))
+}
+
+pub fn native_buff_to_uint_le(input: Value) -> Result<Value> {
+    let bytes = buff_to_bytes(input, EndianDirection::LittleEndian)?;
+    Ok(Value::UInt(u128::from_le_bytes(bytes)))
+}
+
+pub fn native_buff_to_uint_be(input: Value) -> Result<Value> {
+    let bytes = buff_to_bytes(input, EndianDirection::BigEndian)?;
+    Ok(Value::UInt(u128::from_be_bytes(bytes)))
+}
+
+pub fn native_int_to_buff_le(input: Value) -> Result<Value> {
+    let bytes = match input {
+        Value::Int(value) => value.to_le_bytes(),
+        Value::UInt(value) => value.to_le_bytes(),
+        _ => return Err(CheckErrors::UnionTypeValueError(vec![TypeSignature::IntType, TypeSignature::UIntType], input).into())
+    };
+    Value::buff_from(bytes.to_vec())
+}
+
+pub fn native_int_to_buff_be(input: Value) -> Result<Value> {
+    let bytes = match input {
+        Value::Int(value) => value.to_be_bytes(),
+        Value::UInt(value) => value.to_be_bytes(),
+        _ => return Err(CheckErrors::UnionTypeValueError(vec![TypeSignature::IntType, TypeSignature::UIntType], input).into())
+    };
+    Value::buff_from(bytes.to_vec())
+}
@@ -0,0 +1,105 @@
+use vm::types::{Value, PrincipalData, TypeSignature, BUFF_32, BUFF_33, BUFF_65};
+use vm::errors::{CheckErrors, InterpreterResult as Result, check_argument_count};
+use vm::representations::SymbolicExpression;
+use vm::costs::{cost_functions, CostTracker};
+use vm::{eval, LocalContext, Environment};
+
+use address::AddressHashMode;
+use burnchains::PublicKey;
+use chainstate::stacks::{StacksAddress, StacksPublicKey, C32_ADDRESS_VERSION_MAINNET_SINGLESIG, C32_ADDRESS_VERSION_TESTNET_SINGLESIG};
+use util::secp256k1::{Secp256k1PublicKey, MessageSignature};
+
+// Unwraps a buffer admitted by `expected_type`. Buffers which are shorter than
+//   the expected type are returned as is: the callers treat them as malformed input.
+fn unwrap_buff(input: Value, expected_type: &TypeSignature) -> Result<Vec<u8>> {
+    if !expected_type.admits(&input) {
+        return Err(CheckErrors::TypeValueError(expected_type.clone(), input).into())
+    }
+    match input {
+        Value::Buffer(buff) => Ok(buff.data),
+        _ => Err(CheckErrors::TypeValueError(expected_type.clone(), input).into())
+    }
+}
+
+pub fn native_secp256k1_recover(message_hash: Value, signature: Value) -> Result<Value> {
+    let message_hash = unwrap_buff(message_hash, &BUFF_32)?;
+    let signature = unwrap_buff(signature, &BUFF_65)?;
+
+    if message_hash.len() != 32 {
+        return Value::error(Value::UInt(1))
+    }
+
+    // the signature is encoded as a recovery id followed by the compact
+    //   (r, s) signature, i.e., the encoding of a MessageSignature.
+    let signature = match MessageSignature::from_bytes(&signature) {
+        Some(signature) if signature.to_secp256k1_recoverable().is_some() => signature,
+        _ => return Value::error(Value::UInt(1))
+    };
+
+    match Secp256k1PublicKey::recover_to_pubkey(&message_hash, &signature) {
+        Ok(public_key) => Value::okay(Value::buff_from(public_key.to_bytes_compressed())?),
+        Err(_) => Value::error(Value::UInt(2))
+    }
+}
+
+pub fn native_secp256k1_verify(args: Vec<Value>) -> Result<Value> {
+    check_argument_count(3, &args)?;
+    let mut args = args.into_iter();
+    let (message_hash, signature, public_key) = match (args.next(), args.next(), args.next()) {
+        (Some(message_hash), Some(signature), Some(public_key)) => (message_hash, signature, public_key),
+        _ => unreachable!("Argument count checked above")
+    };
+
+    let message_hash = unwrap_buff(message_hash, &BUFF_32)?;
+    let signature = unwrap_buff(signature, &BUFF_65)?;
+    let public_key = unwrap_buff(public_key, &BUFF_33)?;
+
+    if message_hash.len() != 32 {
+        return Ok(Value::Bool(false))
+    }
+
+    let public_key = match Secp256k1PublicKey::from_slice(&public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return Ok(Value::Bool(false))
+    };
+    let signature = match MessageSignature::from_bytes(&signature) {
+        Some(signature) => signature,
+        None => return Ok(Value::Bool(false))
+    };
+
+    // verification errors (malformed or high-S signatures) are simply treated
+    //   as a failed verification.
+    Ok(Value::Bool(public_key.verify(&message_hash, &signature).unwrap_or(false)))
+}
+
+pub fn special_principal_of(args: &[SymbolicExpression],
+                            env: &mut Environment,
+                            context: &LocalContext) -> Result<Value> {
+    check_argument_count(1, args)?;
+
+    runtime_cost!(cost_functions::PRINCIPAL_OF, env, 0)?;
+
+    let public_key = eval(&args[0], env, context)?;
+    principal_of(public_key, env.global_context.mainnet)
+}
+
+pub fn principal_of(public_key: Value, mainnet: bool) -> Result<Value> {
+    let public_key = unwrap_buff(public_key, &BUFF_33)?;
+
+    let public_key = match StacksPublicKey::from_slice(&public_key) {
+        Ok(public_key) => public_key,
+        Err(_) => return Value::error(Value::UInt(1))
+    };
+
+    let version = if mainnet {
+        C32_ADDRESS_VERSION_MAINNET_SINGLESIG
+    } else {
+        C32_ADDRESS_VERSION_TESTNET_SINGLESIG
+    };
+
+    match StacksAddress::from_public_keys(version,
+                                          &AddressHashMode::SerializeP2PKH, 1, &vec![public_key]) {
+        Some(address) => Value::okay(Value::Principal(PrincipalData::from(address))),
+        None => Value::error(Value::UInt(1))
+    }
+}
@@ -5,7 +5,7 @@ use vm::functions::tuples;
 use vm::functions::tuples::TupleDefinitionType::{Implicit, Explicit};
 
 use vm::types::{Value, OptionalData, BuffData, PrincipalData, BlockInfoProperty, TypeSignature, BUFF_32};
-use vm::representations::{SymbolicExpression, SymbolicExpressionType};
+use vm::representations::{SymbolicExpression, SymbolicExpressionType, ClarityName};
 use vm::errors::{CheckErrors, InterpreterError, RuntimeErrorType, InterpreterResult as Result,
                  check_argument_count, check_arguments_at_least};
 use vm::costs::{cost_functions, constants as cost_constants, CostTracker, MemoryConsumer};
@@ -121,6 +121,10 @@ pub fn special_fetch_variable(args: &[SymbolicExpression],
     let var_name = args[0].match_atom()
         .ok_or(CheckErrors::ExpectedName)?;
 
+    fetch_variable(var_name, env)
+}
+
+pub fn fetch_variable(var_name: &ClarityName, env: &mut Environment) -> Result<Value> {
     let contract = &env.contract_context.contract_identifier;
 
     // optimization todo: db metadata like this should just get stored
@@ -146,6 +150,10 @@ pub fn special_set_variable(args: &[SymbolicExpression],
     let var_name = args[0].match_atom()
         .ok_or(CheckErrors::ExpectedName)?;
 
+    set_variable(var_name, value, env)
+}
+
+pub fn set_variable(var_name: &ClarityName, value: Value, env: &mut Environment) -> Result<Value> {
     let contract = &env.contract_context.contract_identifier;
 
     // optimization todo: db metadata like this should just get stored
@@ -172,6 +180,10 @@ pub fn special_fetch_entry(args: &[SymbolicExpression],
         Explicit => eval(&args[1], env, &context)?
     };
 
+    fetch_entry(map_name, key, env)
+}
+
+pub fn fetch_entry(map_name: &ClarityName, key: Value, env: &mut Environment) -> Result<Value> {
     let contract = &env.contract_context.contract_identifier;
 
     // optimization todo: db metadata like this should just get stored
@@ -231,6 +243,10 @@ pub fn special_set_entry(args: &[SymbolicExpression],
     let map_name = args[0].match_atom()
         .ok_or(CheckErrors::ExpectedName)?;
 
+    set_entry(map_name, key, value, env)
+}
+
+pub fn set_entry(map_name: &ClarityName, key: Value, value: Value, env: &mut Environment) -> Result<Value> {
     let contract = &env.contract_context.contract_identifier;
 
     // optimization todo: db metadata like this should just get stored
@@ -268,6 +284,10 @@ pub fn special_insert_entry(args: &[SymbolicExpression],
     let map_name = args[0].match_atom()
         .ok_or(CheckErrors::ExpectedName)?;
 
+    insert_entry(map_name, key, value, env)
+}
+
+pub fn insert_entry(map_name: &ClarityName, key: Value, value: Value, env: &mut Environment) -> Result<Value> {
     let contract = &env.contract_context.contract_identifier;
 
     // optimization todo: db metadata like this should just get stored
@@ -300,6 +320,10 @@ pub fn special_delete_entry(args: &[SymbolicExpression],
     let map_name = args[0].match_atom()
         .ok_or(CheckErrors::ExpectedName)?;
 
+    delete_entry(map_name, key, env)
+}
+
+pub fn delete_entry(map_name: &ClarityName, key: Value, env: &mut Environment) -> Result<Value> {
     let contract = &env.contract_context.contract_identifier;
 
     // optimization todo: db metadata like this should just get stored
@@ -341,7 +365,9 @@ pub fn special_get_block_info(args: &[SymbolicExpression],
         _ => return Ok(Value::none())
     };
 
-    let current_block_height = env.global_context.database.get_current_block_height();
+    // a simulated block height may be past the chain tip, whose later blocks have no info.
+    let current_block_height = cmp::min(env.global_context.database.get_current_block_height(),
+                                        env.global_context.database.get_chain_tip_height());
     if height_value >= current_block_height {
         return Ok(Value::none())
     }
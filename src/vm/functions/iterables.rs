@@ -1,9 +1,9 @@
 use vm::costs::{cost_functions, CostOverflowingMath};
 use vm::errors::{CheckErrors, RuntimeErrorType, InterpreterResult as Result, check_argument_count};
-use vm::types::{Value, ListData, signatures::ListTypeData, TypeSignature::BoolType, TypeSignature};
+use vm::types::{Value, ListData, StringData, signatures::ListTypeData, TypeSignature::BoolType, TypeSignature};
 use vm::representations::{SymbolicExpression, SymbolicExpressionType};
 use vm::{LocalContext, Environment, eval, apply, lookup_function};
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::cmp;
 
 pub fn list_cons(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
@@ -11,6 +11,11 @@ pub fn list_cons(args: &[SymbolicExpression], env: &mut Environment, context: &L
         args.iter().map(|x| eval(x, env, context)).collect();
     let args = eval_tried?;
 
+    list_from_values(args, env)
+}
+
+/// Constructs a list from its evaluated elements.
+pub fn list_from_values(args: Vec<Value>, env: &mut Environment) -> Result<Value> {
     let mut arg_size = 0;
     for a in args.iter() {
         arg_size = arg_size.cost_overflow_add(a.size().into())?;
@@ -152,7 +157,45 @@ pub fn special_append(args: &[SymbolicExpression], env: &mut Environment, contex
                 Err(CheckErrors::TypeValueError(entry_type, element).into())
             }
         },
-        _ => Err(CheckErrors::ExpectedListApplication.into())
+        Value::Buffer(buff) => {
+            let element = eval(&args[1], env, context)?;
+            runtime_cost!(cost_functions::APPEND, env, u64::from(element.size()))?;
+            match element {
+                Value::Buffer(mut element_data) if element_data.data.len() <= 1 => {
+                    let mut data = buff.data;
+                    data.append(&mut element_data.data);
+                    Value::buff_from(data)
+                },
+                _ => Err(CheckErrors::TypeValueError(TypeSignature::min_buffer(), element).into())
+            }
+        },
+        Value::String(StringData::ASCII(ascii)) => {
+            let element = eval(&args[1], env, context)?;
+            runtime_cost!(cost_functions::APPEND, env, u64::from(element.size()))?;
+            match element {
+                Value::String(StringData::ASCII(mut element_data)) if element_data.data.len() <= 1 => {
+                    let mut data = ascii.data;
+                    data.append(&mut element_data.data);
+                    runtime_cost!(cost_functions::STRING_ASCII_CONS, env, data.len() as u64)?;
+                    Value::string_ascii_from_bytes(data)
+                },
+                _ => Err(CheckErrors::TypeValueError(TypeSignature::min_string_ascii(), element).into())
+            }
+        },
+        Value::String(StringData::UTF8(utf8)) => {
+            let element = eval(&args[1], env, context)?;
+            runtime_cost!(cost_functions::APPEND, env, u64::from(element.size()))?;
+            match element {
+                Value::String(StringData::UTF8(mut element_data)) if element_data.data.len() <= 1 => {
+                    let mut data = utf8.data;
+                    data.append(&mut element_data.data);
+                    runtime_cost!(cost_functions::STRING_UTF8_CONS, env, data.len() as u64)?;
+                    Value::string_utf8_from_chars(data)
+                },
+                _ => Err(CheckErrors::TypeValueError(TypeSignature::min_string_utf8(), element).into())
+            }
+        },
+        _ => Err(CheckErrors::ExpectedSequence(TypeSignature::type_of(&iterable)).into())
     }
 }
 
@@ -177,6 +220,18 @@ pub fn special_concat(args: &[SymbolicExpression], env: &mut Environment, contex
             data.append(&mut rhs_data.data);
             Value::buff_from(data)
         },
+        (Value::String(StringData::ASCII(lhs_data)), Value::String(StringData::ASCII(mut rhs_data))) => {
+            let mut data = lhs_data.data;
+            data.append(&mut rhs_data.data);
+            runtime_cost!(cost_functions::STRING_ASCII_CONS, env, data.len() as u64)?;
+            Value::string_ascii_from_bytes(data)
+        },
+        (Value::String(StringData::UTF8(lhs_data)), Value::String(StringData::UTF8(mut rhs_data))) => {
+            let mut data = lhs_data.data;
+            data.append(&mut rhs_data.data);
+            runtime_cost!(cost_functions::STRING_UTF8_CONS, env, data.len() as u64)?;
+            Value::string_utf8_from_chars(data)
+        },
         (_, _) => {
             Err(RuntimeErrorType::BadTypeConstruction.into())
         }
@@ -194,7 +249,8 @@ pub fn special_as_max_len(args: &[SymbolicExpression], env: &mut Environment, co
         let iterable_len = match iterable {
             Value::List(ref list) => list.data.len(),
             Value::Buffer(ref buff) => buff.data.len(),
-            _ => return Err(CheckErrors::ExpectedListOrBuffer(TypeSignature::type_of(&iterable)).into())
+            Value::String(ref string) => string.len() as usize,
+            _ => return Err(CheckErrors::ExpectedSequence(TypeSignature::type_of(&iterable)).into())
         };
         if iterable_len as u128 > *expected_len {
             Ok(Value::none())
@@ -214,6 +270,143 @@ pub fn native_len(iterable: Value) -> Result<Value> {
     match iterable {
         Value::List(list) => Ok(Value::UInt(list.data.len() as u128)),
         Value::Buffer(buff) => Ok(Value::UInt(buff.data.len() as u128)),
-        _ => Err(CheckErrors::ExpectedListOrBuffer(TypeSignature::type_of(&iterable)).into())
+        Value::String(string) => Ok(Value::UInt(string.len() as u128)),
+        _ => Err(CheckErrors::ExpectedSequence(TypeSignature::type_of(&iterable)).into())
+    }
+}
+
+fn parse_sequence_index(index: Value) -> Result<Option<usize>> {
+    match index {
+        Value::UInt(index) => Ok(usize::try_from(index).ok()),
+        _ => Err(CheckErrors::TypeValueError(TypeSignature::UIntType, index).into())
+    }
+}
+
+pub fn special_element_at(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    check_argument_count(2, args)?;
+
+    let sequence = eval(&args[0], env, context)?;
+    let index = eval(&args[1], env, context)?;
+
+    runtime_cost!(cost_functions::ELEMENT_AT, env, 0)?;
+
+    let index = match parse_sequence_index(index)? {
+        Some(index) => index,
+        None => return Ok(Value::none())
+    };
+
+    let element = match sequence {
+        Value::List(mut list) => {
+            if index < list.data.len() {
+                Some(list.data.swap_remove(index))
+            } else {
+                None
+            }
+        },
+        Value::Buffer(buff) => buff.data.get(index)
+            .map(|byte| Value::buff_from_byte(*byte)),
+        Value::String(StringData::ASCII(ascii)) => match ascii.data.get(index) {
+            Some(byte) => Some(Value::string_ascii_from_bytes(vec![*byte])?),
+            None => None
+        },
+        Value::String(StringData::UTF8(mut utf8)) => {
+            if index < utf8.data.len() {
+                Some(Value::string_utf8_from_chars(vec![utf8.data.swap_remove(index)])?)
+            } else {
+                None
+            }
+        },
+        _ => return Err(CheckErrors::ExpectedSequence(TypeSignature::type_of(&sequence)).into())
+    };
+
+    match element {
+        Some(element) => Value::some(element),
+        None => Ok(Value::none())
     }
 }
+
+pub fn special_index_of(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    check_argument_count(2, args)?;
+
+    let sequence = eval(&args[0], env, context)?;
+    let item = eval(&args[1], env, context)?;
+
+    runtime_cost!(cost_functions::INDEX_OF, env, sequence.size())?;
+
+    let position = match (sequence, item) {
+        (Value::List(list), item) => list.data.iter().position(|x| x == &item),
+        (Value::Buffer(buff), Value::Buffer(item)) => {
+            if item.data.len() == 1 {
+                buff.data.iter().position(|x| *x == item.data[0])
+            } else {
+                None
+            }
+        },
+        (Value::String(StringData::ASCII(ascii)), Value::String(StringData::ASCII(item))) => {
+            if item.data.len() == 1 {
+                ascii.data.iter().position(|x| *x == item.data[0])
+            } else {
+                None
+            }
+        },
+        (Value::String(StringData::UTF8(utf8)), Value::String(StringData::UTF8(item))) => {
+            if item.data.len() == 1 {
+                utf8.data.iter().position(|x| x == &item.data[0])
+            } else {
+                None
+            }
+        },
+        (Value::Buffer(_), item) => return Err(CheckErrors::TypeValueError(TypeSignature::min_buffer(), item).into()),
+        (Value::String(StringData::ASCII(_)), item) =>
+            return Err(CheckErrors::TypeValueError(TypeSignature::min_string_ascii(), item).into()),
+        (Value::String(StringData::UTF8(_)), item) =>
+            return Err(CheckErrors::TypeValueError(TypeSignature::min_string_utf8(), item).into()),
+        (sequence, _) => return Err(CheckErrors::ExpectedSequence(TypeSignature::type_of(&sequence)).into())
+    };
+
+    match position {
+        Some(position) => Value::some(Value::UInt(position as u128)),
+        None => Ok(Value::none())
+    }
+}
+
+pub fn special_slice(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    check_argument_count(3, args)?;
+
+    let sequence = eval(&args[0], env, context)?;
+    let left_position = eval(&args[1], env, context)?;
+    let right_position = eval(&args[2], env, context)?;
+
+    runtime_cost!(cost_functions::SLICE, env, sequence.size())?;
+
+    let (left_position, right_position) = match (parse_sequence_index(left_position)?,
+                                                 parse_sequence_index(right_position)?) {
+        (Some(left), Some(right)) if left <= right => (left, right),
+        _ => return Ok(Value::none())
+    };
+
+    let sequence_len = match sequence {
+        Value::List(ref list) => list.data.len(),
+        Value::Buffer(ref buff) => buff.data.len(),
+        Value::String(ref string) => string.len() as usize,
+        _ => return Err(CheckErrors::ExpectedSequence(TypeSignature::type_of(&sequence)).into())
+    };
+    if right_position > sequence_len {
+        return Ok(Value::none())
+    }
+
+    let sliced = match sequence {
+        Value::List(mut list) => {
+            let data = list.data.drain(left_position..right_position).collect();
+            Value::list_with_type(data, list.type_signature)?
+        },
+        Value::Buffer(buff) => Value::buff_from(buff.data[left_position..right_position].to_vec())?,
+        Value::String(StringData::ASCII(ascii)) =>
+            Value::string_ascii_from_bytes(ascii.data[left_position..right_position].to_vec())?,
+        Value::String(StringData::UTF8(utf8)) =>
+            Value::string_utf8_from_chars(utf8.data[left_position..right_position].to_vec())?,
+        _ => unreachable!("Sequence type checked above")
+    };
+
+    Value::some(sliced)
+}
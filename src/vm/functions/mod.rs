@@ -1,16 +1,18 @@
 pub mod define;
 pub mod tuples;
-mod iterables;
+pub mod iterables;
 mod arithmetic;
 mod boolean;
 mod database;
 mod options;
 mod assets;
+mod conversions;
+mod crypto;
 
-use vm::errors::{Error, CheckErrors, RuntimeErrorType, ShortReturnType, InterpreterResult as Result, check_argument_count, check_arguments_at_least};
+use vm::errors::{Error, CheckErrors, InterpreterError, RuntimeErrorType, ShortReturnType, InterpreterResult as Result, check_argument_count, check_arguments_at_least};
 use vm::types::{Value, PrincipalData, ResponseData, TypeSignature};
 use vm::callables::{CallableType, NativeHandle};
-use vm::representations::{SymbolicExpression, SymbolicExpressionType, ClarityName};
+use vm::representations::{SymbolicExpression, SymbolicExpressionType, ClarityName, TraitDefinition};
 use vm::representations::SymbolicExpressionType::{List, Atom};
 use vm::{LocalContext, Environment, eval};
 use vm::costs::{cost_functions, MemoryConsumer, CostTracker, constants as cost_constants};
@@ -30,6 +32,13 @@ define_named_enum!(NativeFunctions {
     Modulo("mod"),
     Power("pow"),
     BitwiseXOR("xor"),
+    BitwiseAnd("bit-and"),
+    BitwiseOr("bit-or"),
+    BitwiseNot("bit-not"),
+    BitwiseLShift("bit-shift-left"),
+    BitwiseRShift("bit-shift-right"),
+    Sqrti("sqrti"),
+    Log2("log2"),
     And("and"),
     Or("or"),
     Not("not"),
@@ -42,6 +51,15 @@ define_named_enum!(NativeFunctions {
     Concat("concat"),
     AsMaxLen("as-max-len?"),
     Len("len"),
+    ElementAt("element-at"),
+    IndexOf("index-of"),
+    Slice("slice"),
+    BuffToIntLe("buff-to-int-le"),
+    BuffToIntBe("buff-to-int-be"),
+    BuffToUIntLe("buff-to-uint-le"),
+    BuffToUIntBe("buff-to-uint-be"),
+    IntToBuffLe("int-to-buff-le"),
+    IntToBuffBe("int-to-buff-be"),
     ListCons("list"),
     FetchVar("var-get"),
     SetVar("var-set"),
@@ -57,10 +75,14 @@ define_named_enum!(NativeFunctions {
     Sha512("sha512"),
     Sha512Trunc256("sha512/256"),
     Keccak256("keccak256"),
+    Secp256k1Recover("secp256k1-recover?"),
+    Secp256k1Verify("secp256k1-verify"),
+    PrincipalOf("principal-of?"),
     Print("print"),
     ContractCall("contract-call?"),
     AsContract("as-contract"),
     ContractOf("contract-of"),
+    ImplementsTrait("implements-trait?"),
     AtBlock("at-block"),
     GetBlockInfo("get-block-info?"),
     ConsError("err"),
@@ -85,6 +107,9 @@ define_named_enum!(NativeFunctions {
     TransferAsset("nft-transfer?"),
     MintAsset("nft-mint?"),
     MintToken("ft-mint?"),
+    GetTokenSupply("ft-get-supply"),
+    BurnToken("ft-burn?"),
+    BurnAsset("nft-burn?"),
     GetStxBalance("stx-get-balance"),
     StxTransfer("stx-transfer?"),
     StxBurn("stx-burn?"),
@@ -108,6 +133,13 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
             Modulo => NativeFunction("native_mod", NativeHandle::DoubleArg(&arithmetic::native_mod), cost_functions::MOD),
             Power => NativeFunction("native_pow", NativeHandle::DoubleArg(&arithmetic::native_pow), cost_functions::POW),
             BitwiseXOR => NativeFunction("native_xor", NativeHandle::DoubleArg(&arithmetic::native_xor), cost_functions::XOR),
+            BitwiseAnd => NativeFunction("native_bitwise_and", NativeHandle::MoreArg(&arithmetic::native_bitwise_and), cost_functions::BITWISE_AND),
+            BitwiseOr => NativeFunction("native_bitwise_or", NativeHandle::MoreArg(&arithmetic::native_bitwise_or), cost_functions::BITWISE_OR),
+            BitwiseNot => NativeFunction("native_bitwise_not", NativeHandle::SingleArg(&arithmetic::native_bitwise_not), cost_functions::BITWISE_NOT),
+            BitwiseLShift => NativeFunction("native_bitwise_left_shift", NativeHandle::DoubleArg(&arithmetic::native_bitwise_left_shift), cost_functions::BITWISE_LEFT_SHIFT),
+            BitwiseRShift => NativeFunction("native_bitwise_right_shift", NativeHandle::DoubleArg(&arithmetic::native_bitwise_right_shift), cost_functions::BITWISE_RIGHT_SHIFT),
+            Sqrti => NativeFunction("native_sqrti", NativeHandle::SingleArg(&arithmetic::native_sqrti), cost_functions::SQRTI),
+            Log2 => NativeFunction("native_log2", NativeHandle::SingleArg(&arithmetic::native_log2), cost_functions::LOG2),
             And => SpecialFunction("special_and", &boolean::special_and),
             Or => SpecialFunction("special_or", &boolean::special_or),
             Not => NativeFunction("native_not", NativeHandle::SingleArg(&boolean::native_not), cost_functions::NOT),
@@ -123,6 +155,15 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
             AsMaxLen => SpecialFunction("special_as_max_len", &iterables::special_as_max_len),
             Append => SpecialFunction("special_append", &iterables::special_append),
             Len => NativeFunction("native_len", NativeHandle::SingleArg(&iterables::native_len), cost_functions::LEN),
+            ElementAt => SpecialFunction("special_element_at", &iterables::special_element_at),
+            IndexOf => SpecialFunction("special_index_of", &iterables::special_index_of),
+            Slice => SpecialFunction("special_slice", &iterables::special_slice),
+            BuffToIntLe => NativeFunction("native_buff_to_int_le", NativeHandle::SingleArg(&conversions::native_buff_to_int_le), cost_functions::BUFF_TO_INT),
+            BuffToIntBe => NativeFunction("native_buff_to_int_be", NativeHandle::SingleArg(&conversions::native_buff_to_int_be), cost_functions::BUFF_TO_INT),
+            BuffToUIntLe => NativeFunction("native_buff_to_uint_le", NativeHandle::SingleArg(&conversions::native_buff_to_uint_le), cost_functions::BUFF_TO_INT),
+            BuffToUIntBe => NativeFunction("native_buff_to_uint_be", NativeHandle::SingleArg(&conversions::native_buff_to_uint_be), cost_functions::BUFF_TO_INT),
+            IntToBuffLe => NativeFunction("native_int_to_buff_le", NativeHandle::SingleArg(&conversions::native_int_to_buff_le), cost_functions::INT_TO_BUFF),
+            IntToBuffBe => NativeFunction("native_int_to_buff_be", NativeHandle::SingleArg(&conversions::native_int_to_buff_be), cost_functions::INT_TO_BUFF),
             ListCons => SpecialFunction("special_list_cons", &iterables::list_cons),
             FetchEntry => SpecialFunction("special_map-get?", &database::special_fetch_entry),
             SetEntry => SpecialFunction("special_set-entry", &database::special_set_entry),
@@ -136,10 +177,14 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
             Sha512 => NativeFunction("native_sha512", NativeHandle::SingleArg(&native_sha512), cost_functions::SHA512),
             Sha512Trunc256 => NativeFunction("native_sha512trunc256", NativeHandle::SingleArg(&native_sha512trunc256), cost_functions::SHA512T256),
             Keccak256 => NativeFunction("native_keccak256", NativeHandle::SingleArg(&native_keccak256), cost_functions::KECCAK256),
+            Secp256k1Recover => NativeFunction("native_secp256k1_recover", NativeHandle::DoubleArg(&crypto::native_secp256k1_recover), cost_functions::SECP256K1RECOVER),
+            Secp256k1Verify => NativeFunction("native_secp256k1_verify", NativeHandle::MoreArg(&crypto::native_secp256k1_verify), cost_functions::SECP256K1VERIFY),
+            PrincipalOf => SpecialFunction("special_principal_of", &crypto::special_principal_of),
             Print => SpecialFunction("special_print", &special_print),
             ContractCall => SpecialFunction("special_contract-call", &database::special_contract_call),
             AsContract => SpecialFunction("special_as-contract", &special_as_contract),
             ContractOf => SpecialFunction("special_contract-of", &special_contract_of),
+            ImplementsTrait => SpecialFunction("special_implements-trait", &special_implements_trait),
             GetBlockInfo => SpecialFunction("special_get_block_info", &database::special_get_block_info),
             ConsSome => NativeFunction("native_some", NativeHandle::SingleArg(&options::native_some), cost_functions::SOME_CONS),
             ConsOkay => NativeFunction("native_okay", NativeHandle::SingleArg(&options::native_okay), cost_functions::OK_CONS),
@@ -162,6 +207,9 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
             TransferToken => SpecialFunction("special_transfer_token", &assets::special_transfer_token),
             GetTokenBalance => SpecialFunction("special_get_balance", &assets::special_get_balance),
             GetAssetOwner => SpecialFunction("special_get_owner", &assets::special_get_owner),
+            GetTokenSupply => SpecialFunction("special_get_supply", &assets::special_get_supply),
+            BurnToken => SpecialFunction("special_burn_token", &assets::special_burn_token),
+            BurnAsset => SpecialFunction("special_burn_asset", &assets::special_burn_asset),
             AtBlock => SpecialFunction("special_at_block", &database::special_at_block),
             GetStxBalance => SpecialFunction("special_stx_balance", &assets::special_stx_balance),
             StxTransfer => SpecialFunction("special_stx_transfer", &assets::special_stx_transfer),
@@ -173,6 +221,95 @@ pub fn lookup_reserved_functions(name: &str) -> Option<CallableType> {
     }
 }
 
+/// Returns the shape of the special functions which evaluate each of their operands once, in
+///   order, and then act on the database, the assets or the event log: whether their first
+///   argument names a variable, map or token, and how many operands follow it. Compiled code
+///   evaluates these operands itself, and then calls `apply_data_function`.
+pub fn data_function_signature(function: &NativeFunctions) -> Option<(bool, usize)> {
+    use vm::functions::NativeFunctions::*;
+    match function {
+        FetchVar | GetTokenSupply => Some((true, 0)),
+        SetVar | FetchEntry | DeleteEntry | GetTokenBalance | GetAssetOwner => Some((true, 1)),
+        SetEntry | InsertEntry | MintToken | MintAsset | BurnToken | BurnAsset => Some((true, 2)),
+        TransferToken | TransferAsset => Some((true, 3)),
+        GetStxBalance | Print => Some((false, 1)),
+        StxBurn => Some((false, 2)),
+        StxTransfer => Some((false, 3)),
+        _ => None
+    }
+}
+
+/// Performs the checks and charges the costs which a data function handles before evaluating
+///   its operands.
+pub fn begin_data_function(function: &NativeFunctions, env: &mut Environment) -> Result<()> {
+    use vm::functions::NativeFunctions::*;
+    match function {
+        SetVar | SetEntry | InsertEntry | DeleteEntry => {
+            if env.global_context.is_read_only() {
+                return Err(CheckErrors::WriteAttemptedInReadOnly.into())
+            }
+        },
+        GetStxBalance => runtime_cost!(cost_functions::STX_BALANCE, env, 0)?,
+        StxTransfer | StxBurn => runtime_cost!(cost_functions::STX_TRANSFER, env, 0)?,
+        MintToken => runtime_cost!(cost_functions::FT_MINT, env, 0)?,
+        TransferToken => runtime_cost!(cost_functions::FT_TRANSFER, env, 0)?,
+        GetTokenBalance => runtime_cost!(cost_functions::FT_BALANCE, env, 0)?,
+        GetTokenSupply => runtime_cost!(cost_functions::FT_SUPPLY, env, 0)?,
+        BurnToken => runtime_cost!(cost_functions::FT_BURN, env, 0)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Applies a data function to its evaluated operands. `name` is the variable, map or token
+///   named by the function's first argument.
+pub fn apply_data_function(function: &NativeFunctions, name: Option<&ClarityName>,
+                           operands: Vec<Value>, env: &mut Environment) -> Result<Value> {
+    use vm::functions::NativeFunctions::*;
+
+    let (named, operand_count) = data_function_signature(function)
+        .ok_or_else(|| InterpreterError::InterpreterError(format!("{} is not a data function", function.get_name())))?;
+    if named != name.is_some() || operand_count != operands.len() {
+        return Err(InterpreterError::InterpreterError(format!("Bad operands for {}", function.get_name())).into())
+    }
+
+    let name = match name {
+        Some(name) => name,
+        None => {
+            let mut operands = operands.into_iter();
+            let mut next = || operands.next().unwrap();
+            return match function {
+                GetStxBalance => assets::stx_balance(next(), env),
+                StxTransfer => assets::stx_transfer(next(), next(), next(), env),
+                StxBurn => assets::stx_burn(next(), next(), env),
+                Print => print_value(next(), env),
+                _ => unreachable!("Checked against the data function signature")
+            }
+        }
+    };
+
+    let mut operands = operands.into_iter();
+    let mut next = || operands.next().unwrap();
+    match function {
+        FetchVar => database::fetch_variable(name, env),
+        SetVar => database::set_variable(name, next(), env),
+        FetchEntry => database::fetch_entry(name, next(), env),
+        SetEntry => database::set_entry(name, next(), next(), env),
+        InsertEntry => database::insert_entry(name, next(), next(), env),
+        DeleteEntry => database::delete_entry(name, next(), env),
+        MintToken => assets::mint_token(name, next(), next(), env),
+        MintAsset => assets::mint_asset(name, next(), next(), env),
+        TransferToken => assets::transfer_token(name, next(), next(), next(), env),
+        TransferAsset => assets::transfer_asset(name, next(), next(), next(), env),
+        GetTokenBalance => assets::get_balance(name, next(), env),
+        GetAssetOwner => assets::get_owner(name, next(), env),
+        GetTokenSupply => assets::get_supply(name, env),
+        BurnToken => assets::burn_token(name, next(), next(), env),
+        BurnAsset => assets::burn_asset(name, next(), next(), env),
+        _ => unreachable!("Checked against the data function signature")
+    }
+}
+
 fn native_eq(args: Vec<Value>) -> Result<Value> {
     // TODO: this currently uses the derived equality checks of Value,
     //   however, that's probably not how we want to implement equality
@@ -225,6 +362,10 @@ fn native_begin(mut args: Vec<Value>) -> Result<Value> {
 fn special_print(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
     let input = eval(&args[0], env, context)?;
 
+    print_value(input, env)
+}
+
+fn print_value(input: Value, env: &mut Environment) -> Result<Value> {
     runtime_cost!(cost_functions::PRINT, env, input.size())?;
 
     if cfg!(feature = "developer-mode") {
@@ -397,3 +538,48 @@ fn special_contract_of(args: &[SymbolicExpression], env: &mut Environment, conte
     let contract_principal = Value::Principal(PrincipalData::Contract(contract_identifier.clone()));
     Ok(contract_principal)
 }
+
+fn special_implements_trait(args: &[SymbolicExpression], env: &mut Environment, context: &LocalContext) -> Result<Value> {
+    // (implements-trait? principal <trait>)
+    // arg0 => principal to look up
+    // arg1 => trait reference or trait identifier
+    check_argument_count(2, args)?;
+
+    runtime_cost!(cost_functions::IMPLEMENTS_TRAIT, env, 0)?;
+
+    let trait_identifier = match &args[1].expr {
+        SymbolicExpressionType::TraitReference(_, TraitDefinition::Defined(trait_identifier)) |
+        SymbolicExpressionType::TraitReference(_, TraitDefinition::Imported(trait_identifier)) |
+        SymbolicExpressionType::Field(trait_identifier) => trait_identifier,
+        _ => return Err(CheckErrors::ExpectedTraitIdentifier.into())
+    };
+
+    let principal = eval(&args[0], env, context)?;
+    let implements = match principal {
+        Value::Principal(PrincipalData::Contract(ref contract_identifier)) => {
+            if *contract_identifier == env.contract_context.contract_identifier {
+                // the contract being evaluated may not be stored yet, if it is being deployed.
+                env.contract_context.implemented_traits.contains(trait_identifier)
+            } else if env.global_context.database.has_contract(contract_identifier) {
+                // the traits a contract implements are stored with it, so reading them is charged
+                //   like loading the contract for a call.
+                let contract_size = env.global_context.database.get_contract_size(contract_identifier)?;
+                runtime_cost!(cost_functions::LOAD_CONTRACT, env, contract_size)?;
+
+                env.global_context.add_memory(contract_size)?;
+
+                finally_drop_memory!(env.global_context, contract_size; {
+                    env.global_context.database.get_contract(contract_identifier)
+                        .map(|contract| contract.contract_context.implemented_traits.contains(trait_identifier))
+                })?
+            } else {
+                false
+            }
+        },
+        // only contracts may implement traits.
+        Value::Principal(PrincipalData::Standard(_)) => false,
+        _ => return Err(CheckErrors::TypeValueError(TypeSignature::PrincipalType, principal).into())
+    };
+
+    Ok(Value::Bool(implements))
+}
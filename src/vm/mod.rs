@@ -15,6 +15,7 @@ pub mod ast;
 pub mod contexts;
 pub mod database;
 pub mod clarity;
+pub mod version;
 
 mod functions;
 mod variables;
@@ -22,14 +23,18 @@ mod callables;
 
 pub mod docs;
 pub mod analysis;
+pub mod debug;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 #[cfg(test)]
 pub mod tests;
 
 pub use vm::types::Value;
-use vm::callables::CallableType;
+use vm::callables::{CallableType, FunctionIdentifier};
 use vm::contexts::{ContractContext, LocalContext, Environment, CallStack};
-use vm::contexts::{GlobalContext};
+use vm::contexts::{GlobalContext, run_eval_hooks};
 use vm::functions::define::DefineResult;
 use vm::errors::{Error, InterpreterError, RuntimeErrorType, CheckErrors, InterpreterResult as Result};
 use vm::database::MemoryBackingStore;
@@ -39,6 +44,7 @@ use vm::costs::{cost_functions, CostOverflowingMath, LimitedCostTracker, MemoryC
 pub use vm::representations::{SymbolicExpression, SymbolicExpressionType, ClarityName, ContractName};
 
 pub use vm::contexts::MAX_CONTEXT_DEPTH;
+pub use vm::version::ClarityVersion;
 use std::convert::TryInto;
 
 const MAX_CALL_STACK_DEPTH: usize = 64;
@@ -87,33 +93,14 @@ fn add_stack_trace(result: &mut Result<Value>, env: &Environment) {
 
 pub fn apply(function: &CallableType, args: &[SymbolicExpression],
              env: &mut Environment, context: &LocalContext) -> Result<Value> {
-    let identifier = function.get_identifier();
-    // Aaron: in non-debug executions, we shouldn't track a full call-stack.
-    //        only enough to do recursion detection.
-
-    // do recursion check on user functions.
-    let track_recursion = match function {
-        CallableType::UserFunction(_) => true,
-        _ => false
-    };
-
-    if track_recursion && env.call_stack.contains(&identifier) {
-        return Err(CheckErrors::CircularReference(vec![identifier.to_string()]).into())
-    }
-
-    if env.call_stack.depth() >= MAX_CALL_STACK_DEPTH {
-        return Err(RuntimeErrorType::MaxStackDepthReached.into())
-    }
+    let (identifier, track_recursion) = begin_application(function, env)?;
 
     if let CallableType::SpecialFunction(_, function) = function {
-        env.call_stack.insert(&identifier, track_recursion);
         let mut resp = function(args, env, context);
         add_stack_trace(&mut resp, env);
         env.call_stack.remove(&identifier, track_recursion)?;
         resp
     } else {
-        env.call_stack.insert(&identifier, track_recursion);
-
         let mut used_memory = 0;
         let mut evaluated_args = vec![];
         for arg_x in args.iter() {
@@ -137,38 +124,79 @@ pub fn apply(function: &CallableType, args: &[SymbolicExpression],
             used_memory += arg_value.get_memory_use();
             evaluated_args.push(arg_value);
         }
-        let mut resp = match function {
-            CallableType::NativeFunction(_, function, cost_function) => {
-                let arg_size = evaluated_args.len();
-                runtime_cost!(cost_function, env, arg_size)?;
-                function.apply(evaluated_args)
-            },
-            CallableType::UserFunction(function) => function.apply(&evaluated_args, env),
-            _ => panic!("Should be unreachable.")
-        };
-        add_stack_trace(&mut resp, env);
-        env.drop_memory(used_memory);
-        env.call_stack.remove(&identifier, track_recursion)?;
-        resp
+        finish_application(function, &identifier, track_recursion, evaluated_args, used_memory, env)
     }
 }
 
+/// Checks for recursion and the call stack depth, and pushes the application of
+///   `function` onto the call stack.
+fn begin_application(function: &CallableType, env: &mut Environment) -> Result<(FunctionIdentifier, bool)> {
+    let identifier = function.get_identifier();
+    // Aaron: in non-debug executions, we shouldn't track a full call-stack.
+    //        only enough to do recursion detection.
+
+    // do recursion check on user functions.
+    let track_recursion = match function {
+        CallableType::UserFunction(_) => true,
+        _ => false
+    };
+
+    if track_recursion && env.call_stack.contains(&identifier) {
+        return Err(CheckErrors::CircularReference(vec![identifier.to_string()]).into())
+    }
+
+    if env.call_stack.depth() >= MAX_CALL_STACK_DEPTH {
+        return Err(RuntimeErrorType::MaxStackDepthReached.into())
+    }
+
+    env.call_stack.insert(&identifier, track_recursion);
+    Ok((identifier, track_recursion))
+}
+
+/// Applies a native or user function to its evaluated arguments, and pops the application
+///   pushed by `begin_application`.
+fn finish_application(function: &CallableType, identifier: &FunctionIdentifier, track_recursion: bool,
+                      evaluated_args: Vec<Value>, used_memory: u64, env: &mut Environment) -> Result<Value> {
+    let mut resp = match function {
+        CallableType::NativeFunction(_, function, cost_function) => {
+            let arg_size = evaluated_args.len();
+            runtime_cost!(cost_function, env, arg_size)?;
+            function.apply(evaluated_args)
+        },
+        CallableType::UserFunction(function) => function.apply(&evaluated_args, env),
+        _ => panic!("Should be unreachable.")
+    };
+    add_stack_trace(&mut resp, env);
+    env.drop_memory(used_memory);
+    env.call_stack.remove(identifier, track_recursion)?;
+    resp
+}
+
 pub fn eval <'a> (exp: &SymbolicExpression, env: &'a mut Environment, context: &LocalContext) -> Result<Value> {
     use vm::representations::SymbolicExpressionType::{AtomValue, Atom, List, LiteralValue, TraitReference, Field};
 
-    match exp.expr {
-        AtomValue(ref value) | LiteralValue(ref value) => Ok(value.clone()),
-        Atom(ref value) => lookup_variable(&value, context, env),
-        List(ref children) => {
-            let (function_variable, rest) = children.split_first()
-                .ok_or(CheckErrors::NonFunctionApplication)?;
-            let function_name = function_variable.match_atom()
-                .ok_or(CheckErrors::BadFunctionName)?;
-            let f = lookup_function(&function_name, env)?;
-            apply(&f, &rest, env, context)
-        },
-        TraitReference(_, _) | Field(_) => unreachable!("can't be evaluated"),
-    }
+    run_eval_hooks(env, |eval_hook, env| eval_hook.will_begin_eval(env, context, exp));
+
+    // wrap in a closure so that `?` does not skip the hook's did_finish_eval.
+    let result = (|| -> Result<Value> {
+        match exp.expr {
+            AtomValue(ref value) | LiteralValue(ref value) => Ok(value.clone()),
+            Atom(ref value) => lookup_variable(&value, context, env),
+            List(ref children) => {
+                let (function_variable, rest) = children.split_first()
+                    .ok_or(CheckErrors::NonFunctionApplication)?;
+                let function_name = function_variable.match_atom()
+                    .ok_or(CheckErrors::BadFunctionName)?;
+                let f = lookup_function(&function_name, env)?;
+                apply(&f, &rest, env, context)
+            },
+            TraitReference(_, _) | Field(_) => unreachable!("can't be evaluated"),
+        }
+    })();
+
+    run_eval_hooks(env, |eval_hook, env| eval_hook.did_finish_eval(env, context, exp, &result));
+
+    result
 }
 
 
@@ -192,6 +220,24 @@ fn eval_all (expressions: &[SymbolicExpression],
     let context = LocalContext::new();
     let mut total_memory_use = 0;
 
+    #[cfg(feature = "wasm")]
+    {
+        if global_context.compile_contracts {
+            // a malformed definition fails with the error the interpreter would report for it.
+            let compiled = wasm::WasmContract::compile_expressions(&contract_context.contract_identifier, expressions)
+                .map_err(|e| -> Error {
+                    match e {
+                        wasm::WasmError::BadDefinition(e) => e.into(),
+                        e => InterpreterError::InterpreterError(
+                            format!("Failed to compile contract {}: {}", contract_context.contract_identifier, e)).into()
+                    }
+                })?;
+            #[cfg(test)]
+            compiled.record_compiled();
+            global_context.wasm_contracts.insert(contract_context.contract_identifier.clone(), std::rc::Rc::new(compiled));
+        }
+    }
+
     finally_drop_memory!(global_context, total_memory_use; {
         for exp in expressions {
             let try_define = global_context.execute(|context| {
@@ -290,13 +336,19 @@ fn eval_all (expressions: &[SymbolicExpression],
  *  Only used by CLI.
  */
 pub fn execute(program: &str) -> Result<Option<Value>> {
+    execute_with_version(program, ClarityVersion::Clarity1)
+}
+
+/* Run provided program like `execute`, reading its source as the given version of Clarity.
+ */
+pub fn execute_with_version(program: &str, version: ClarityVersion) -> Result<Option<Value>> {
     let contract_id = QualifiedContractIdentifier::transient();
     let mut contract_context = ContractContext::new(contract_id.clone());
     let mut marf = MemoryBackingStore::new();
     let conn = marf.as_clarity_db();
-    let mut global_context = GlobalContext::new(conn, LimitedCostTracker::new_max_limit());
+    let mut global_context = GlobalContext::new(false, conn, LimitedCostTracker::new_max_limit());
     global_context.execute(|g| {
-        let parsed = ast::build_ast(&contract_id, program, &mut ())?
+        let parsed = ast::build_ast(&contract_id, program, &mut (), version)?
             .expressions;
         eval_all(&parsed, &mut contract_context, g)
     })
@@ -343,7 +395,7 @@ mod test {
         let mut contract_context = ContractContext::new(QualifiedContractIdentifier::transient());
 
         let mut marf = MemoryBackingStore::new();
-        let mut global_context = GlobalContext::new(marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
+        let mut global_context = GlobalContext::new(false, marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
 
         contract_context.variables.insert("a".into(), Value::Int(59));
         contract_context.functions.insert("do_work".into(), user_function);
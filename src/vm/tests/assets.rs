@@ -5,6 +5,7 @@ use vm::contexts::{OwnedEnvironment, GlobalContext, AssetMap, AssetMapEntry};
 use chainstate::stacks::events::{StacksTransactionEvent};
 use vm::representations::SymbolicExpression;
 use vm::contracts::Contract;
+use vm::version::CLARITY2_ACTIVATION_HEIGHT;
 use util::hash::hex_bytes;
 use vm::tests::{with_memory_environment, with_marfed_environment, symbols_from_values,
                 execute, is_err_code, is_committed};
@@ -525,6 +526,150 @@ fn total_supply(owned_env: &mut OwnedEnvironment) {
     });
 }
 
+fn test_burns(owned_env: &mut OwnedEnvironment) {
+    let contract = "(define-fungible-token stackaroos u5)
+         (define-non-fungible-token names int)
+         (define-read-only (get-supply)
+            (ft-get-supply stackaroos))
+         (define-read-only (get-owner (name int))
+            (nft-get-owner? names name))
+         (define-public (faucet (amount uint))
+            (ft-mint? stackaroos amount tx-sender))
+         (define-public (burn (amount uint))
+            (ft-burn? stackaroos amount tx-sender))
+         (define-public (register (name int))
+            (nft-mint? names name tx-sender))
+         (define-public (burn-name (name int))
+            (nft-burn? names name tx-sender))";
+
+    let p1 = execute("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR");
+    let p2 = execute("'SM2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQVX8X0G");
+
+    let (p1_std_principal, p1_principal) = match p1 {
+        Value::Principal(PrincipalData::Standard(ref data)) => (data.clone(), PrincipalData::Standard(data.clone())),
+        _ => panic!()
+    };
+
+    let token_contract_id = QualifiedContractIdentifier::new(p1_std_principal.clone(), "tokens".into());
+    let token_identifier = AssetIdentifier { contract_identifier: token_contract_id.clone(),
+                                             asset_name: "stackaroos".into() };
+    let names_identifier = AssetIdentifier { contract_identifier: token_contract_id.clone(),
+                                             asset_name: "names".into() };
+
+    owned_env.initialize_contract(token_contract_id.clone(), contract).unwrap();
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "faucet", &symbols_from_values(vec![Value::UInt(5)])).unwrap();
+    assert!(is_committed(&result));
+
+    // burns are recorded as tokens sent by the burning principal.
+    let (result, asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "burn", &symbols_from_values(vec![Value::UInt(2)])).unwrap();
+    assert!(is_committed(&result));
+    assert_eq!(asset_map.to_table()[&p1_principal][&token_identifier], AssetMapEntry::Token(2));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "get-supply", &vec![]).unwrap();
+    assert_eq!(result, Value::UInt(3));
+
+    // burned tokens no longer count against the supply cap.
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "faucet", &symbols_from_values(vec![Value::UInt(2)])).unwrap();
+    assert!(is_committed(&result));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "burn", &symbols_from_values(vec![Value::UInt(6)])).unwrap();
+    assert!(is_err_code(&result, 1));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "burn", &symbols_from_values(vec![Value::UInt(0)])).unwrap();
+    assert!(is_err_code(&result, 3));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "register", &symbols_from_values(vec![Value::Int(1)])).unwrap();
+    assert!(is_committed(&result));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p2.clone(), &token_contract_id, "burn-name", &symbols_from_values(vec![Value::Int(1)])).unwrap();
+    assert!(is_err_code(&result, 1));
+
+    let (result, asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "burn-name", &symbols_from_values(vec![Value::Int(1)])).unwrap();
+    assert!(is_committed(&result));
+    assert_eq!(asset_map.to_table()[&p1_principal][&names_identifier], AssetMapEntry::Asset(vec![Value::Int(1)]));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "get-owner", &symbols_from_values(vec![Value::Int(1)])).unwrap();
+    assert_eq!(result, Value::none());
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &token_contract_id, "burn-name", &symbols_from_values(vec![Value::Int(1)])).unwrap();
+    assert!(is_err_code(&result, 3));
+}
+
+fn test_uncapped_burns(owned_env: &mut OwnedEnvironment) {
+    let contract = "(define-fungible-token stackaroos)
+         (define-read-only (get-supply)
+            (ft-get-supply stackaroos))
+         (define-public (faucet (amount uint))
+            (ft-mint? stackaroos amount tx-sender))
+         (define-public (burn (amount uint))
+            (ft-burn? stackaroos amount tx-sender))";
+
+    let p1 = execute("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR");
+    let p1_std_principal = match p1 {
+        Value::Principal(PrincipalData::Standard(ref data)) => data.clone(),
+        _ => panic!()
+    };
+
+    // uncapped tokens defined before Clarity2 do not track their supply.
+    let untracked_contract_id = QualifiedContractIdentifier::new(p1_std_principal.clone(), "untracked".into());
+    owned_env.initialize_contract(untracked_contract_id.clone(), contract).unwrap();
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &untracked_contract_id, "faucet", &symbols_from_values(vec![Value::UInt(5)])).unwrap();
+    assert!(is_committed(&result));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &untracked_contract_id, "burn", &symbols_from_values(vec![Value::UInt(3)])).unwrap();
+    assert!(is_committed(&result));
+
+    let err = execute_transaction(owned_env,
+        p1.clone(), &untracked_contract_id, "get-supply", &vec![]).unwrap_err();
+    assert!( match err {
+        Error::Runtime(RuntimeErrorType::UntrackedTokenSupply, _) => true,
+        _ => false
+    });
+
+    // uncapped tokens defined from Clarity2 do.
+    let tracked_contract_id = QualifiedContractIdentifier::new(p1_std_principal.clone(), "tracked".into());
+    owned_env.execute_in_env(p1.clone(), |env| -> Result<(), Error> {
+        env.global_context.database.set_simulated_block_height(CLARITY2_ACTIVATION_HEIGHT);
+        Ok(())
+    }).unwrap();
+    owned_env.initialize_contract(tracked_contract_id.clone(), contract).unwrap();
+    owned_env.execute_in_env(p1.clone(), |env| -> Result<(), Error> {
+        env.global_context.database.clear_simulated_block_height();
+        Ok(())
+    }).unwrap();
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &tracked_contract_id, "get-supply", &vec![]).unwrap();
+    assert_eq!(result, Value::UInt(0));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &tracked_contract_id, "faucet", &symbols_from_values(vec![Value::UInt(4)])).unwrap();
+    assert!(is_committed(&result));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &tracked_contract_id, "burn", &symbols_from_values(vec![Value::UInt(1)])).unwrap();
+    assert!(is_committed(&result));
+
+    let (result, _asset_map, _events) = execute_transaction(owned_env,
+        p1.clone(), &tracked_contract_id, "get-supply", &vec![]).unwrap();
+    assert_eq!(result, Value::UInt(3));
+}
+
 fn test_overlapping_nfts(owned_env: &mut OwnedEnvironment) {
     let tokens_contract = FIRST_CLASS_TOKENS;
     let names_contract = ASSET_NAMES;
@@ -731,7 +876,8 @@ fn test_simple_naming_system(owned_env: &mut OwnedEnvironment) {
 #[test]
 fn test_all() {
     let to_test = [test_overlapping_nfts, test_simple_token_system,
-                   test_simple_naming_system, total_supply, test_native_stx_ops];
+                   test_simple_naming_system, total_supply, test_burns, test_uncapped_burns,
+                   test_native_stx_ops];
     for test in to_test.iter() {
         with_memory_environment(test, true);
         with_marfed_environment(test, true);
@@ -13,11 +13,12 @@ use util::hash::hex_bytes;
 use vm::database::{MemoryBackingStore, MarfedKV, NULL_HEADER_DB, ClarityDatabase};
 use vm::clarity::ClarityInstance;
 use vm::ast;
+use vm::ClarityVersion;
 use vm::costs::ExecutionCost;
 
 use vm::tests::{with_memory_environment, with_marfed_environment, execute, symbols_from_values};
 
-const FACTORIAL_CONTRACT: &str = "(define-map factorials ((id int)) ((current int) (index int)))
+pub const FACTORIAL_CONTRACT: &str = "(define-map factorials ((id int)) ((current int) (index int)))
          (define-private (init-factorial (id int) (factorial int))
            (print (map-insert factorials (tuple (id id)) (tuple (current 1) (index factorial)))))
          (define-public (compute (id int))
@@ -163,7 +164,7 @@ fn test_block_headers(n: u8) -> StacksBlockId {
 
 #[test]
 fn test_simple_token_system() {
-    let mut clarity = ClarityInstance::new(MarfedKV::temporary(), ExecutionCost::max_value());
+    let mut clarity = ClarityInstance::new(false, MarfedKV::temporary(), ExecutionCost::max_value());
     let p1 = PrincipalData::from(PrincipalData::parse_standard_principal("SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR").unwrap());
     let p2 = PrincipalData::from(PrincipalData::parse_standard_principal("SM2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQVX8X0G").unwrap());
     let contract_identifier = QualifiedContractIdentifier::local("tokens").unwrap();
@@ -175,7 +176,7 @@ fn test_simple_token_system() {
 
         let tokens_contract = SIMPLE_TOKENS;
 
-        let contract_ast = ast::build_ast(&contract_identifier, tokens_contract, &mut ()).unwrap();
+        let contract_ast = ast::build_ast(&contract_identifier, tokens_contract, &mut (), ClarityVersion::Clarity1).unwrap();
 
         block.as_transaction(|tx| tx.initialize_smart_contract(&contract_identifier, &contract_ast, tokens_contract, |_, _| false)
                              .unwrap());
@@ -35,6 +35,13 @@ pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
         Modulo => "(mod 2 1)",
         Power => "(pow 2 3)",
         BitwiseXOR => "(xor 1 2)",
+        BitwiseAnd => "(bit-and 1 2)",
+        BitwiseOr => "(bit-or 1 2)",
+        BitwiseNot => "(bit-not 1)",
+        BitwiseLShift => "(bit-shift-left 1 u2)",
+        BitwiseRShift => "(bit-shift-right 4 u2)",
+        Sqrti => "(sqrti 16)",
+        Log2 => "(log2 16)",
         And => "(and true false)",
         Or => "(or true false)",
         Not => "(not true)",
@@ -50,6 +57,15 @@ pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
         Concat => "(concat list-bar list-bar)",
         AsMaxLen => "(as-max-len? list-bar u3)",
         Len => "(len list-bar)",
+        ElementAt => "(element-at list-bar u1)",
+        IndexOf => "(index-of list-bar 1)",
+        Slice => "(slice list-bar u0 u1)",
+        BuffToIntLe => "(buff-to-int-le 0x00)",
+        BuffToIntBe => "(buff-to-int-be 0x00)",
+        BuffToUIntLe => "(buff-to-uint-le 0x00)",
+        BuffToUIntBe => "(buff-to-uint-be 0x00)",
+        IntToBuffLe => "(int-to-buff-le 1)",
+        IntToBuffBe => "(int-to-buff-be 1)",
         ListCons => "(list 1 2 3 4)",
         FetchEntry => "(map-get? map-foo {a: 1})",
         SetEntry => "(map-set map-foo {a: 1} {b: 2})",
@@ -63,9 +79,13 @@ pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
         Sha512 => "(sha512 1)",
         Sha512Trunc256 => "(sha512/256 1)",
         Keccak256 => "(keccak256 1)",
+        Secp256k1Recover => "(secp256k1-recover? 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae)",
+        Secp256k1Verify => "(secp256k1-verify 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)",
+        PrincipalOf => "(principal-of? 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)",
         Print => "(print 1)",
         ContractCall => "(contract-call? .contract-other foo-exec 1)",
         ContractOf => "(contract-of contract)",
+        ImplementsTrait => "(implements-trait? .contract-other <trait-1>)",
         AsContract => "(as-contract 1)",
         GetBlockInfo => "(get-block-info? time u1)",
         ConsOkay => "(ok 1)",
@@ -89,6 +109,9 @@ pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
         GetAssetOwner => "(nft-get-owner? nft-foo 1)",
         TransferToken => "(ft-transfer? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
         TransferAsset => "(nft-transfer? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        GetTokenSupply => "(ft-get-supply ft-foo)",
+        BurnToken => "(ft-burn? ft-foo u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        BurnAsset => "(nft-burn? nft-foo 1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
         AtBlock => "(at-block 0x0000000000000000000000000000000000000000000000000000000000000000 1)",
         GetStxBalance => "(stx-get-balance 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
         StxTransfer => "(stx-transfer? u1 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
@@ -112,7 +135,7 @@ fn test_tracked_costs(prog: &str) -> ExecutionCost {
 
     let contract_self = format!("(define-map map-foo ((a int)) ((b int)))
                          (define-non-fungible-token nft-foo int)
-                         (define-fungible-token ft-foo)
+                         (define-fungible-token ft-foo u1000000)
                          (define-data-var var-foo int 0)
                          (define-constant tuple-foo (tuple (a 1)))
                          (define-constant list-foo (list true))
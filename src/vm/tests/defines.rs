@@ -1,3 +1,4 @@
+use vm::ClarityVersion;
 use vm::execute;
 use vm::errors::{CheckErrors, RuntimeErrorType, Error};
 use vm::types::{Value, TypeSignature, QualifiedContractIdentifier};
@@ -157,7 +158,7 @@ fn test_recursive_panic() {
               (* a (factorial (- a 1)))))
          (factorial 10)";
 
-    let err = build_ast(&QualifiedContractIdentifier::transient(), tests, &mut ()).unwrap_err();
+    let err = build_ast(&QualifiedContractIdentifier::transient(), tests, &mut (), ClarityVersion::Clarity1).unwrap_err();
     match err.err {
         ParseErrors::CircularReference(_) => {},
         _ => {
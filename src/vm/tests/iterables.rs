@@ -2,11 +2,15 @@ use vm::types::{Value, TypeSignature};
 use vm::types::TypeSignature::{IntType, UIntType, BoolType, ListType, BufferType};
 use vm::types::signatures::{ListTypeData};
 
-use vm::execute;
+use vm::{execute, execute_with_version, ClarityVersion};
 use vm::errors::{CheckErrors, RuntimeErrorType, Error};
 use vm::analysis::errors::{CheckError};
 use std::convert::TryInto;
 
+fn execute_clarity2(program: &str) -> Result<Option<Value>, Error> {
+    execute_with_version(program, ClarityVersion::Clarity2)
+}
+
 #[test]
 fn test_simple_list_admission() {
     let defines =
@@ -188,7 +192,7 @@ fn test_simple_buff_assert_max_len() {
 
     assert_eq!(
         execute("(as-max-len? 1 u3)").unwrap_err(),
-        CheckErrors::ExpectedListOrBuffer(IntType).into());
+        CheckErrors::ExpectedSequence(IntType).into());
 
     assert_eq!(
         execute("(as-max-len? \"123\" \"1\")").unwrap_err(),
@@ -311,12 +315,12 @@ fn test_simple_folds_buffer() {
     let tests =
         ["(define-private (get-len (x (buff 1)) (acc int)) (+ acc 1))
          (fold get-len \"blockstack\" 0)",
-        "(define-private (slice (x (buff 1)) (acc (tuple (limit uint) (cursor uint) (data (buff 10)))))
+        "(define-private (take (x (buff 1)) (acc (tuple (limit uint) (cursor uint) (data (buff 10)))))
             (if (< (get cursor acc) (get limit acc))
                 (let ((data (default-to (get data acc) (as-max-len? (concat (get data acc) x) u10))))
                     (tuple (limit (get limit acc)) (cursor (+ u1 (get cursor acc))) (data data))) 
                 acc))
-        (get data (fold slice \"0123456789\" (tuple (limit u5) (cursor u0) (data \"\"))))"];
+        (get data (fold take \"0123456789\" (tuple (limit u5) (cursor u0) (data \"\"))))"];
 
     let expected = [
         Value::Int(10),
@@ -342,6 +346,229 @@ fn test_buff_len() {
 }
 
 
+#[test]
+fn test_string_len() {
+    let tests = [
+        "(len \"blockstack\")",
+        "(len \"\")",
+        "(len u\"bl\\u{f6}ckst\\u{e4}ck\")",
+        "(len u\"\\u{1F600}\")"];
+
+    let expected = [
+        Value::UInt(10),
+        Value::UInt(0),
+        Value::UInt(10),
+        Value::UInt(1)];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        assert_eq!(expected.clone(), execute(test).unwrap().unwrap());
+    }
+}
+
+#[test]
+fn test_simple_string_concat() {
+    let tests = [
+        "(concat \"012\" \"34\")",
+        "(concat \"\" \"\")",
+        "(concat u\"caf\\u{e9} \" u\"cr\\u{e8}me\")"];
+
+    let expected = [
+        Value::string_ascii_from_bytes("01234".as_bytes().to_vec()).unwrap(),
+        Value::string_ascii_from_bytes(vec![]).unwrap(),
+        Value::string_utf8_from_string("caf\u{e9} cr\u{e8}me").unwrap()];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        assert_eq!(expected.clone(), execute_clarity2(test).unwrap().unwrap());
+    }
+
+    assert_eq!(
+        execute_clarity2("(concat \"1\" 0x31)").unwrap_err(),
+        RuntimeErrorType::BadTypeConstruction.into());
+
+    assert_eq!(
+        execute_clarity2("(concat \"1\" u\"1\")").unwrap_err(),
+        RuntimeErrorType::BadTypeConstruction.into());
+}
+
+#[test]
+fn test_simple_append() {
+    let tests = [
+        "(append \"blockstac\" \"k\")",
+        "(append \"\" \"\")",
+        "(append u\"caf\" u\"\\u{e9}\")",
+        "(append 0x0102 0x03)"];
+
+    let expected = [
+        Value::string_ascii_from_bytes("blockstack".as_bytes().to_vec()).unwrap(),
+        Value::string_ascii_from_bytes(vec![]).unwrap(),
+        Value::string_utf8_from_string("caf\u{e9}").unwrap(),
+        Value::buff_from(vec![1, 2, 3]).unwrap()];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        assert_eq!(expected.clone(), execute_clarity2(test).unwrap().unwrap());
+    }
+
+    // strings and buffers may only be extended by a single element
+    assert_eq!(
+        execute_clarity2("(append \"block\" \"stack\")").unwrap_err(),
+        CheckErrors::TypeValueError(TypeSignature::min_string_ascii(),
+                                    Value::string_ascii_from_bytes("stack".as_bytes().to_vec()).unwrap()).into());
+
+    assert_eq!(
+        execute_clarity2("(append 1 2)").unwrap_err(),
+        CheckErrors::ExpectedSequence(IntType).into());
+}
+
+#[test]
+fn test_simple_string_assert_max_len() {
+    let tests = [
+        "(as-max-len? \"123\" u3)",
+        "(as-max-len? \"123\" u2)",
+        "(as-max-len? u\"\\u{1F600}\\u{1F600}\" u2)"];
+
+    let expected = [
+        Value::some(Value::string_ascii_from_bytes("123".as_bytes().to_vec()).unwrap()).unwrap(),
+        Value::none(),
+        Value::some(Value::string_utf8_from_string("\u{1F600}\u{1F600}").unwrap()).unwrap()];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        assert_eq!(expected.clone(), execute_clarity2(test).unwrap().unwrap());
+    }
+}
+
+#[test]
+fn test_element_at() {
+    let tests = [
+        "(element-at (list 1 2 3) u1)",
+        "(element-at (list 1 2 3) u3)",
+        "(element-at 0x0102 u0)",
+        "(element-at \"blockstack\" u9)",
+        "(element-at u\"caf\\u{e9}\" u3)",
+        "(element-at \"\" u0)"];
+
+    let expected = [
+        Value::some(Value::Int(2)).unwrap(),
+        Value::none(),
+        Value::some(Value::buff_from_byte(1)).unwrap(),
+        Value::some(Value::string_ascii_from_bytes("k".as_bytes().to_vec()).unwrap()).unwrap(),
+        Value::some(Value::string_utf8_from_string("\u{e9}").unwrap()).unwrap(),
+        Value::none()];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        assert_eq!(expected.clone(), execute_clarity2(test).unwrap().unwrap());
+    }
+
+    assert_eq!(
+        execute_clarity2("(element-at (list 1 2 3) 1)").unwrap_err(),
+        CheckErrors::TypeValueError(UIntType, Value::Int(1)).into());
+
+    assert_eq!(
+        execute_clarity2("(element-at 1 u1)").unwrap_err(),
+        CheckErrors::ExpectedSequence(IntType).into());
+}
+
+#[test]
+fn test_index_of() {
+    let tests = [
+        "(index-of (list 1 2 3 2) 2)",
+        "(index-of (list 1 2 3) 4)",
+        "(index-of 0x010203 0x03)",
+        "(index-of 0x010203 0x)",
+        "(index-of \"blockstack\" \"k\")",
+        "(index-of u\"caf\\u{e9}\" u\"\\u{e9}\")",
+        "(index-of (list (list 1) (list 2 3)) (list 2 3))"];
+
+    let expected = [
+        Value::some(Value::UInt(1)).unwrap(),
+        Value::none(),
+        Value::some(Value::UInt(2)).unwrap(),
+        Value::none(),
+        Value::some(Value::UInt(4)).unwrap(),
+        Value::some(Value::UInt(3)).unwrap(),
+        Value::some(Value::UInt(1)).unwrap()];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        assert_eq!(expected.clone(), execute_clarity2(test).unwrap().unwrap());
+    }
+
+    assert_eq!(
+        execute_clarity2("(index-of \"blockstack\" u\"b\")").unwrap_err(),
+        CheckErrors::TypeValueError(TypeSignature::min_string_ascii(),
+                                    Value::string_utf8_from_string("b").unwrap()).into());
+
+    assert_eq!(
+        execute_clarity2("(index-of 1 1)").unwrap_err(),
+        CheckErrors::ExpectedSequence(IntType).into());
+}
+
+#[test]
+fn test_slice() {
+    let tests = [
+        "(slice (list 1 2 3 4 5) u1 u3)",
+        "(slice (list 1 2 3 4 5) u3 u1)",
+        "(slice (list 1 2 3 4 5) u3 u6)",
+        "(slice 0x01020304 u0 u4)",
+        "(slice \"blockstack\" u5 u10)",
+        "(slice u\"caf\\u{e9} cr\\u{e8}me\" u5 u5)"];
+
+    let expected = [
+        Value::some(Value::list_with_type(vec![Value::Int(2), Value::Int(3)],
+                                          ListTypeData::new_list(IntType, 5).unwrap()).unwrap()).unwrap(),
+        Value::none(),
+        Value::none(),
+        Value::some(Value::buff_from(vec![1, 2, 3, 4]).unwrap()).unwrap(),
+        Value::some(Value::string_ascii_from_bytes("stack".as_bytes().to_vec()).unwrap()).unwrap(),
+        Value::some(Value::string_utf8_from_string("").unwrap()).unwrap()];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        assert_eq!(expected.clone(), execute_clarity2(test).unwrap().unwrap());
+    }
+
+    assert_eq!(
+        execute_clarity2("(slice 1 u1 u2)").unwrap_err(),
+        CheckErrors::ExpectedSequence(IntType).into());
+}
+
+#[test]
+fn test_buff_int_conversions() {
+    let tests = [
+        "(buff-to-int-le 0x01)",
+        "(buff-to-int-be 0x01)",
+        "(buff-to-int-be 0xffffffffffffffffffffffffffffffff)",
+        "(buff-to-int-le 0xff)",
+        "(buff-to-int-be 0xffff)",
+        "(buff-to-uint-le 0x0001)",
+        "(buff-to-uint-be 0x0001)",
+        "(buff-to-uint-be 0x)",
+        "(buff-to-int-le (int-to-buff-le -1234))",
+        "(buff-to-uint-be (int-to-buff-be u1234))",
+        "(int-to-buff-be 258)",
+        "(int-to-buff-le u258)"];
+
+    let expected = [
+        Value::Int(1),
+        Value::Int(1),
+        Value::Int(-1),
+        Value::Int(255),
+        Value::Int(65535),
+        Value::UInt(256),
+        Value::UInt(1),
+        Value::UInt(0),
+        Value::Int(-1234),
+        Value::UInt(1234),
+        Value::buff_from(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2]).unwrap(),
+        Value::buff_from(vec![2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap()];
+
+    for (test, expected) in tests.iter().zip(expected.iter()) {
+        assert_eq!(expected.clone(), execute(test).unwrap().unwrap());
+    }
+
+    assert_eq!(
+        execute("(buff-to-int-le 0x0102030405060708090a0b0c0d0e0f1011)").unwrap_err(),
+        CheckErrors::TypeValueError(TypeSignature::from("(buff 16)"),
+                                    Value::buff_from((1..18).collect()).unwrap()).into());
+}
+
 #[test]
 fn test_construct_bad_list() {
     let test1 = "(list 1 2 3 true)";
@@ -23,6 +23,8 @@ mod contracts;
 pub mod costs;
 mod traits;
 mod large_contract;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 pub fn with_memory_environment<F>(f: F, top_level: bool)
 where F: FnOnce(&mut OwnedEnvironment) -> ()
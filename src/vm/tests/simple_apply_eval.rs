@@ -146,6 +146,51 @@ fn test_keccak256() {
         .for_each(|(program, expectation)| assert_eq!(to_buffer(expectation), execute(program)));
 }
 
+#[test]
+fn test_secp256k1() {
+    let secp256k1_evals = [
+        "(secp256k1-recover? 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae)",
+        "(secp256k1-recover? 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10 0x04fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae)",
+        "(secp256k1-recover? 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10 0x01)",
+        "(secp256k1-verify 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)",
+        "(secp256k1-verify 0x0000000000000000000000000000000000000000000000000000000000000000 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)",
+        "(secp256k1-verify 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae 0x02)",
+        "(principal-of? 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)",
+        "(principal-of? 0x02)",
+    ];
+
+    let expectations = [
+        Value::okay(Value::buff_from(hex_bytes("03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110").unwrap()).unwrap()).unwrap(),
+        Value::error(Value::UInt(1)).unwrap(),
+        Value::error(Value::UInt(1)).unwrap(),
+        Value::Bool(true),
+        Value::Bool(false),
+        Value::Bool(false),
+        Value::okay(execute("'ST1AW6EKPGT61SQ9FNVDS17RKNWT8ZP582VF9HSCP")).unwrap(),
+        Value::error(Value::UInt(1)).unwrap(),
+    ];
+
+    secp256k1_evals.iter().zip(expectations.iter())
+        .for_each(|(program, expectation)| assert_eq!(expectation.clone(), execute(program)));
+
+    assert_eq!(vm_execute("(secp256k1-verify 0x355e3c7fe5067ff07c04739f892085bd3ff235f4d5a67762a0d3c468536f0c10 0x01fa78a5e90f7020a787ffd02f999a82d512d4092fd2e27ad1f131d7384d84ed4f46f11baa03708cdf8f6ecc3e597976ba73d893f5f308aa3594ac2f5d926133ae)").unwrap_err(),
+               CheckErrors::IncorrectArgumentCount(3, 2).into());
+    assert_eq!(vm_execute("(principal-of? 1)").unwrap_err(),
+               CheckErrors::TypeValueError(TypeSignature::from("(buff 33)"), Value::Int(1)).into());
+}
+
+#[test]
+fn test_principal_of_mainnet() {
+    let program = "(principal-of? 0x03adb8de4bfb65db2cfd6120d55c6526ae9c52e675db7e47308636534ba7786110)";
+    for (mainnet, expected) in [(false, "'ST1AW6EKPGT61SQ9FNVDS17RKNWT8ZP582VF9HSCP"),
+                                (true, "'SP1AW6EKPGT61SQ9FNVDS17RKNWT8ZP582RS0JSRJ")].iter() {
+        let mut marf = MemoryBackingStore::new();
+        let mut owned_env = OwnedEnvironment::new_cost_limited(*mainnet, marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
+        let (result, _, _) = owned_env.eval_raw(program).unwrap();
+        assert_eq!(Value::okay(execute(expected)).unwrap(), result);
+    }
+}
+
 #[test]
 fn test_buffer_equality() {
     let tests = [
@@ -219,7 +264,7 @@ fn test_simple_if_functions() {
         let context = LocalContext::new();
         let mut contract_context = ContractContext::new(QualifiedContractIdentifier::transient());
         let mut marf = MemoryBackingStore::new();
-        let mut global_context = GlobalContext::new(marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
+        let mut global_context = GlobalContext::new(false, marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
 
         contract_context.functions.insert("with_else".into(), user_function1);
         contract_context.functions.insert("without_else".into(), user_function2);
@@ -387,6 +432,82 @@ fn test_unsigned_arithmetic() {
     }
 }
 
+#[test]
+fn test_bitwise_and_integer_math_functions() {
+    let tests = [
+        "(bit-and 24 16)",
+        "(bit-and u255 u15 u6)",
+        "(bit-or 4 8 1)",
+        "(bit-or u4 u3)",
+        "(bit-not 0)",
+        "(bit-not u0)",
+        "(bit-shift-left 1 u127)",
+        "(bit-shift-left u3 u127)",
+        "(bit-shift-left 1 u129)",
+        "(bit-shift-right -1 u127)",
+        "(bit-shift-right (bit-not u0) u127)",
+        "(sqrti 24)",
+        "(sqrti 1000000)",
+        "(sqrti (bit-not u0))",
+        "(log2 1)",
+        "(log2 1025)",
+        "(log2 (bit-not u0))"];
+
+    let expectations = [
+        Value::Int(16),
+        Value::UInt(6),
+        Value::Int(13),
+        Value::UInt(7),
+        Value::Int(-1),
+        Value::UInt(u128::max_value()),
+        Value::Int(i128::min_value()),
+        Value::UInt(1 << 127),
+        Value::Int(2),
+        Value::Int(-1),
+        Value::UInt(1),
+        Value::Int(4),
+        Value::Int(1000),
+        Value::UInt(u64::max_value() as u128),
+        Value::Int(0),
+        Value::Int(10),
+        Value::UInt(127),
+    ];
+
+    tests.iter().zip(expectations.iter())
+        .for_each(|(program, expectation)| assert_eq!(expectation.clone(), execute(program)));
+}
+
+#[test]
+fn test_bitwise_and_integer_math_errors() {
+    let tests = [
+        "(bit-and 1 u2)",
+        "(bit-or)",
+        "(bit-not true)",
+        "(bit-not 1 2)",
+        "(bit-shift-left 1 2)",
+        "(bit-shift-right u1 u2 u3)",
+        "(sqrti -1)",
+        "(log2 0)",
+        "(log2 -8)",
+    ];
+
+    let expectations: &[Error] = &[
+        CheckErrors::TypeValueError(TypeSignature::IntType, Value::UInt(2)).into(),
+        CheckErrors::IncorrectArgumentCount(1,0).into(),
+        CheckErrors::UnionTypeValueError(vec![TypeSignature::IntType, TypeSignature::UIntType], Value::Bool(true)).into(),
+        CheckErrors::IncorrectArgumentCount(1,2).into(),
+        CheckErrors::TypeValueError(TypeSignature::UIntType, Value::Int(2)).into(),
+        CheckErrors::IncorrectArgumentCount(2,3).into(),
+        RuntimeErrorType::Arithmetic("sqrti must be passed a non-negative integer".to_string()).into(),
+        RuntimeErrorType::Arithmetic("log2 must be passed a positive integer".to_string()).into(),
+        RuntimeErrorType::Arithmetic("log2 must be passed a positive integer".to_string()).into(),
+    ];
+
+    for (program, expectation) in tests.iter().zip(expectations.iter()) {
+        assert_eq!(*expectation, vm_execute(program).unwrap_err());
+    }
+}
+
 #[test]
 fn test_options_errors() {
     let tests = [
@@ -670,3 +791,54 @@ fn test_asserts_short_circuit() {
     tests.iter().zip(expectations.iter())
         .for_each(|(program, expectation)| assert_eq!((*expectation), vm_execute(program).unwrap_err()));
 }
+
+#[test]
+fn test_apply_unwinds_call_stack() {
+    use vm::callables::DefineType::Private;
+
+    let contract_id = QualifiedContractIdentifier::transient();
+
+    let evals = parse(&contract_id, &"(outer 0) (outer 2) (recurse 1) (deep 1)").unwrap();
+
+    // applying `deep` plus its body's applications exceeds the maximum call stack depth.
+    let deep_body = format!("{}x{}", "(+ 1 ".repeat(64), ")".repeat(64));
+    let function_bodies = parse(&contract_id, &format!("(inner x) (/ 4 x) (recurse x) {}", deep_body)).unwrap();
+
+    let mut contract_context = ContractContext::new(QualifiedContractIdentifier::transient());
+    for (name, body) in ["outer", "inner", "recurse", "deep"].iter().zip(function_bodies.iter()) {
+        let function = DefinedFunction::new(vec![("x".into(), TypeSignature::IntType)], body.clone(),
+                                            Private, &(*name).into(), &"");
+        contract_context.functions.insert((*name).into(), function);
+    }
+
+    let context = LocalContext::new();
+    let mut marf = MemoryBackingStore::new();
+    let mut global_context = GlobalContext::new(false, marf.as_clarity_db(), LimitedCostTracker::new_max_limit());
+    let mut call_stack = CallStack::new();
+    let mut env = Environment::new(&mut global_context, &contract_context, &mut call_stack, None, None);
+
+    // the runtime error carries the stack trace at the point of failure, and every
+    //   application is popped on the way out.
+    match eval(&evals[0], &mut env, &context) {
+        Err(Error::Runtime(RuntimeErrorType::DivisionByZero, Some(_stack_trace))) => {
+            #[cfg(feature = "developer-mode")]
+            {
+                let trace: Vec<_> = _stack_trace.iter().map(|id| id.to_string()).collect();
+                assert_eq!(trace.len(), 3);
+                assert!(trace[0].ends_with(":outer"));
+                assert!(trace[1].ends_with(":inner"));
+            }
+        },
+        x => panic!("Expected a division by zero with a stack trace, got {:?}", x)
+    }
+    assert_eq!(env.call_stack.depth(), 0);
+    assert_eq!(Ok(Value::Int(2)), eval(&evals[1], &mut env, &context));
+
+    assert_eq!(Err(CheckErrors::CircularReference(vec![":recurse".to_string()]).into()),
+               eval(&evals[2], &mut env, &context));
+    assert_eq!(env.call_stack.depth(), 0);
+
+    assert_eq!(Err(RuntimeErrorType::MaxStackDepthReached.into()),
+               eval(&evals[3], &mut env, &context));
+    assert_eq!(env.call_stack.depth(), 0);
+}
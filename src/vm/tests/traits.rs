@@ -30,6 +30,7 @@ fn test_all() {
         test_good_call_2_with_trait,
         test_contract_of_value,
         test_contract_of_no_impl,
+        test_implements_trait,
         test_dynamic_dispatch_by_implementing_imported_trait_mul_funcs,
         test_dynamic_dispatch_pass_literal_principal_as_trait_in_user_defined_functions,
         ];
@@ -639,3 +640,66 @@ fn test_contract_of_no_impl(owned_env: &mut OwnedEnvironment) {
             Value::okay(result_contract).unwrap());
     }
 }
+
+fn test_implements_trait(owned_env: &mut OwnedEnvironment) {
+    let contract_defining_trait =
+        "(define-trait trait-1 (
+            (get-1 (uint) (response uint uint))))";
+    let checking_contract =
+        "(use-trait trait-1 .defun.trait-1)
+        (define-read-only (check-alias (contract principal))
+            (implements-trait? contract <trait-1>))
+        (define-read-only (check-identifier (contract principal))
+            (implements-trait? contract .defun.trait-1))";
+    let impl_contract =
+        "(impl-trait .defun.trait-1)
+        (define-public (get-1 (x uint)) (ok u99))";
+    let no_impl_contract =
+        "(define-public (get-1 (x uint)) (ok u99))";
+
+    let p1 = execute("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR");
+
+    {
+        let mut env = owned_env.get_exec_environment(None);
+        env.initialize_contract(QualifiedContractIdentifier::local("defun").unwrap(), contract_defining_trait).unwrap();
+        env.initialize_contract(QualifiedContractIdentifier::local("checker").unwrap(), checking_contract).unwrap();
+    }
+
+    {
+        // declaring an implementation doesn't write anything besides the contract itself.
+        let before = owned_env.get_cost_total().write_count;
+        owned_env.get_exec_environment(None)
+            .initialize_contract(QualifiedContractIdentifier::local("implem").unwrap(), impl_contract).unwrap();
+        let impl_writes = owned_env.get_cost_total().write_count - before;
+
+        let before = owned_env.get_cost_total().write_count;
+        owned_env.get_exec_environment(None)
+            .initialize_contract(QualifiedContractIdentifier::local("no-implem").unwrap(), no_impl_contract).unwrap();
+        let no_impl_writes = owned_env.get_cost_total().write_count - before;
+
+        assert_eq!(impl_writes, no_impl_writes);
+    }
+
+    {
+        let implem = Value::from(PrincipalData::Contract(QualifiedContractIdentifier::local("implem").unwrap()));
+        let no_implem = Value::from(PrincipalData::Contract(QualifiedContractIdentifier::local("no-implem").unwrap()));
+        let not_deployed = Value::from(PrincipalData::Contract(QualifiedContractIdentifier::local("not-deployed").unwrap()));
+        let checker = QualifiedContractIdentifier::local("checker").unwrap();
+        let mut env = owned_env.get_exec_environment(Some(p1.clone()));
+
+        for function in ["check-alias", "check-identifier"].iter() {
+            assert_eq!(
+                env.execute_contract(&checker, function, &symbols_from_values(vec![implem.clone()]), false).unwrap(),
+                Value::Bool(true));
+            assert_eq!(
+                env.execute_contract(&checker, function, &symbols_from_values(vec![no_implem.clone()]), false).unwrap(),
+                Value::Bool(false));
+            assert_eq!(
+                env.execute_contract(&checker, function, &symbols_from_values(vec![p1.clone()]), false).unwrap(),
+                Value::Bool(false));
+            assert_eq!(
+                env.execute_contract(&checker, function, &symbols_from_values(vec![not_deployed.clone()]), false).unwrap(),
+                Value::Bool(false));
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::process::{self, Command};
+
+use vm::analysis::type_check;
+use vm::ast;
+use vm::ClarityVersion;
+use vm::contexts::{OwnedEnvironment, AssetMapEntry};
+use vm::costs::ExecutionCost;
+use vm::functions::NativeFunctions;
+use vm::types::{Value, PrincipalData, QualifiedContractIdentifier, AssetIdentifier};
+use vm::database::{MarfedKV, MemoryBackingStore, NULL_HEADER_DB};
+use vm::wasm::{WasmContract, COMPILE_CONTRACTS_ENV};
+use vm::tests::{execute, symbols_from_values};
+use vm::tests::costs::get_simple_test;
+use vm::tests::contracts::FACTORIAL_CONTRACT;
+use chainstate::stacks::events::StacksTransactionEvent;
+use chainstate::stacks::index::storage::{TrieFileStorage};
+use chainstate::stacks::StacksBlockId;
+
+type TransactionResult = Result<(Value, HashMap<PrincipalData, HashMap<AssetIdentifier, AssetMapEntry>>, Vec<StacksTransactionEvent>), String>;
+
+fn p1() -> Value {
+    execute("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR")
+}
+
+fn p2() -> Value {
+    execute("'SM2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQVX8X0G")
+}
+
+fn contract_id(name: &str) -> QualifiedContractIdentifier {
+    match p1() {
+        Value::Principal(PrincipalData::Standard(issuer)) => QualifiedContractIdentifier::new(issuer, name.into()),
+        _ => panic!()
+    }
+}
+
+fn compile_contracts(contracts: &[(&str, &str)]) -> Vec<WasmContract> {
+    let mut analysis_store = MemoryBackingStore::new();
+    let mut analysis_db = analysis_store.as_analysis_db();
+
+    contracts.iter()
+        .map(|(name, contract)| {
+            let contract_identifier = contract_id(name);
+            let mut contract_ast = ast::build_ast(&contract_identifier, contract, &mut (), ClarityVersion::Clarity1).unwrap();
+            let contract_analysis = analysis_db.execute(|db| {
+                db.test_insert_contract_hash(&contract_identifier);
+                type_check(&contract_identifier, &mut contract_ast.expressions, db, true)
+            }).unwrap();
+            WasmContract::compile(&contract_ast, &contract_analysis).unwrap()
+        })
+        .collect()
+}
+
+/// Deploys `contracts` and executes `transactions` against them, with the compiled contracts
+///   registered if `compiled` is set. Returns the result of each transaction, and the total cost.
+fn run_transactions(contracts: &[(&str, &str)], transactions: &[(Value, &str, &str, Vec<Value>)],
+                    compiled: bool) -> (Vec<TransactionResult>, ExecutionCost) {
+    let mut marf_kv = MarfedKV::temporary();
+    marf_kv.begin(&TrieFileStorage::block_sentinel(),
+                  &StacksBlockId([0 as u8; 32]));
+
+    {
+        marf_kv.as_clarity_db(&NULL_HEADER_DB).initialize();
+    }
+
+    marf_kv.test_commit();
+    marf_kv.begin(&StacksBlockId([0 as u8; 32]),
+                  &StacksBlockId([1 as u8; 32]));
+
+    let mut owned_env = OwnedEnvironment::new(marf_kv.as_clarity_db(&NULL_HEADER_DB));
+
+    for (name, contract) in contracts.iter() {
+        owned_env.initialize_contract(contract_id(name), contract).unwrap();
+    }
+
+    if compiled {
+        for contract in compile_contracts(contracts).drain(..) {
+            owned_env.register_wasm_contract(contract).unwrap();
+        }
+    }
+
+    let results = transactions.iter()
+        .map(|(sender, contract, function, args)| {
+            owned_env.execute_transaction(sender.clone(), contract_id(contract), function, &symbols_from_values(args.clone()))
+                .map(|(value, asset_map, events)| (value, asset_map.to_table(), events))
+                .map_err(|e| format!("{:?}", e))
+        })
+        .collect();
+
+    let (_db, tracker) = owned_env.destruct().unwrap();
+    (results, tracker.get_total())
+}
+
+fn assert_compiled_matches_interpreter(contracts: &[(&str, &str)], transactions: &[(Value, &str, &str, Vec<Value>)]) {
+    let interpreted = run_transactions(contracts, transactions, false);
+    let compiled = run_transactions(contracts, transactions, true);
+    assert_eq!(interpreted, compiled);
+}
+
+#[test]
+fn test_native_functions() {
+    let contract_trait = "(define-trait trait-1 (
+                            (foo-exec (int) (response int int))
+                          ))";
+    let contract_other = "(impl-trait .contract-trait.trait-1)
+                          (define-map map-foo ((a int)) ((b int)))
+                          (define-public (foo-exec (a int)) (ok 1))";
+
+    for f in NativeFunctions::ALL.iter() {
+        let contract_self = format!("(define-map map-foo ((a int)) ((b int)))
+                         (define-non-fungible-token nft-foo int)
+                         (define-fungible-token ft-foo u1000000)
+                         (define-data-var var-foo int 0)
+                         (define-constant tuple-foo (tuple (a 1)))
+                         (define-constant list-foo (list true))
+                         (define-constant list-bar (list 1))
+                         (use-trait trait-1 .contract-trait.trait-1)
+                         (define-public (execute (contract <trait-1>)) (ok {}))", get_simple_test(f));
+
+        let contracts = [("contract-trait", contract_trait),
+                         ("contract-other", contract_other),
+                         ("self", &contract_self)];
+        let target_contract = Value::from(PrincipalData::Contract(contract_id("contract-other")));
+
+        assert_compiled_matches_interpreter(&contracts, &[(p2(), "self", "execute", vec![target_contract])]);
+    }
+}
+
+#[test]
+fn test_factorial_contract() {
+    let mut transactions = vec![];
+    for _ in 0..5 {
+        transactions.push((p1(), "factorial", "compute", vec![Value::Int(1337)]));
+        transactions.push((p1(), "factorial", "compute", vec![Value::Int(8008)]));
+    }
+    transactions.push((p1(), "factorial", "compute", vec![Value::Int(1)]));
+    transactions.push((p1(), "factorial", "init-factorial", vec![Value::Int(9000), Value::Int(15)]));
+
+    assert_compiled_matches_interpreter(&[("factorial", FACTORIAL_CONTRACT)], &transactions);
+}
+
+#[test]
+fn test_tokens() {
+    let contract = "(define-fungible-token stackaroos u1000000)
+         (define-non-fungible-token names int)
+         (define-map deposits ((account principal)) ((balance uint)))
+         (define-data-var minted uint u0)
+         (define-read-only (get-deposit (account principal))
+           (default-to u0 (get balance (map-get? deposits ((account account))))))
+         (define-public (deposit (amount uint))
+           (let ((current (get-deposit tx-sender)))
+             (begin (map-set deposits ((account tx-sender)) ((balance (+ current amount))))
+                    (ok (+ current amount)))))
+         (define-public (withdraw (amount uint))
+           (let ((current (get-deposit tx-sender)))
+             (if (> amount current)
+                 (err u1)
+                 (begin (if (is-eq amount current)
+                            (map-delete deposits ((account tx-sender)))
+                            (map-set deposits ((account tx-sender)) ((balance (- current amount)))))
+                        (ok (- current amount))))))
+         (define-public (open (amount uint))
+           (if (map-insert deposits ((account tx-sender)) ((balance amount)))
+               (ok amount)
+               (err u2)))
+         (define-public (mint (amount uint) (recipient principal))
+           (begin (var-set minted (+ (var-get minted) amount))
+                  (ft-mint? stackaroos amount recipient)))
+         (define-public (transfer (amount uint) (recipient principal))
+           (ft-transfer? stackaroos amount tx-sender recipient))
+         (define-public (burn (amount uint))
+           (ft-burn? stackaroos amount tx-sender))
+         (define-public (faucet)
+           (let ((original-sender tx-sender))
+             (as-contract (transfer u1 original-sender))))
+         (define-public (register (name int))
+           (nft-mint? names name tx-sender))
+         (define-public (give (name int) (recipient principal))
+           (nft-transfer? names name tx-sender recipient))
+         (define-public (retire (name int))
+           (nft-burn? names name tx-sender))
+         (define-public (pay (amount uint) (recipient principal))
+           (stx-transfer? amount tx-sender recipient))
+         (define-read-only (summary (account principal) (name int))
+           (list (stx-get-balance account) (ft-get-balance stackaroos account) (ft-get-supply stackaroos)
+                 (get-deposit account) (var-get minted)
+                 (if (is-eq (nft-get-owner? names name) (some account)) u1 u0)))
+         (begin (ft-mint? stackaroos u10000 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)
+                (ft-mint? stackaroos u4 .tokens))";
+
+    let transactions = [
+        (p1(), "tokens", "deposit", vec![Value::UInt(10)]),
+        (p1(), "tokens", "deposit", vec![Value::UInt(5)]),
+        (p1(), "tokens", "withdraw", vec![Value::UInt(20)]),
+        (p1(), "tokens", "withdraw", vec![Value::UInt(5)]),
+        (p1(), "tokens", "withdraw", vec![Value::UInt(10)]),
+        (p2(), "tokens", "open", vec![Value::UInt(3)]),
+        (p2(), "tokens", "open", vec![Value::UInt(3)]),
+        (p1(), "tokens", "mint", vec![Value::UInt(100), p2()]),
+        (p1(), "tokens", "mint", vec![Value::UInt(0), p2()]),
+        (p2(), "tokens", "transfer", vec![Value::UInt(210), p1()]),
+        (p1(), "tokens", "transfer", vec![Value::UInt(9000), p2()]),
+        (p2(), "tokens", "burn", vec![Value::UInt(50)]),
+        (p2(), "tokens", "faucet", vec![]),
+        (p2(), "tokens", "register", vec![Value::Int(1)]),
+        (p1(), "tokens", "register", vec![Value::Int(1)]),
+        (p2(), "tokens", "give", vec![Value::Int(1), p1()]),
+        (p2(), "tokens", "give", vec![Value::Int(1), p1()]),
+        (p1(), "tokens", "retire", vec![Value::Int(1)]),
+        (p1(), "tokens", "pay", vec![Value::UInt(10), p2()]),
+        (p1(), "tokens", "summary", vec![p1(), Value::Int(1)]),
+        (p2(), "tokens", "summary", vec![p2(), Value::Int(1)]),
+    ];
+
+    assert_compiled_matches_interpreter(&[("tokens", contract)], &transactions);
+}
+
+#[test]
+fn test_control_flow_and_errors() {
+    let contract = "(define-data-var counter int 0)
+         (define-private (bump (by int))
+           (begin (var-set counter (+ (var-get counter) by))
+                  (var-get counter)))
+         (define-public (nested (x int))
+           (let ((a (let ((b (+ x 1))) (* b 2))))
+             (let ((c (bump a)))
+               (if (and (> c 0) (or (is-eq a 4) (< a 0)))
+                   (ok (list a c (bump 1)))
+                   (err (+ a c))))))
+         (define-public (underflow (x uint))
+           (let ((y (- x u1)))
+             (ok y)))
+         (define-public (early-return (x int))
+           (begin (asserts! (> x 0) (err x))
+                  (ok (unwrap! (if (> x 10) none (some x)) (err 0)))))";
+
+    let transactions = [
+        (p1(), "control", "nested", vec![Value::Int(1)]),
+        (p1(), "control", "nested", vec![Value::Int(-5)]),
+        (p1(), "control", "underflow", vec![Value::UInt(0)]),
+        (p1(), "control", "underflow", vec![Value::UInt(5)]),
+        (p1(), "control", "early-return", vec![Value::Int(-1)]),
+        (p1(), "control", "early-return", vec![Value::Int(5)]),
+        (p1(), "control", "early-return", vec![Value::Int(50)]),
+    ];
+
+    assert_compiled_matches_interpreter(&[("control", contract)], &transactions);
+}
+
+#[test]
+fn test_asserts_and_lists() {
+    let contract = "(define-public (check (x int))
+           (begin (asserts! (> x 0) (err (list x (- x))))
+                  (asserts! (< x 100) (err (list)))
+                  (ok (list x (* x 2) (to-int (len (list 1 2 3)))))))
+         (define-read-only (nested (x int))
+           (list (list x) (list) (list (+ x 1) (+ x 2))))";
+
+    let transactions = [
+        (p1(), "lists", "check", vec![Value::Int(-3)]),
+        (p1(), "lists", "check", vec![Value::Int(300)]),
+        (p1(), "lists", "check", vec![Value::Int(3)]),
+        (p1(), "lists", "nested", vec![Value::Int(7)]),
+    ];
+
+    assert_compiled_matches_interpreter(&[("lists", contract)], &transactions);
+}
+
+// the number of contracts, including the transient contracts of `execute`, which the corpus
+//   deployed when this test was written. Every one of them must compile.
+const COMPILED_CORPUS_CONTRACTS: usize = 1176;
+
+/// Re-runs the rest of the `vm::tests` corpus with every contract it deploys compiled, so that
+///   each of its assertions, including the ones on costs, also holds for compiled contracts.
+#[test]
+fn test_compiled_corpus() {
+    if env::var(COMPILE_CONTRACTS_ENV).is_ok() {
+        return;
+    }
+
+    let compiled_log = env::temp_dir().join(format!("clarity-wasm-compiled-{}", process::id()));
+    let _ = fs::remove_file(&compiled_log);
+
+    let output = Command::new(env::current_exe().unwrap())
+        .args(&["vm::tests::", "--skip", "vm::tests::wasm::"])
+        .env(COMPILE_CONTRACTS_ENV, &compiled_log)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "compiled corpus failed:\n{}\n{}",
+            String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+
+    let compiled = fs::read_to_string(&compiled_log).unwrap_or_default();
+    let _ = fs::remove_file(&compiled_log);
+    let compiled_count = compiled.lines().count();
+    assert!(compiled_count >= COMPILED_CORPUS_CONTRACTS,
+            "only {} contracts of the corpus were compiled", compiled_count);
+}
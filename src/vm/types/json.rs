@@ -0,0 +1,275 @@
+use vm::types::{Value, PrincipalData, TupleData, TypeSignature, StringData, StringSubtype};
+use vm::types::serialization::SerializationError;
+
+use serde_json::{Value as JSONValue, Map as JSONMap};
+use util::hash::{hex_bytes, to_hex};
+
+/// The canonical JSON representation of Clarity values:
+///
+///   int, uint          => decimal strings ("-1", "1"), as they may exceed JSON's number precision
+///   bool               => true, false
+///   buff               => "0x"-prefixed hex strings
+///   string-ascii/utf8  => strings
+///   principal          => address strings ("SP...", or "SP....contract-name")
+///   list               => arrays
+///   tuple              => objects, keyed by field name
+///   optional           => null for `none`, {"some": value} for `(some value)`
+///   response           => {"ok": value} or {"err": value}
+///
+/// Several representations are shared by different types (e.g., ints and strings), so decoding
+///   is directed by the expected type signature.
+impl Value {
+    pub fn to_json(&self) -> JSONValue {
+        match self {
+            Value::Int(value) => JSONValue::String(value.to_string()),
+            Value::UInt(value) => JSONValue::String(value.to_string()),
+            Value::Bool(value) => JSONValue::Bool(*value),
+            Value::Buffer(value) => JSONValue::String(format!("0x{}", to_hex(&value.data))),
+            Value::String(StringData::ASCII(value)) => {
+                JSONValue::String(value.data.iter().map(|b| *b as char).collect())
+            },
+            Value::String(StringData::UTF8(value)) => {
+                // each entry is the encoding of a single character, and is valid utf8.
+                JSONValue::String(String::from_utf8_lossy(&value.to_bytes()).into_owned())
+            },
+            Value::Principal(value) => JSONValue::String(value.to_string()),
+            Value::List(value) => JSONValue::Array(value.data.iter().map(|x| x.to_json()).collect()),
+            Value::Tuple(value) => {
+                let mut fields = JSONMap::new();
+                for (name, field_value) in value.data_map.iter() {
+                    fields.insert(name.to_string(), field_value.to_json());
+                }
+                JSONValue::Object(fields)
+            },
+            Value::Optional(value) => match value.data {
+                Some(ref inner) => wrap_json("some", inner.to_json()),
+                None => JSONValue::Null
+            },
+            Value::Response(value) => {
+                let variant = if value.committed { "ok" } else { "err" };
+                wrap_json(variant, value.data.to_json())
+            }
+        }
+    }
+
+    pub fn try_deserialize_json(json: &str, expected: &TypeSignature) -> Result<Value, SerializationError> {
+        let json: JSONValue = serde_json::from_str(json)
+            .map_err(|_| "Bad JSON string")?;
+        Value::try_from_json(&json, expected)
+    }
+
+    pub fn try_from_json(json: &JSONValue, expected: &TypeSignature) -> Result<Value, SerializationError> {
+        // values are checked against the expected type by their constructors: any error
+        //   is a mismatch between the input and the expected type.
+        let mismatch = || SerializationError::DeserializeExpected(expected.clone());
+
+        match expected {
+            TypeSignature::IntType => {
+                let value = match json {
+                    JSONValue::String(s) => s.parse::<i128>().ok(),
+                    JSONValue::Number(n) => n.as_i64().map(i128::from),
+                    _ => None
+                }.ok_or_else(mismatch)?;
+                Ok(Value::Int(value))
+            },
+            TypeSignature::UIntType => {
+                let value = match json {
+                    JSONValue::String(s) => s.parse::<u128>().ok(),
+                    JSONValue::Number(n) => n.as_u64().map(u128::from),
+                    _ => None
+                }.ok_or_else(mismatch)?;
+                Ok(Value::UInt(value))
+            },
+            TypeSignature::BoolType => {
+                let value = json.as_bool()
+                    .ok_or_else(mismatch)?;
+                Ok(Value::Bool(value))
+            },
+            TypeSignature::BufferType(max_len) => {
+                let hex = json.as_str()
+                    .ok_or_else(mismatch)?;
+                let hex = if hex.starts_with("0x") { &hex[2..] } else { hex };
+                let data = hex_bytes(hex)
+                    .map_err(|_| "Bad hex string")?;
+                if data.len() > u32::from(max_len) as usize {
+                    return Err(mismatch())
+                }
+                Value::buff_from(data)
+                    .map_err(|_| mismatch())
+            },
+            TypeSignature::StringType(StringSubtype::ASCII(max_len)) => {
+                let string = json.as_str()
+                    .ok_or_else(mismatch)?;
+                if string.len() > u32::from(max_len) as usize {
+                    return Err(mismatch())
+                }
+                Value::string_ascii_from_bytes(string.as_bytes().to_vec())
+                    .map_err(|_| mismatch())
+            },
+            TypeSignature::StringType(StringSubtype::UTF8(max_len)) => {
+                let string = json.as_str()
+                    .ok_or_else(mismatch)?;
+                if string.chars().count() > u32::from(max_len) as usize {
+                    return Err(mismatch())
+                }
+                Value::string_utf8_from_string(string)
+                    .map_err(|_| mismatch())
+            },
+            TypeSignature::PrincipalType => {
+                let literal = json.as_str()
+                    .ok_or_else(mismatch)?;
+                let principal = PrincipalData::parse(literal)
+                    .map_err(|_| mismatch())?;
+                Ok(Value::Principal(principal))
+            },
+            TypeSignature::ListType(list_type) => {
+                let items = json.as_array()
+                    .ok_or_else(mismatch)?;
+                if items.len() > list_type.get_max_len() as usize {
+                    return Err(mismatch())
+                }
+                let item_type = list_type.get_list_item_type();
+                let mut values = Vec::with_capacity(items.len());
+                for item in items.iter() {
+                    values.push(Value::try_from_json(item, item_type)?);
+                }
+                Value::list_with_type(values, list_type.clone())
+                    .map_err(|_| mismatch())
+            },
+            TypeSignature::TupleType(tuple_type) => {
+                let fields = json.as_object()
+                    .ok_or_else(mismatch)?;
+                let type_map = tuple_type.get_type_map();
+                if fields.len() != type_map.len() {
+                    return Err(mismatch())
+                }
+                let mut data = Vec::with_capacity(type_map.len());
+                for (name, field_type) in type_map.iter() {
+                    let field = fields.get(name.as_str())
+                        .ok_or_else(mismatch)?;
+                    data.push((name.clone(), Value::try_from_json(field, field_type)?));
+                }
+                let tuple = TupleData::from_data_typed(data, tuple_type)
+                    .map_err(|_| mismatch())?;
+                Ok(Value::Tuple(tuple))
+            },
+            TypeSignature::OptionalType(inner_type) => {
+                if json.is_null() {
+                    return Ok(Value::none())
+                }
+                let inner = unwrap_json(json, "some")
+                    .ok_or_else(mismatch)?;
+                Value::some(Value::try_from_json(inner, inner_type)?)
+                    .map_err(|_| mismatch())
+            },
+            TypeSignature::ResponseType(response_type) => {
+                let (ref ok_type, ref err_type) = **response_type;
+                if let Some(inner) = unwrap_json(json, "ok") {
+                    Value::okay(Value::try_from_json(inner, ok_type)?)
+                        .map_err(|_| mismatch())
+                } else if let Some(inner) = unwrap_json(json, "err") {
+                    Value::error(Value::try_from_json(inner, err_type)?)
+                        .map_err(|_| mismatch())
+                } else {
+                    Err(mismatch())
+                }
+            },
+            // no value inhabits these types, e.g., the `ok` type of a response which is
+            //   always an `err`.
+            TypeSignature::NoType | TypeSignature::TraitReferenceType(_) => Err(mismatch())
+        }
+    }
+}
+
+fn wrap_json(variant: &str, inner: JSONValue) -> JSONValue {
+    let mut wrapper = JSONMap::new();
+    wrapper.insert(variant.to_string(), inner);
+    JSONValue::Object(wrapper)
+}
+
+fn unwrap_json<'a>(json: &'a JSONValue, variant: &str) -> Option<&'a JSONValue> {
+    match json.as_object() {
+        Some(wrapper) if wrapper.len() == 1 => wrapper.get(variant),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+    use vm::types::serialization::SerializationError;
+
+    fn test_roundtrip(v: Value, expected_json: &str) {
+        let json = v.to_json();
+        assert_eq!(json.to_string(), expected_json);
+        assert_eq!(Value::try_deserialize_json(expected_json, &TypeSignature::type_of(&v)).unwrap(), v);
+    }
+
+    fn test_bad_expectation(json: &str, expected: &str) {
+        assert!(
+            match Value::try_deserialize_json(json, &TypeSignature::from(expected)).unwrap_err() {
+                SerializationError::DeserializeExpected(_) => true,
+                _ => false
+            })
+    }
+
+    #[test]
+    fn test_atoms() {
+        test_roundtrip(Value::Int(i128::min_value()), "\"-170141183460469231731687303715884105728\"");
+        test_roundtrip(Value::UInt(u128::max_value()), "\"340282366920938463463374607431768211455\"");
+        test_roundtrip(Value::Bool(true), "true");
+        test_roundtrip(Value::buff_from(vec![0xde, 0xad]).unwrap(), "\"0xdead\"");
+        test_roundtrip(Value::string_ascii_from_bytes("hello \"world\"".as_bytes().to_vec()).unwrap(),
+                       "\"hello \\\"world\\\"\"");
+        test_roundtrip(Value::string_utf8_from_string("h\u{e9}llo \u{1F600}").unwrap(),
+                       "\"h\u{e9}llo \u{1F600}\"");
+        test_roundtrip(Value::Principal(PrincipalData::parse("SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR").unwrap()),
+                       "\"SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR\"");
+        test_roundtrip(Value::Principal(PrincipalData::parse("SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR.tokens").unwrap()),
+                       "\"SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR.tokens\"");
+
+        // numbers are accepted in place of decimal strings.
+        assert_eq!(Value::try_deserialize_json("-12", &TypeSignature::IntType).unwrap(), Value::Int(-12));
+        assert_eq!(Value::try_deserialize_json("12", &TypeSignature::UIntType).unwrap(), Value::UInt(12));
+
+        test_bad_expectation("-1", "uint");
+        test_bad_expectation("\"1.5\"", "int");
+        test_bad_expectation("\"0xdeadbeef\"", "(buff 2)");
+        test_bad_expectation("\"hello\"", "(string-ascii 4)");
+        test_bad_expectation("\"h\u{e9}llo\"", "(string-ascii 10)");
+        test_bad_expectation("\"h\u{e9}llo\"", "(string-utf8 4)");
+        test_bad_expectation("\"not-a-principal\"", "principal");
+        test_bad_expectation("true", "int");
+    }
+
+    #[test]
+    fn test_compound_values() {
+        let tuple = Value::Tuple(TupleData::from_data(vec![
+            ("a".into(), Value::Int(1)),
+            ("b".into(), Value::list_from(vec![Value::Bool(true), Value::Bool(false)]).unwrap())]).unwrap());
+        test_roundtrip(tuple, "{\"a\":\"1\",\"b\":[true,false]}");
+
+        test_roundtrip(Value::none(), "null");
+        test_roundtrip(Value::some(Value::none()).unwrap(), "{\"some\":null}");
+        test_roundtrip(Value::some(Value::UInt(1)).unwrap(), "{\"some\":\"1\"}");
+
+        let expected = TypeSignature::from("(response (optional int) uint)");
+        assert_eq!(Value::okay(Value::some(Value::Int(1)).unwrap()).unwrap().to_json().to_string(),
+                   "{\"ok\":{\"some\":\"1\"}}");
+        assert_eq!(Value::try_deserialize_json("{\"ok\":{\"some\":\"1\"}}", &expected).unwrap(),
+                   Value::okay(Value::some(Value::Int(1)).unwrap()).unwrap());
+        assert_eq!(Value::try_deserialize_json("{\"err\":\"2\"}", &expected).unwrap(),
+                   Value::error(Value::UInt(2)).unwrap());
+
+        // the decoded values take the expected type, rather than the type of the literal.
+        let list = Value::try_deserialize_json("[[\"1\"], []]", &TypeSignature::from("(list 3 (list 2 int))")).unwrap();
+        assert_eq!(TypeSignature::type_of(&list), TypeSignature::from("(list 3 (list 2 int))"));
+
+        test_bad_expectation("[\"1\", \"2\", \"3\"]", "(list 2 int)");
+        test_bad_expectation("{\"a\":\"1\"}", "(tuple (a int) (b int))");
+        test_bad_expectation("{\"a\":\"1\",\"b\":\"2\",\"c\":\"3\"}", "(tuple (a int) (b int))");
+        test_bad_expectation("\"1\"", "(optional int)");
+        test_bad_expectation("{\"ok\":\"1\",\"err\":\"1\"}", "(response int int)");
+        test_bad_expectation("{\"some\":\"1\"}", "(response int int)");
+    }
+}
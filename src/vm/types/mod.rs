@@ -1,5 +1,6 @@
 pub mod serialization;
 pub mod signatures;
+pub mod json;
 
 use std::{fmt, cmp};
 use std::convert::{TryInto, TryFrom};
@@ -13,7 +14,7 @@ use util::hash;
 pub use vm::types::signatures::{
     TupleTypeSignature, AssetIdentifier, FixedFunction, FunctionSignature,
     TypeSignature, FunctionType, ListTypeData, FunctionArg, parse_name_type_pairs,
-    BUFF_64, BUFF_32, BUFF_20, BufferLength
+    BUFF_65, BUFF_64, BUFF_33, BUFF_32, BUFF_20, BUFF_16, BufferLength, StringUTF8Length, StringSubtype
 };
 
 pub const MAX_VALUE_SIZE: u32 = 1024 * 1024; // 1MB
@@ -36,6 +37,24 @@ pub struct BuffData {
     pub data: Vec<u8>,
 }
 
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ASCIIData {
+    pub data: Vec<u8>,
+}
+
+// utf8 strings are stored as a vector of characters, where each
+//   character is the (1 to 4 byte) utf8 encoding of a single code point.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UTF8Data {
+    pub data: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StringData {
+    ASCII(ASCIIData),
+    UTF8(UTF8Data),
+}
+
 #[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub struct ListData {
     pub data: Vec<Value>,
@@ -175,6 +194,7 @@ pub enum Value {
     UInt(u128),
     Bool(bool),
     Buffer(BuffData),
+    String(StringData),
     List(ListData),
     Principal(PrincipalData),
     Tuple(TupleData),
@@ -343,6 +363,42 @@ impl Value {
     pub fn buff_from_byte(byte: u8) -> Value {
         Value::Buffer(BuffData { data: vec![byte] })
     }
+
+    /// Construct a string-ascii value. Only printable ascii characters and
+    ///   ascii whitespace are admitted.
+    pub fn string_ascii_from_bytes(bytes: Vec<u8>) -> Result<Value> {
+        BufferLength::try_from(bytes.len())?;
+        for b in bytes.iter() {
+            if !ASCIIData::is_admitted_byte(*b) {
+                return Err(CheckErrors::InvalidCharactersDetected.into())
+            }
+        }
+        Ok(Value::String(StringData::ASCII(ASCIIData { data: bytes })))
+    }
+
+    /// Construct a string-utf8 value from utf8 encoded bytes.
+    pub fn string_utf8_from_bytes(bytes: Vec<u8>) -> Result<Value> {
+        let validated = String::from_utf8(bytes)
+            .map_err(|_| CheckErrors::InvalidUTF8Encoding)?;
+        Value::string_utf8_from_string(&validated)
+    }
+
+    pub fn string_utf8_from_string(string: &str) -> Result<Value> {
+        let data: Vec<Vec<u8>> = string.chars()
+            .map(|c| {
+                let mut encoded = [0u8; 4];
+                c.encode_utf8(&mut encoded).as_bytes().to_vec()
+            })
+            .collect();
+        Value::string_utf8_from_chars(data)
+    }
+
+    /// Invariant: each entry of `chars` is the utf8 encoding of a single character,
+    ///   e.g., obtained from another UTF8Data.
+    pub fn string_utf8_from_chars(chars: Vec<Vec<u8>>) -> Result<Value> {
+        StringUTF8Length::try_from(chars.len())?;
+        Ok(Value::String(StringData::UTF8(UTF8Data { data: chars })))
+    }
 }
 
 impl BuffData {
@@ -351,6 +407,48 @@ impl BuffData {
     }
 }
 
+impl ASCIIData {
+    pub fn len(&self) -> BufferLength {
+        self.data.len().try_into().unwrap()
+    }
+
+    /// Whether `b` may appear in a string-ascii value: printable characters, plus the
+    ///   whitespace that string literals can escape (`\n`, `\t` and `\r`).
+    pub fn is_admitted_byte(b: u8) -> bool {
+        match b {
+            b' ' ..= b'~' | b'\n' | b'\t' | b'\r' => true,
+            _ => false
+        }
+    }
+}
+
+impl UTF8Data {
+    pub fn len(&self) -> StringUTF8Length {
+        self.data.len().try_into().unwrap()
+    }
+
+    /// Returns the utf8 encoded bytes of the whole string.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data.iter().flatten().cloned().collect()
+    }
+}
+
+impl StringData {
+    pub fn len(&self) -> u32 {
+        match self {
+            StringData::ASCII(data) => data.len().into(),
+            StringData::UTF8(data) => data.len().into(),
+        }
+    }
+
+    pub fn type_signature(&self) -> StringSubtype {
+        match self {
+            StringData::ASCII(data) => StringSubtype::ASCII(data.len()),
+            StringData::UTF8(data) => StringSubtype::UTF8(data.len()),
+        }
+    }
+}
+
 impl ListData {
     pub fn len(&self) -> u32 {
         self.data.len().try_into().unwrap()
@@ -387,6 +485,72 @@ impl fmt::Debug for BuffData {
     }
 }
 
+impl fmt::Display for ASCIIData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"")?;
+        for b in self.data.iter() {
+            match *b {
+                b'"' => write!(f, "\\\"")?,
+                b'\\' => write!(f, "\\\\")?,
+                b'\n' => write!(f, "\\n")?,
+                b'\t' => write!(f, "\\t")?,
+                b'\r' => write!(f, "\\r")?,
+                other => write!(f, "{}", other as char)?,
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+impl fmt::Debug for ASCIIData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for UTF8Data {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "u\"")?;
+        for c in self.data.iter() {
+            if c.len() > 1 {
+                // non-ascii characters are displayed as unicode escapes,
+                //   so that the output can be parsed back as a literal.
+                let as_str = std::str::from_utf8(c)
+                    .map_err(|_| fmt::Error)?;
+                for scalar in as_str.chars() {
+                    write!(f, "\\u{{{:x}}}", scalar as u32)?;
+                }
+            } else {
+                match c[0] {
+                    b'"' => write!(f, "\\\"")?,
+                    b'\\' => write!(f, "\\\\")?,
+                    b'\n' => write!(f, "\\n")?,
+                    b'\t' => write!(f, "\\t")?,
+                    b'\r' => write!(f, "\\r")?,
+                    other if other.is_ascii_control() => write!(f, "\\u{{{:x}}}", other)?,
+                    other => write!(f, "{}", other as char)?,
+                }
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+impl fmt::Debug for UTF8Data {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Display for StringData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StringData::ASCII(data) => write!(f, "{}", data),
+            StringData::UTF8(data) => write!(f, "{}", data),
+        }
+    }
+}
+
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -394,6 +558,7 @@ impl fmt::Display for Value {
             Value::UInt(int) => write!(f, "u{}", int),
             Value::Bool(boolean) => write!(f, "{}", boolean),
             Value::Buffer(vec_bytes) => write!(f, "0x{}", &vec_bytes),
+            Value::String(string_data) => write!(f, "{}", string_data),
             Value::Tuple(data) => write!(f, "{}", data),
             Value::Principal(principal_data) => write!(f, "{}", principal_data),
             Value::Optional(opt_data) => write!(f, "{}", opt_data),
@@ -695,5 +860,30 @@ mod test {
         assert_eq!(&format!("{}", Value::from(TupleData::from_data(
             vec![("a".into(), Value::Int(2))]).unwrap())),
                    "(tuple (a 2))");
+        assert_eq!(&format!("{}", Value::string_ascii_from_bytes(
+            "say \"hi\"\n".as_bytes().to_vec()).unwrap()),
+                   "\"say \\\"hi\\\"\\n\"");
+        assert_eq!(&format!("{}", Value::string_utf8_from_string("hi \u{1F600}").unwrap()),
+                   "u\"hi \\u{1f600}\"");
+        assert_eq!(&format!("{}", Value::string_utf8_from_string("a\u{c}b\u{7f}").unwrap()),
+                   "u\"a\\u{c}b\\u{7f}\"");
+    }
+
+    #[test]
+    fn test_string_constructors() {
+        assert_eq!(Value::string_ascii_from_bytes(vec![0x80]),
+                   Err(CheckErrors::InvalidCharactersDetected.into()));
+        // form feed and vertical tab have no escape in a string-ascii literal.
+        assert_eq!(Value::string_ascii_from_bytes(vec![0x0c]),
+                   Err(CheckErrors::InvalidCharactersDetected.into()));
+        assert_eq!(Value::string_ascii_from_bytes(vec![0x0b]),
+                   Err(CheckErrors::InvalidCharactersDetected.into()));
+        assert_eq!(Value::string_utf8_from_bytes(vec![0xc3, 0x28]),
+                   Err(CheckErrors::InvalidUTF8Encoding.into()));
+
+        let value = Value::string_utf8_from_bytes("h\u{e9}llo".as_bytes().to_vec()).unwrap();
+        assert_eq!(TypeSignature::type_of(&value),
+                   TypeSignature::StringType(StringSubtype::UTF8(5u32.try_into().unwrap())));
+        assert_eq!(value.size(), 24);
     }
 }
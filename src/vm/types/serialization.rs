@@ -1,6 +1,7 @@
 use vm::errors::{RuntimeErrorType, InterpreterResult, InterpreterError, 
                  IncomparableError, Error as ClarityError, CheckErrors};
 use vm::types::{Value, StandardPrincipalData, OptionalData, PrincipalData, BufferLength, MAX_VALUE_SIZE,
+                StringData, StringSubtype,
                 BOUND_VALUE_SERIALIZATION_BYTES,
                 TypeSignature, TupleData, QualifiedContractIdentifier, ResponseData};
 use vm::database::{ClaritySerializable, ClarityDeserializable};
@@ -88,7 +89,9 @@ define_u8_enum!(TypePrefix {
     OptionalNone = 9,
     OptionalSome = 10,
     List = 11,
-    Tuple = 12
+    Tuple = 12,
+    StringASCII = 13,
+    StringUTF8 = 14
 });
 
 impl From<&PrincipalData> for TypePrefix {
@@ -109,6 +112,8 @@ impl From<&Value> for TypePrefix {
             Int(_) => TypePrefix::Int,
             UInt(_) => TypePrefix::UInt,
             Buffer(_) => TypePrefix::Buffer,
+            String(StringData::ASCII(_)) => TypePrefix::StringASCII,
+            String(StringData::UTF8(_)) => TypePrefix::StringUTF8,
             Bool(value) => {
                 if *value {
                     TypePrefix::BoolTrue
@@ -303,6 +308,64 @@ impl Value {
                 // can safely unwrap, because the buffer length was _already_ checked.
                 Ok(Value::buff_from(data).unwrap())
             },
+            TypePrefix::StringASCII => {
+                let mut buffer_len = [0; 4];
+                r.read_exact(&mut buffer_len)?;
+                let buffer_len = BufferLength::try_from(
+                    u32::from_be_bytes(buffer_len))?;
+
+                if let Some(x) = expected_type {
+                    let passed_test = match x {
+                        TypeSignature::StringType(StringSubtype::ASCII(expected_len)) => {
+                            u32::from(&buffer_len) <= u32::from(expected_len)
+                        },
+                        _ => false
+                    };
+                    if !passed_test {
+                        return Err(SerializationError::DeserializeExpected(x.clone()))
+                    }
+                }
+
+                let mut data = vec![0; u32::from(buffer_len) as usize];
+
+                r.read_exact(&mut data[..])?;
+
+                // the length was already checked, but the characters still need to be validated.
+                Value::string_ascii_from_bytes(data)
+                    .map_err(|_| "Illegal string-ascii value".into())
+            },
+            TypePrefix::StringUTF8 => {
+                // utf8 strings are serialized with their length in _bytes_,
+                //   the character length is checked after decoding.
+                let mut total_len = [0; 4];
+                r.read_exact(&mut total_len)?;
+                let total_len = u32::from_be_bytes(total_len);
+                if total_len > MAX_VALUE_SIZE {
+                    return Err("Illegal string-utf8 type".into());
+                }
+
+                let mut data = vec![0; total_len as usize];
+
+                r.read_exact(&mut data[..])?;
+
+                let value = Value::string_utf8_from_bytes(data)
+                    .map_err(|_| "Illegal string-utf8 value")?;
+
+                if let Some(x) = expected_type {
+                    let passed_test = match (x, &value) {
+                        (TypeSignature::StringType(StringSubtype::UTF8(expected_len)),
+                         String(StringData::UTF8(utf8))) => {
+                            u32::from(utf8.len()) <= u32::from(expected_len)
+                        },
+                        _ => false
+                    };
+                    if !passed_test {
+                        return Err(SerializationError::DeserializeExpected(x.clone()))
+                    }
+                }
+
+                Ok(value)
+            },
             TypePrefix::BoolTrue => {
                 check_match!(expected_type, TypeSignature::BoolType)?;
                 Ok(Bool(true))
@@ -463,7 +526,18 @@ impl Value {
             Buffer(value) => {
                 w.write_all(&(u32::from(value.len()).to_be_bytes()))?;
                 w.write_all(&value.data)?
-            }
+            },
+            String(StringData::ASCII(value)) => {
+                w.write_all(&(u32::from(value.len()).to_be_bytes()))?;
+                w.write_all(&value.data)?
+            },
+            String(StringData::UTF8(value)) => {
+                let bytes = value.to_bytes();
+                // consensus serialization writes the length of the encoded bytes,
+                //   not the number of characters.
+                w.write_all(&(u32::try_from(bytes.len()).unwrap().to_be_bytes()))?;
+                w.write_all(&bytes)?
+            },
             Principal(Standard(data)) => {
                 data.serialize_write(w)?
             },
@@ -720,6 +794,40 @@ mod tests {
         
     }
 
+    #[test]
+    fn test_strings() {
+        test_deser_ser(Value::string_ascii_from_bytes(vec![]).unwrap());
+        test_deser_ser(Value::string_ascii_from_bytes("hello world".as_bytes().to_vec()).unwrap());
+        test_deser_ser(Value::string_utf8_from_string("hello \u{1F600} world").unwrap());
+
+        test_bad_expectation(
+            Value::string_ascii_from_bytes("hello".as_bytes().to_vec()).unwrap(),
+            TypeSignature::from("(buff 5)"));
+        test_bad_expectation(
+            Value::string_ascii_from_bytes("hello".as_bytes().to_vec()).unwrap(),
+            TypeSignature::from("(string-ascii 4)"));
+        test_bad_expectation(
+            Value::string_ascii_from_bytes("hello".as_bytes().to_vec()).unwrap(),
+            TypeSignature::from("(string-utf8 5)"));
+
+        // the expected length of a utf8 string is its number of characters, not bytes.
+        Value::try_deserialize_hex(
+            &Value::string_utf8_from_string("\u{1F600}\u{1F600}").unwrap().serialize(),
+            &TypeSignature::from("(string-utf8 2)")).unwrap();
+        test_bad_expectation(
+            Value::string_utf8_from_string("\u{1F600}\u{1F600}").unwrap(),
+            TypeSignature::from("(string-utf8 1)"));
+
+        // non-ascii bytes in an ascii string
+        assert_eq!(Value::try_deserialize_hex_untyped("0d0000000168").unwrap(),
+                   Value::string_ascii_from_bytes(vec![0x68]).unwrap());
+        assert_eq!(Value::try_deserialize_hex_untyped("0d00000001ff").unwrap_err(),
+                   "Illegal string-ascii value".into());
+        // invalid utf8 encoding
+        assert_eq!(Value::try_deserialize_hex_untyped("0e00000002c328").unwrap_err(),
+                   "Illegal string-utf8 value".into());
+    }
+
     #[test]
     fn test_tuples() {
         let t_1 = Value::from(TupleData::from_data(vec![
@@ -806,7 +914,11 @@ mod tests {
                  Value::Int(1), Value::Int(2), Value::Int(3), Value::Int(-4)]).unwrap())),
             ("0c000000020362617a0906666f6f62617203",
              Ok(Value::from(TupleData::from_data(vec![
-                 ("baz".into(), Value::none()), ("foobar".into(), Value::Bool(true))]).unwrap())))
+                 ("baz".into(), Value::none()), ("foobar".into(), Value::Bool(true))]).unwrap()))),
+            ("0d0000000568656c6c6f", Ok(Value::string_ascii_from_bytes(
+                "hello".as_bytes().to_vec()).unwrap())),
+            ("0e0000000668c3a96c6c6f", Ok(Value::string_utf8_from_string(
+                "h\u{e9}llo").unwrap())),
         ];
 
         for (test, expected) in tests.iter() {
@@ -44,6 +44,15 @@ pub struct TupleTypeSignature {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BufferLength (u32);
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StringUTF8Length (u32);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StringSubtype {
+    ASCII(BufferLength),
+    UTF8(StringUTF8Length),
+}
+
 // INVARIANTS enforced by the Type Signatures.
 //   1. A TypeSignature constructor will always fail rather than construct a
 //        type signature for a too large or invalid type. This is why any variable length
@@ -58,6 +67,7 @@ pub enum TypeSignature {
     UIntType,
     BoolType,
     BufferType(BufferLength),
+    StringType(StringSubtype),
     PrincipalType,
     ListType(ListTypeData),
     TupleType(TupleTypeSignature),
@@ -72,6 +82,7 @@ use self::TypeSignature::{
     UIntType, 
     BoolType, 
     BufferType,
+    StringType,
     PrincipalType, 
     ListType, 
     TupleType, 
@@ -80,9 +91,12 @@ use self::TypeSignature::{
     TraitReferenceType
 };
 
+pub const BUFF_65: TypeSignature = BufferType(BufferLength(65));
 pub const BUFF_64: TypeSignature = BufferType(BufferLength(64));
+pub const BUFF_33: TypeSignature = BufferType(BufferLength(33));
 pub const BUFF_32: TypeSignature = BufferType(BufferLength(32));
 pub const BUFF_20: TypeSignature = BufferType(BufferLength(20));
+pub const BUFF_16: TypeSignature = BufferType(BufferLength(16));
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ListTypeData {
@@ -108,7 +122,7 @@ pub enum FunctionType {
     Fixed(FixedFunction),
     // Functions where the single input is a union type, e.g., Buffer or Int
     UnionArgs(Vec<TypeSignature>, TypeSignature),
-    ArithmeticVariadic, ArithmeticBinary, ArithmeticComparison
+    ArithmeticVariadic, ArithmeticUnary, ArithmeticBinary, ArithmeticComparison
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -191,6 +205,77 @@ impl TryFrom<i128> for BufferLength {
     }
 }
 
+impl From<&StringUTF8Length> for u32 {
+    fn from(v: &StringUTF8Length) -> u32 {
+        v.0
+    }
+}
+
+impl From<StringUTF8Length> for u32 {
+    fn from(v: StringUTF8Length) -> u32 {
+        v.0
+    }
+}
+
+// a utf8 character may take up to 4 bytes, so the
+//   maximum number of characters is bound by MAX_VALUE_SIZE / 4
+impl TryFrom<u32> for StringUTF8Length {
+    type Error = CheckErrors;
+    fn try_from(data: u32) -> Result<StringUTF8Length> {
+        let len = data.checked_mul(4)
+            .ok_or_else(|| CheckErrors::ValueTooLarge)?;
+        if len > MAX_VALUE_SIZE {
+            Err(CheckErrors::ValueTooLarge)
+        } else {
+            Ok(StringUTF8Length(data))
+        }
+    }
+}
+
+impl TryFrom<usize> for StringUTF8Length {
+    type Error = CheckErrors;
+    fn try_from(data: usize) -> Result<StringUTF8Length> {
+        let data = u32::try_from(data)
+            .map_err(|_| CheckErrors::ValueTooLarge)?;
+        StringUTF8Length::try_from(data)
+    }
+}
+
+impl TryFrom<i128> for StringUTF8Length {
+    type Error = CheckErrors;
+    fn try_from(data: i128) -> Result<StringUTF8Length> {
+        let data = u32::try_from(data)
+            .map_err(|_| CheckErrors::ValueTooLarge)?;
+        StringUTF8Length::try_from(data)
+    }
+}
+
+impl StringSubtype {
+    /// The maximum number of characters (or bytes, for ascii) that this string type admits.
+    pub fn get_max_len(&self) -> u32 {
+        match self {
+            StringSubtype::ASCII(len) => u32::from(len),
+            StringSubtype::UTF8(len) => u32::from(len),
+        }
+    }
+
+    /// Construct a string type of the same subtype as this one, with a new max length.
+    pub fn with_max_len(&self, max_len: u32) -> Result<StringSubtype> {
+        match self {
+            StringSubtype::ASCII(_) => Ok(StringSubtype::ASCII(BufferLength::try_from(max_len)?)),
+            StringSubtype::UTF8(_) => Ok(StringSubtype::UTF8(StringUTF8Length::try_from(max_len)?)),
+        }
+    }
+
+    pub fn is_same_subtype(&self, other: &StringSubtype) -> bool {
+        match (self, other) {
+            (StringSubtype::ASCII(_), StringSubtype::ASCII(_)) |
+            (StringSubtype::UTF8(_), StringSubtype::UTF8(_)) => true,
+            _ => false
+        }
+    }
+}
+
 impl ListTypeData {
     pub fn new_list(entry_type: TypeSignature, max_len: u32) -> Result<ListTypeData> {
         let would_be_depth = 1 + entry_type.depth();
@@ -330,6 +415,20 @@ impl TypeSignature {
                     false
                 }
             },
+            StringType(StringSubtype::ASCII(ref my_len)) => {
+                if let StringType(StringSubtype::ASCII(ref other_len)) = other {
+                    my_len.0 >= other_len.0
+                } else {
+                    false
+                }
+            },
+            StringType(StringSubtype::UTF8(ref my_len)) => {
+                if let StringType(StringSubtype::UTF8(ref other_len)) = other {
+                    my_len.0 >= other_len.0
+                } else {
+                    false
+                }
+            },
             TupleType(ref tuple_sig) => {
                 if let TupleType(ref other_tuple_sig) = other {
                     tuple_sig.admits(other_tuple_sig)
@@ -491,6 +590,14 @@ impl TypeSignature {
                                 .expect("FAIL: Max Clarity Value Size is no longer realizable in Buffer Type")))
     }
 
+    pub fn min_string_ascii() -> TypeSignature {
+        StringType(StringSubtype::ASCII(1_u32.try_into().unwrap()))
+    }
+
+    pub fn min_string_utf8() -> TypeSignature {
+        StringType(StringSubtype::UTF8(1_u32.try_into().unwrap()))
+    }
+
     /// If one of the types is a NoType, return Ok(the other type), otherwise return least_supertype(a, b)
     fn factor_out_no_type(a: &TypeSignature, b: &TypeSignature) -> Result<TypeSignature> {
         if a.is_no_type() {
@@ -520,6 +627,8 @@ impl TypeSignature {
     ///        if some_a | some_b is NoType, use the other type's entry.
     ///  For buffers:
     ///      least_supertype(A, B) := (buff len: max(len A, len B))
+    ///  For strings:
+    ///      least_supertype(A, B) := if subtype A != subtype B, error, else (string len: max(len A, len B))
     ///  For ints, uints, principals, bools:
     ///      least_supertype(A, B) := if A != B, error, else A
     ///
@@ -569,6 +678,14 @@ impl TypeSignature {
                 }.clone();
                 Ok(BufferType(buff_len))
             },
+            (StringType(StringSubtype::ASCII(len_a)), StringType(StringSubtype::ASCII(len_b))) => {
+                let str_len = cmp::max(u32::from(len_a), u32::from(len_b));
+                Ok(StringType(StringSubtype::ASCII(BufferLength(str_len))))
+            },
+            (StringType(StringSubtype::UTF8(len_a)), StringType(StringSubtype::UTF8(len_b))) => {
+                let str_len = cmp::max(u32::from(len_a), u32::from(len_b));
+                Ok(StringType(StringSubtype::UTF8(StringUTF8Length(str_len))))
+            },
             (NoType, x) | (x, NoType) => {
                 Ok(x.clone())
             },
@@ -604,6 +721,7 @@ impl TypeSignature {
                     .expect("ERROR: Too large of a buffer successfully constructed.");
                 BufferType(buff_length)
             },
+            Value::String(string_data) => StringType(string_data.type_signature()),
             Value::Tuple(v) => TupleType(
                 v.type_signature.clone()),
             Value::List(list_data) => ListType(list_data.type_signature.clone()),
@@ -686,6 +804,34 @@ impl TypeSignature {
         }
     }
 
+    // Parses type signatures of the form:
+    // (string-ascii 10)
+    fn parse_string_ascii_type_repr(type_args: &[SymbolicExpression]) -> Result<TypeSignature> {
+        if type_args.len() != 1 {
+            return Err(CheckErrors::InvalidTypeDescription)
+        }
+        if let SymbolicExpressionType::LiteralValue(Value::Int(str_len)) = &type_args[0].expr {
+            BufferLength::try_from(*str_len)
+                .map(|str_len| TypeSignature::StringType(StringSubtype::ASCII(str_len)))
+        } else {
+            Err(CheckErrors::InvalidTypeDescription)
+        }
+    }
+
+    // Parses type signatures of the form:
+    // (string-utf8 10)
+    fn parse_string_utf8_type_repr(type_args: &[SymbolicExpression]) -> Result<TypeSignature> {
+        if type_args.len() != 1 {
+            return Err(CheckErrors::InvalidTypeDescription)
+        }
+        if let SymbolicExpressionType::LiteralValue(Value::Int(str_len)) = &type_args[0].expr {
+            StringUTF8Length::try_from(*str_len)
+                .map(|str_len| TypeSignature::StringType(StringSubtype::UTF8(str_len)))
+        } else {
+            Err(CheckErrors::InvalidTypeDescription)
+        }
+    }
+
     fn parse_optional_type_repr<A: CostTracker>(type_args: &[SymbolicExpression], accounting: &mut A) -> Result<TypeSignature> {
         if type_args.len() != 1 {
             return Err(CheckErrors::InvalidTypeDescription)
@@ -719,6 +865,8 @@ impl TypeSignature {
                     match compound_type.as_ref() {
                         "list" => TypeSignature::parse_list_type_repr(rest, accounting),
                         "buff" => TypeSignature::parse_buff_type_repr(rest),
+                        "string-ascii" => TypeSignature::parse_string_ascii_type_repr(rest),
+                        "string-utf8" => TypeSignature::parse_string_utf8_type_repr(rest),
                         "tuple" => TypeSignature::parse_tuple_type_repr(rest, accounting),
                         "optional" => TypeSignature::parse_optional_type_repr(rest, accounting),
                         "response" => TypeSignature::parse_response_type_repr(rest, accounting),
@@ -791,7 +939,7 @@ impl TypeSignature {
         match self {
             // NoType's may be asked for their size at runtime --
             //  legal constructions like `(ok 1)` have NoType parts (if they have unknown error variant types).
-            TraitReferenceType(_) | NoType | IntType | UIntType | BoolType | PrincipalType | BufferType(_) => 1,
+            TraitReferenceType(_) | NoType | IntType | UIntType | BoolType | PrincipalType | BufferType(_) | StringType(_) => 1,
             TupleType(tuple_sig) => {
                 1 + tuple_sig.max_depth()
             },
@@ -818,6 +966,9 @@ impl TypeSignature {
             BoolType => Some(1),
             PrincipalType => Some(148), // 20+128
             BufferType(len) => Some(4 + u32::from(len)),
+            StringType(StringSubtype::ASCII(len)) => Some(4 + u32::from(len)),
+            // each utf8 character is stored with up to 4 bytes
+            StringType(StringSubtype::UTF8(len)) => Some(4 + 4 * u32::from(len)),
             TupleType(tuple_sig) => tuple_sig.inner_size(),
             ListType(list_type) => list_type.inner_size(),
             OptionalType(t) => t.size().checked_add(WRAPPER_VALUE_SIZE),
@@ -847,7 +998,7 @@ impl TypeSignature {
             // These types all only use ~1 byte for their type enum
             NoType | IntType | UIntType | BoolType | PrincipalType => Some(1),
             // u32 length + type enum
-            BufferType(_) => Some(1 + 4),
+            BufferType(_) | StringType(_) => Some(1 + 4),
             TupleType(tuple_sig) => tuple_sig.type_size(),
             ListType(list_type) => list_type.type_size(),
             OptionalType(t) => {
@@ -1011,6 +1162,8 @@ impl fmt::Display for TypeSignature {
             UIntType => write!(f, "uint"),
             BoolType => write!(f, "bool"),
             BufferType(len) => write!(f, "(buff {})", len),
+            StringType(StringSubtype::ASCII(len)) => write!(f, "(string-ascii {})", len),
+            StringType(StringSubtype::UTF8(len)) => write!(f, "(string-utf8 {})", len),
             OptionalType(t) => write!(f, "(optional {})", t),
             ResponseType(v) => write!(f, "(response {} {})", v.0, v.1),
             TupleType(t) => write!(f, "{}", t),
@@ -1027,6 +1180,12 @@ impl fmt::Display for BufferLength {
     }
 }
 
+impl fmt::Display for StringUTF8Length {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl fmt::Display for FunctionArg {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.signature)
@@ -1038,7 +1197,7 @@ impl fmt::Display for FunctionArg {
 mod test {
     use super::*;
     use super::CheckErrors::*;
-    use vm::execute;
+    use vm::{execute, execute_with_version, ClarityVersion};
 
     fn fail_parse(val: &str) -> CheckErrors {
         use vm::ast::parse;
@@ -1053,6 +1212,17 @@ mod test {
         assert_eq!(TypeSignature::type_of(&value), type_descr);
     }
 
+    #[test]
+    fn type_of_list_of_strings() {
+        let value = execute_with_version("(list \"abc\" \"abcde\")", ClarityVersion::Clarity2).unwrap().unwrap();
+        let type_descr = "(list 2 (string-ascii 5))".into();
+        assert_eq!(TypeSignature::type_of(&value), type_descr);
+
+        let value = execute("(list u\"abc\" u\"\\u{1F600}\")").unwrap().unwrap();
+        let type_descr = "(list 2 (string-utf8 3))".into();
+        assert_eq!(TypeSignature::type_of(&value), type_descr);
+    }
+
     #[test]
     fn type_signature_way_too_big() {
         // first_tuple.type_size ~= 131
@@ -1094,6 +1264,10 @@ mod test {
             ("(list 4294967295 (buff 2))", ValueTooLarge),
             ("(list 2147483647 (buff 2))", ValueTooLarge),
             ("(tuple (l (buff 1048576)))", ValueTooLarge),
+            ("(string-ascii)", InvalidTypeDescription),
+            ("(string-ascii 1048577)", ValueTooLarge),
+            ("(string-utf8 262145)", ValueTooLarge),
+            ("(string-utf8 int)", InvalidTypeDescription),
         ];
 
         for (desc, expected) in bad_type_descriptions.iter() {
@@ -1107,6 +1281,8 @@ mod test {
             "(buff 1048576)",
             "(list 4400 bool)",
             "(tuple (l (buff 1048550)))",
+            "(string-ascii 1048576)",
+            "(string-utf8 262140)",
         ];
 
         for desc in okay_types.iter() {
@@ -0,0 +1,29 @@
+/// The Stacks block height from which newly deployed contracts are parsed as Clarity2.
+///   Contracts deployed below this height keep the meaning they were deployed with.
+pub const CLARITY2_ACTIVATION_HEIGHT: u32 = 50_000;
+
+/// Revisions of the Clarity language that change how the same source text is read.
+///   A contract is parsed with the version in effect at the block that deploys it,
+///   so that replaying the chain builds the same contracts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ClarityVersion {
+    /// `"..."` literals are buffers.
+    Clarity1,
+    /// `"..."` literals are string-ascii values.
+    Clarity2,
+}
+
+impl ClarityVersion {
+    pub fn latest() -> ClarityVersion {
+        ClarityVersion::Clarity2
+    }
+
+    /// The version of contracts deployed in a block at `block_height`.
+    pub fn at_block_height(block_height: u32) -> ClarityVersion {
+        if block_height >= CLARITY2_ACTIVATION_HEIGHT {
+            ClarityVersion::Clarity2
+        } else {
+            ClarityVersion::Clarity1
+        }
+    }
+}
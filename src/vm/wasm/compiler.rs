@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use parity_wasm::elements::{BlockType, CodeSection, ExportEntry, ExportSection, External, Func, FuncBody, FunctionSection,
+                            FunctionType, ImportEntry, ImportSection, Instruction, Instructions, Internal, Module, Section,
+                            Type, TypeSection, ValueType};
+
+use vm::callables::CallableType;
+use vm::errors::CheckErrors;
+use vm::functions::{self, NativeFunctions, data_function_signature};
+use vm::functions::define::DefineFunctionsParsed;
+use vm::functions::tuples;
+use vm::functions::tuples::TupleDefinitionType::Implicit;
+use vm::representations::{ClarityName, SymbolicExpression};
+use vm::representations::SymbolicExpressionType::{AtomValue, Atom, List, LiteralValue, TraitReference, Field};
+use vm::Value;
+
+use super::{HostFunction, WasmError};
+
+/// The costs charged by the special forms which are compiled to Wasm control flow.
+pub enum SpecialCost {
+    If,
+    Let(usize),
+    And(usize),
+    Or(usize),
+    Asserts
+}
+
+/// The operands of the host functions which cannot be passed as i32s. Compiled code refers to
+///   them by their index in the contract's entries.
+pub enum Entry {
+    Value(Value),
+    Variable(ClarityName),
+    Function(ClarityName),
+    Charge(SpecialCost),
+    Binding(ClarityName),
+    DataFunction(NativeFunctions, Option<ClarityName>),
+    Tuple(Vec<SymbolicExpression>),
+    Expression(SymbolicExpression),
+}
+
+pub struct CompiledModule {
+    pub module: Module,
+    pub entries: Vec<Entry>,
+    pub function_bodies: HashMap<ClarityName, SymbolicExpression>,
+}
+
+// the host functions are imported first, so that they take the first function indexes.
+//   every compiled function has the type `() -> i32`, which it shares with `apply`.
+const FUNCTION_TYPE: u32 = 1;
+
+fn import_type(function: &HostFunction) -> u32 {
+    match function.arity() {
+        (0, false) => 0,
+        (0, true) => 1,
+        (1, false) => 2,
+        (1, true) => 3,
+        (2, false) => 4,
+        _ => unreachable!("No host function has this arity")
+    }
+}
+
+fn types() -> TypeSection {
+    let i32_params = |count| vec![ValueType::I32; count];
+    TypeSection::with_types(vec![
+        Type::Function(FunctionType::new(i32_params(0), None)),
+        Type::Function(FunctionType::new(i32_params(0), Some(ValueType::I32))),
+        Type::Function(FunctionType::new(i32_params(1), None)),
+        Type::Function(FunctionType::new(i32_params(1), Some(ValueType::I32))),
+        Type::Function(FunctionType::new(i32_params(2), None)),
+    ])
+}
+
+fn imports() -> ImportSection {
+    ImportSection::with_entries(
+        HostFunction::ALL.iter()
+            .map(|function| ImportEntry::new("clarity".to_string(), function.get_name(),
+                                             External::Function(import_type(function))))
+            .collect())
+}
+
+pub fn compile(expressions: &[SymbolicExpression]) -> Result<CompiledModule, WasmError> {
+    let mut entries = Vec::new();
+    let mut function_bodies = HashMap::new();
+    let mut functions = Vec::new();
+    let mut exports = Vec::new();
+    let mut bodies = Vec::new();
+
+    for expression in expressions.iter() {
+        let parsed = DefineFunctionsParsed::try_parse(expression)
+            .map_err(WasmError::BadDefinition)?;
+        let (signature, body) = match parsed {
+            Some(DefineFunctionsParsed::PrivateFunction { signature, body }) |
+            Some(DefineFunctionsParsed::ReadOnlyFunction { signature, body }) |
+            Some(DefineFunctionsParsed::PublicFunction { signature, body }) => (signature, body),
+            _ => continue
+        };
+
+        // the same checks, in the same order, as `handle_define_function`.
+        let name = signature.get(0)
+            .ok_or(WasmError::BadDefinition(CheckErrors::DefineFunctionBadSignature))?
+            .match_atom()
+            .ok_or(WasmError::BadDefinition(CheckErrors::ExpectedName))?;
+
+        let mut compiler = FunctionCompiler { entries: &mut entries, code: Vec::new() };
+        compiler.compile_expression(body);
+        compiler.code.push(Instruction::End);
+
+        let function_index = (HostFunction::ALL.len() + functions.len()) as u32;
+        functions.push(Func::new(FUNCTION_TYPE));
+        exports.push(ExportEntry::new(name.to_string(), Internal::Function(function_index)));
+        bodies.push(FuncBody::new(vec![], Instructions::new(compiler.code)));
+        function_bodies.insert(name.clone(), body.clone());
+    }
+
+    let module = Module::new(vec![
+        Section::Type(types()),
+        Section::Import(imports()),
+        Section::Function(FunctionSection::with_entries(functions)),
+        Section::Export(ExportSection::with_entries(exports)),
+        Section::Code(CodeSection::with_bodies(bodies)),
+    ]);
+
+    Ok(CompiledModule { module, entries, function_bodies })
+}
+
+/// Emits the code of a function body. Each compiled expression leaves the handle of its
+///   value on the Wasm stack.
+struct FunctionCompiler <'a> {
+    entries: &'a mut Vec<Entry>,
+    code: Vec<Instruction>
+}
+
+impl <'a> FunctionCompiler <'a> {
+    fn call(&mut self, function: HostFunction) {
+        self.code.push(Instruction::Call(function as u32));
+    }
+
+    fn call_with_entry(&mut self, function: HostFunction, entry: Entry) {
+        self.code.push(Instruction::I32Const(self.entries.len() as i32));
+        self.entries.push(entry);
+        self.call(function);
+    }
+
+    fn compile_expression(&mut self, expression: &SymbolicExpression) {
+        match expression.expr {
+            AtomValue(ref value) | LiteralValue(ref value) =>
+                self.call_with_entry(HostFunction::Const, Entry::Value(value.clone())),
+            Atom(ref name) =>
+                self.call_with_entry(HostFunction::Lookup, Entry::Variable(name.clone())),
+            List(ref children) => self.compile_list(expression, children),
+            TraitReference(_, _) | Field(_) =>
+                self.call_with_entry(HostFunction::Eval, Entry::Expression(expression.clone())),
+        }
+    }
+
+    fn compile_list(&mut self, expression: &SymbolicExpression, children: &[SymbolicExpression]) {
+        use vm::functions::NativeFunctions::*;
+
+        let (function_name, args) = match children.split_first() {
+            Some((function_name, args)) => match function_name.match_atom() {
+                Some(function_name) => (function_name, args),
+                None => return self.call_with_entry(HostFunction::Eval, Entry::Expression(expression.clone()))
+            },
+            None => return self.call_with_entry(HostFunction::Eval, Entry::Expression(expression.clone()))
+        };
+
+        let native_function = match NativeFunctions::lookup_by_name(function_name) {
+            Some(native_function) => native_function,
+            None => return self.compile_application(function_name, args)
+        };
+
+        let compiled = match native_function {
+            If => self.compile_if(function_name, args),
+            Let => self.compile_let(function_name, args),
+            And => self.compile_and_or(function_name, args, true),
+            Or => self.compile_and_or(function_name, args, false),
+            Asserts => self.compile_asserts(function_name, args),
+            ListCons => self.compile_list_cons(function_name, args),
+            _ => {
+                if data_function_signature(&native_function).is_some() {
+                    self.compile_data_function(function_name, native_function, args)
+                } else if let Some(CallableType::NativeFunction(..)) = functions::lookup_reserved_functions(function_name) {
+                    self.compile_application(function_name, args);
+                    true
+                } else {
+                    false
+                }
+            }
+        };
+
+        if !compiled {
+            self.call_with_entry(HostFunction::Eval, Entry::Expression(expression.clone()));
+        }
+    }
+
+    fn compile_application(&mut self, function_name: &ClarityName, args: &[SymbolicExpression]) {
+        self.call_with_entry(HostFunction::Enter, Entry::Function(function_name.clone()));
+        for arg in args.iter() {
+            self.compile_expression(arg);
+            self.call(HostFunction::PushArg);
+        }
+        self.call(HostFunction::Apply);
+    }
+
+    fn compile_if(&mut self, function_name: &ClarityName, args: &[SymbolicExpression]) -> bool {
+        if args.len() != 3 {
+            return false
+        }
+
+        self.call_with_entry(HostFunction::Enter, Entry::Function(function_name.clone()));
+        self.call_with_entry(HostFunction::Charge, Entry::Charge(SpecialCost::If));
+        self.compile_expression(&args[0]);
+        self.call(HostFunction::Test);
+        self.code.push(Instruction::If(BlockType::Value(ValueType::I32)));
+        self.compile_expression(&args[1]);
+        self.code.push(Instruction::Else);
+        self.compile_expression(&args[2]);
+        self.code.push(Instruction::End);
+        self.call(HostFunction::Exit);
+        true
+    }
+
+    fn compile_let(&mut self, function_name: &ClarityName, args: &[SymbolicExpression]) -> bool {
+        if args.len() < 2 {
+            return false
+        }
+
+        let bindings = match args[0].match_list() {
+            Some(bindings) => bindings,
+            None => return false
+        };
+
+        let mut parsed_bindings = Vec::new();
+        for binding in bindings.iter() {
+            match binding.match_list() {
+                Some(binding) if binding.len() == 2 => match binding[0].match_atom() {
+                    Some(name) => parsed_bindings.push((name, &binding[1])),
+                    None => return false
+                },
+                _ => return false
+            }
+        }
+
+        self.call_with_entry(HostFunction::Enter, Entry::Function(function_name.clone()));
+        self.call_with_entry(HostFunction::Charge, Entry::Charge(SpecialCost::Let(bindings.len())));
+        self.call(HostFunction::PushScope);
+        for (name, value) in parsed_bindings.drain(..) {
+            let binding = self.entries.len() as i32;
+            self.call_with_entry(HostFunction::CheckBinding, Entry::Binding(name.clone()));
+            self.compile_expression(value);
+            self.code.push(Instruction::I32Const(binding));
+            self.call(HostFunction::Bind);
+        }
+        self.call(HostFunction::ActivateScope);
+
+        let (last_body, bodies) = args[1..].split_last()
+            .expect("Checked the argument count");
+        for body in bodies.iter() {
+            self.compile_expression(body);
+            self.code.push(Instruction::Drop);
+        }
+        self.compile_expression(last_body);
+
+        self.call(HostFunction::PopScope);
+        self.call(HostFunction::Exit);
+        true
+    }
+
+    fn compile_and_or(&mut self, function_name: &ClarityName, args: &[SymbolicExpression], is_and: bool) -> bool {
+        if args.len() < 1 {
+            return false
+        }
+
+        let charge = if is_and { SpecialCost::And(args.len()) } else { SpecialCost::Or(args.len()) };
+        self.call_with_entry(HostFunction::Enter, Entry::Function(function_name.clone()));
+        self.call_with_entry(HostFunction::Charge, Entry::Charge(charge));
+
+        // `and` stops at the first false argument, and `or` at the first true one.
+        self.code.push(Instruction::Block(BlockType::Value(ValueType::I32)));
+        for arg in args.iter() {
+            self.compile_expression(arg);
+            self.call(HostFunction::Test);
+            if is_and {
+                self.code.push(Instruction::I32Eqz);
+            }
+            self.code.push(Instruction::If(BlockType::NoResult));
+            self.code.push(Instruction::I32Const(if is_and { 0 } else { 1 }));
+            self.call(HostFunction::Bool);
+            self.code.push(Instruction::Br(1));
+            self.code.push(Instruction::End);
+        }
+        self.code.push(Instruction::I32Const(if is_and { 1 } else { 0 }));
+        self.call(HostFunction::Bool);
+        self.code.push(Instruction::End);
+
+        self.call(HostFunction::Exit);
+        true
+    }
+
+    fn compile_asserts(&mut self, function_name: &ClarityName, args: &[SymbolicExpression]) -> bool {
+        if args.len() != 2 {
+            return false
+        }
+
+        self.call_with_entry(HostFunction::Enter, Entry::Function(function_name.clone()));
+        self.call_with_entry(HostFunction::Charge, Entry::Charge(SpecialCost::Asserts));
+        self.compile_expression(&args[0]);
+        self.call(HostFunction::Test);
+        self.code.push(Instruction::If(BlockType::Value(ValueType::I32)));
+        self.code.push(Instruction::I32Const(1));
+        self.call(HostFunction::Bool);
+        self.code.push(Instruction::Else);
+        // the thrown value is returned from the enclosing function by the host.
+        self.compile_expression(&args[1]);
+        self.call(HostFunction::Throw);
+        self.code.push(Instruction::End);
+        self.call(HostFunction::Exit);
+        true
+    }
+
+    fn compile_list_cons(&mut self, function_name: &ClarityName, args: &[SymbolicExpression]) -> bool {
+        self.call_with_entry(HostFunction::Enter, Entry::Function(function_name.clone()));
+        for arg in args.iter() {
+            self.compile_expression(arg);
+            self.call(HostFunction::PushOperand);
+        }
+        self.call(HostFunction::List);
+        true
+    }
+
+    fn compile_data_function(&mut self, function_name: &ClarityName, native_function: NativeFunctions,
+                             args: &[SymbolicExpression]) -> bool {
+        use vm::functions::NativeFunctions::*;
+
+        let (named, operand_count) = data_function_signature(&native_function)
+            .expect("Checked by the caller");
+        let (name, operands) = if named {
+            match args.split_first() {
+                Some((name, operands)) => match name.match_atom() {
+                    Some(name) => (Some(name.clone()), operands),
+                    None => return false
+                },
+                None => return false
+            }
+        } else {
+            (None, args)
+        };
+        if operands.len() != operand_count {
+            return false
+        }
+
+        // the keys and values of maps may be written as implicit tuples.
+        let map_function = match native_function {
+            FetchEntry | SetEntry | InsertEntry | DeleteEntry => true,
+            _ => false
+        };
+
+        self.call_with_entry(HostFunction::Enter, Entry::Function(function_name.clone()));
+        let data_function = self.entries.len() as i32;
+        self.call_with_entry(HostFunction::Begin, Entry::DataFunction(native_function, name));
+        for operand in operands.iter() {
+            match tuples::get_definition_type_of_tuple_argument(operand) {
+                Implicit(ref pairs) if map_function =>
+                    self.call_with_entry(HostFunction::Tuple, Entry::Tuple(pairs.to_vec())),
+                _ => self.compile_expression(operand)
+            }
+            self.call(HostFunction::PushOperand);
+        }
+        self.code.push(Instruction::I32Const(data_function));
+        self.call(HostFunction::Perform);
+        true
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+use std::mem;
+
+use wasmi;
+use wasmi::{Externals, FuncInstance, FuncRef, ModuleImportResolver, RuntimeArgs, RuntimeValue, Signature, Trap, TrapKind};
+
+use vm::callables::{CallableType, FunctionIdentifier};
+use vm::contexts::{Environment, LocalContext, MAX_CONTEXT_DEPTH};
+use vm::costs::{cost_functions, CostTracker, MemoryConsumer};
+use vm::errors::{Error, CheckErrors, InterpreterError, RuntimeErrorType, ShortReturnType, InterpreterResult as Result};
+use vm::functions::{apply_data_function, begin_data_function};
+use vm::functions::{iterables, tuples};
+use vm::representations::ClarityName;
+use vm::types::{PrincipalData, TypeSignature, Value};
+use vm::{add_stack_trace, begin_application, finish_application, eval, is_reserved, lookup_function, variables};
+
+use super::HostFunction;
+use super::compiler::{Entry, SpecialCost};
+
+pub struct HostResolver;
+
+impl ModuleImportResolver for HostResolver {
+    fn resolve_func(&self, field_name: &str, signature: &Signature) -> std::result::Result<FuncRef, wasmi::Error> {
+        let function = HostFunction::lookup_by_name(field_name)
+            .ok_or_else(|| wasmi::Error::Instantiation(format!("Unknown host function {}", field_name)))?;
+        if *signature != function.signature() {
+            return Err(wasmi::Error::Instantiation(format!("Bad signature for host function {}", field_name)))
+        }
+        let signature = function.signature();
+        Ok(FuncInstance::alloc_host(signature, function as usize))
+    }
+}
+
+/// A function application whose arguments or operands are being evaluated by compiled code.
+enum Frame {
+    Application { function: CallableType, identifier: FunctionIdentifier, track_recursion: bool,
+                  arguments: Vec<Value>, used_memory: u64 },
+    Special { identifier: FunctionIdentifier, track_recursion: bool, operands: Vec<Value> },
+}
+
+/// The variables bound by a `let`. A scope becomes visible once all of its bindings are
+///   evaluated, and it extends the scope which was visible when it was pushed.
+struct Scope {
+    variables: HashMap<ClarityName, Value>,
+    parent: Option<usize>,
+    depth: u16,
+    visible: bool,
+    used_memory: u64,
+}
+
+/// The host of an invocation of a compiled function. It holds the values computed by the
+///   compiled code, and mirrors the call stack and the local contexts of the interpreter.
+pub struct Host <'a, 'b, 'c, 'd> {
+    env: &'a mut Environment<'b, 'c>,
+    context: &'a LocalContext<'d>,
+    entries: &'a [Entry],
+    values: Vec<Option<Value>>,
+    frames: Vec<Frame>,
+    scopes: Vec<Scope>,
+    error: Option<Error>,
+}
+
+impl <'a, 'b, 'c, 'd> Externals for Host <'a, 'b, 'c, 'd> {
+    fn invoke_index(&mut self, index: usize, args: RuntimeArgs) -> std::result::Result<Option<RuntimeValue>, Trap> {
+        // the functions which re-enter the interpreter or the module are kept out of the large
+        //   frame of `invoke`, since they nest once per Clarity function application.
+        let result = match HostFunction::ALL.get(index) {
+            Some(HostFunction::Apply) => self.apply().map(Some),
+            Some(HostFunction::Eval) => self.eval(args.nth(0)).map(Some),
+            Some(function) => self.invoke(function, &args),
+            None => Err(InterpreterError::InterpreterError(format!("Unknown host function index {}", index)).into())
+        };
+        match result {
+            Ok(result) => Ok(result.map(RuntimeValue::I32)),
+            Err(e) => {
+                // the error is picked up once the module traps.
+                self.error = Some(e);
+                Err(TrapKind::Unreachable.into())
+            }
+        }
+    }
+}
+
+impl <'a, 'b, 'c, 'd> Host <'a, 'b, 'c, 'd> {
+    pub fn new(env: &'a mut Environment<'b, 'c>, context: &'a LocalContext<'d>, entries: &'a [Entry]) -> Host<'a, 'b, 'c, 'd> {
+        Host {
+            env, context, entries,
+            values: Vec::new(),
+            frames: Vec::new(),
+            scopes: Vec::new(),
+            error: None
+        }
+    }
+
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    pub fn take_value(&mut self, handle: i32) -> Result<Value> {
+        self.values.get_mut(handle as usize)
+            .and_then(|value| value.take())
+            .ok_or_else(|| InterpreterError::InterpreterError(format!("Bad value handle {}", handle)).into())
+    }
+
+    fn push_value(&mut self, value: Value) -> i32 {
+        self.values.push(Some(value));
+        (self.values.len() - 1) as i32
+    }
+
+    fn entry(&self, index: i32) -> Result<&'a Entry> {
+        let entries = self.entries;
+        entries.get(index as usize)
+            .ok_or_else(|| InterpreterError::InterpreterError(format!("Bad entry index {}", index)).into())
+    }
+
+    /// Cleans up after an error the way the interpreter does while the error propagates:
+    ///   the memory of pending arguments and bindings is dropped, and pending applications are
+    ///   popped from the call stack.
+    pub fn unwind(&mut self, error: Error) -> Error {
+        let mut error = error;
+        for scope in self.scopes.drain(..) {
+            self.env.drop_memory(scope.used_memory);
+        }
+        while let Some(frame) = self.frames.pop() {
+            let (identifier, track_recursion) = match frame {
+                Frame::Application { identifier, track_recursion, used_memory, .. } => {
+                    self.env.drop_memory(used_memory);
+                    (identifier, track_recursion)
+                },
+                Frame::Special { identifier, track_recursion, .. } => {
+                    let mut resp = Err(error);
+                    add_stack_trace(&mut resp, self.env);
+                    error = match resp {
+                        Err(e) => e,
+                        Ok(_) => unreachable!("Constructed as an error")
+                    };
+                    (identifier, track_recursion)
+                }
+            };
+            if let Err(e) = self.env.call_stack.remove(&identifier, track_recursion) {
+                error = e;
+            }
+        }
+        error
+    }
+
+    fn invoke(&mut self, function: &HostFunction, args: &RuntimeArgs) -> Result<Option<i32>> {
+        use super::HostFunction::*;
+        match function {
+            Const => match self.entry(args.nth(0))? {
+                Entry::Value(value) => Ok(Some(self.push_value(value.clone()))),
+                _ => Err(bad_entry(function))
+            },
+            Lookup => match self.entry(args.nth(0))? {
+                Entry::Variable(name) => {
+                    let value = self.lookup_variable(name)?;
+                    Ok(Some(self.push_value(value)))
+                },
+                _ => Err(bad_entry(function))
+            },
+            Enter => match self.entry(args.nth(0))? {
+                Entry::Function(name) => {
+                    self.enter(name)?;
+                    Ok(None)
+                },
+                _ => Err(bad_entry(function))
+            },
+            PushArg => {
+                let value = self.take_value(args.nth(0))?;
+                match self.frames.last_mut() {
+                    Some(Frame::Application { ref mut arguments, ref mut used_memory, .. }) => {
+                        let arg_use = value.get_memory_use();
+                        self.env.add_memory(arg_use)?;
+                        *used_memory += arg_use;
+                        arguments.push(value);
+                        Ok(None)
+                    },
+                    _ => Err(bad_frame(function))
+                }
+            },
+            Apply => self.apply().map(Some),
+            Exit => match self.frames.pop() {
+                Some(Frame::Special { identifier, track_recursion, .. }) => {
+                    self.env.call_stack.remove(&identifier, track_recursion)?;
+                    Ok(Some(args.nth(0)))
+                },
+                _ => Err(bad_frame(function))
+            },
+            Charge => match self.entry(args.nth(0))? {
+                Entry::Charge(charge) => {
+                    match charge {
+                        SpecialCost::If => runtime_cost!(cost_functions::IF, self.env, 0)?,
+                        SpecialCost::Let(bindings) => runtime_cost!(cost_functions::LET, self.env, *bindings)?,
+                        SpecialCost::And(args) => runtime_cost!(cost_functions::AND, self.env, *args)?,
+                        SpecialCost::Or(args) => runtime_cost!(cost_functions::OR, self.env, *args)?,
+                        SpecialCost::Asserts => runtime_cost!(cost_functions::ASSERTS, self.env, 0)?,
+                    }
+                    Ok(None)
+                },
+                _ => Err(bad_entry(function))
+            },
+            Test => match self.take_value(args.nth(0))? {
+                Value::Bool(result) => Ok(Some(result as i32)),
+                value => Err(CheckErrors::TypeValueError(TypeSignature::BoolType, value).into())
+            },
+            Bool => {
+                let result: i32 = args.nth(0);
+                Ok(Some(self.push_value(Value::Bool(result != 0))))
+            },
+            PushScope => {
+                let parent = self.visible_scope();
+                let depth = self.depth(parent);
+                if depth >= MAX_CONTEXT_DEPTH {
+                    return Err(RuntimeErrorType::MaxContextDepthReached.into())
+                }
+                self.scopes.push(Scope {
+                    variables: HashMap::new(),
+                    parent,
+                    depth: depth + 1,
+                    visible: false,
+                    used_memory: 0
+                });
+                Ok(None)
+            },
+            CheckBinding => match self.entry(args.nth(0))? {
+                Entry::Binding(name) => {
+                    let pending = self.scopes.len().checked_sub(1);
+                    if is_reserved(name) ||
+                        self.env.contract_context.lookup_function(name).is_some() ||
+                        self.find_variable(pending, name).is_some() {
+                            return Err(CheckErrors::NameAlreadyUsed(name.clone().into()).into())
+                        }
+                    Ok(None)
+                },
+                _ => Err(bad_entry(function))
+            },
+            Bind => match self.entry(args.nth(1))? {
+                Entry::Binding(name) => {
+                    let value = self.take_value(args.nth(0))?;
+                    match self.scopes.last_mut() {
+                        Some(scope) => {
+                            let bind_mem_use = value.get_memory_use();
+                            self.env.add_memory(bind_mem_use)?;
+                            scope.used_memory += bind_mem_use;
+                            scope.variables.insert(name.clone(), value);
+                            Ok(None)
+                        },
+                        None => Err(bad_frame(function))
+                    }
+                },
+                _ => Err(bad_entry(function))
+            },
+            ActivateScope => match self.scopes.last_mut() {
+                Some(scope) => {
+                    scope.visible = true;
+                    Ok(None)
+                },
+                None => Err(bad_frame(function))
+            },
+            PopScope => match self.scopes.pop() {
+                Some(scope) => {
+                    self.env.drop_memory(scope.used_memory);
+                    Ok(None)
+                },
+                None => Err(bad_frame(function))
+            },
+            Begin => match self.entry(args.nth(0))? {
+                Entry::DataFunction(data_function, _) => {
+                    begin_data_function(data_function, self.env)?;
+                    Ok(None)
+                },
+                _ => Err(bad_entry(function))
+            },
+            PushOperand => {
+                let value = self.take_value(args.nth(0))?;
+                match self.frames.last_mut() {
+                    Some(Frame::Special { ref mut operands, .. }) => {
+                        operands.push(value);
+                        Ok(None)
+                    },
+                    _ => Err(bad_frame(function))
+                }
+            },
+            Perform => match (self.entry(args.nth(0))?, self.frames.pop()) {
+                (Entry::DataFunction(data_function, name), Some(Frame::Special { identifier, track_recursion, operands })) => {
+                    let resp = apply_data_function(data_function, name.as_ref(), operands, self.env);
+                    self.finish_special(&identifier, track_recursion, resp).map(Some)
+                },
+                _ => Err(bad_frame(function))
+            },
+            List => match self.frames.pop() {
+                Some(Frame::Special { identifier, track_recursion, operands }) => {
+                    let resp = iterables::list_from_values(operands, self.env);
+                    self.finish_special(&identifier, track_recursion, resp).map(Some)
+                },
+                _ => Err(bad_frame(function))
+            },
+            Throw => {
+                let thrown = self.take_value(args.nth(0))?;
+                Err(ShortReturnType::AssertionFailed(thrown).into())
+            },
+            Tuple => match self.entry(args.nth(0))? {
+                Entry::Tuple(pairs) => {
+                    let value = self.eval_in_visible_scopes(&|env, context| tuples::tuple_cons(pairs, env, context))?;
+                    Ok(Some(self.push_value(value)))
+                },
+                _ => Err(bad_entry(function))
+            },
+            Eval => self.eval(args.nth(0)).map(Some),
+        }
+    }
+
+    /// Pops the application of a special function whose operands compiled code evaluated, like
+    ///   `vm::apply`.
+    fn finish_special(&mut self, identifier: &FunctionIdentifier, track_recursion: bool, resp: Result<Value>) -> Result<i32> {
+        let mut resp = resp;
+        add_stack_trace(&mut resp, self.env);
+        self.env.call_stack.remove(identifier, track_recursion)?;
+        let value = resp?;
+        Ok(self.push_value(value))
+    }
+
+    fn apply(&mut self) -> Result<i32> {
+        match self.frames.pop() {
+            Some(Frame::Application { function, identifier, track_recursion, arguments, used_memory }) => {
+                let value = finish_application(&function, &identifier, track_recursion, arguments, used_memory, self.env)?;
+                Ok(self.push_value(value))
+            },
+            _ => Err(bad_frame(&HostFunction::Apply))
+        }
+    }
+
+    fn eval(&mut self, entry: i32) -> Result<i32> {
+        match self.entry(entry)? {
+            Entry::Expression(expression) => {
+                let value = self.eval_in_visible_scopes(&|env, context| eval(expression, env, context))?;
+                Ok(self.push_value(value))
+            },
+            _ => Err(bad_entry(&HostFunction::Eval))
+        }
+    }
+
+    /// Pushes the application of a function onto the call stack, like `vm::apply`.
+    fn enter(&mut self, name: &ClarityName) -> Result<()> {
+        let function = lookup_function(name, self.env)?;
+        let (identifier, track_recursion) = begin_application(&function, self.env)?;
+        let frame = match function {
+            CallableType::SpecialFunction(_, _) =>
+                Frame::Special { identifier, track_recursion, operands: vec![] },
+            function =>
+                Frame::Application { function, identifier, track_recursion, arguments: vec![], used_memory: 0 }
+        };
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    fn visible_scope(&self) -> Option<usize> {
+        self.scopes.iter().rposition(|scope| scope.visible)
+    }
+
+    fn depth(&self, scope: Option<usize>) -> u16 {
+        match scope {
+            Some(scope) => self.scopes[scope].depth,
+            None => self.context.depth()
+        }
+    }
+
+    fn find_variable(&self, scope: Option<usize>, name: &str) -> Option<&Value> {
+        let mut scope = scope;
+        while let Some(ix) = scope {
+            if let Some(value) = self.scopes[ix].variables.get(name) {
+                return Some(value)
+            }
+            scope = self.scopes[ix].parent;
+        }
+        self.context.lookup_variable(name)
+    }
+
+    /// Looks up a variable in the visible scopes, like `vm::lookup_variable`.
+    fn lookup_variable(&mut self, name: &str) -> Result<Value> {
+        if name.starts_with(char::is_numeric) || name.starts_with('\'') {
+            Err(InterpreterError::BadSymbolicRepresentation(format!("Unexpected variable name: {}", name)).into())
+        } else {
+            if let Some(value) = variables::lookup_reserved_variable(name, self.context, self.env)? {
+                Ok(value)
+            } else {
+                let scope = self.visible_scope();
+                runtime_cost!(cost_functions::LOOKUP_VARIABLE_DEPTH, self.env, self.depth(scope))?;
+                let value = self.find_variable(scope, name).cloned()
+                    .or_else(|| self.env.contract_context.lookup_variable(name).cloned());
+                if let Some(value) = value {
+                    runtime_cost!(cost_functions::LOOKUP_VARIABLE_SIZE, self.env, value.size())?;
+                    Ok(value)
+                } else if let Some(value) = self.context.lookup_callable_contract(name) {
+                    let contract_identifier = &value.0;
+                    Ok(Value::Principal(PrincipalData::Contract(contract_identifier.clone())))
+                } else {
+                    Err(CheckErrors::UndefinedVariable(name.to_string()).into())
+                }
+            }
+        }
+    }
+
+    /// Hands an expression back to the interpreter, in a local context holding the
+    ///   visible scopes.
+    fn eval_in_visible_scopes(&mut self, f: &dyn Fn(&mut Environment, &LocalContext) -> Result<Value>) -> Result<Value> {
+        let mut chain = vec![];
+        let mut scope = self.visible_scope();
+        while let Some(ix) = scope {
+            chain.push(ix);
+            scope = self.scopes[ix].parent;
+        }
+        chain.reverse();
+        eval_in_scopes(f, self.env, self.context, &mut self.scopes, &chain)
+    }
+}
+
+fn eval_in_scopes(f: &dyn Fn(&mut Environment, &LocalContext) -> Result<Value>,
+                  env: &mut Environment, context: &LocalContext, scopes: &mut [Scope], chain: &[usize]) -> Result<Value> {
+    match chain.split_first() {
+        None => f(env, context),
+        Some((ix, rest)) => {
+            let mut inner_context = context.extend()?;
+            // the variables are moved into the context for the evaluation, and then moved back.
+            mem::swap(&mut inner_context.variables, &mut scopes[*ix].variables);
+            let result = eval_in_scopes(f, env, &inner_context, scopes, rest);
+            mem::swap(&mut inner_context.variables, &mut scopes[*ix].variables);
+            result
+        }
+    }
+}
+
+fn bad_entry(function: &HostFunction) -> Error {
+    InterpreterError::InterpreterError(format!("Bad entry passed to host function {}", function.get_name())).into()
+}
+
+fn bad_frame(function: &HostFunction) -> Error {
+    InterpreterError::InterpreterError(format!("Host function {} called out of order", function.get_name())).into()
+}
@@ -0,0 +1,250 @@
+/*
+ An experimental WebAssembly compilation target for Clarity contracts.
+
+ The bodies of a contract's functions are compiled to the exports of a Wasm module, which is
+   executed in-process by `wasmi` when the contract is registered with the `GlobalContext`.
+   Clarity values never cross into the module: they stay with the host, and the compiled code
+   passes around i32 handles to them. The module implements the control flow of `if`, `let`,
+   `and`, `or` and `asserts!`, builds `list`s, and applies native and user-defined functions,
+   calling back into the host (the functions imported from the `clarity` module) to lookup
+   variables, apply functions and operate on the `ClarityDatabase`, the assets and the event
+   log. Any other form is handed back to `vm::eval`.
+
+ The host charges exactly the costs which the interpreter charges for each form, in the same
+   order, so that results and costs match the interpreter. `vm::tests::wasm` checks this for
+   the whole `vm::tests` corpus, by re-running it with `CLARITY_WASM_COMPILE_CONTRACTS` set,
+   which compiles every contract the tests deploy, and fails any deployment which does not
+   compile.
+
+ Compiled contracts are not faster than interpreted ones yet: each form still calls back into
+   the host, and on `benches/wasm_bench.rs` the compiled contract takes longer to execute.
+*/
+
+mod compiler;
+mod host;
+
+use std::collections::HashMap;
+#[cfg(test)]
+use std::env;
+#[cfg(test)]
+use std::fs::OpenOptions;
+#[cfg(test)]
+use std::io::Write;
+use std::error;
+use std::fmt;
+
+use wasmi;
+use wasmi::{ImportsBuilder, ModuleInstance, ModuleRef, RuntimeValue, Signature, StackRecycler, ValueType};
+
+use vm::analysis::ContractAnalysis;
+use vm::ast::ContractAST;
+use vm::contexts::{ContractContext, Environment, LocalContext};
+use vm::errors::{CheckErrors, InterpreterError, InterpreterResult as Result};
+use vm::representations::{ClarityName, SymbolicExpression};
+use vm::types::QualifiedContractIdentifier;
+use vm::Value;
+
+use self::compiler::Entry;
+use self::host::{Host, HostResolver};
+
+/// Set in the environment of a test run to compile every contract deployed by the tests. If it is
+///   not empty, it names a file to which the identifier of each compiled contract is appended.
+#[cfg(test)]
+pub const COMPILE_CONTRACTS_ENV: &str = "CLARITY_WASM_COMPILE_CONTRACTS";
+
+// each invocation of a compiled function gets its own stacks, because compiled functions
+//   re-enter the module whenever they apply a user function.
+const VALUE_STACK_LIMIT: usize = 64 * 1024;
+const CALL_STACK_LIMIT: usize = 16;
+
+// The functions a compiled module imports from the host. Their parameters are handles to values
+//   held by the host, or indexes into the compiled contract's entries.
+define_named_enum!(HostFunction {
+    Const("const"),
+    Lookup("lookup"),
+    Enter("enter"),
+    PushArg("push-arg"),
+    Apply("apply"),
+    Exit("exit"),
+    Charge("charge"),
+    Test("test"),
+    Bool("bool"),
+    PushScope("push-scope"),
+    CheckBinding("check-binding"),
+    Bind("bind"),
+    ActivateScope("activate-scope"),
+    PopScope("pop-scope"),
+    Begin("begin"),
+    PushOperand("push-operand"),
+    Perform("perform"),
+    Tuple("tuple"),
+    Eval("eval"),
+    Throw("throw"),
+    List("list"),
+});
+
+impl HostFunction {
+    /// Returns the number of i32 parameters of this function, and whether it returns an i32.
+    fn arity(&self) -> (usize, bool) {
+        use self::HostFunction::*;
+        match self {
+            Const | Lookup | Exit | Test | Bool | Perform | Tuple | Eval | Throw => (1, true),
+            Enter | PushArg | Charge | CheckBinding | Begin | PushOperand => (1, false),
+            Apply | List => (0, true),
+            PushScope | ActivateScope | PopScope => (0, false),
+            Bind => (2, false),
+        }
+    }
+
+    fn signature(&self) -> Signature {
+        const PARAMS: &[ValueType] = &[ValueType::I32, ValueType::I32];
+        let (param_count, returns) = self.arity();
+        Signature::new(&PARAMS[..param_count], if returns { Some(ValueType::I32) } else { None })
+    }
+}
+
+#[derive(Debug)]
+pub enum WasmError {
+    ContractMismatch(QualifiedContractIdentifier, QualifiedContractIdentifier),
+    BadDefinition(CheckErrors),
+    UnknownFunction(String),
+    Wasm(wasmi::Error),
+}
+
+impl fmt::Display for WasmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WasmError::ContractMismatch(ast, analysis) =>
+                write!(f, "Contract AST of {} does not match the analysis of {}", ast, analysis),
+            WasmError::BadDefinition(e) => write!(f, "Bad function definition: {:?}", e),
+            WasmError::UnknownFunction(name) => write!(f, "Function {} is missing from the contract analysis", name),
+            WasmError::Wasm(e) => write!(f, "Wasm error: {}", e),
+        }
+    }
+}
+
+impl error::Error for WasmError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl From<wasmi::Error> for WasmError {
+    fn from(err: wasmi::Error) -> Self {
+        WasmError::Wasm(err)
+    }
+}
+
+pub struct WasmContract {
+    contract_identifier: QualifiedContractIdentifier,
+    instance: ModuleRef,
+    entries: Vec<Entry>,
+    function_bodies: HashMap<ClarityName, SymbolicExpression>,
+}
+
+impl WasmContract {
+    /// Compiles the functions of a type-checked contract.
+    pub fn compile(contract_ast: &ContractAST, contract_analysis: &ContractAnalysis) -> std::result::Result<WasmContract, WasmError> {
+        if contract_ast.contract_identifier != contract_analysis.contract_identifier {
+            return Err(WasmError::ContractMismatch(contract_ast.contract_identifier.clone(),
+                                                   contract_analysis.contract_identifier.clone()))
+        }
+
+        let contract = WasmContract::compile_expressions(&contract_ast.contract_identifier, &contract_ast.expressions)?;
+        for name in contract.function_bodies.keys() {
+            let analyzed = contract_analysis.private_function_types.contains_key(name) ||
+                contract_analysis.read_only_function_types.contains_key(name) ||
+                contract_analysis.public_function_types.contains_key(name);
+            if !analyzed {
+                return Err(WasmError::UnknownFunction(name.to_string()))
+            }
+        }
+        Ok(contract)
+    }
+
+    /// Compiles the functions defined by the top-level `expressions` of a contract, which
+    ///   have not necessarily been type-checked.
+    pub fn compile_expressions(contract_identifier: &QualifiedContractIdentifier, expressions: &[SymbolicExpression]) -> std::result::Result<WasmContract, WasmError> {
+        let compiled = compiler::compile(expressions)?;
+        let module = wasmi::Module::from_parity_wasm_module(compiled.module)?;
+        let instance = ModuleInstance::new(&module, &ImportsBuilder::new().with_resolver("clarity", &HostResolver))?
+            .assert_no_start();
+
+        Ok(WasmContract {
+            contract_identifier: contract_identifier.clone(),
+            instance,
+            entries: compiled.entries,
+            function_bodies: compiled.function_bodies
+        })
+    }
+
+    /// Whether new global contexts compile their contracts on deployment. This is only the case
+    ///   for the test runs of the `vm::tests` corpus which set `COMPILE_CONTRACTS_ENV`.
+    #[cfg(test)]
+    pub fn compile_by_default() -> bool {
+        env::var(COMPILE_CONTRACTS_ENV).is_ok()
+    }
+
+    #[cfg(not(test))]
+    pub fn compile_by_default() -> bool {
+        false
+    }
+
+    /// Appends this contract's identifier to the file named by `COMPILE_CONTRACTS_ENV`, so that a
+    ///   test run can count the contracts it compiled.
+    #[cfg(test)]
+    pub fn record_compiled(&self) {
+        let path = match env::var(COMPILE_CONTRACTS_ENV) {
+            Ok(ref path) if !path.is_empty() => path.clone(),
+            _ => return
+        };
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)
+            .expect("Failed to open the compiled contracts file");
+        // a single write, so that the lines of concurrent tests do not interleave.
+        file.write_all(format!("{}\n", self.contract_identifier).as_bytes())
+            .expect("Failed to record a compiled contract");
+    }
+
+    pub fn contract_identifier(&self) -> &QualifiedContractIdentifier {
+        &self.contract_identifier
+    }
+
+    pub fn has_function(&self, name: &str) -> bool {
+        self.function_bodies.contains_key(name)
+    }
+
+    /// Checks that each compiled function was compiled from the body of the function of the
+    ///   same name in the deployed contract.
+    pub fn check_against(&self, contract_context: &ContractContext) -> Result<()> {
+        for (name, body) in self.function_bodies.iter() {
+            let matches = contract_context.lookup_function(name)
+                .map(|function| function.get_body() == body)
+                .unwrap_or(false);
+            if !matches {
+                return Err(InterpreterError::InterpreterError(
+                    format!("Compiled function {} does not match the deployed contract {}", name.as_str(), self.contract_identifier)).into())
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluates the body of the function `name` in `context`. Returns None if the function
+    ///   was not compiled.
+    pub fn execute_function(&self, name: &str, env: &mut Environment, context: &LocalContext) -> Option<Result<Value>> {
+        if !self.has_function(name) {
+            return None
+        }
+
+        let mut host = Host::new(env, context, &self.entries);
+        let mut stack = StackRecycler::with_limits(VALUE_STACK_LIMIT, CALL_STACK_LIMIT);
+        let result = match self.instance.invoke_export_with_stack(name, &[], &mut host, &mut stack) {
+            Ok(Some(RuntimeValue::I32(handle))) => host.take_value(handle),
+            Ok(_) => Err(InterpreterError::InterpreterError(
+                format!("Compiled function {} did not return a value", name)).into()),
+            Err(e) => Err(host.take_error().unwrap_or_else(|| InterpreterError::InterpreterError(
+                format!("Failed to execute compiled function {}: {}", name, e)).into()))
+        };
+
+        Some(result.map_err(|e| host.unwind(e)))
+    }
+}
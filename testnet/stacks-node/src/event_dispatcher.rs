@@ -128,6 +128,7 @@ impl EventObserver {
                 "tx_index": tx_index,
                 "status": success,
                 "raw_result": format!("0x{}", raw_result.join("")),
+                "result_json": result.to_json(),
                 "raw_tx": format!("0x{}", raw_tx.join("")),
                 "contract_abi": contract_interface_json,
             });
@@ -205,12 +206,18 @@ impl EventDispatcher {
                     StacksTransactionEvent::NFTEvent(NFTEventType::NFTMintEvent(event_data)) => {
                         self.update_dispatch_matrix_if_observer_subscribed(&event_data.asset_identifier, i, &mut dispatch_matrix);
                     },
+                    StacksTransactionEvent::NFTEvent(NFTEventType::NFTBurnEvent(event_data)) => {
+                        self.update_dispatch_matrix_if_observer_subscribed(&event_data.asset_identifier, i, &mut dispatch_matrix);
+                    },
                     StacksTransactionEvent::FTEvent(FTEventType::FTTransferEvent(event_data)) => {
                         self.update_dispatch_matrix_if_observer_subscribed(&event_data.asset_identifier, i, &mut dispatch_matrix);
                     },
                     StacksTransactionEvent::FTEvent(FTEventType::FTMintEvent(event_data)) => {
                         self.update_dispatch_matrix_if_observer_subscribed(&event_data.asset_identifier, i, &mut dispatch_matrix);
                     },
+                    StacksTransactionEvent::FTEvent(FTEventType::FTBurnEvent(event_data)) => {
+                        self.update_dispatch_matrix_if_observer_subscribed(&event_data.asset_identifier, i, &mut dispatch_matrix);
+                    },
                 }
                 events.push((!receipt.post_condition_aborted, tx_hash, event));
                 for o_i in &self.any_event_observers_lookup {
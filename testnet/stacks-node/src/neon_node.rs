@@ -245,6 +245,7 @@ fn spawn_peer(mut this: PeerNetwork, p2p_sock: &SocketAddr, rpc_sock: &SocketAdd
     let mut chainstate = StacksChainState::open_with_block_limit(
         false, TESTNET_CHAIN_ID, &stacks_chainstate_path, block_limit)
         .map_err(|e| NetError::ChainstateError(e.to_string()))?;
+    chainstate.tx_index = config.node.tx_index;
     
     let mut mem_pool = MemPoolDB::open(
         false, TESTNET_CHAIN_ID, &stacks_chainstate_path)
@@ -323,6 +324,7 @@ fn spawn_miner_relayer(mut relayer: Relayer, local_peer: LocalPeer,
     let mut chainstate = StacksChainState::open_with_block_limit(
         false, TESTNET_CHAIN_ID, &stacks_chainstate_path, config.block_limit.clone())
         .map_err(|e| NetError::ChainstateError(e.to_string()))?;
+    chainstate.tx_index = config.node.tx_index;
     
     let mut mem_pool = MemPoolDB::open(
         false, TESTNET_CHAIN_ID, &stacks_chainstate_path)
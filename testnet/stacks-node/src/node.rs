@@ -77,7 +77,7 @@ pub struct Node {
 
 fn spawn_peer(mut this: PeerNetwork, p2p_sock: &SocketAddr, rpc_sock: &SocketAddr,
               burn_db_path: String, stacks_chainstate_path: String, event_dispatcher: EventDispatcher,
-              exit_at_block_height: Option<u64>, poll_timeout: u64) -> Result<JoinHandle<()>, NetError> {
+              exit_at_block_height: Option<u64>, tx_index: bool, poll_timeout: u64) -> Result<JoinHandle<()>, NetError> {
     this.bind(p2p_sock, rpc_sock).unwrap();
     let server_thread = thread::spawn(move || {
         let handler_args = RPCHandlerArgs { exit_at_block_height: exit_at_block_height.as_ref(),
@@ -102,6 +102,7 @@ fn spawn_peer(mut this: PeerNetwork, p2p_sock: &SocketAddr, rpc_sock: &SocketAdd
                     continue;
                 },
             };
+            chainstate.tx_index = tx_index;
 
             let mut mem_pool = match MemPoolDB::open(
                 false, TESTNET_CHAIN_ID, &stacks_chainstate_path) {
@@ -139,10 +140,11 @@ impl Node {
             false, TESTNET_CHAIN_ID, &config.get_chainstate_path(),
             Some(initial_balances), boot_block_exec, config.block_limit.clone());
 
-        let chain_state = match chain_state_result {
+        let mut chain_state = match chain_state_result {
             Ok(res) => res,
             Err(err) => panic!("Error while opening chain state at path {}: {:?}", config.get_chainstate_path(), err)
         };
+        chain_state.tx_index = config.node.tx_index;
         let mut event_dispatcher = EventDispatcher::new();
 
         for observer in &config.events_observers {
@@ -178,7 +180,7 @@ impl Node {
         let chainstate_path = config.get_chainstate_path();
         let burndb_path = config.get_burn_db_file_path();
 
-        let chain_state = match StacksChainState::open(
+        let mut chain_state = match StacksChainState::open(
             false, 
             TESTNET_CHAIN_ID, 
             &chainstate_path) {
@@ -187,6 +189,7 @@ impl Node {
                 panic!()
             },
         };
+        chain_state.tx_index = config.node.tx_index;
 
         let mut node = Node {
             active_registered_key: None,
@@ -293,6 +296,7 @@ impl Node {
             self.config.get_chainstate_path(),
             event_dispatcher,
             exit_at_block_height,
+            self.config.node.tx_index,
             1000).unwrap();
 
         info!("Bound HTTP server on: {}", &self.config.node.rpc_bind);
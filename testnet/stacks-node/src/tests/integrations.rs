@@ -10,7 +10,9 @@ use stacks::chainstate::stacks::{
     db::StacksChainState, StacksPrivateKey, StacksTransaction, StacksAddress };
 use stacks::chainstate::burn::VRFSeed;
 use stacks::burnchains::Address;
-use stacks::net::{AccountEntryResponse, ContractSrcResponse, CallReadOnlyRequestBody};
+use stacks::net::{AccountEntryResponse, ContractSrcResponse, CallReadOnlyRequestBody, ContractDependenciesResponse, TraitImplementersResponse,
+                  SimulateTransactionRequestBody, SimulationOverridesBody, SimulateTransactionResponse,
+                  IndexedTransactionResponse, IndexedTransactionsResponse, HistoricalAccountEntryResponse, AccountChangesResponse};
 use stacks::net::StacksMessageCodec;
 use stacks::vm::clarity::ClarityConnection;
 
@@ -113,6 +115,7 @@ fn integration_test_get_info() {
     });
 
     conf.burnchain.commit_anchor_block_within = 5000;
+    conf.node.tx_index = true;
 
     let num_rounds = 4;
 
@@ -429,6 +432,100 @@ fn integration_test_get_info() {
                 eprintln!("Test: GET {}", path);
                 assert_eq!(client.get(&path).send().unwrap().status(), 404);
 
+                // the get-info contract calls no other contracts
+
+                let path = format!("{}/v2/contracts/dependencies/{}/{}", &http_origin, &contract_addr, "get-info");
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<ContractDependenciesResponse>().unwrap();
+                assert!(res.dependencies.is_empty());
+                assert_eq!(res.graph.len(), 1);
+
+                let path = format!("{}/v2/contracts/dependencies/{}/{}", &http_origin, &contract_addr, "not-there");
+                eprintln!("Test: GET {}", path);
+                assert_eq!(client.get(&path).send().unwrap().status(), 404);
+
+                // the transaction index has the get-info contract's deployment and calls,
+                //   most recent first
+
+                let path = format!("{}/v2/contracts/transactions/{}/{}", &http_origin, &contract_addr, "get-info");
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<IndexedTransactionsResponse>().unwrap();
+                assert!(res.transactions.len() >= 2);
+                assert!(res.transactions.iter().all(|tx| tx.status == "success"));
+                let publish_tx = res.transactions.last().unwrap().clone();
+                assert_eq!(publish_tx.block_height, 2);
+
+                let path = format!("{}/v2/transactions/{}", &http_origin, &publish_tx.txid);
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<IndexedTransactionResponse>().unwrap();
+                assert_eq!(res, publish_tx);
+
+                let path = format!("{}/v2/transactions/{}?tip={}", &http_origin, &publish_tx.txid, &publish_tx.index_block_hash);
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<IndexedTransactionResponse>().unwrap();
+                assert_eq!(res, publish_tx);
+
+                let path = format!("{}/v2/accounts/{}/transactions", &http_origin, &contract_addr);
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<IndexedTransactionsResponse>().unwrap();
+                assert_eq!(res.transactions, vec![publish_tx.clone()]);
+
+                // the deployment changed the publisher's nonce
+
+                let path = format!("{}/v2/accounts/{}/changes", &http_origin, &contract_addr);
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<AccountChangesResponse>().unwrap();
+                assert_eq!(res.changes.len(), 1);
+                let publish_change = res.changes[0].clone();
+                assert_eq!(publish_change.index_block_hash, publish_tx.index_block_hash);
+                assert_eq!(publish_change.block_height, 2);
+                assert_eq!(publish_change.txids, vec![publish_tx.txid.clone()]);
+                assert_eq!(publish_change.nonce, 1);
+
+                let path = format!("{}/v2/accounts/{}/changes?before=2", &http_origin, &contract_addr);
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<AccountChangesResponse>().unwrap();
+                assert!(res.changes.is_empty());
+
+                // the publisher's account as of that block, by index block hash and by height
+
+                let path = format!("{}/v2/accounts/{}/at/{}", &http_origin, &contract_addr, &publish_tx.index_block_hash);
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<HistoricalAccountEntryResponse>().unwrap();
+                assert_eq!(res.balance, publish_change.balance);
+                assert_eq!(res.nonce, 1);
+                assert_eq!(res.block_height, 2);
+                assert!(res.nonce_proof.is_some());
+                assert!(res.balance_proof.is_some());
+
+                let path = format!("{}/v2/accounts/{}/at/2?proof=0", &http_origin, &contract_addr);
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<HistoricalAccountEntryResponse>().unwrap();
+                assert_eq!(res.index_block_hash, publish_tx.index_block_hash);
+                assert_eq!(res.nonce, 1);
+                assert!(res.nonce_proof.is_none());
+                assert!(res.balance_proof.is_none());
+
+                let path = format!("{}/v2/accounts/{}/at/1", &http_origin, &contract_addr);
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<HistoricalAccountEntryResponse>().unwrap();
+                assert_eq!(res.nonce, 0);
+
+                let path = format!("{}/v2/accounts/{}/at/{}", &http_origin, &contract_addr, 1000000);
+                eprintln!("Test: GET {}", path);
+                assert_eq!(client.get(&path).send().unwrap().status(), 404);
+
+                let path = format!("{}/v2/transactions/{}", &http_origin, "00".repeat(32));
+                eprintln!("Test: GET {}", path);
+                assert_eq!(client.get(&path).send().unwrap().status(), 404);
+
+                // no contract implements a trait which was never defined
+
+                let path = format!("{}/v2/traits/implementers/{}/{}/{}", &http_origin, &contract_addr, "get-info", "not-there");
+                eprintln!("Test: GET {}", path);
+                let res = client.get(&path).send().unwrap().json::<TraitImplementersResponse>().unwrap();
+                assert!(res.implementers.is_empty());
+
                 // let's get a contract SRC
 
                 let path = format!("{}/v2/contracts/source/{}/{}", &http_origin, &contract_addr, "get-info");
@@ -499,6 +596,53 @@ fn integration_test_get_info() {
                                                                        "(get-exotic-data-info? u1)");
                 assert_eq!(result_data, expected_data);
 
+                // let's simulate the same call
+                let path = format!("{}/v2/contracts/simulate/{}/{}/{}", &http_origin, &contract_addr, "get-info", "get-exotic-data-info");
+                eprintln!("Test: POST {}", path);
+
+                let body = SimulateTransactionRequestBody {
+                    sender: "'SP139Q3N9RXCJCD1XVA4N5RYWQ5K9XQ0T9PKQ8EE5".into(),
+                    arguments: vec![Value::UInt(1).serialize()],
+                    tip: None,
+                    post_conditions: vec![],
+                    post_condition_mode: None,
+                    overrides: SimulationOverridesBody::default()
+                };
+
+                let res = client.post(&path)
+                    .json(&body)
+                    .send()
+                    .unwrap().json::<SimulateTransactionResponse>().unwrap();
+                assert!(res.okay);
+                assert_eq!(res.post_conditions_passed, Some(true));
+                assert!(res.cost.unwrap().read_count > 0);
+
+                let result_data = Value::try_deserialize_hex_untyped(&res.result.unwrap()[2..]).unwrap();
+                assert_eq!(result_data, expected_data);
+
+                // and simulate a public function which writes
+                let path = format!("{}/v2/contracts/simulate/{}/{}/{}", &http_origin, &contract_addr, "get-info", "update-info");
+                eprintln!("Test: POST {}", path);
+
+                let body = SimulateTransactionRequestBody {
+                    sender: "'SP139Q3N9RXCJCD1XVA4N5RYWQ5K9XQ0T9PKQ8EE5".into(),
+                    arguments: vec![],
+                    tip: None,
+                    post_conditions: vec![],
+                    post_condition_mode: None,
+                    overrides: SimulationOverridesBody::default()
+                };
+
+                let res = client.post(&path)
+                    .json(&body)
+                    .send()
+                    .unwrap().json::<SimulateTransactionResponse>().unwrap();
+                assert!(res.okay);
+                assert!(res.cost.unwrap().write_count > 0);
+
+                let result_data = Value::try_deserialize_hex_untyped(&res.result.unwrap()[2..]).unwrap();
+                assert_eq!(result_data, Value::okay_true());
+
                 // let's have a runtime error!
                 let path = format!("{}/v2/contracts/call-read/{}/{}/{}", &http_origin, &contract_addr, "get-info", "get-exotic-data-info");
                 eprintln!("Test: POST {}", path);
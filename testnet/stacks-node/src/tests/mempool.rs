@@ -48,6 +48,35 @@ pub fn make_bad_stacks_transfer(sender: &StacksPrivateKey, nonce: u64, fee_rate:
     buf
 }
 
+pub fn make_sponsored_stacks_transfer(sender: &StacksPrivateKey, nonce: u64,
+                                      sponsor: &StacksPrivateKey, sponsor_nonce: u64, fee_rate: u64,
+                                      recipient: &PrincipalData, amount: u64) -> Vec<u8> {
+    let payload = TransactionPayload::TokenTransfer(recipient.clone(), amount, TokenTransferMemo([0; 34]));
+
+    let mut origin_condition = TransactionSpendingCondition::new_singlesig_p2pkh(StacksPublicKey::from_private(sender))
+        .expect("Failed to create p2pkh spending condition from public key.");
+    origin_condition.set_nonce(nonce);
+    let auth = TransactionAuth::Sponsored(origin_condition, TransactionSpendingCondition::new_initial_sighash());
+
+    let mut unsigned_tx = StacksTransaction::new(TransactionVersion::Testnet, auth, payload);
+    unsigned_tx.chain_id = TESTNET_CHAIN_ID;
+
+    let mut tx_signer = StacksTransactionSigner::new(&unsigned_tx);
+    tx_signer.sign_origin(sender).unwrap();
+
+    let mut sponsor_condition = TransactionSpendingCondition::new_singlesig_p2pkh(StacksPublicKey::from_private(sponsor))
+        .expect("Failed to create p2pkh spending condition from public key.");
+    sponsor_condition.set_nonce(sponsor_nonce);
+    sponsor_condition.set_fee_rate(fee_rate);
+
+    let mut tx_signer = StacksTransactionSigner::new_sponsor(&tx_signer.get_tx_incomplete(), sponsor_condition).unwrap();
+    tx_signer.sign_sponsor(sponsor).unwrap();
+
+    let mut buf = vec![];
+    tx_signer.get_tx().unwrap().consensus_serialize(&mut buf).unwrap();
+    buf
+}
+
 #[test]
 fn mempool_setup_chainstate() {
     let mut conf = super::new_test_conf();
@@ -154,6 +183,21 @@ fn mempool_setup_chainstate() {
             eprintln!("Err: {:?}", e);
             assert!(if let MemPoolRejection::BadNonces(_) = e { true } else { false });
 
+            // bad sponsor nonce
+            let sponsor_sk = StacksPrivateKey::new();
+            let tx_bytes = make_sponsored_stacks_transfer(&contract_sk, 1, &sponsor_sk, 1, 300, &other_addr, 1000);
+            let tx = StacksTransaction::consensus_deserialize(&mut tx_bytes.as_slice()).unwrap();
+            let e = chain_state.will_admit_mempool_tx(burn_hash, block_hash, &tx, tx_bytes.len() as u64).unwrap_err(); 
+            eprintln!("Err: {:?}", e);
+            assert!(if let MemPoolRejection::BadSponsorNonce(_) = e { true } else { false });
+
+            // sponsor can't pay the fee, even though the origin could
+            let tx_bytes = make_sponsored_stacks_transfer(&contract_sk, 1, &sponsor_sk, 0, 300, &other_addr, 1000);
+            let tx = StacksTransaction::consensus_deserialize(&mut tx_bytes.as_slice()).unwrap();
+            let e = chain_state.will_admit_mempool_tx(burn_hash, block_hash, &tx, tx_bytes.len() as u64).unwrap_err(); 
+            eprintln!("Err: {:?}", e);
+            assert!(if let MemPoolRejection::NotEnoughSponsorFunds(300, 0) = e { true } else { false });
+
             // not enough funds
             let tx_bytes = make_stacks_transfer(&contract_sk, 1, 110000, &other_addr, 1000);
             let tx = StacksTransaction::consensus_deserialize(&mut tx_bytes.as_slice()).unwrap();
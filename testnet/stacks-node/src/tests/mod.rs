@@ -253,7 +253,7 @@ fn should_succeed_mining_valid_txs() {
                 let contract_tx = &chain_tip.block.txs[1];
                 assert!(contract_tx.chain_id == TESTNET_CHAIN_ID);
                 assert!(match contract_tx.payload {
-                    TransactionPayload::SmartContract(_) => true,
+                    TransactionPayload::SmartContract(..) => true,
                     _ => false,
                 });
 
@@ -488,7 +488,7 @@ fn should_succeed_handling_malformed_and_valid_txs() {
                 let contract_tx = &chain_tip.block.txs[1];
                 assert!(contract_tx.chain_id == TESTNET_CHAIN_ID);
                 assert!(match contract_tx.payload {
-                    TransactionPayload::SmartContract(_) => true,
+                    TransactionPayload::SmartContract(..) => true,
                     _ => false,
                 });
             },